@@ -15,7 +15,7 @@ pub trait IActionIndicator {
     // name of the action this indicator applies to
     fn get_action_name(&self) -> String;
     
-    // when to run / how often
+    // when to run / how often; empty when the indicator is only checked on demand
     fn get_cron_schedule(&self) -> String;
 
     // the code to run to return if the action should be indicated or not
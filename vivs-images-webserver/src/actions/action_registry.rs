@@ -9,20 +9,63 @@ use crate::actions::import::new_image_paths_action::InsertNewImagePathsAction;
 use crate::actions::import::new_iptc_action::InsertNewIptcsOrchestratorAction;
 use crate::actions::import::new_tags_action::InsertNewImageTagsFromDiskAction;
 use crate::actions::import::new_xmp_action::InsertNewXmpOrchestratorAction;
+use crate::actions::import::new_color_action::InsertNewColorOrchestratorAction;
+use crate::actions::import::new_sharpness_action::InsertNewSharpnessOrchestratorAction;
 use crate::actions::refresh::delete_missing_brightness_action::DeleteMissingBrightnessOrchestratorAction;
+use crate::actions::refresh::delete_missing_image_paths_action::DeleteMissingImagePathsOrchestratorAction;
 use crate::actions::refresh::delete_missing_exif_action::DeleteMissingExifOrchestratorAction;
 use crate::actions::refresh::delete_missing_similarity_action::DeleteMissingSimilarityOrchestratorAction;
+use crate::actions::refresh::prune_similarity_to_top_k_action::PruneSimilarityToTopKOrchestratorAction;
+use crate::actions::refresh::recompute_oriented_images_action::RecomputeOrientedImagesOrchestratorAction;
+use crate::actions::refresh::relocate_thumbnails_action::RelocateThumbnailsOrchestratorAction;
+use crate::actions::refresh::warm_thumbnail_cache_action::WarmThumbnailCacheOrchestratorAction;
+use crate::actions::refresh::cluster_image_events_action::ClusterImageEventsOrchestratorAction;
 use crate::actions::refresh::delete_missing_thumbnails_action::DeleteMissingThumbnailsOrchestratorAction;
+use crate::actions::refresh::delete_missing_color_action::DeleteMissingColorOrchestratorAction;
+use crate::actions::refresh::delete_missing_sharpness_action::DeleteMissingSharpnessOrchestratorAction;
+use crate::actions::refresh::delete_missing_exposure_action::DeleteMissingExposureOrchestratorAction;
+use crate::actions::refresh::delete_missing_ocr_summary_action::DeleteMissingOcrSummaryOrchestratorAction;
+use crate::actions::refresh::delete_missing_content_hash_action::DeleteMissingContentHashOrchestratorAction;
+use crate::actions::refresh::delete_missing_raw_action::DeleteMissingRawOrchestratorAction;
+use crate::actions::refresh::delete_missing_frames_action::DeleteMissingFramesOrchestratorAction;
 use crate::actions::import::new_brightness_action::InsertNewBrightnessOrchestratorAction;
 use crate::actions::import::new_exif_action::InsertNewExifsOrchestratorAction;
 use crate::actions::import::new_ocr_text_action::InsertNewOcrTextsOrchestratorAction;
 use crate::actions::import::new_similarity_action::{InsertNewSimilaritysFromDiskOrchestratorAction, InsertNewSimilaritysFromThumbnailsOrchestratorAction};
+use crate::actions::import::new_similarity_neighbors_action::InsertNewSimilarityNeighborsOrchestratorAction;
 use crate::actions::import::new_thumbnail_action::InsertNewThumbnailsOrchestratorAction;
+use crate::actions::import::new_exposure_action::InsertNewExposureOrchestratorAction;
+use crate::actions::import::new_content_hash_action::InsertNewContentHashOrchestratorAction;
+use crate::actions::import::new_raw_action::InsertNewRawOrchestratorAction;
+use crate::actions::import::new_frames_action::InsertNewFramesOrchestratorAction;
+use crate::actions::import::new_geocode_action::InsertNewGeocodeOrchestratorAction;
+use crate::actions::import::new_burst_action::InsertNewBurstsOrchestratorAction;
 use crate::actions::channels::TaskToWorkerSender;
 use crate::core::data_context::WebServerActionDataContext;
+use crate::models::image_brightness::ImageToBrightnessAlgo;
+use crate::models::image_similarity::ImageComparisonAlgorithm;
 
 
 
+// A per-run setting shown on the action page; "bool" options render as checkboxes
+#[derive(Clone, Debug)]
+pub struct ActionOption {
+    pub name: String,
+    pub label: String,
+    pub field_type: String,
+    pub default: String,
+}
+
+impl ActionOption {
+    pub fn new_bool(name: &str, label: &str, default: bool) -> Self {
+        Self { name: name.to_string(), label: label.to_string(), field_type: "bool".to_string(), default: default.to_string() }
+    }
+
+    pub fn new_string(name: &str, label: &str, default: &str) -> Self {
+        Self { name: name.to_string(), label: label.to_string(), field_type: "string".to_string(), default: default.to_string() }
+    }
+}
+
 #[async_trait]
 pub trait IWebServerAction: Send + Sync {
     fn get_name(&self) -> String;
@@ -30,6 +73,7 @@ pub trait IWebServerAction: Send + Sync {
     fn get_description(&self) -> String;
     fn get_is_runnable(&self) -> bool;
     fn get_can_dry_run(&self) -> bool;
+    fn get_action_options(&self) -> Vec<ActionOption> { vec![] }
     async fn run_task(&self, 
         pool: WebServerActionDataContext, 
         send: TaskToWorkerSender, 
@@ -80,6 +124,7 @@ pub fn find_action(name: String) -> Option<Arc<dyn IWebServerAction>> {
 pub fn get_all_actions() -> Vec<Arc<dyn IWebServerAction>> {
     let mut actions: Vec<Arc<dyn IWebServerAction>> = vec![
         Arc::new(InsertNewImagePathsAction::new()),
+        Arc::new(DeleteMissingImagePathsOrchestratorAction::new()),
         Arc::new(InsertNewBrightnessOrchestratorAction::new()),
         Arc::new(DeleteMissingBrightnessOrchestratorAction::new()),
         Arc::new(InsertNewExifsOrchestratorAction::new()),
@@ -87,6 +132,11 @@ pub fn get_all_actions() -> Vec<Arc<dyn IWebServerAction>> {
         Arc::new(InsertNewSimilaritysFromDiskOrchestratorAction::new()),
         Arc::new(InsertNewSimilaritysFromThumbnailsOrchestratorAction::new()),
         Arc::new(DeleteMissingSimilarityOrchestratorAction::new()),
+        Arc::new(InsertNewSimilarityNeighborsOrchestratorAction::new()),
+        Arc::new(PruneSimilarityToTopKOrchestratorAction::new()),
+        Arc::new(RecomputeOrientedImagesOrchestratorAction::new()),
+        Arc::new(RelocateThumbnailsOrchestratorAction::new()),
+        Arc::new(WarmThumbnailCacheOrchestratorAction::new()),
         Arc::new(InsertNewThumbnailsOrchestratorAction::new()),
         Arc::new(DeleteMissingThumbnailsOrchestratorAction::new()),
         Arc::new(InsertNewOcrTextsOrchestratorAction::new()),
@@ -95,8 +145,33 @@ pub fn get_all_actions() -> Vec<Arc<dyn IWebServerAction>> {
         Arc::new(InsertNewIptcsOrchestratorAction::new()),
         Arc::new(InsertNewImageTagsFromDiskAction::new()),
         Arc::new(InsertNewXmpOrchestratorAction::new()),
+        Arc::new(InsertNewColorOrchestratorAction::new()),
+        Arc::new(DeleteMissingColorOrchestratorAction::new()),
+        Arc::new(InsertNewSharpnessOrchestratorAction::new()),
+        Arc::new(DeleteMissingSharpnessOrchestratorAction::new()),
+        Arc::new(InsertNewExposureOrchestratorAction::new()),
+        Arc::new(DeleteMissingExposureOrchestratorAction::new()),
+        Arc::new(DeleteMissingOcrSummaryOrchestratorAction::new()),
+        Arc::new(InsertNewContentHashOrchestratorAction::new()),
+        Arc::new(DeleteMissingContentHashOrchestratorAction::new()),
+        Arc::new(InsertNewRawOrchestratorAction::new()),
+        Arc::new(DeleteMissingRawOrchestratorAction::new()),
+        Arc::new(InsertNewFramesOrchestratorAction::new()),
+        Arc::new(DeleteMissingFramesOrchestratorAction::new()),
+        Arc::new(InsertNewGeocodeOrchestratorAction::new()),
+        Arc::new(ClusterImageEventsOrchestratorAction::new()),
+        Arc::new(InsertNewBurstsOrchestratorAction::new()),
         // Arc::new(DeleteMissingAspectRatioOrchestratorAction::new()),
     ];
+    for algo in ImageToBrightnessAlgo::all() {
+        actions.push(Arc::new(InsertNewBrightnessOrchestratorAction::new_with_algo(algo)));
+    }
+    // magick is the default from-disk action and thumbnail comparisons have their own action
+    for algo in ImageComparisonAlgorithm::all() {
+        if !matches!(algo, ImageComparisonAlgorithm::Magick | ImageComparisonAlgorithm::CustomV2Thumbnails) {
+            actions.push(Arc::new(InsertNewSimilaritysFromDiskOrchestratorAction::new_with_algo(algo)));
+        }
+    }
     actions.extend_from_slice(&crate::actions::sql_db_actions::get_sql_db_actions());
     actions
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 // analysis_task_item_processor.rs
 
+use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::sync::Arc;
 use std::thread;
@@ -15,6 +16,7 @@ use crate::core::data_context::WebServerActionDataContext;
 use crate::actions::channels::TaskToWorkerSender;
 use crate::actions::channels::TaskToWorkerMessage;
 use crate::actions::channels::task_to_worker_send_helper;
+use crate::actions::action_registry::ActionOption;
 use crate::actions::action_registry::IWebServerAction;
 use crate::calc::math::calculate_progress;
 
@@ -40,6 +42,12 @@ where
     fn get_description(&self) -> String;
     fn get_item_name(&self) -> String;
     fn get_process_action_name(&self) -> String;
+
+    // Options the action page shows for a single run; empty when the processor has none
+    fn get_action_options(&self) -> Vec<ActionOption> { vec![] }
+
+    // A copy of the processor configured from the submitted options, or None to use it as is
+    fn with_action_options(&self, _action_options: &HashMap<String, String>) -> Option<Arc<dyn AnalysisTaskItemProcessor<TAnalysis, TTaskItem, TTaskItemList, TTaskOutput>>> { None }
 }
 
 pub struct AnalysisTaskItemProcessorOrchestrator<TAnalysis, TTaskItem, TTaskItemList, TTaskOutput> 
@@ -417,6 +425,8 @@ where
     fn get_is_runnable(&self) -> bool { true }
     
     fn get_can_dry_run(&self) -> bool { true }
+
+    fn get_action_options(&self) -> Vec<ActionOption> { self.processor.get_action_options() }
     
     async fn run_task(&self,
         pool: WebServerActionDataContext,
@@ -425,6 +435,9 @@ where
         task_id: u32,
         orch_options: TaskOrchestrationOptions
     ) -> actix_web::Result<(), Box<dyn std::error::Error + Send>> {
+        if let Some(processor) = self.processor.with_action_options(&orch_options.action_options) {
+            return Self::new(processor).run_task_parallel_option(pool, send, dry_run, task_id, orch_options).await;
+        }
         self.run_task_parallel_option(pool, send, dry_run, task_id, orch_options).await
     }
 }
@@ -434,6 +447,8 @@ pub struct TaskOrchestrationOptions {
     pub run_in_parallel: bool,
     pub max_concurrent: usize,
     pub requests_per_second: f32,
+    // per-run settings for the processor, see AnalysisTaskItemProcessor::get_action_options
+    pub action_options: HashMap<String, String>,
 }
 
 impl TaskOrchestrationOptions {
@@ -442,6 +457,7 @@ impl TaskOrchestrationOptions {
             run_in_parallel: false,
             max_concurrent: 0,
             requests_per_second: 0.0,
+            action_options: HashMap::new(),
         }
     }
 
@@ -452,10 +468,16 @@ impl TaskOrchestrationOptions {
         Self {
             run_in_parallel: true,
             max_concurrent,
-            requests_per_second
+            requests_per_second,
+            action_options: HashMap::new(),
         }
     }
 
+    pub fn with_action_options(mut self, action_options: HashMap<String, String>) -> Self {
+        self.action_options = action_options;
+        self
+    }
+
     pub fn new_defaults() -> Self { Self::new(8, 16.0) }
 
     pub fn new_faster() -> Self { Self::new_defaults().mul(2) }
@@ -467,6 +489,7 @@ impl TaskOrchestrationOptions {
             run_in_parallel: self.run_in_parallel,
             max_concurrent: self.max_concurrent * n,
             requests_per_second: self.requests_per_second * (n as f32),
+            action_options: self.action_options.clone(),
         }
     }
 }
\ No newline at end of file
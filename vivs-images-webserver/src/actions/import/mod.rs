@@ -7,4 +7,13 @@ pub mod new_similarity_action;
 pub mod new_thumbnail_action;
 pub mod new_ocr_text_action;
 pub mod new_image_paths_action;
-pub mod new_xmp_action;
\ No newline at end of file
+pub mod new_xmp_action;
+pub mod new_color_action;
+pub mod new_sharpness_action;
+pub mod new_exposure_action;
+pub mod new_content_hash_action;
+pub mod new_similarity_neighbors_action;
+pub mod new_raw_action;
+pub mod new_frames_action;
+pub mod new_geocode_action;
+pub mod new_burst_action;
\ No newline at end of file
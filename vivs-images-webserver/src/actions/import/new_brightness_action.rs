@@ -10,19 +10,33 @@ use crate::core::data_context::WebServerActionDataContext;
 use crate::actions::analysis_task_item_processor::LogProgListenerPair;
 use crate::calc::file_paths_comparison::FilePathComparisonModel;
 use crate::converters::extract_image_brightness::extract_image_brightness_model;
-use crate::converters::extract_image_brightness::ImageToBrightnessAlgo;
+use crate::converters::extract_image_brightness::extract_image_brightness_model_from_image;
 use crate::converters::extract_image_brightness::ImageToBrightnessOptions;
+use crate::converters::extract_image_brightness::BRIGHTNESS_SAMPLE_MAX_DIMENSION;
+use crate::database::query::query_image_brightness::get_image_paths_with_other_brightness_algo_from_db;
 use crate::database::query::query_image_brightness::query_brightness_table_count;
+use crate::database::query::query_image_brightness::query_brightness_table_count_for_algo;
 use crate::database::update::update_image_brightness::execute_insert_image_brightness_sql;
 use crate::metrics::brightness_metrics::get_image_path_comparison_brightness_table_analysis;
 use crate::models::image_brightness::ImageBrightness;
+use crate::models::image_brightness::ImageToBrightnessAlgo;
 use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
 use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
 
 
-pub struct BrightnessProcessor;
+pub struct BrightnessProcessor {
+    algo: ImageToBrightnessAlgo,
+    // also recompute rows that were produced by a different algorithm
+    replace_other_algos: bool,
+}
 impl BrightnessProcessor {
-    pub fn new() -> Self { Self {} }
+    pub fn new() -> Self {
+        Self { algo: ImageToBrightnessAlgo::SimpleImageRS, replace_other_algos: false }
+    }
+
+    pub fn new_with_algo(algo: ImageToBrightnessAlgo) -> Self {
+        Self { algo, replace_other_algos: true }
+    }
 }
 
 
@@ -34,36 +48,51 @@ impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<Str
             .map_err(|e| Box::new(std::io::Error::new(ErrorKind::Other, format!("{}", e))) as Box<dyn std::error::Error + Send>)
     }
 
-    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<FilePathComparisonModel>, log_prog_listener: Option<LogProgListenerPair>) -> Result<HashSet<String>, Box<dyn std::error::Error + Send>> {
-        Ok(analysis.files_missing_from_b.clone())
+    async fn get_task_items_from_analysis(&self, pool: WebServerActionDataContext, analysis: Arc<FilePathComparisonModel>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<HashSet<String>, Box<dyn std::error::Error + Send>> {
+        let mut task_items = analysis.files_missing_from_b.clone();
+        if self.replace_other_algos {
+            task_items.extend(get_image_paths_with_other_brightness_algo_from_db(&self.algo, &pool.pool).await?);
+        }
+        Ok(task_items)
     }
 
-    async fn process_task_item(&self, task_item: String, _dry_run: bool, _pool: WebServerActionDataContext) -> Result<Option<Arc<ImageBrightness>>, Box<dyn std::error::Error + Send>> {
+    async fn process_task_item(&self, task_item: String, _dry_run: bool, pool: WebServerActionDataContext) -> Result<Option<Arc<ImageBrightness>>, Box<dyn std::error::Error + Send>> {
         let options = ImageToBrightnessOptions {
-            algo: ImageToBrightnessAlgo::SimpleImageRS
+            algo: self.algo
         };
-        extract_image_brightness_model(&task_item, &options)
-            .map(Arc::new)
-            .map(Some)
-            .map_err(|e| {
-                Box::new(e) as Box<dyn std::error::Error + Send>
-            })
+        // prefer an existing thumbnail over decoding the full image
+        let thumbnail = pool.get_thumbnail_at_most_width_length(&task_item, BRIGHTNESS_SAMPLE_MAX_DIMENSION).await?;
+        let brightness = match thumbnail.map(|t| t.to_image()) {
+            Some(Ok(img)) => extract_image_brightness_model_from_image(&task_item, &img, &options),
+            _ => extract_image_brightness_model(&task_item, &options)
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send>)?,
+        };
+        Ok(Some(Arc::new(brightness)))
     }
 
     async fn process_task_output(&self, task_output: Arc<ImageBrightness>, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
-        execute_insert_image_brightness_sql(&task_output.image_path, task_output.brightness, &pool.pool).await
+        execute_insert_image_brightness_sql(&task_output.image_path, task_output.brightness, &task_output.brightness_algo, &pool.pool).await
             .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)?;
         Ok(())
     }
 
     async fn task_already_completed(&self, task_input: &String, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
-        query_brightness_table_count(&task_input, &pool.pool).await
+        let count = if self.replace_other_algos {
+            query_brightness_table_count_for_algo(task_input, &self.algo, &pool.pool).await
+        } else {
+            query_brightness_table_count(task_input, &pool.pool).await
+        };
+        count
             .map(|v| v > 0)
             .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
     }
 
     fn get_description(&self) -> String {
-        "if the brightness table is missing any entries, it will add them".to_string()
+        if self.replace_other_algos {
+            format!("adds missing brightness entries using the {} algorithm, and recomputes entries made by any other algorithm", self.algo.get_name())
+        } else {
+            "if the brightness table is missing any entries, it will add them".to_string()
+        }
     }
 
     fn get_item_name(&self) -> String {
@@ -71,7 +100,11 @@ impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<Str
     }
 
     fn get_process_action_name(&self) -> String {
-        "add".to_string()
+        if self.replace_other_algos {
+            format!("add_{}", self.algo.get_name())
+        } else {
+            "add".to_string()
+        }
     }
 }
 
@@ -80,4 +113,8 @@ impl InsertNewBrightnessOrchestratorAction {
     pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageBrightness>> {
         AnalysisTaskItemProcessorOrchestrator::new(Arc::new(BrightnessProcessor::new()))
     }
+
+    pub fn new_with_algo(algo: ImageToBrightnessAlgo) -> AnalysisTaskItemProcessorOrchestrator<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageBrightness>> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(BrightnessProcessor::new_with_algo(algo)))
+    }
 }
\ No newline at end of file
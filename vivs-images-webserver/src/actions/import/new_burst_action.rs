@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Duration;
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::actions::analysis_task_item_processor::LogProgListenerPair;
+use crate::calc::burst_detection::{split_burst_runs, split_run_into_bursts};
+use crate::converters::extract_image_similarity::{extract_image_similarity_using_ssim_confidence, get_similarity_thumbnail_image};
+use crate::converters::extract_image_similarity::ComputeImageSimilarityOptions;
+use crate::database::query::query_image_burst::{query_burst_checked_image_count, query_image_burst_candidates};
+use crate::database::update::update_image_burst::execute_replace_image_bursts_sql;
+use crate::models::config::bursts::{get_burst_max_gap_seconds, get_burst_min_similarity};
+use crate::models::config::similarity::{SIMILARITY_THUMBNAIL_SIZE, SIMILARITY_TOP_K_ALGORITHM};
+use crate::models::image_burst::{ImageBurstAnalysis, ImageBurstRun, ImageBurstScan};
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+
+
+pub struct InsertNewBurstsProcessor {
+    max_gap_seconds: f64,
+    min_similarity: f32,
+}
+impl InsertNewBurstsProcessor {
+    pub fn new() -> Self {
+        Self { max_gap_seconds: get_burst_max_gap_seconds(), min_similarity: get_burst_min_similarity() }
+    }
+
+    pub fn with_limits(max_gap_seconds: f64, min_similarity: f32) -> Self {
+        Self { max_gap_seconds, min_similarity }
+    }
+}
+
+// Each run of images taken close together is one task item; only consecutive frames of a run are compared, so the
+// cost grows with the number of images rather than its square
+#[async_trait]
+impl AnalysisTaskItemProcessor<Arc<ImageBurstAnalysis>, ImageBurstRun, Vec<ImageBurstRun>, Arc<ImageBurstScan>> for InsertNewBurstsProcessor {
+    async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<ImageBurstAnalysis>, Box<dyn std::error::Error + Send>> {
+        if let Some(x) = &log_prog_listener {
+            x.1("listing images with a capture time");
+            x.0(0.25);
+        }
+        let candidates = query_image_burst_candidates(&pool.pool).await?;
+        let candidate_count = candidates.len();
+        if let Some(x) = &log_prog_listener {
+            x.1(&format!("finding images taken within {} seconds of each other among {}", self.max_gap_seconds, candidate_count));
+            x.0(0.5);
+        }
+        let max_gap = Duration::milliseconds((self.max_gap_seconds * 1000.0) as i64);
+        let runs = split_burst_runs(candidates, max_gap);
+        Ok(Arc::new(ImageBurstAnalysis { candidate_count, runs }))
+    }
+
+    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<ImageBurstAnalysis>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<Vec<ImageBurstRun>, Box<dyn std::error::Error + Send>> {
+        Ok(analysis.runs.clone())
+    }
+
+    async fn process_task_item(&self, task_item: ImageBurstRun, _dry_run: bool, pool: WebServerActionDataContext) -> Result<Option<Arc<ImageBurstScan>>, Box<dyn std::error::Error + Send>> {
+        // a frame without a thumbnail cannot be compared, which ends the burst at it
+        let mut thumbnails = vec![];
+        for frame in &task_item.frames {
+            thumbnails.push(get_similarity_thumbnail_image(&frame.image_path, &pool).await?);
+        }
+        let mut similarities = vec![];
+        for (i, pair) in thumbnails.windows(2).enumerate() {
+            let similarity = match (&pair[0], &pair[1]) {
+                (Some(a), Some(b)) => {
+                    let options = ComputeImageSimilarityOptions {
+                        algo: SIMILARITY_TOP_K_ALGORITHM,
+                        max_dimension: Some(SIMILARITY_THUMBNAIL_SIZE),
+                        filter_type: Some(image::imageops::FilterType::Nearest),
+                        image_path_a: task_item.frames[i].image_path.clone(),
+                        image_path_b: task_item.frames[i + 1].image_path.clone(),
+                    };
+                    // a pair that cannot be compared ends the burst there rather than failing the whole run
+                    match extract_image_similarity_using_ssim_confidence(a.clone(), b.clone(), &options) {
+                        Ok((similarity_value, _)) => Some(similarity_value),
+                        Err(e) => {
+                            println!("could not compare burst frames {}: {}", options, e);
+                            None
+                        }
+                    }
+                }
+                _ => None,
+            };
+            similarities.push(similarity);
+        }
+
+        let bursts = split_run_into_bursts(&task_item, &similarities, self.min_similarity);
+        Ok(Some(Arc::new(ImageBurstScan { image_paths: task_item.get_image_paths(), bursts })))
+    }
+
+    async fn process_task_output(&self, task_output: Arc<ImageBurstScan>, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        execute_replace_image_bursts_sql(&task_output, &pool.pool).await
+    }
+
+    // A run is checked again once it gains an image that was not checked yet, e.g. after more photos are imported
+    async fn task_already_completed(&self, task_input: &ImageBurstRun, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
+        let image_paths = task_input.get_image_paths();
+        Ok(query_burst_checked_image_count(&image_paths, &pool.pool).await? == image_paths.len())
+    }
+
+    fn get_description(&self) -> String {
+        format!("finds bursts of images taken no more than {} seconds apart whose thumbnails have similarity >= {}, scores each frame by sharpness, brightness and exposure, and suggests the best one to keep", self.max_gap_seconds, self.min_similarity)
+    }
+
+    fn get_item_name(&self) -> String {
+        "bursts".to_string()
+    }
+
+    fn get_process_action_name(&self) -> String {
+        "detect".to_string()
+    }
+}
+
+pub struct InsertNewBurstsOrchestratorAction;
+impl InsertNewBurstsOrchestratorAction {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<ImageBurstAnalysis>, ImageBurstRun, Vec<ImageBurstRun>, Arc<ImageBurstScan>> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(InsertNewBurstsProcessor::new()))
+    }
+}
\ No newline at end of file
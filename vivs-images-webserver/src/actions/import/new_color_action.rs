@@ -0,0 +1,81 @@
+// new_color_action.rs
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::actions::analysis_task_item_processor::LogProgListenerPair;
+use crate::calc::file_paths_comparison::FilePathComparisonModel;
+use crate::converters::extract_image_color::extract_image_color_model;
+use crate::converters::extract_image_color::open_and_extract_image_color_model;
+use crate::converters::extract_image_color::COLOR_SAMPLE_MAX_DIMENSION;
+use crate::database::query::query_image_color::query_color_table_count;
+use crate::database::update::update_image_color::execute_insert_image_color_sql;
+use crate::metrics::color_metrics::get_image_path_comparison_color_table_analysis;
+use crate::models::image_color::ImageColor;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+
+
+pub struct ColorProcessor;
+impl ColorProcessor {
+    pub fn new() -> Self { Self {} }
+}
+
+
+#[async_trait]
+impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageColor>> for ColorProcessor {
+    async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<FilePathComparisonModel>, Box<dyn std::error::Error + Send>> {
+        get_image_path_comparison_color_table_analysis(&pool.pool, log_prog_listener).await
+            .map(Arc::new)
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+    }
+
+    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<FilePathComparisonModel>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<HashSet<String>, Box<dyn std::error::Error + Send>> {
+        Ok(analysis.files_missing_from_b.clone())
+    }
+
+    async fn process_task_item(&self, task_item: String, _dry_run: bool, pool: WebServerActionDataContext) -> Result<Option<Arc<ImageColor>>, Box<dyn std::error::Error + Send>> {
+        // prefer an existing thumbnail over decoding the full image
+        let thumbnail = pool.get_thumbnail_at_most_width_length(&task_item, COLOR_SAMPLE_MAX_DIMENSION).await?;
+        let color = match thumbnail.map(|t| t.to_image()) {
+            Some(Ok(img)) => extract_image_color_model(&task_item, &img),
+            _ => open_and_extract_image_color_model(&task_item)
+                .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)?,
+        };
+        Ok(Some(Arc::new(color)))
+    }
+
+    async fn process_task_output(&self, task_output: Arc<ImageColor>, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        execute_insert_image_color_sql(&task_output, &pool.pool).await
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)?;
+        Ok(())
+    }
+
+    async fn task_already_completed(&self, task_input: &String, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
+        query_color_table_count(task_input, &pool.pool).await
+            .map(|v| v > 0)
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+    }
+
+    fn get_description(&self) -> String {
+        "if the color table is missing any entries, it will add the dominant colours, hsv histogram, saturation and warmth".to_string()
+    }
+
+    fn get_item_name(&self) -> String {
+        "color".to_string()
+    }
+
+    fn get_process_action_name(&self) -> String {
+        "add".to_string()
+    }
+}
+
+pub struct InsertNewColorOrchestratorAction;
+impl InsertNewColorOrchestratorAction {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageColor>> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(ColorProcessor::new()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,99 @@
+// new_content_hash_action.rs
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::actions::analysis_task_item_processor::LogProgListenerPair;
+use crate::calc::file_paths_comparison::FilePathComparisonModel;
+use crate::converters::extract_image_content_hash::open_and_extract_image_content_hash_model;
+use crate::converters::extract_image_content_hash::ImageContentHashResult;
+use crate::database::query::query_image_content_hash::query_content_hash_table_count;
+use crate::database::query::query_image_content_hash::query_image_paths_with_content_hash;
+use crate::database::update::update_image_content_hash::execute_insert_image_content_hash_sql;
+use crate::database::update::update_image_path_rekey::execute_rekey_image_path_sql;
+use crate::metrics::content_hash_metrics::get_image_path_comparison_content_hash_table_analysis;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+
+
+pub struct ContentHashProcessor;
+impl ContentHashProcessor {
+    pub fn new() -> Self { Self {} }
+}
+
+
+#[async_trait]
+impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageContentHashResult>> for ContentHashProcessor {
+    async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<FilePathComparisonModel>, Box<dyn std::error::Error + Send>> {
+        get_image_path_comparison_content_hash_table_analysis(&pool.pool, log_prog_listener).await
+            .map(Arc::new)
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+    }
+
+    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<FilePathComparisonModel>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<HashSet<String>, Box<dyn std::error::Error + Send>> {
+        Ok(analysis.files_missing_from_b.clone())
+    }
+
+    async fn process_task_item(&self, task_item: String, _dry_run: bool, pool: WebServerActionDataContext) -> Result<Option<Arc<ImageContentHashResult>>, Box<dyn std::error::Error + Send>> {
+        hash_image_and_find_moved_from(&task_item, &pool.pool).await
+            .map(|v| Some(Arc::new(v)))
+    }
+
+    async fn process_task_output(&self, task_output: Arc<ImageContentHashResult>, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        store_image_content_hash_result(&task_output, &pool.pool).await?;
+        Ok(())
+    }
+
+    async fn task_already_completed(&self, task_input: &String, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
+        query_content_hash_table_count(task_input, &pool.pool).await
+            .map(|v| v > 0)
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+    }
+
+    fn get_description(&self) -> String {
+        "if the content_hash table is missing any entries, it will hash the file; when a missing path had the same hash, every table is re-keyed to the new path instead. Adding image paths does this too, so this is only needed for images added before it did".to_string()
+    }
+
+    fn get_item_name(&self) -> String {
+        "content_hash".to_string()
+    }
+
+    fn get_process_action_name(&self) -> String {
+        "add".to_string()
+    }
+}
+
+// A path on record with the same contents that is gone from disk means the file was moved or renamed
+pub async fn hash_image_and_find_moved_from(image_path: &str, pool: &SqlitePool) -> Result<ImageContentHashResult, Box<dyn std::error::Error + Send>> {
+    let content_hash = open_and_extract_image_content_hash_model(image_path)
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send>)?;
+    let moved_from = query_image_paths_with_content_hash(&content_hash.content_hash, image_path, pool).await?
+        .into_iter()
+        .find(|p| !Path::new(p).exists());
+    Ok(ImageContentHashResult { content_hash, moved_from })
+}
+
+// Re-keys every table from the path the file was moved from, otherwise stores the hash. Returns whether rows moved;
+// nothing moves when another copy with the same contents already claimed the old rows
+pub async fn store_image_content_hash_result(result: &ImageContentHashResult, pool: &SqlitePool) -> Result<bool, Box<dyn std::error::Error + Send>> {
+    if let Some(old_path) = &result.moved_from {
+        if execute_rekey_image_path_sql(old_path, &result.content_hash.image_path, pool).await? > 0 {
+            return Ok(true);
+        }
+    }
+    execute_insert_image_content_hash_sql(&result.content_hash, pool).await
+        .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)?;
+    Ok(false)
+}
+
+pub struct InsertNewContentHashOrchestratorAction;
+impl InsertNewContentHashOrchestratorAction {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageContentHashResult>> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(ContentHashProcessor::new()))
+    }
+}
\ No newline at end of file
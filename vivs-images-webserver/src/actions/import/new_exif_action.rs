@@ -10,8 +10,8 @@ use crate::core::data_context::WebServerActionDataContext;
 use crate::actions::analysis_task_item_processor::LogProgListenerPair;
 use crate::calc::file_paths_comparison::FilePathComparisonModel;
 use crate::converters::extract_image_exif::extract_image_exif;
-use crate::database::query::query_image_exif::query_exif_table_count;
-use crate::database::update::update_image_exif::execute_insert_image_exif_sql;
+use crate::database::query::query_image_exif::{get_exif_pending_image_paths, query_exif_pending_count, query_exif_table_count};
+use crate::database::update::update_image_exif::{execute_delete_image_exif_pending_sql, execute_insert_image_exif_sql, execute_update_image_exif_sql};
 use crate::metrics::exif_metrics::get_image_path_comparison_exif_table_analysis;
 use crate::models::image_exif::ImageExif;
 use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
@@ -33,8 +33,11 @@ impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<Str
             .map_err(|e| Box::new(std::io::Error::new(ErrorKind::Other, format!("{}", e))) as Box<dyn std::error::Error + Send>)
     }
 
-    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<FilePathComparisonModel>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<HashSet<String>, Box<dyn std::error::Error + Send>> {
-        Ok(analysis.files_missing_from_b.clone())
+    // images without an exif row, plus those whose row was queued to be read again
+    async fn get_task_items_from_analysis(&self, pool: WebServerActionDataContext, analysis: Arc<FilePathComparisonModel>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<HashSet<String>, Box<dyn std::error::Error + Send>> {
+        let mut task_items = analysis.files_missing_from_b.clone();
+        task_items.extend(get_exif_pending_image_paths(&pool.pool).await?);
+        Ok(task_items)
     }
 
     async fn process_task_item(&self, task_item: String, _dry_run: bool, _pool: WebServerActionDataContext) -> Result<Option<Arc<ImageExif>>, Box<dyn std::error::Error + Send>> {
@@ -45,18 +48,21 @@ impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<Str
     }
 
     async fn process_task_output(&self, task_output: Arc<ImageExif>, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
-        execute_insert_image_exif_sql((*task_output).clone(), pool.pool).await
-            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+        if query_exif_table_count(&task_output.image_path, &pool.pool).await? > 0 {
+            execute_update_image_exif_sql(&task_output, &pool.pool).await?;
+        } else {
+            execute_insert_image_exif_sql((*task_output).clone(), pool.pool.clone()).await
+                .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)?;
+        }
+        execute_delete_image_exif_pending_sql(&task_output.image_path, &pool.pool).await
     }
 
     async fn task_already_completed(&self, task_input: &String, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
-        query_exif_table_count(&task_input, &pool.pool).await
-            .map(|v| v > 0)
-            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+        Ok(query_exif_table_count(task_input, &pool.pool).await? > 0 && query_exif_pending_count(task_input, &pool.pool).await? == 0)
     }
 
     fn get_description(&self) -> String {
-        "if the exif table is missing any entries, it will add them".to_string()
+        "if the exif table is missing any entries, it will add them; rows queued to be read again are updated in place".to_string()
     }
 
     fn get_item_name(&self) -> String {
@@ -0,0 +1,77 @@
+// new_exposure_action.rs
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::actions::analysis_task_item_processor::LogProgListenerPair;
+use crate::calc::file_paths_comparison::FilePathComparisonModel;
+use crate::converters::extract_image_exposure::open_and_extract_image_exposure_model;
+use crate::database::query::query_image_exposure::query_exposure_table_count;
+use crate::database::update::update_image_exposure::execute_insert_image_exposure_sql;
+use crate::metrics::exposure_metrics::get_image_path_comparison_exposure_table_analysis;
+use crate::models::image_exposure::ImageExposure;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+
+
+pub struct ExposureProcessor;
+impl ExposureProcessor {
+    pub fn new() -> Self { Self {} }
+}
+
+
+#[async_trait]
+impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageExposure>> for ExposureProcessor {
+    async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<FilePathComparisonModel>, Box<dyn std::error::Error + Send>> {
+        get_image_path_comparison_exposure_table_analysis(&pool.pool, log_prog_listener).await
+            .map(Arc::new)
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+    }
+
+    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<FilePathComparisonModel>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<HashSet<String>, Box<dyn std::error::Error + Send>> {
+        Ok(analysis.files_missing_from_b.clone())
+    }
+
+    async fn process_task_item(&self, task_item: String, _dry_run: bool, _pool: WebServerActionDataContext) -> Result<Option<Arc<ImageExposure>>, Box<dyn std::error::Error + Send>> {
+        open_and_extract_image_exposure_model(&task_item)
+            .map(Arc::new)
+            .map(Some)
+            .map_err(|e| {
+                Box::new(e) as Box<dyn std::error::Error + Send>
+            })
+    }
+
+    async fn process_task_output(&self, task_output: Arc<ImageExposure>, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        execute_insert_image_exposure_sql(&task_output, &pool.pool).await
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)?;
+        Ok(())
+    }
+
+    async fn task_already_completed(&self, task_input: &String, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
+        query_exposure_table_count(task_input, &pool.pool).await
+            .map(|v| v > 0)
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+    }
+
+    fn get_description(&self) -> String {
+        "if the exposure table is missing any entries, it will add the clipping percentages, luma percentiles, contrast and luma histogram".to_string()
+    }
+
+    fn get_item_name(&self) -> String {
+        "exposure".to_string()
+    }
+
+    fn get_process_action_name(&self) -> String {
+        "add".to_string()
+    }
+}
+
+pub struct InsertNewExposureOrchestratorAction;
+impl InsertNewExposureOrchestratorAction {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageExposure>> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(ExposureProcessor::new()))
+    }
+}
\ No newline at end of file
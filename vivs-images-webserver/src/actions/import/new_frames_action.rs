@@ -0,0 +1,77 @@
+// new_frames_action.rs
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::actions::analysis_task_item_processor::LogProgListenerPair;
+use crate::calc::file_paths_comparison::FilePathComparisonModel;
+use crate::converters::extract_image_frames::{open_and_extract_image_frames, MAX_FRAME_THUMBNAILS};
+use crate::database::query::query_image_frames::query_frames_table_count;
+use crate::database::update::update_image_frames::execute_insert_image_frames_sql;
+use crate::metrics::frames_metrics::get_image_path_comparison_frames_table_analysis;
+use crate::models::image_frames::ImageFramesExtraction;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+
+
+pub struct FramesProcessor;
+impl FramesProcessor {
+    pub fn new() -> Self { Self {} }
+}
+
+
+#[async_trait]
+impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageFramesExtraction>> for FramesProcessor {
+    async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<FilePathComparisonModel>, Box<dyn std::error::Error + Send>> {
+        get_image_path_comparison_frames_table_analysis(&pool.pool, log_prog_listener).await
+            .map(Arc::new)
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+    }
+
+    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<FilePathComparisonModel>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<HashSet<String>, Box<dyn std::error::Error + Send>> {
+        Ok(analysis.files_missing_from_b.clone())
+    }
+
+    async fn process_task_item(&self, task_item: String, _dry_run: bool, _pool: WebServerActionDataContext) -> Result<Option<Arc<ImageFramesExtraction>>, Box<dyn std::error::Error + Send>> {
+        open_and_extract_image_frames(&task_item)
+            .map(Arc::new)
+            .map(Some)
+            .map_err(|e| {
+                Box::new(e) as Box<dyn std::error::Error + Send>
+            })
+    }
+
+    async fn process_task_output(&self, task_output: Arc<ImageFramesExtraction>, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        execute_insert_image_frames_sql(&task_output, &pool.pool).await
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)?;
+        Ok(())
+    }
+
+    async fn task_already_completed(&self, task_input: &String, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
+        query_frames_table_count(task_input, &pool.pool).await
+            .map(|v| v > 0)
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+    }
+
+    fn get_description(&self) -> String {
+        format!("if the frames table is missing any GIF, WebP or TIFF images, it will add their frame or page count, animation duration and thumbnails of up to {} of their frames", MAX_FRAME_THUMBNAILS)
+    }
+
+    fn get_item_name(&self) -> String {
+        "frames".to_string()
+    }
+
+    fn get_process_action_name(&self) -> String {
+        "add".to_string()
+    }
+}
+
+pub struct InsertNewFramesOrchestratorAction;
+impl InsertNewFramesOrchestratorAction {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageFramesExtraction>> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(FramesProcessor::new()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,95 @@
+// new_geocode_action.rs
+
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::actions::analysis_task_item_processor::LogProgListenerPair;
+use crate::calc::reverse_geocode::ReverseGeocoder;
+use crate::converters::extract_image_geocode::extract_image_geocode;
+use crate::database::query::query_image_geocode::{query_geocode_table_count, query_gps_coordinates_missing_geocode};
+use crate::database::update::update_image_geocode::execute_insert_image_geocode_sql;
+use crate::models::config::geocoding::get_geocode_max_distance_km;
+use crate::models::image_geocode::{ImageGeocode, ImageGeocodeAnalysis, ImageGpsCoordinates};
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+
+
+pub struct GeocodeProcessor {
+    max_distance_km: f64,
+    // the place list is only read the first time the action runs, not when the server starts
+    geocoder: OnceLock<Arc<ReverseGeocoder>>,
+}
+impl GeocodeProcessor {
+    pub fn new() -> Self {
+        Self { max_distance_km: get_geocode_max_distance_km(), geocoder: OnceLock::new() }
+    }
+
+    pub fn with_geocoder(geocoder: ReverseGeocoder, max_distance_km: f64) -> Self {
+        Self { max_distance_km, geocoder: OnceLock::from(Arc::new(geocoder)) }
+    }
+
+    fn get_or_load_geocoder(&self) -> Result<Arc<ReverseGeocoder>, Box<dyn std::error::Error + Send>> {
+        if let Some(geocoder) = self.geocoder.get() {
+            return Ok(geocoder.clone());
+        }
+        let geocoder = ReverseGeocoder::from_config()
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send>)?;
+        Ok(self.geocoder.get_or_init(|| Arc::new(geocoder)).clone())
+    }
+}
+
+
+#[async_trait]
+impl AnalysisTaskItemProcessor<Arc<ImageGeocodeAnalysis>, ImageGpsCoordinates, Vec<ImageGpsCoordinates>, ImageGeocode> for GeocodeProcessor {
+    async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<ImageGeocodeAnalysis>, Box<dyn std::error::Error + Send>> {
+        if let Some(x) = &log_prog_listener {
+            x.1("loading place list");
+            x.0(0.25);
+        }
+        let geocoder = self.get_or_load_geocoder()?;
+        if let Some(x) = &log_prog_listener {
+            x.1(&format!("listing images with gps coordinates and no place names, {} known places", geocoder.len()));
+            x.0(0.5);
+        }
+        let coordinates = query_gps_coordinates_missing_geocode(&pool.pool).await?;
+        Ok(Arc::new(ImageGeocodeAnalysis { dataset_name: geocoder.get_dataset_name().to_string(), coordinates }))
+    }
+
+    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<ImageGeocodeAnalysis>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<Vec<ImageGpsCoordinates>, Box<dyn std::error::Error + Send>> {
+        Ok(analysis.coordinates.clone())
+    }
+
+    async fn process_task_item(&self, task_item: ImageGpsCoordinates, _dry_run: bool, _pool: WebServerActionDataContext) -> Result<Option<ImageGeocode>, Box<dyn std::error::Error + Send>> {
+        let geocoder = self.get_or_load_geocoder()?;
+        Ok(Some(extract_image_geocode(&task_item, &geocoder, self.max_distance_km)))
+    }
+
+    async fn process_task_output(&self, task_output: ImageGeocode, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        execute_insert_image_geocode_sql(&task_output, &pool.pool).await
+    }
+
+    async fn task_already_completed(&self, task_input: &ImageGpsCoordinates, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
+        Ok(query_geocode_table_count(&task_input.image_path, &pool.pool).await? > 0)
+    }
+
+    fn get_description(&self) -> String {
+        format!("looks up the country, region and nearest city of images with gps coordinates in an offline place list, leaving places further than {} km from any known city unnamed", self.max_distance_km)
+    }
+
+    fn get_item_name(&self) -> String {
+        "geocode".to_string()
+    }
+
+    fn get_process_action_name(&self) -> String {
+        "add".to_string()
+    }
+}
+
+pub struct InsertNewGeocodeOrchestratorAction;
+impl InsertNewGeocodeOrchestratorAction {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<ImageGeocodeAnalysis>, ImageGpsCoordinates, Vec<ImageGpsCoordinates>, ImageGeocode> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(GeocodeProcessor::new()))
+    }
+}
\ No newline at end of file
@@ -11,12 +11,30 @@ use crate::core::data_context::WebServerActionDataContext;
 use crate::actions::analysis_task_item_processor::LogProgListenerPair;
 use crate::calc::file_paths_comparison::FilePathComparisonModel;
 use crate::database::query::query_image_paths::query_image_path_table_count;
+use crate::actions::import::new_content_hash_action::{hash_image_and_find_moved_from, store_image_content_hash_result};
+use crate::converters::extract_image_content_hash::ImageContentHashResult;
+use crate::database::update::update_image_content_hash::execute_insert_image_content_hash_sql;
 use crate::database::update::update_image_image_paths::execute_insert_image_path_sql;
 use crate::metrics::image_paths_metrics::get_image_path_comparison_analysis;
 use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
 use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
 
 
+// A new path, with its content hash unless the file could not be read
+pub struct ImagePathImport {
+    pub image_path: String,
+    pub content_hash: Option<ImageContentHashResult>,
+}
+
+impl std::fmt::Display for ImagePathImport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.content_hash {
+            Some(content_hash) => write!(f, "{}", content_hash),
+            None => write!(f, "{}", self.image_path),
+        }
+    }
+}
+
 pub struct ImagePathsProcessor;
 impl ImagePathsProcessor {
     pub fn new() -> Self { Self {} }
@@ -24,7 +42,7 @@ impl ImagePathsProcessor {
 
 
 #[async_trait]
-impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<String>> for ImagePathsProcessor {
+impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImagePathImport>> for ImagePathsProcessor {
     async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<FilePathComparisonModel>, Box<dyn std::error::Error + Send>> {
         get_image_path_comparison_analysis(&pool.pool, log_prog_listener).await
             .map(|v| Arc::new(v))
@@ -35,13 +53,33 @@ impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<Str
         Ok(analysis.files_missing_from_b.clone())
     }
 
-    async fn process_task_item(&self, task_item: String, _dry_run: bool, _pool: WebServerActionDataContext) -> Result<Option<Arc<String>>, Box<dyn std::error::Error + Send>> {
-        Ok(Some(Arc::new(task_item)))
+    // Hashed while being added, so a file that was moved or renamed takes over the rows of its old path before
+    // the delete_missing actions would drop them
+    async fn process_task_item(&self, task_item: String, _dry_run: bool, pool: WebServerActionDataContext) -> Result<Option<Arc<ImagePathImport>>, Box<dyn std::error::Error + Send>> {
+        let content_hash = match hash_image_and_find_moved_from(&task_item, &pool.pool).await {
+            Ok(v) => Some(v),
+            Err(e) => {
+                println!("could not hash {}: {}", task_item, e);
+                None
+            }
+        };
+        Ok(Some(Arc::new(ImagePathImport { image_path: task_item, content_hash })))
     }
 
-    async fn process_task_output(&self, task_output: Arc<String>, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
-        execute_insert_image_path_sql(&task_output, &pool.pool).await
+    async fn process_task_output(&self, task_output: Arc<ImagePathImport>, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        let moved = match &task_output.content_hash {
+            Some(content_hash) if content_hash.moved_from.is_some() => store_image_content_hash_result(content_hash, &pool.pool).await?,
+            _ => false,
+        };
+        if moved {
+            return Ok(());
+        }
+        execute_insert_image_path_sql(&task_output.image_path, &pool.pool).await
             .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)?;
+        if let Some(content_hash) = &task_output.content_hash {
+            execute_insert_image_content_hash_sql(&content_hash.content_hash, &pool.pool).await
+                .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)?;
+        }
         Ok(())
     }
 
@@ -52,7 +90,7 @@ impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<Str
     }
 
     fn get_description(&self) -> String {
-        "if the image_paths table is missing any entries, it will add them".to_string()
+        "if the image_paths table is missing any entries, it will add them with their content hash; a file whose hash matches a path missing from disk takes over that path's rows instead".to_string()
     }
 
     fn get_item_name(&self) -> String {
@@ -66,7 +104,7 @@ impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<Str
 
 pub struct InsertNewImagePathsAction;
 impl InsertNewImagePathsAction {
-    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<String>> {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImagePathImport>> {
         AnalysisTaskItemProcessorOrchestrator::new(Arc::new(ImagePathsProcessor::new()))
     }
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 // new_ocr_text_action.rs
 
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::io::ErrorKind;
 use std::sync::Arc;
@@ -7,57 +8,69 @@ use std::sync::Arc;
 use async_trait::async_trait;
 
 use crate::core::data_context::WebServerActionDataContext;
+use crate::actions::action_registry::ActionOption;
 use crate::actions::analysis_task_item_processor::LogProgListenerPair;
 use crate::calc::file_paths_comparison::FilePathComparisonModel;
-use crate::converters::extract_image_ocr_text::extract_image_ocr_text;
-use crate::database::query::query_image_ocr_text::query_ocr_text_table_count;
-use crate::database::update::update_image_ocr_text::execute_insert_image_ocr_text_sql;
-use crate::metrics::ocr_text_metrics::get_image_path_comparison_ocr_text_table_analysis;
-use crate::models::image_ocr_text::ImageOcrText;
+use crate::converters::extract_image_ocr_text::extract_image_ocr_structured;
+use crate::converters::extract_image_ocr_text::ImageOcrResult;
+use crate::converters::extract_image_ocr_text::OcrOptions;
+use crate::database::query::query_image_ocr_summary::{query_ocr_summary_current_count, query_ocr_summary_stale_image_paths, query_ocr_summary_table_count};
+use crate::database::update::update_image_ocr_result::execute_replace_image_ocr_result_sql;
+use crate::metrics::ocr_summary_metrics::get_image_path_comparison_ocr_summary_table_analysis;
 use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
 use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
 
 
 
-pub struct OcrTextProcessor;
+pub struct OcrTextProcessor {
+    options: OcrOptions,
+}
 impl OcrTextProcessor {
-    pub fn new() -> Self { Self {} }
+    pub fn new() -> Self { Self::new_with_options(OcrOptions::from_config()) }
+    pub fn new_with_options(options: OcrOptions) -> Self { Self { options } }
 }
 
 
 #[async_trait]
-impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageOcrText>> for OcrTextProcessor {
+impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageOcrResult>> for OcrTextProcessor {
     async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<FilePathComparisonModel>, Box<dyn std::error::Error + Send>> {
-        get_image_path_comparison_ocr_text_table_analysis(&pool.pool, log_prog_listener).await
+        get_image_path_comparison_ocr_summary_table_analysis(&pool.pool, log_prog_listener).await
             .map(|v| Arc::new(v))
             .map_err(|e| Box::new(std::io::Error::new(ErrorKind::Other, format!("{}", e))) as Box<dyn std::error::Error + Send>)
     }
 
-    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<FilePathComparisonModel>, log_prog_listener: Option<LogProgListenerPair>) -> Result<HashSet<String>, Box<dyn std::error::Error + Send>> {
-        Ok(analysis.files_missing_from_b.clone())
+    async fn get_task_items_from_analysis(&self, pool: WebServerActionDataContext, analysis: Arc<FilePathComparisonModel>, log_prog_listener: Option<LogProgListenerPair>) -> Result<HashSet<String>, Box<dyn std::error::Error + Send>> {
+        let mut items = analysis.files_missing_from_b.clone();
+        if self.options.reprocess {
+            let languages = self.options.get_languages_arg();
+            items.extend(query_ocr_summary_stale_image_paths(&languages, &self.options.preprocess.get_enabled_names(), &pool.pool).await?);
+        }
+        Ok(items)
     }
 
-    async fn process_task_item(&self, task_item: String, _dry_run: bool, _pool: WebServerActionDataContext) -> Result<Option<Arc<ImageOcrText>>, Box<dyn std::error::Error + Send>> {
-        extract_image_ocr_text(&task_item)
-            .map(|ocr_text| ImageOcrText { image_path: task_item, ocr_text })
+    async fn process_task_item(&self, task_item: String, _dry_run: bool, _pool: WebServerActionDataContext) -> Result<Option<Arc<ImageOcrResult>>, Box<dyn std::error::Error + Send>> {
+        extract_image_ocr_structured(&task_item, &self.options)
             .map(Arc::new)
             .map(Some)
             .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
     }
 
-    async fn process_task_output(&self, task_output: Arc<ImageOcrText>, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
-        execute_insert_image_ocr_text_sql(&task_output, pool.pool).await
-            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+    async fn process_task_output(&self, task_output: Arc<ImageOcrResult>, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        execute_replace_image_ocr_result_sql(&task_output, &pool.pool).await
     }
 
     async fn task_already_completed(&self, task_input: &String, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
-        query_ocr_text_table_count(&task_input, &pool.pool).await
+        let count = match self.options.reprocess {
+            true => query_ocr_summary_current_count(task_input, &self.options.get_languages_arg(), &self.options.preprocess.get_enabled_names(), &pool.pool).await,
+            false => query_ocr_summary_table_count(task_input, &pool.pool).await,
+        };
+        count
             .map(|v| v > 0)
             .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
     }
 
     fn get_description(&self) -> String {
-        "if the ocr_text table is missing any entries, it will add them".to_string()
+        "if the ocr tables are missing any entries, it will run tesseract for the text, word boxes and confidences; with reprocessing on, images read with other languages or preprocessing options are read again".to_string()
     }
 
     fn get_item_name(&self) -> String {
@@ -67,11 +80,19 @@ impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<Str
     fn get_process_action_name(&self) -> String {
         "add".to_string()
     }
+
+    fn get_action_options(&self) -> Vec<ActionOption> {
+        OcrOptions::get_action_options()
+    }
+
+    fn with_action_options(&self, action_options: &HashMap<String, String>) -> Option<Arc<dyn AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageOcrResult>>>> {
+        Some(Arc::new(Self::new_with_options(OcrOptions::from_action_options(action_options))))
+    }
 }
 
 pub struct InsertNewOcrTextsOrchestratorAction;
 impl InsertNewOcrTextsOrchestratorAction {
-    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageOcrText>> {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageOcrResult>> {
         AnalysisTaskItemProcessorOrchestrator::new(Arc::new(OcrTextProcessor::new()))
     }
 }
\ No newline at end of file
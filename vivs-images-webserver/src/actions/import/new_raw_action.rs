@@ -0,0 +1,77 @@
+// new_raw_action.rs
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::actions::analysis_task_item_processor::LogProgListenerPair;
+use crate::calc::file_paths_comparison::FilePathComparisonModel;
+use crate::converters::extract_image_raw::open_and_extract_image_raw_model;
+use crate::database::query::query_image_raw::query_raw_table_count;
+use crate::database::update::update_image_raw::execute_insert_image_raw_sql;
+use crate::metrics::raw_metrics::get_image_path_comparison_raw_table_analysis;
+use crate::models::image_raw::ImageRaw;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+
+
+pub struct RawProcessor;
+impl RawProcessor {
+    pub fn new() -> Self { Self {} }
+}
+
+
+#[async_trait]
+impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageRaw>> for RawProcessor {
+    async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<FilePathComparisonModel>, Box<dyn std::error::Error + Send>> {
+        get_image_path_comparison_raw_table_analysis(&pool.pool, log_prog_listener).await
+            .map(Arc::new)
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+    }
+
+    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<FilePathComparisonModel>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<HashSet<String>, Box<dyn std::error::Error + Send>> {
+        Ok(analysis.files_missing_from_b.clone())
+    }
+
+    async fn process_task_item(&self, task_item: String, _dry_run: bool, _pool: WebServerActionDataContext) -> Result<Option<Arc<ImageRaw>>, Box<dyn std::error::Error + Send>> {
+        open_and_extract_image_raw_model(&task_item)
+            .map(Arc::new)
+            .map(Some)
+            .map_err(|e| {
+                Box::new(e) as Box<dyn std::error::Error + Send>
+            })
+    }
+
+    async fn process_task_output(&self, task_output: Arc<ImageRaw>, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        execute_insert_image_raw_sql(&task_output, &pool.pool).await
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)?;
+        Ok(())
+    }
+
+    async fn task_already_completed(&self, task_input: &String, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
+        query_raw_table_count(task_input, &pool.pool).await
+            .map(|v| v > 0)
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+    }
+
+    fn get_description(&self) -> String {
+        "if the raw table is missing any RAW images, it will add the size of their embedded JPEG preview and the JPEG written alongside them, if any".to_string()
+    }
+
+    fn get_item_name(&self) -> String {
+        "raw".to_string()
+    }
+
+    fn get_process_action_name(&self) -> String {
+        "add".to_string()
+    }
+}
+
+pub struct InsertNewRawOrchestratorAction;
+impl InsertNewRawOrchestratorAction {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageRaw>> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(RawProcessor::new()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+// new_sharpness_action.rs
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::actions::analysis_task_item_processor::LogProgListenerPair;
+use crate::calc::file_paths_comparison::FilePathComparisonModel;
+use crate::converters::extract_image_sharpness::open_and_extract_image_sharpness_model;
+use crate::database::query::query_image_sharpness::query_sharpness_table_count;
+use crate::database::update::update_image_sharpness::execute_insert_image_sharpness_sql;
+use crate::metrics::sharpness_metrics::get_image_path_comparison_sharpness_table_analysis;
+use crate::models::image_sharpness::ImageSharpness;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+
+
+pub struct SharpnessProcessor;
+impl SharpnessProcessor {
+    pub fn new() -> Self { Self {} }
+}
+
+
+#[async_trait]
+impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageSharpness>> for SharpnessProcessor {
+    async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<FilePathComparisonModel>, Box<dyn std::error::Error + Send>> {
+        get_image_path_comparison_sharpness_table_analysis(&pool.pool, log_prog_listener).await
+            .map(Arc::new)
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+    }
+
+    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<FilePathComparisonModel>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<HashSet<String>, Box<dyn std::error::Error + Send>> {
+        Ok(analysis.files_missing_from_b.clone())
+    }
+
+    async fn process_task_item(&self, task_item: String, _dry_run: bool, _pool: WebServerActionDataContext) -> Result<Option<Arc<ImageSharpness>>, Box<dyn std::error::Error + Send>> {
+        open_and_extract_image_sharpness_model(&task_item)
+            .map(Arc::new)
+            .map(Some)
+            .map_err(|e| {
+                Box::new(e) as Box<dyn std::error::Error + Send>
+            })
+    }
+
+    async fn process_task_output(&self, task_output: Arc<ImageSharpness>, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        execute_insert_image_sharpness_sql(&task_output, &pool.pool).await
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)?;
+        Ok(())
+    }
+
+    async fn task_already_completed(&self, task_input: &String, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
+        query_sharpness_table_count(task_input, &pool.pool).await
+            .map(|v| v > 0)
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+    }
+
+    fn get_description(&self) -> String {
+        "if the sharpness table is missing any entries, it will add the laplacian variance and motion blur estimate".to_string()
+    }
+
+    fn get_item_name(&self) -> String {
+        "sharpness".to_string()
+    }
+
+    fn get_process_action_name(&self) -> String {
+        "add".to_string()
+    }
+}
+
+pub struct InsertNewSharpnessOrchestratorAction;
+impl InsertNewSharpnessOrchestratorAction {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<FilePathComparisonModel>, String, HashSet<String>, Arc<ImageSharpness>> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(SharpnessProcessor::new()))
+    }
+}
\ No newline at end of file
@@ -20,9 +20,19 @@ use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
 
 
 
-pub struct SimilarityFromDiskProcessor;
+pub struct SimilarityFromDiskProcessor {
+    algo: ImageComparisonAlgorithm,
+}
 impl SimilarityFromDiskProcessor {
-    pub fn new() -> Self { Self {} }
+    pub fn new() -> Self { Self { algo: ImageComparisonAlgorithm::Magick } }
+
+    pub fn new_with_algo(algo: ImageComparisonAlgorithm) -> Self { Self { algo } }
+
+    fn get_task_item(&self, pair: &(String, String)) -> ComputeImageSimilarityOptions {
+        let mut options = ComputeImageSimilarityOptions::new_defaults(pair.0.clone(), pair.1.clone());
+        options.algo = self.algo.clone();
+        options
+    }
 }
 
 use async_trait::async_trait;
@@ -31,7 +41,7 @@ use async_trait::async_trait;
 #[async_trait]
 impl AnalysisTaskItemProcessor<Arc<CrossFilePathComparisonModel>, Arc<ComputeImageSimilarityOptions>, HashSet<Arc<ComputeImageSimilarityOptions>>, Arc<ImageSimilarity>> for SimilarityFromDiskProcessor {
     async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<CrossFilePathComparisonModel>, Box<dyn std::error::Error + Send>> {
-        get_image_paths_full_difference_similarity_analysis(&pool.pool, log_prog_listener).await
+        get_image_paths_full_difference_similarity_analysis(&self.algo, &pool.pool, log_prog_listener).await
             .map(|v| Arc::new(v))
             .map_err(|e| Box::new(std::io::Error::new(ErrorKind::Other, format!("{}", e))) as Box<dyn std::error::Error + Send>)
     }
@@ -46,7 +56,7 @@ impl AnalysisTaskItemProcessor<Arc<CrossFilePathComparisonModel>, Arc<ComputeIma
                     if i % 4000 == 0 {
                         log_prog_listener.0(calculate_progress(i, total));
                     }
-                    ComputeImageSimilarityOptions::new_defaults(x.0.clone(), x.1.clone())
+                    self.get_task_item(x)
                 })
                 .map(Arc::new)
                 .collect()
@@ -54,7 +64,7 @@ impl AnalysisTaskItemProcessor<Arc<CrossFilePathComparisonModel>, Arc<ComputeIma
         } else {
             Ok(analysis.pairs_missing_from_b
                 .iter()
-                .map(|x| ComputeImageSimilarityOptions::new_defaults(x.0.clone(), x.1.clone()))
+                .map(|x| self.get_task_item(x))
                 .map(Arc::new)
                 .collect()
             )
@@ -79,12 +89,16 @@ impl AnalysisTaskItemProcessor<Arc<CrossFilePathComparisonModel>, Arc<ComputeIma
     }
 
     async fn task_already_completed(&self, task_input: &Arc<ComputeImageSimilarityOptions>, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
-        get_image_similarity_value_exists_in_db(&task_input.image_path_a, &task_input.image_path_b, &pool.pool).await
+        get_image_similarity_value_exists_in_db(&task_input.image_path_a, &task_input.image_path_b, &task_input.algo, &pool.pool).await
             .map_err(|e| Box::new(std::io::Error::new(ErrorKind::Other, format!("{}", e))) as Box<dyn std::error::Error + Send>)
     }
 
     fn get_description(&self) -> String {
-        "if the similarity table is missing any entries from the disk, it will add them".to_string()
+        if self.algo == ImageComparisonAlgorithm::Magick {
+            "if the similarity table is missing any entries from the disk, it will add them".to_string()
+        } else {
+            format!("if the similarity table is missing any entries from the disk for the {} algorithm, it will add them", self.algo.get_name())
+        }
     }
 
     fn get_item_name(&self) -> String {
@@ -92,7 +106,11 @@ impl AnalysisTaskItemProcessor<Arc<CrossFilePathComparisonModel>, Arc<ComputeIma
     }
 
     fn get_process_action_name(&self) -> String {
-        "add_from_disk".to_string()
+        if self.algo == ImageComparisonAlgorithm::Magick {
+            "add_from_disk".to_string()
+        } else {
+            format!("add_from_disk_{}", self.algo.get_name())
+        }
     }
 }
 
@@ -101,6 +119,10 @@ impl InsertNewSimilaritysFromDiskOrchestratorAction {
     pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<CrossFilePathComparisonModel>, Arc<ComputeImageSimilarityOptions>, HashSet<Arc<ComputeImageSimilarityOptions>>, Arc<ImageSimilarity>> {
         AnalysisTaskItemProcessorOrchestrator::new(Arc::new(SimilarityFromDiskProcessor::new()))
     }
+
+    pub fn new_with_algo(algo: ImageComparisonAlgorithm) -> AnalysisTaskItemProcessorOrchestrator<Arc<CrossFilePathComparisonModel>, Arc<ComputeImageSimilarityOptions>, HashSet<Arc<ComputeImageSimilarityOptions>>, Arc<ImageSimilarity>> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(SimilarityFromDiskProcessor::new_with_algo(algo)))
+    }
 }
 
 
@@ -195,7 +217,7 @@ impl AnalysisTaskItemProcessor<Arc<CrossFilePathComparisonModel>, Arc<ComputeIma
     }
 
     async fn task_already_completed(&self, task_input: &Arc<ComputeImageSimilarityOptions>, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
-        get_image_similarity_value_exists_in_db(&task_input.image_path_a, &task_input.image_path_b, &pool.pool).await
+        get_image_similarity_value_exists_in_db(&task_input.image_path_a, &task_input.image_path_b, &task_input.algo, &pool.pool).await
             .map_err(|e| Box::new(std::io::Error::new(ErrorKind::Other, format!("{}", e))) as Box<dyn std::error::Error + Send>)
     }
 
@@ -0,0 +1,156 @@
+// new_similarity_neighbors_action.rs
+
+use std::io::ErrorKind;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use image::DynamicImage;
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::actions::analysis_task_item_processor::LogProgListenerPair;
+use crate::calc::file_paths_comparison::FilePathComparisonModel;
+use crate::converters::extract_image_similarity::{extract_image_similarity_using_ssim_confidence, get_similarity_thumbnail_image};
+use crate::converters::extract_image_similarity::ComputeImageSimilarityOptions;
+use crate::database::query::query_image_similarity_neighbors::query_similarity_neighbor_scan_count;
+use crate::database::query::query_image_thumbnail::get_thumbnail_image_paths_from_db;
+use crate::database::update::update_image_similarity_neighbors::execute_replace_image_similarity_neighbors_sql;
+use crate::metrics::similarity_metrics::get_similarity_neighbor_scan_analysis;
+use crate::models::config::similarity::{get_similarity_top_k, get_similarity_top_k_min_value, SIMILARITY_THUMBNAIL_SIZE, SIMILARITY_TOP_K_ALGORITHM};
+use crate::models::image_similarity_neighbor::{ImageSimilarityNeighbor, ImageSimilarityNeighborScan};
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+
+
+// An image to scan, along with every image it gets compared against. The candidate thumbnails are loaded and
+// decoded once per run and shared by every task, rather than fetched again for each comparison
+#[derive(Clone)]
+pub struct SimilarityNeighborScanTask {
+    pub image_path: String,
+    pub candidates: Arc<Vec<(String, DynamicImage)>>,
+}
+
+impl std::fmt::Display for SimilarityNeighborScanTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.image_path)
+    }
+}
+
+pub struct SimilarityNeighborsProcessor {
+    top_k: usize,
+    min_value: f32,
+}
+impl SimilarityNeighborsProcessor {
+    pub fn new() -> Self {
+        Self { top_k: get_similarity_top_k(), min_value: get_similarity_top_k_min_value() }
+    }
+}
+
+#[async_trait]
+impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, SimilarityNeighborScanTask, Vec<SimilarityNeighborScanTask>, Arc<ImageSimilarityNeighborScan>> for SimilarityNeighborsProcessor {
+    async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<FilePathComparisonModel>, Box<dyn std::error::Error + Send>> {
+        get_similarity_neighbor_scan_analysis(&SIMILARITY_TOP_K_ALGORITHM, &pool.pool, log_prog_listener).await
+            .map(Arc::new)
+    }
+
+    async fn get_task_items_from_analysis(&self, pool: WebServerActionDataContext, analysis: Arc<FilePathComparisonModel>, log_prog_listener: Option<LogProgListenerPair>) -> Result<Vec<SimilarityNeighborScanTask>, Box<dyn std::error::Error + Send>> {
+        if analysis.files_missing_from_b.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut candidate_paths: Vec<String> = get_thumbnail_image_paths_from_db(&pool.pool).await?.into_iter().collect();
+        candidate_paths.sort();
+        if let Some(x) = &log_prog_listener {
+            x.1(&format!("loading {} candidate thumbnails", candidate_paths.len()));
+        }
+        let mut candidates = vec![];
+        for candidate_path in candidate_paths {
+            // a thumbnail that cannot be loaded or decoded leaves that image out of every comparison
+            match get_similarity_thumbnail_image(&candidate_path, &pool).await {
+                Ok(Some(image)) => candidates.push((candidate_path, image)),
+                Ok(None) => {},
+                Err(e) => println!("could not load similarity thumbnail of {}: {}", candidate_path, e),
+            }
+        }
+        let candidates = Arc::new(candidates);
+        Ok(analysis.files_missing_from_b
+            .iter()
+            .map(|image_path| SimilarityNeighborScanTask { image_path: image_path.clone(), candidates: candidates.clone() })
+            .collect())
+    }
+
+    async fn process_task_item(&self, task_item: SimilarityNeighborScanTask, _dry_run: bool, pool: WebServerActionDataContext) -> Result<Option<Arc<ImageSimilarityNeighborScan>>, Box<dyn std::error::Error + Send>> {
+        let image = match task_item.candidates.iter().find(|(path, _)| *path == task_item.image_path) {
+            Some((_, image)) => Some(image.clone()),
+            None => get_similarity_thumbnail_image(&task_item.image_path, &pool).await?,
+        };
+        let image = image
+            .ok_or_else(|| Box::new(std::io::Error::new(ErrorKind::NotFound, "thumbnail not found")) as Box<dyn std::error::Error + Send>)?;
+        let options = ComputeImageSimilarityOptions {
+            algo: SIMILARITY_TOP_K_ALGORITHM,
+            max_dimension: Some(SIMILARITY_THUMBNAIL_SIZE),
+            filter_type: Some(image::imageops::FilterType::Nearest),
+            image_path_a: task_item.image_path.clone(),
+            image_path_b: String::new(),
+        };
+
+        let mut compared_count = 0;
+        let mut neighbors = vec![];
+        for (candidate, candidate_image) in task_item.candidates.iter() {
+            if *candidate == task_item.image_path {
+                continue;
+            }
+            let (similarity_value, similarity_confidence) = match extract_image_similarity_using_ssim_confidence(image.clone(), candidate_image.clone(), &options) {
+                Ok(v) => v,
+                Err(e) => {
+                    println!("could not compare {} with {}: {}", task_item.image_path, candidate, e);
+                    continue;
+                }
+            };
+            compared_count += 1;
+            if similarity_value >= self.min_value {
+                neighbors.push(ImageSimilarityNeighbor {
+                    image_path: task_item.image_path.clone(),
+                    neighbor_path: candidate.clone(),
+                    image_comparison_algorithm: SIMILARITY_TOP_K_ALGORITHM,
+                    similarity_value,
+                    similarity_confidence,
+                });
+            }
+        }
+        neighbors.sort_by(|a, b| b.similarity_value.total_cmp(&a.similarity_value).then_with(|| a.neighbor_path.cmp(&b.neighbor_path)));
+
+        Ok(Some(Arc::new(ImageSimilarityNeighborScan {
+            image_path: task_item.image_path,
+            image_comparison_algorithm: SIMILARITY_TOP_K_ALGORITHM,
+            compared_count,
+            neighbors,
+        })))
+    }
+
+    async fn process_task_output(&self, task_output: Arc<ImageSimilarityNeighborScan>, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        execute_replace_image_similarity_neighbors_sql(&task_output, self.top_k, &pool.pool).await
+    }
+
+    async fn task_already_completed(&self, task_input: &SimilarityNeighborScanTask, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
+        query_similarity_neighbor_scan_count(&task_input.image_path, &SIMILARITY_TOP_K_ALGORITHM, &pool.pool).await
+            .map(|v| v > 0)
+    }
+
+    fn get_description(&self) -> String {
+        format!("compares each image that has a thumbnail against every other one and keeps its {} most similar neighbours with similarity >= {}; newly scanned images are also offered to the lists of images scanned before them", self.top_k, self.min_value)
+    }
+
+    fn get_item_name(&self) -> String {
+        "similarity".to_string()
+    }
+
+    fn get_process_action_name(&self) -> String {
+        "add_top_k_neighbors".to_string()
+    }
+}
+
+pub struct InsertNewSimilarityNeighborsOrchestratorAction;
+impl InsertNewSimilarityNeighborsOrchestratorAction {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<FilePathComparisonModel>, SimilarityNeighborScanTask, Vec<SimilarityNeighborScanTask>, Arc<ImageSimilarityNeighborScan>> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(SimilarityNeighborsProcessor::new()))
+    }
+}
\ No newline at end of file
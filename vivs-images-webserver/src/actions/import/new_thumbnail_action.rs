@@ -13,6 +13,7 @@ use crate::converters::extract_image_thumbnail::open_and_extract_multiple_image_
 use crate::database::query::query_image_thumbnail::query_thumbnail_table_count;
 use crate::database::update::update_image_thumbnail::execute_insert_image_thumbnail_sql;
 use crate::metrics::thumbnail_metrics::get_image_path_comparison_thumbnail_table_analysis;
+use crate::models::config::thumbnails::get_thumbnail_format;
 use crate::models::image_thumbnail::ImageThumbnail;
 use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
 use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
@@ -20,9 +21,11 @@ use crate::models::image_thumbnail::ImageThumbnailVec;
 use crate::models::image_thumbnail::ThumbnailFormat;
 
 
-pub struct ThumbnailProcessor;
+pub struct ThumbnailProcessor {
+    thumbnail_format: ThumbnailFormat,
+}
 impl ThumbnailProcessor {
-    pub fn new() -> Self { Self {} }
+    pub fn new() -> Self { Self { thumbnail_format: get_thumbnail_format() } }
 }
 
 
@@ -42,7 +45,7 @@ impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<Str
         open_and_extract_multiple_image_thumbnails_standard_sizes(&task_item)
             .map(|vals| {
                 let imgs = vals.iter()
-                    .map(|img| ImageThumbnail::from_image(task_item.clone(), ThumbnailFormat::PNG, img))
+                    .map(|img| ImageThumbnail::from_image(task_item.clone(), self.thumbnail_format, img))
                     .collect::<Vec<ImageThumbnail>>();
                 Some(Arc::new(ImageThumbnailVec(imgs)))
             }).map_err(|e| {
@@ -7,4 +7,14 @@ pub mod update_ocr_text_indicator;
 pub mod update_tags_indicator;
 pub mod update_iptc_indicator;
 pub mod update_image_paths_indicator;
-pub mod update_xmp_indicator;
\ No newline at end of file
+pub mod update_xmp_indicator;
+pub mod update_color_indicator;
+pub mod update_sharpness_indicator;
+pub mod update_exposure_indicator;
+pub mod update_ocr_summary_indicator;
+pub mod update_content_hash_indicator;
+pub mod update_raw_indicator;
+pub mod update_frames_indicator;
+pub mod update_orientation_indicator;
+pub mod update_geocode_indicator;
+pub mod update_event_indicator;
\ No newline at end of file
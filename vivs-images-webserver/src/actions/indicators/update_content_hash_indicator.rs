@@ -0,0 +1,75 @@
+use std::error::Error;
+
+use async_trait::async_trait;
+use convert_case::{Case, Casing};
+use nameof::name_of_type;
+use sqlx::SqlitePool;
+
+use crate::actions::action_indicator::{ActionIndicatorCheckMessage, IActionIndicator};
+use crate::metrics::content_hash_metrics::{get_content_hash_missing_in_sql_count, get_content_hash_missing_on_disk_count};
+
+
+
+pub struct ImagesOnDiskWithMissingContentHashIndicator;
+impl ImagesOnDiskWithMissingContentHashIndicator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl IActionIndicator for ImagesOnDiskWithMissingContentHashIndicator {
+    fn get_name(&self) -> String {
+        name_of_type!(ImagesOnDiskWithMissingContentHashIndicator).to_case(Case::Snake)
+    }
+
+    fn get_label(&self) -> String {
+        name_of_type!(ImagesOnDiskWithMissingContentHashIndicator).to_case(Case::Sentence)
+    }
+
+    fn get_description(&self) -> String {
+        "If the content_hash table is missing any images that are on the disk".to_string()
+    }
+
+    fn get_action_name(&self) -> String { "add_content_hash".to_string() }
+
+    fn get_cron_schedule(&self) -> String { String::new() }
+
+    async fn perform_indicator_check_action(&self, pool: &SqlitePool) -> Result<ActionIndicatorCheckMessage, Box<dyn Error + Send>> {
+        let (difference_total, msg) = get_content_hash_missing_in_sql_count(pool).await?;
+        Ok(ActionIndicatorCheckMessage(difference_total != 0, msg))
+    }
+}
+
+
+
+pub struct ImagesInContentHashSqlDbWithMissingImageOnDiskIndicator;
+impl ImagesInContentHashSqlDbWithMissingImageOnDiskIndicator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl IActionIndicator for ImagesInContentHashSqlDbWithMissingImageOnDiskIndicator {
+    fn get_name(&self) -> String {
+        name_of_type!(ImagesInContentHashSqlDbWithMissingImageOnDiskIndicator).to_case(Case::Snake)
+    }
+
+    fn get_label(&self) -> String {
+        name_of_type!(ImagesInContentHashSqlDbWithMissingImageOnDiskIndicator).to_case(Case::Sentence)
+    }
+
+    fn get_description(&self) -> String {
+        "If the content_hash table has values for images that are not found or valid on the disk".to_string()
+    }
+
+    fn get_action_name(&self) -> String { "delete_missing_content_hash".to_string() }
+
+    fn get_cron_schedule(&self) -> String { String::new() }
+
+    async fn perform_indicator_check_action(&self, pool: &SqlitePool) -> Result<ActionIndicatorCheckMessage, Box<dyn Error + Send>> {
+        let (difference_total, msg) = get_content_hash_missing_on_disk_count(pool).await?;
+        Ok(ActionIndicatorCheckMessage(difference_total != 0, msg))
+    }
+}
\ No newline at end of file
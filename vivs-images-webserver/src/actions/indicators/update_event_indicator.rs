@@ -0,0 +1,42 @@
+use std::error::Error;
+
+use async_trait::async_trait;
+use convert_case::{Case, Casing};
+use nameof::name_of_type;
+use sqlx::SqlitePool;
+
+use crate::actions::action_indicator::{ActionIndicatorCheckMessage, IActionIndicator};
+use crate::database::query::query_image_event::query_images_missing_event_count;
+
+
+
+pub struct ImagesWithCaptureTimeMissingEventIndicator;
+impl ImagesWithCaptureTimeMissingEventIndicator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl IActionIndicator for ImagesWithCaptureTimeMissingEventIndicator {
+    fn get_name(&self) -> String {
+        name_of_type!(ImagesWithCaptureTimeMissingEventIndicator).to_case(Case::Snake)
+    }
+
+    fn get_label(&self) -> String {
+        name_of_type!(ImagesWithCaptureTimeMissingEventIndicator).to_case(Case::Sentence)
+    }
+
+    fn get_description(&self) -> String {
+        "If any images with a capture time have not been grouped into an event yet".to_string()
+    }
+
+    fn get_action_name(&self) -> String { "cluster_events".to_string() }
+
+    fn get_cron_schedule(&self) -> String { String::new() }
+
+    async fn perform_indicator_check_action(&self, pool: &SqlitePool) -> Result<ActionIndicatorCheckMessage, Box<dyn Error + Send>> {
+        let total = query_images_missing_event_count(pool).await?;
+        Ok(ActionIndicatorCheckMessage(total != 0, format!("There are {} images with a capture time and no event", total)))
+    }
+}
\ No newline at end of file
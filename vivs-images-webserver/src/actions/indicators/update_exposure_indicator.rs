@@ -0,0 +1,75 @@
+use std::error::Error;
+
+use async_trait::async_trait;
+use convert_case::{Case, Casing};
+use nameof::name_of_type;
+use sqlx::SqlitePool;
+
+use crate::actions::action_indicator::{ActionIndicatorCheckMessage, IActionIndicator};
+use crate::metrics::exposure_metrics::{get_exposure_missing_in_sql_count, get_exposure_missing_on_disk_count};
+
+
+
+pub struct ImagesOnDiskWithMissingExposureIndicator;
+impl ImagesOnDiskWithMissingExposureIndicator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl IActionIndicator for ImagesOnDiskWithMissingExposureIndicator {
+    fn get_name(&self) -> String {
+        name_of_type!(ImagesOnDiskWithMissingExposureIndicator).to_case(Case::Snake)
+    }
+
+    fn get_label(&self) -> String {
+        name_of_type!(ImagesOnDiskWithMissingExposureIndicator).to_case(Case::Sentence)
+    }
+
+    fn get_description(&self) -> String {
+        "If the exposure table is missing any images that are on the disk".to_string()
+    }
+
+    fn get_action_name(&self) -> String { "add_exposure".to_string() }
+
+    fn get_cron_schedule(&self) -> String { String::new() }
+
+    async fn perform_indicator_check_action(&self, pool: &SqlitePool) -> Result<ActionIndicatorCheckMessage, Box<dyn Error + Send>> {
+        let (difference_total, msg) = get_exposure_missing_in_sql_count(pool).await?;
+        Ok(ActionIndicatorCheckMessage(difference_total != 0, msg))
+    }
+}
+
+
+
+pub struct ImagesInExposureSqlDbWithMissingImageOnDiskIndicator;
+impl ImagesInExposureSqlDbWithMissingImageOnDiskIndicator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl IActionIndicator for ImagesInExposureSqlDbWithMissingImageOnDiskIndicator {
+    fn get_name(&self) -> String {
+        name_of_type!(ImagesInExposureSqlDbWithMissingImageOnDiskIndicator).to_case(Case::Snake)
+    }
+
+    fn get_label(&self) -> String {
+        name_of_type!(ImagesInExposureSqlDbWithMissingImageOnDiskIndicator).to_case(Case::Sentence)
+    }
+
+    fn get_description(&self) -> String {
+        "If the exposure table has values for images that are not found or valid on the disk".to_string()
+    }
+
+    fn get_action_name(&self) -> String { "delete_missing_exposure".to_string() }
+
+    fn get_cron_schedule(&self) -> String { String::new() }
+
+    async fn perform_indicator_check_action(&self, pool: &SqlitePool) -> Result<ActionIndicatorCheckMessage, Box<dyn Error + Send>> {
+        let (difference_total, msg) = get_exposure_missing_on_disk_count(pool).await?;
+        Ok(ActionIndicatorCheckMessage(difference_total != 0, msg))
+    }
+}
\ No newline at end of file
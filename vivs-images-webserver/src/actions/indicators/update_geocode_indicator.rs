@@ -0,0 +1,42 @@
+use std::error::Error;
+
+use async_trait::async_trait;
+use convert_case::{Case, Casing};
+use nameof::name_of_type;
+use sqlx::SqlitePool;
+
+use crate::actions::action_indicator::{ActionIndicatorCheckMessage, IActionIndicator};
+use crate::database::query::query_image_geocode::query_gps_coordinates_missing_geocode_count;
+
+
+
+pub struct ImagesWithGpsMissingPlaceNamesIndicator;
+impl ImagesWithGpsMissingPlaceNamesIndicator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl IActionIndicator for ImagesWithGpsMissingPlaceNamesIndicator {
+    fn get_name(&self) -> String {
+        name_of_type!(ImagesWithGpsMissingPlaceNamesIndicator).to_case(Case::Snake)
+    }
+
+    fn get_label(&self) -> String {
+        name_of_type!(ImagesWithGpsMissingPlaceNamesIndicator).to_case(Case::Sentence)
+    }
+
+    fn get_description(&self) -> String {
+        "If any images with exif gps coordinates have not been looked up in the place list yet".to_string()
+    }
+
+    fn get_action_name(&self) -> String { "add_geocode".to_string() }
+
+    fn get_cron_schedule(&self) -> String { String::new() }
+
+    async fn perform_indicator_check_action(&self, pool: &SqlitePool) -> Result<ActionIndicatorCheckMessage, Box<dyn Error + Send>> {
+        let total = query_gps_coordinates_missing_geocode_count(pool).await?;
+        Ok(ActionIndicatorCheckMessage(total != 0, format!("There are {} images with gps coordinates and no place names", total)))
+    }
+}
\ No newline at end of file
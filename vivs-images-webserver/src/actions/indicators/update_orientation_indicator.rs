@@ -0,0 +1,42 @@
+use std::error::Error;
+
+use async_trait::async_trait;
+use convert_case::{Case, Casing};
+use nameof::name_of_type;
+use sqlx::SqlitePool;
+
+use crate::actions::action_indicator::{ActionIndicatorCheckMessage, IActionIndicator};
+use crate::database::query::query_image_orientation_pending::query_orientation_pending_total;
+
+
+
+pub struct ImagesWithPendingOrientationRecomputeIndicator;
+impl ImagesWithPendingOrientationRecomputeIndicator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl IActionIndicator for ImagesWithPendingOrientationRecomputeIndicator {
+    fn get_name(&self) -> String {
+        name_of_type!(ImagesWithPendingOrientationRecomputeIndicator).to_case(Case::Snake)
+    }
+
+    fn get_label(&self) -> String {
+        name_of_type!(ImagesWithPendingOrientationRecomputeIndicator).to_case(Case::Sentence)
+    }
+
+    fn get_description(&self) -> String {
+        "If any images were analysed before their exif orientation was applied and still need checking".to_string()
+    }
+
+    fn get_action_name(&self) -> String { "recompute_oriented_images".to_string() }
+
+    fn get_cron_schedule(&self) -> String { String::new() }
+
+    async fn perform_indicator_check_action(&self, pool: &SqlitePool) -> Result<ActionIndicatorCheckMessage, Box<dyn Error + Send>> {
+        let total = query_orientation_pending_total(pool).await?;
+        Ok(ActionIndicatorCheckMessage(total != 0, format!("{} images pending an orientation check", total)))
+    }
+}
\ No newline at end of file
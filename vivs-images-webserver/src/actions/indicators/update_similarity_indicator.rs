@@ -7,7 +7,8 @@ use sqlx::SqlitePool;
 
 use crate::actions::action_indicator::{ActionIndicatorCheckMessage, IActionIndicator};
 use crate::database::query::query_image_similarity::{get_count_of_comparisons_per_image_path, get_count_of_image_paths_from_db};
-use crate::metrics::similarity_metrics::{get_simple_similarity_missing_in_sql_count, get_simple_similarity_missing_on_disk_count};
+use crate::metrics::similarity_metrics::{get_similarity_neighbor_scan_missing_count, get_simple_similarity_missing_in_sql_count, get_simple_similarity_missing_on_disk_count};
+use crate::models::config::similarity::{get_similarity_storage_mode, SimilarityStorageMode};
 
 
 
@@ -41,6 +42,10 @@ impl IActionIndicator for ImagesOnDiskWithMissingSimilarityIndicator {
     }
 
     async fn perform_indicator_check_action(&self, pool: &SqlitePool) -> Result<ActionIndicatorCheckMessage, Box<dyn Error + Send>> {
+        // pruned images can be left without any pair, coverage is tracked by the top-k scans instead
+        if get_similarity_storage_mode() == SimilarityStorageMode::TopK {
+            return Ok(ActionIndicatorCheckMessage(false, "similarity is stored as top-k neighbours".to_string()));
+        }
         let (difference_total, msg) = get_simple_similarity_missing_in_sql_count(pool).await?;
         Ok(ActionIndicatorCheckMessage(difference_total != 0, msg))
     }
@@ -65,11 +70,17 @@ impl IActionIndicator for ImagesInSqlDbWithLessThanExpectedSimilarityIndicator {
     }
 
     fn get_description(&self) -> String {
-        "If the similarity table is missing any entries that are in the sql db of known images".to_string()
+        match get_similarity_storage_mode() {
+            SimilarityStorageMode::Full => "If the similarity table is missing any entries that are in the sql db of known images".to_string(),
+            SimilarityStorageMode::TopK => "If any image with a thumbnail has not had its top-k similar neighbours found".to_string(),
+        }
     }
 
     fn get_action_name(&self) -> String {
-        "add_from_db_similarity".to_string()
+        match get_similarity_storage_mode() {
+            SimilarityStorageMode::Full => "add_from_db_similarity".to_string(),
+            SimilarityStorageMode::TopK => "add_top_k_neighbors_similarity".to_string(),
+        }
     }
 
     fn get_cron_schedule(&self) -> String {
@@ -77,6 +88,11 @@ impl IActionIndicator for ImagesInSqlDbWithLessThanExpectedSimilarityIndicator {
     }
 
     async fn perform_indicator_check_action(&self, pool: &SqlitePool) -> Result<ActionIndicatorCheckMessage, Box<dyn Error + Send>> {
+        // top-k storage never has every pair, only each image's nearest neighbours
+        if get_similarity_storage_mode() == SimilarityStorageMode::TopK {
+            let (difference_total, msg) = get_similarity_neighbor_scan_missing_count(pool).await?;
+            return Ok(ActionIndicatorCheckMessage(difference_total != 0, msg));
+        }
         let all = get_count_of_comparisons_per_image_path(pool).await?;
         let expected_total_for_each = get_count_of_image_paths_from_db(pool).await?;
         let difference_total = all.iter().filter(|x| *x.1 != expected_total_for_each).count();
@@ -7,6 +7,8 @@ use sqlx::SqlitePool;
 
 use crate::actions::action_indicator::{ActionIndicatorCheckMessage, IActionIndicator};
 use crate::metrics::thumbnail_metrics::{get_thumbnail_missing_in_sql_count, get_thumbnail_missing_on_disk_count};
+use crate::database::query::query_image_thumbnail::query_thumbnail_count_not_in_storage;
+use crate::models::config::thumbnails::get_thumbnail_storage;
 
 
 
@@ -80,4 +82,41 @@ impl IActionIndicator for ImagesInThumbnailSqlDbWithMissingImageOnDiskIndicator
         let (difference_total, msg) = get_thumbnail_missing_on_disk_count(pool).await?;
         Ok(ActionIndicatorCheckMessage(difference_total != 0, msg))
     }
+}
+
+
+pub struct ThumbnailsOutsideConfiguredStorageIndicator {}
+impl ThumbnailsOutsideConfiguredStorageIndicator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl IActionIndicator for ThumbnailsOutsideConfiguredStorageIndicator {
+    fn get_name(&self) -> String {
+        name_of_type!(ThumbnailsOutsideConfiguredStorageIndicator).to_case(Case::Snake)
+    }
+
+    fn get_label(&self) -> String {
+        name_of_type!(ThumbnailsOutsideConfiguredStorageIndicator).to_case(Case::Sentence)
+    }
+
+    fn get_description(&self) -> String {
+        "If any thumbnail data is kept somewhere other than the storage set with VIVS_THUMBNAIL_STORAGE".to_string()
+    }
+
+    fn get_action_name(&self) -> String {
+        "relocate_thumbnails".to_string()
+    }
+
+    fn get_cron_schedule(&self) -> String {
+        todo!()
+    }
+
+    async fn perform_indicator_check_action(&self, pool: &SqlitePool) -> Result<ActionIndicatorCheckMessage, Box<dyn Error + Send>> {
+        let storage = get_thumbnail_storage();
+        let total = query_thumbnail_count_not_in_storage(&storage, None, pool).await?;
+        Ok(ActionIndicatorCheckMessage(total != 0, format!("{} thumbnails not in {} storage", total, storage.get_name())))
+    }
 }
\ No newline at end of file
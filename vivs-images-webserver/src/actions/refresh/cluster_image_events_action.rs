@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Duration;
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::actions::analysis_task_item_processor::LogProgListenerPair;
+use crate::calc::event_clustering::cluster_image_events;
+use crate::database::query::query_image_event::query_image_event_photos;
+use crate::database::update::update_image_event::execute_replace_image_events_sql;
+use crate::models::config::events::{get_event_max_distance_km, get_event_max_time_gap_hours};
+use crate::models::image_event::ImageEventClustering;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+
+
+pub struct ClusterImageEventsProcessor {
+    max_time_gap_hours: f64,
+    max_distance_km: f64,
+}
+impl ClusterImageEventsProcessor {
+    pub fn new() -> Self {
+        Self { max_time_gap_hours: get_event_max_time_gap_hours(), max_distance_km: get_event_max_distance_km() }
+    }
+
+    pub fn with_gaps(max_time_gap_hours: f64, max_distance_km: f64) -> Self {
+        Self { max_time_gap_hours, max_distance_km }
+    }
+}
+
+
+// The whole clustering is a single task item, since every event boundary depends on the photos either side of it
+#[async_trait]
+impl AnalysisTaskItemProcessor<Arc<ImageEventClustering>, Arc<ImageEventClustering>, Vec<Arc<ImageEventClustering>>, Arc<ImageEventClustering>> for ClusterImageEventsProcessor {
+    async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<ImageEventClustering>, Box<dyn std::error::Error + Send>> {
+        if let Some(x) = &log_prog_listener {
+            x.1("listing images with a capture time");
+            x.0(0.25);
+        }
+        let photos = query_image_event_photos(&pool.pool).await?;
+        let photo_count = photos.len();
+        if let Some(x) = &log_prog_listener {
+            x.1(&format!("splitting {} images into events", photo_count));
+            x.0(0.5);
+        }
+        let max_time_gap = Duration::seconds((self.max_time_gap_hours * 3600.0) as i64);
+        let events = cluster_image_events(photos, max_time_gap, self.max_distance_km);
+        Ok(Arc::new(ImageEventClustering { photo_count, events }))
+    }
+
+    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<ImageEventClustering>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<Vec<Arc<ImageEventClustering>>, Box<dyn std::error::Error + Send>> {
+        Ok(vec![analysis])
+    }
+
+    async fn process_task_item(&self, task_item: Arc<ImageEventClustering>, _dry_run: bool, _pool: WebServerActionDataContext) -> Result<Option<Arc<ImageEventClustering>>, Box<dyn std::error::Error + Send>> {
+        Ok(Some(task_item))
+    }
+
+    async fn process_task_output(&self, task_output: Arc<ImageEventClustering>, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        execute_replace_image_events_sql(&task_output.events, &pool.pool).await
+    }
+
+    async fn task_already_completed(&self, _task_input: &Arc<ImageEventClustering>, _pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
+        Ok(false)
+    }
+
+    fn get_description(&self) -> String {
+        format!("groups images with a capture time into events, starting a new event after a gap of more than {} hours or a move of more than {} km, and replaces the stored events", self.max_time_gap_hours, self.max_distance_km)
+    }
+
+    fn get_item_name(&self) -> String {
+        "events".to_string()
+    }
+
+    fn get_process_action_name(&self) -> String {
+        "cluster".to_string()
+    }
+}
+
+pub struct ClusterImageEventsOrchestratorAction;
+impl ClusterImageEventsOrchestratorAction {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<ImageEventClustering>, Arc<ImageEventClustering>, Vec<Arc<ImageEventClustering>>, Arc<ImageEventClustering>> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(ClusterImageEventsProcessor::new()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,70 @@
+// delete_missing_exposure_action.rs
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
+use crate::actions::analysis_task_item_processor::LogProgListenerPair;
+use crate::calc::file_paths_comparison::FilePathComparisonModel;
+use crate::database::query::query_image_exposure::query_exposure_table_count;
+use crate::database::update::update_image_exposure::execute_delete_image_exposure_sql;
+use crate::metrics::exposure_metrics::get_image_path_comparison_exposure_table_analysis;
+
+
+
+pub struct ExposureProcessor;
+impl ExposureProcessor {
+    pub fn new() -> Self { Self {} }
+}
+
+
+#[async_trait]
+impl AnalysisTaskItemProcessor<Arc<FilePathComparisonModel>, String, HashSet<String>, String> for ExposureProcessor {
+    async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<FilePathComparisonModel>, Box<dyn std::error::Error + Send>> {
+        get_image_path_comparison_exposure_table_analysis(&pool.pool, log_prog_listener).await
+            .map(Arc::new)
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+    }
+
+    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<FilePathComparisonModel>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<HashSet<String>, Box<dyn std::error::Error + Send>> {
+        Ok(analysis.files_missing_from_a.clone())
+    }
+
+    async fn process_task_item(&self, task_item: String, _dry_run: bool, _pool: WebServerActionDataContext) -> Result<Option<String>, Box<dyn std::error::Error + Send>> {
+        Ok(Some(task_item))
+    }
+
+    async fn process_task_output(&self, task_output: String, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        execute_delete_image_exposure_sql(&task_output, &pool.pool).await
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+    }
+
+    async fn task_already_completed(&self, task_input: &String, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
+        query_exposure_table_count(task_input, &pool.pool).await
+            .map(|v| v == 0)
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send>)
+    }
+
+    fn get_description(&self) -> String {
+        "if the exposure table has any entries missing from disk, it will delete them".to_string()
+    }
+
+    fn get_item_name(&self) -> String {
+        "exposure".to_string()
+    }
+
+    fn get_process_action_name(&self) -> String {
+        "delete_missing".to_string()
+    }
+}
+
+pub struct DeleteMissingExposureOrchestratorAction;
+impl DeleteMissingExposureOrchestratorAction {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<FilePathComparisonModel>, String, HashSet<String>, String> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(ExposureProcessor::new()))
+    }
+}
\ No newline at end of file
@@ -4,4 +4,17 @@ pub mod delete_missing_similarity_action;
 pub mod delete_missing_thumbnails_action;
 pub mod delete_missing_aspect_ratio_action;
 pub mod delete_missing_iptc_action;
-pub mod delete_missing_xmp_action;
\ No newline at end of file
+pub mod delete_missing_xmp_action;
+pub mod delete_missing_color_action;
+pub mod delete_missing_sharpness_action;
+pub mod delete_missing_exposure_action;
+pub mod delete_missing_ocr_summary_action;
+pub mod delete_missing_content_hash_action;
+pub mod delete_missing_image_paths_action;
+pub mod prune_similarity_to_top_k_action;
+pub mod delete_missing_raw_action;
+pub mod delete_missing_frames_action;
+pub mod recompute_oriented_images_action;
+pub mod relocate_thumbnails_action;
+pub mod warm_thumbnail_cache_action;
+pub mod cluster_image_events_action;
\ No newline at end of file
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::actions::analysis_task_item_processor::LogProgListenerPair;
+use crate::database::query::query_image_similarity_neighbors::query_similarity_prune_plans;
+use crate::database::update::update_image_similarity_neighbors::execute_prune_image_similarity_to_top_k_sql;
+use crate::models::config::similarity::{get_similarity_storage_mode, get_similarity_top_k, get_similarity_top_k_min_value, SimilarityStorageMode, SIMILARITY_STORAGE_ENV_VAR};
+use crate::models::image_similarity_neighbor::{ImageSimilarityPruneAnalysis, ImageSimilarityPrunePlan};
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+
+
+pub struct PruneSimilarityToTopKProcessor {
+    top_k: usize,
+    min_value: f32,
+}
+impl PruneSimilarityToTopKProcessor {
+    pub fn new() -> Self {
+        Self { top_k: get_similarity_top_k(), min_value: get_similarity_top_k_min_value() }
+    }
+}
+
+
+#[async_trait]
+impl AnalysisTaskItemProcessor<Arc<ImageSimilarityPruneAnalysis>, ImageSimilarityPrunePlan, Vec<ImageSimilarityPrunePlan>, ImageSimilarityPrunePlan> for PruneSimilarityToTopKProcessor {
+    async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<ImageSimilarityPruneAnalysis>, Box<dyn std::error::Error + Send>> {
+        // in full mode the pairwise actions would just compute the deleted pairs again
+        if get_similarity_storage_mode() != SimilarityStorageMode::TopK {
+            return Err(Box::new(std::io::Error::other(
+                format!("similarity is stored in full; set {}=top_k before pruning", SIMILARITY_STORAGE_ENV_VAR))));
+        }
+        if let Some(x) = &log_prog_listener {
+            x.1("counting similarity rows per algorithm");
+            x.0(0.5);
+        }
+        let plans = query_similarity_prune_plans(self.top_k, self.min_value, &pool.pool).await?;
+        Ok(Arc::new(ImageSimilarityPruneAnalysis { plans }))
+    }
+
+    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<ImageSimilarityPruneAnalysis>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<Vec<ImageSimilarityPrunePlan>, Box<dyn std::error::Error + Send>> {
+        Ok(analysis.plans.clone())
+    }
+
+    async fn process_task_item(&self, task_item: ImageSimilarityPrunePlan, _dry_run: bool, _pool: WebServerActionDataContext) -> Result<Option<ImageSimilarityPrunePlan>, Box<dyn std::error::Error + Send>> {
+        Ok(Some(task_item))
+    }
+
+    async fn process_task_output(&self, task_output: ImageSimilarityPrunePlan, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        execute_prune_image_similarity_to_top_k_sql(&task_output, &pool.pool).await?;
+        Ok(())
+    }
+
+    async fn task_already_completed(&self, _task_input: &ImageSimilarityPrunePlan, _pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
+        Ok(false)
+    }
+
+    fn get_description(&self) -> String {
+        format!("moves each image's {} most similar neighbours (similarity >= {}) from the similarity table into the top-k neighbours table and deletes the pairs no image kept", self.top_k, self.min_value)
+    }
+
+    fn get_item_name(&self) -> String {
+        "similarity".to_string()
+    }
+
+    fn get_process_action_name(&self) -> String {
+        "prune_to_top_k".to_string()
+    }
+}
+
+pub struct PruneSimilarityToTopKOrchestratorAction;
+impl PruneSimilarityToTopKOrchestratorAction {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<ImageSimilarityPruneAnalysis>, ImageSimilarityPrunePlan, Vec<ImageSimilarityPrunePlan>, ImageSimilarityPrunePlan> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(PruneSimilarityToTopKProcessor::new()))
+    }
+}
\ No newline at end of file
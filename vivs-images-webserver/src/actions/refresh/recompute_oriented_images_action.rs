@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::actions::analysis_task_item_processor::LogProgListenerPair;
+use crate::converters::extract_image_aspect_ratio::extract_image_aspect_ratio_model_from_image;
+use crate::converters::extract_image_sharpness::extract_image_sharpness_model;
+use crate::converters::extract_image_thumbnail::extract_multiple_image_thumbnails_standard_sizes;
+use crate::converters::open_image::{open_image, read_image_orientation};
+use crate::database::query::query_image_orientation_pending::{query_orientation_pending_count, query_orientation_pending_paths};
+use crate::database::update::update_image_orientation::execute_apply_image_orientation_recompute_sql;
+use crate::models::image_orientation::{ImageOrientationPendingAnalysis, ImageOrientationRecompute};
+use crate::models::config::thumbnails::get_thumbnail_format;
+use crate::models::image_thumbnail::ImageThumbnail;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+
+
+pub struct RecomputeOrientedImagesProcessor {}
+impl RecomputeOrientedImagesProcessor {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+
+#[async_trait]
+impl AnalysisTaskItemProcessor<Arc<ImageOrientationPendingAnalysis>, String, Vec<String>, ImageOrientationRecompute> for RecomputeOrientedImagesProcessor {
+    async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<ImageOrientationPendingAnalysis>, Box<dyn std::error::Error + Send>> {
+        if let Some(x) = &log_prog_listener {
+            x.1("listing images pending an orientation check");
+            x.0(0.5);
+        }
+        let image_paths = query_orientation_pending_paths(&pool.pool).await?;
+        Ok(Arc::new(ImageOrientationPendingAnalysis { image_paths }))
+    }
+
+    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<ImageOrientationPendingAnalysis>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<Vec<String>, Box<dyn std::error::Error + Send>> {
+        Ok(analysis.image_paths.clone())
+    }
+
+    async fn process_task_item(&self, task_item: String, _dry_run: bool, _pool: WebServerActionDataContext) -> Result<Option<ImageOrientationRecompute>, Box<dyn std::error::Error + Send>> {
+        let to_send_error = |e: image::ImageError| Box::new(e) as Box<dyn std::error::Error + Send>;
+        let orientation = read_image_orientation(&task_item).map_err(to_send_error)?.to_exif();
+        let mut item = ImageOrientationRecompute {
+            image_path: task_item.clone(),
+            orientation,
+            aspect_ratio: None,
+            thumbnails: vec![],
+            sharpness: None,
+        };
+        if item.is_upright() {
+            return Ok(Some(item));
+        }
+
+        // decode once and derive every orientation dependent row from the upright image
+        let img = open_image(&task_item).map_err(to_send_error)?;
+        item.aspect_ratio = Some(extract_image_aspect_ratio_model_from_image(&task_item, &img));
+        item.thumbnails = extract_multiple_image_thumbnails_standard_sizes(&img)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send>)?
+            .iter()
+            .map(|thumb| ImageThumbnail::from_image(task_item.clone(), get_thumbnail_format(), thumb))
+            .collect();
+        item.sharpness = Some(extract_image_sharpness_model(&task_item, &img));
+        Ok(Some(item))
+    }
+
+    async fn process_task_output(&self, task_output: ImageOrientationRecompute, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        execute_apply_image_orientation_recompute_sql(&task_output, &pool.pool).await
+    }
+
+    async fn task_already_completed(&self, task_input: &String, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
+        Ok(query_orientation_pending_count(task_input, &pool.pool).await? == 0)
+    }
+
+    fn get_description(&self) -> String {
+        "recomputes the aspect ratio, thumbnails and sharpness of images that were decoded without their exif orientation, and drops their similarity rows so they are computed again the right way up".to_string()
+    }
+
+    fn get_item_name(&self) -> String {
+        "oriented_images".to_string()
+    }
+
+    fn get_process_action_name(&self) -> String {
+        "recompute".to_string()
+    }
+}
+
+pub struct RecomputeOrientedImagesOrchestratorAction;
+impl RecomputeOrientedImagesOrchestratorAction {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<ImageOrientationPendingAnalysis>, String, Vec<String>, ImageOrientationRecompute> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(RecomputeOrientedImagesProcessor::new()))
+    }
+}
\ No newline at end of file
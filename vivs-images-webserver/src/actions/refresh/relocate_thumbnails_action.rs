@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::actions::analysis_task_item_processor::LogProgListenerPair;
+use crate::database::query::query_image_thumbnail::{query_thumbnail_by_key, query_thumbnail_count_not_in_storage, query_thumbnail_keys_not_in_storage};
+use crate::database::update::update_image_thumbnail::execute_relocate_image_thumbnail_sql;
+use crate::models::config::thumbnails::{get_thumbnail_storage, ThumbnailStorage};
+use crate::models::image_thumbnail::{ImageThumbnailRelocation, ImageThumbnailRelocationAnalysis};
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+
+
+pub struct RelocateThumbnailsProcessor {
+    storage: ThumbnailStorage,
+}
+impl RelocateThumbnailsProcessor {
+    pub fn new() -> Self {
+        Self { storage: get_thumbnail_storage() }
+    }
+}
+
+
+#[async_trait]
+impl AnalysisTaskItemProcessor<Arc<ImageThumbnailRelocationAnalysis>, i64, Vec<i64>, ImageThumbnailRelocation> for RelocateThumbnailsProcessor {
+    async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<ImageThumbnailRelocationAnalysis>, Box<dyn std::error::Error + Send>> {
+        if let Some(x) = &log_prog_listener {
+            x.1("listing thumbnails kept outside the configured storage");
+            x.0(0.5);
+        }
+        let image_thumbnail_keys = query_thumbnail_keys_not_in_storage(&self.storage, &pool.pool).await?;
+        Ok(Arc::new(ImageThumbnailRelocationAnalysis { storage_name: self.storage.get_name().to_string(), image_thumbnail_keys }))
+    }
+
+    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<ImageThumbnailRelocationAnalysis>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<Vec<i64>, Box<dyn std::error::Error + Send>> {
+        Ok(analysis.image_thumbnail_keys.clone())
+    }
+
+    async fn process_task_item(&self, task_item: i64, _dry_run: bool, pool: WebServerActionDataContext) -> Result<Option<ImageThumbnailRelocation>, Box<dyn std::error::Error + Send>> {
+        Ok(query_thumbnail_by_key(task_item, &pool.pool).await?
+            .map(|thumbnail| ImageThumbnailRelocation { image_thumbnail_key: task_item, thumbnail }))
+    }
+
+    async fn process_task_output(&self, task_output: ImageThumbnailRelocation, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        execute_relocate_image_thumbnail_sql(&task_output, &self.storage, &pool.pool).await
+    }
+
+    async fn task_already_completed(&self, task_input: &i64, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
+        Ok(query_thumbnail_count_not_in_storage(&self.storage, Some(*task_input), &pool.pool).await? == 0)
+    }
+
+    fn get_description(&self) -> String {
+        format!("moves thumbnail data into the configured {} thumbnail storage; run VACUUM afterwards to shrink the database file", self.storage.get_name())
+    }
+
+    fn get_item_name(&self) -> String {
+        "thumbnails".to_string()
+    }
+
+    fn get_process_action_name(&self) -> String {
+        "relocate".to_string()
+    }
+}
+
+pub struct RelocateThumbnailsOrchestratorAction;
+impl RelocateThumbnailsOrchestratorAction {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<ImageThumbnailRelocationAnalysis>, i64, Vec<i64>, ImageThumbnailRelocation> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(RelocateThumbnailsProcessor::new()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::actions::analysis_task_item_processor::LogProgListenerPair;
+use crate::database::query::query_image_thumbnail::{get_thumbnail_image_paths_from_db, query_thumbnail_table_at_most_width_length};
+use crate::models::config::similarity::SIMILARITY_THUMBNAIL_SIZE;
+use crate::models::image_thumbnail::{ImageThumbnail, ThumbnailCacheWarmAnalysis};
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessorOrchestrator;
+use crate::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+
+
+pub struct WarmThumbnailCacheProcessor {
+    size: u32,
+}
+impl WarmThumbnailCacheProcessor {
+    pub fn new() -> Self {
+        Self { size: SIMILARITY_THUMBNAIL_SIZE }
+    }
+}
+
+
+#[async_trait]
+impl AnalysisTaskItemProcessor<Arc<ThumbnailCacheWarmAnalysis>, String, Vec<String>, ImageThumbnail> for WarmThumbnailCacheProcessor {
+    async fn get_analysis(&self, pool: WebServerActionDataContext, log_prog_listener: Option<LogProgListenerPair>) -> Result<Arc<ThumbnailCacheWarmAnalysis>, Box<dyn std::error::Error + Send>> {
+        if let Some(x) = &log_prog_listener {
+            x.1("listing images with thumbnails");
+            x.0(0.5);
+        }
+        let mut image_paths = vec![];
+        for image_path in get_thumbnail_image_paths_from_db(&pool.pool).await? {
+            if !pool.thumbnail_cache.contains(&image_path, self.size).await {
+                image_paths.push(image_path);
+            }
+        }
+        image_paths.sort();
+        Ok(Arc::new(ThumbnailCacheWarmAnalysis { size: self.size, image_paths }))
+    }
+
+    async fn get_task_items_from_analysis(&self, _pool: WebServerActionDataContext, analysis: Arc<ThumbnailCacheWarmAnalysis>, _log_prog_listener: Option<LogProgListenerPair>) -> Result<Vec<String>, Box<dyn std::error::Error + Send>> {
+        Ok(analysis.image_paths.clone())
+    }
+
+    // read straight from the database, so warming up does not count as cache misses
+    async fn process_task_item(&self, task_item: String, _dry_run: bool, pool: WebServerActionDataContext) -> Result<Option<ImageThumbnail>, Box<dyn std::error::Error + Send>> {
+        query_thumbnail_table_at_most_width_length(&task_item, self.size, &pool.pool).await
+    }
+
+    async fn process_task_output(&self, task_output: ImageThumbnail, pool: WebServerActionDataContext) -> Result<(), Box<dyn std::error::Error + Send>> {
+        pool.thumbnail_cache.insert(task_output.image_path.clone(), self.size, task_output).await;
+        Ok(())
+    }
+
+    async fn task_already_completed(&self, task_input: &String, pool: WebServerActionDataContext) -> Result<bool, Box<dyn std::error::Error + Send>> {
+        Ok(pool.thumbnail_cache.contains(task_input, self.size).await)
+    }
+
+    fn get_description(&self) -> String {
+        format!("loads the {} px thumbnails compared by thumbnail similarity into the in-memory thumbnail cache; once the cache is full the least recently used ones are dropped again", self.size)
+    }
+
+    fn get_item_name(&self) -> String {
+        "thumbnail_cache".to_string()
+    }
+
+    fn get_process_action_name(&self) -> String {
+        "warm".to_string()
+    }
+}
+
+pub struct WarmThumbnailCacheOrchestratorAction;
+impl WarmThumbnailCacheOrchestratorAction {
+    pub fn new() -> AnalysisTaskItemProcessorOrchestrator<Arc<ThumbnailCacheWarmAnalysis>, String, Vec<String>, ImageThumbnail> {
+        AnalysisTaskItemProcessorOrchestrator::new(Arc::new(WarmThumbnailCacheProcessor::new()))
+    }
+}
\ No newline at end of file
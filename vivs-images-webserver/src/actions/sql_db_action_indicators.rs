@@ -7,6 +7,7 @@ use crate::actions::indicators::update_brightness_indicator::ImagesInBrightnessS
 use crate::actions::action_indicator::IActionIndicator;
 use crate::actions::indicators::update_exif_indicator::ImagesOnDiskWithMissingExifIndicator;
 use crate::actions::indicators::update_exif_indicator::ImagesInExifSqlDbWithMissingImageOnDiskIndicator;
+use crate::actions::indicators::update_image_paths_indicator::ImagesInImagePathsSqlDbWithMissingImageOnDiskIndicator;
 use crate::actions::indicators::update_image_paths_indicator::ImagesOnDiskWithMissingImagePathsIndicator;
 use crate::actions::indicators::update_iptc_indicator::ImagesInIptcSqlDbWithMissingImageOnDiskIndicator;
 use crate::actions::indicators::update_iptc_indicator::ImagesOnDiskWithMissingIptcIndicator;
@@ -18,14 +19,33 @@ use crate::actions::indicators::update_similarity_indicator::ImagesOnDiskWithMis
 use crate::actions::indicators::update_tags_indicator::ImagesOnDiskWithMissingTagsIndicator;
 use crate::actions::indicators::update_thumbnail_indicator::ImagesInThumbnailSqlDbWithMissingImageOnDiskIndicator;
 use crate::actions::indicators::update_thumbnail_indicator::ImagesOnDiskWithMissingThumbnailIndicator;
+use crate::actions::indicators::update_thumbnail_indicator::ThumbnailsOutsideConfiguredStorageIndicator;
 use crate::actions::indicators::update_xmp_indicator::ImagesInXmpSqlDbWithMissingImageOnDiskIndicator;
 use crate::actions::indicators::update_xmp_indicator::ImagesOnDiskWithMissingXmpIndicator;
+use crate::actions::indicators::update_color_indicator::ImagesInColorSqlDbWithMissingImageOnDiskIndicator;
+use crate::actions::indicators::update_color_indicator::ImagesOnDiskWithMissingColorIndicator;
+use crate::actions::indicators::update_sharpness_indicator::ImagesInSharpnessSqlDbWithMissingImageOnDiskIndicator;
+use crate::actions::indicators::update_sharpness_indicator::ImagesOnDiskWithMissingSharpnessIndicator;
+use crate::actions::indicators::update_exposure_indicator::ImagesInExposureSqlDbWithMissingImageOnDiskIndicator;
+use crate::actions::indicators::update_exposure_indicator::ImagesOnDiskWithMissingExposureIndicator;
+use crate::actions::indicators::update_ocr_summary_indicator::ImagesInOcrSummarySqlDbWithMissingImageOnDiskIndicator;
+use crate::actions::indicators::update_ocr_summary_indicator::ImagesOnDiskWithMissingOcrSummaryIndicator;
+use crate::actions::indicators::update_content_hash_indicator::ImagesInContentHashSqlDbWithMissingImageOnDiskIndicator;
+use crate::actions::indicators::update_content_hash_indicator::ImagesOnDiskWithMissingContentHashIndicator;
+use crate::actions::indicators::update_raw_indicator::ImagesInRawSqlDbWithMissingImageOnDiskIndicator;
+use crate::actions::indicators::update_raw_indicator::ImagesOnDiskWithMissingRawIndicator;
+use crate::actions::indicators::update_frames_indicator::ImagesInFramesSqlDbWithMissingImageOnDiskIndicator;
+use crate::actions::indicators::update_frames_indicator::ImagesOnDiskWithMissingFramesIndicator;
+use crate::actions::indicators::update_orientation_indicator::ImagesWithPendingOrientationRecomputeIndicator;
+use crate::actions::indicators::update_geocode_indicator::ImagesWithGpsMissingPlaceNamesIndicator;
+use crate::actions::indicators::update_event_indicator::ImagesWithCaptureTimeMissingEventIndicator;
 
 
 
 pub fn get_sql_db_action_indicators() -> Vec<Rc<dyn IActionIndicator>> {
     vec![
         Rc::new(ImagesOnDiskWithMissingImagePathsIndicator::new()),
+        Rc::new(ImagesInImagePathsSqlDbWithMissingImageOnDiskIndicator::new()),
         Rc::new(ImagesInBrightnessSqlDbWithMissingImageOnDiskIndicator::new()),
         Rc::new(ImagesOnDiskWithMissingBrightnessIndicator::new()),
         Rc::new(ImagesInExifSqlDbWithMissingImageOnDiskIndicator::new()),
@@ -35,6 +55,7 @@ pub fn get_sql_db_action_indicators() -> Vec<Rc<dyn IActionIndicator>> {
         Rc::new(ImagesOnDiskWithMissingSimilarityIndicator::new()),
         Rc::new(ImagesInThumbnailSqlDbWithMissingImageOnDiskIndicator::new()),
         Rc::new(ImagesOnDiskWithMissingThumbnailIndicator::new()),
+        Rc::new(ThumbnailsOutsideConfiguredStorageIndicator::new()),
         Rc::new(ImagesOnDiskWithMissingOcrTextIndicator::new()),
         Rc::new(ImagesInOcrTextSqlDbWithMissingImageOnDiskIndicator::new()),
         Rc::new(ImagesInAspectRatioSqlDbWithMissingImageOnDiskIndicator::new()),
@@ -44,5 +65,22 @@ pub fn get_sql_db_action_indicators() -> Vec<Rc<dyn IActionIndicator>> {
         Rc::new(ImagesOnDiskWithMissingTagsIndicator::new()),
         Rc::new(ImagesOnDiskWithMissingXmpIndicator::new()),
         Rc::new(ImagesInXmpSqlDbWithMissingImageOnDiskIndicator::new()),
+        Rc::new(ImagesOnDiskWithMissingColorIndicator::new()),
+        Rc::new(ImagesInColorSqlDbWithMissingImageOnDiskIndicator::new()),
+        Rc::new(ImagesOnDiskWithMissingSharpnessIndicator::new()),
+        Rc::new(ImagesInSharpnessSqlDbWithMissingImageOnDiskIndicator::new()),
+        Rc::new(ImagesOnDiskWithMissingExposureIndicator::new()),
+        Rc::new(ImagesInExposureSqlDbWithMissingImageOnDiskIndicator::new()),
+        Rc::new(ImagesOnDiskWithMissingOcrSummaryIndicator::new()),
+        Rc::new(ImagesInOcrSummarySqlDbWithMissingImageOnDiskIndicator::new()),
+        Rc::new(ImagesOnDiskWithMissingContentHashIndicator::new()),
+        Rc::new(ImagesInContentHashSqlDbWithMissingImageOnDiskIndicator::new()),
+        Rc::new(ImagesOnDiskWithMissingRawIndicator::new()),
+        Rc::new(ImagesInRawSqlDbWithMissingImageOnDiskIndicator::new()),
+        Rc::new(ImagesOnDiskWithMissingFramesIndicator::new()),
+        Rc::new(ImagesInFramesSqlDbWithMissingImageOnDiskIndicator::new()),
+        Rc::new(ImagesWithPendingOrientationRecomputeIndicator::new()),
+        Rc::new(ImagesWithGpsMissingPlaceNamesIndicator::new()),
+        Rc::new(ImagesWithCaptureTimeMissingEventIndicator::new()),
     ]
 }
\ No newline at end of file
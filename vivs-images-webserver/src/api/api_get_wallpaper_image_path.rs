@@ -5,11 +5,13 @@ use actix_web::HttpResponse;
 use crate::core::data_context::WebServerActionDataContext;
 use crate::models::query_params::default_search_params::get_image_wallpaper_based_on_brightness_search_params;
 use crate::database::query::search::search_images_by_criteria;
+use crate::models::image_brightness::ImageToBrightnessAlgo;
+use crate::models::query_params::wallpaper_params::WallpaperParams;
 
 
 
-pub async fn api_get_wallpaper_image_path_inner(pool: WebServerActionDataContext) -> Result<Option<String>, Box<dyn Error + Send>> {
-    let wallpaper_search_params = get_image_wallpaper_based_on_brightness_search_params();
+pub async fn api_get_wallpaper_image_path_inner(pool: WebServerActionDataContext, brightness_algo: Option<ImageToBrightnessAlgo>) -> Result<Option<String>, Box<dyn Error + Send>> {
+    let wallpaper_search_params = get_image_wallpaper_based_on_brightness_search_params(brightness_algo);
     let wallpaper_search = search_images_by_criteria(pool, &wallpaper_search_params, Some("RANDOM()")).await?;
     if wallpaper_search.total_count > 0 {
         if let Some(wallpaper) = wallpaper_search.items.first() {
@@ -27,8 +29,15 @@ pub async fn api_get_wallpaper_image_path_inner(pool: WebServerActionDataContext
     Ok(None)
 }
 
-pub async fn api_get_wallpaper_image_path(pool: actix_web::web::Data<WebServerActionDataContext>) -> Result<HttpResponse, actix_web::Error> {
-    api_get_wallpaper_image_path_inner(pool.get_ref().clone()).await
+pub async fn api_get_wallpaper_image_path(
+    pool: actix_web::web::Data<WebServerActionDataContext>,
+    query: actix_web::web::Query<WallpaperParams>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let brightness_algo = match query.get_brightness_algo() {
+        Ok(algo) => algo,
+        Err(e) => return Ok(HttpResponse::BadRequest().body(e)),
+    };
+    api_get_wallpaper_image_path_inner(pool.get_ref().clone(), brightness_algo).await
         .map(|wallpaper| {
             if let Some(w) = wallpaper {
                 let json = serde_json::json!({ "wallpaper": w });
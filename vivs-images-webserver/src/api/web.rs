@@ -5,16 +5,53 @@ use actix_web::HttpRequest;
 use std::io;
 use std::collections::HashMap;
 
+use actix_web::http::header;
+
+use crate::cache::derivative_cache::DerivativeCache;
+use crate::converters::extract_raw_preview::extract_raw_preview_jpeg;
+use crate::converters::resize_image::open_and_encode_resized_image;
+use crate::core::data_context::WebServerActionDataContext;
+use crate::database::query::query_image_thumbnail::query_thumbnail_table_nearest_width_length;
+use crate::database::thumbnail_store::thumbnail_content_hash;
+use crate::filesystem::query::images::is_raw_image_path;
+use crate::models::config::derivatives::{get_derivative_quality, DERIVATIVE_CACHE_MAX_AGE_SECONDS};
+use crate::models::query_params::image_resize_params::ImageResizeParams;
+
+// Thumbnails are replaced when an image is reprocessed, so browsers revalidate them daily using the ETag
+pub const THUMBNAIL_CACHE_MAX_AGE_SECONDS: u32 = 24 * 60 * 60;
+pub const DEFAULT_THUMBNAIL_REQUEST_SIZE: u32 = 128;
+
 
 pub async fn get_image(req: HttpRequest, path: web::Query<HashMap<String, String>>) -> Result<HttpResponse> {
     if let Some(image_path) = path.get("path") {
         let path_buf = PathBuf::from(image_path);
 
         // Security check: prevent directory traversal attacks
-        if path_buf.components().any(|comp| comp == std::path::Component::ParentDir) {
+        if has_parent_dir(image_path) {
             return Ok(HttpResponse::BadRequest().body("Invalid path"));
         }
 
+        // w, h, fit and format ask for a resized copy instead of the original
+        match ImageResizeParams::from_query(&path) {
+            Ok(Some(params)) => return get_resized_image(&req, image_path, params).await,
+            Ok(None) => {},
+            Err(e) => return Ok(HttpResponse::BadRequest().body(e)),
+        }
+
+        // browsers cannot show camera RAW files, so serve the JPEG preview they embed
+        if is_raw_image_path(image_path) {
+            return match extract_raw_preview_jpeg(image_path) {
+                Ok(jpeg) => Ok(HttpResponse::Ok().content_type("image/jpeg").body(jpeg)),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    Ok(HttpResponse::NotFound().body(format!("Image {} not found", image_path)))
+                }
+                Err(e) => {
+                    eprintln!("Error serving RAW preview {}: {}", image_path, e);
+                    Ok(HttpResponse::InternalServerError().body("Error serving image"))
+                }
+            };
+        }
+
         match NamedFile::open(&path_buf) {
             Ok(file) => {
                 // Determine content type based on file extension
@@ -46,6 +83,105 @@ pub async fn get_image(req: HttpRequest, path: web::Query<HashMap<String, String
     }
 }
 
+// Serves the stored thumbnail nearest to `size`, so pages link to thumbnails instead of inlining them
+pub async fn get_thumbnail(req: HttpRequest, query: web::Query<HashMap<String, String>>, data: web::Data<WebServerActionDataContext>) -> Result<HttpResponse> {
+    let Some(image_path) = query.get("path") else {
+        return Ok(HttpResponse::BadRequest().body("Missing path parameter"));
+    };
+    // checked like get_image, even though the path is only looked up in the database
+    if has_parent_dir(image_path) {
+        return Ok(HttpResponse::BadRequest().body("Invalid path"));
+    }
+    let size = match query.get("size").map(|s| s.parse::<u32>()) {
+        None => DEFAULT_THUMBNAIL_REQUEST_SIZE,
+        Some(Ok(size)) if size > 0 => size,
+        Some(_) => return Ok(HttpResponse::BadRequest().body("Invalid size parameter")),
+    };
+
+    let thumbnail = match query_thumbnail_table_nearest_width_length(image_path, size, &data.pool).await {
+        Ok(Some(thumbnail)) => thumbnail,
+        Ok(None) => return Ok(HttpResponse::NotFound().body(format!("No thumbnail stored for {}", image_path))),
+        Err(e) => {
+            eprintln!("Error serving thumbnail {}: {}", image_path, e);
+            return Ok(HttpResponse::InternalServerError().body("Error serving thumbnail"));
+        }
+    };
+
+    let etag = format!("\"{}\"", &thumbnail_content_hash(&thumbnail.thumbnail_data)[..32]);
+    if is_not_modified(&req, &etag) {
+        return Ok(not_modified_response(etag, THUMBNAIL_CACHE_MAX_AGE_SECONDS));
+    }
+    Ok(cacheable_response(thumbnail.thumbnail_format.mime_type(), etag, THUMBNAIL_CACHE_MAX_AGE_SECONDS, thumbnail.thumbnail_data))
+}
+
+// Resizes on demand, keeping the result in the derivative cache for the next request
+async fn get_resized_image(req: &HttpRequest, image_path: &str, params: ImageResizeParams) -> Result<HttpResponse> {
+    let quality = get_derivative_quality();
+    let key = match DerivativeCache::cache_key(image_path, &params, quality) {
+        Ok(key) => key,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Ok(HttpResponse::NotFound().body(format!("Image {} not found", image_path)));
+        }
+        Err(e) => {
+            eprintln!("Error serving image {}: {}", image_path, e);
+            return Ok(HttpResponse::InternalServerError().body("Error serving image"));
+        }
+    };
+    let etag = format!("\"{}\"", &key[..32]);
+    if is_not_modified(req, &etag) {
+        return Ok(not_modified_response(etag, DERIVATIVE_CACHE_MAX_AGE_SECONDS));
+    }
+
+    let content_type = params.format.mime_type();
+    let cache = DerivativeCache::from_config();
+    if let Some(data) = cache.get(&key) {
+        return Ok(cacheable_response(content_type, etag, DERIVATIVE_CACHE_MAX_AGE_SECONDS, data));
+    }
+
+    let path = image_path.to_string();
+    let data = match web::block(move || open_and_encode_resized_image(&path, &params, quality)).await {
+        Ok(Ok(data)) => data,
+        Ok(Err(e)) => {
+            eprintln!("Error resizing image {}: {}", image_path, e);
+            return Ok(HttpResponse::InternalServerError().body("Error resizing image"));
+        }
+        Err(e) => {
+            eprintln!("Error resizing image {}: {}", image_path, e);
+            return Ok(HttpResponse::InternalServerError().body("Error resizing image"));
+        }
+    };
+    // a cache that cannot be written to only costs the next request a resize
+    if let Err(e) = cache.insert(&key, &data) {
+        eprintln!("Could not cache resized image {}: {}", image_path, e);
+    }
+    Ok(cacheable_response(content_type, etag, DERIVATIVE_CACHE_MAX_AGE_SECONDS, data))
+}
+
+fn has_parent_dir(image_path: &str) -> bool {
+    PathBuf::from(image_path).components().any(|comp| comp == std::path::Component::ParentDir)
+}
+
+fn is_not_modified(req: &HttpRequest, etag: &str) -> bool {
+    req.headers().get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|t| t.trim() == etag || t.trim() == "*"))
+}
+
+fn not_modified_response(etag: String, max_age_seconds: u32) -> HttpResponse {
+    HttpResponse::NotModified()
+        .insert_header((header::ETAG, etag))
+        .insert_header((header::CACHE_CONTROL, format!("public, max-age={}", max_age_seconds)))
+        .finish()
+}
+
+fn cacheable_response(content_type: &str, etag: String, max_age_seconds: u32, data: Vec<u8>) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header((header::ETAG, etag))
+        .insert_header((header::CACHE_CONTROL, format!("public, max-age={}", max_age_seconds)))
+        .body(data)
+}
+
 pub fn get_file_from_exe_dir(path: &str) -> PathBuf {
     std::env::current_dir().unwrap().join("vivs-images-webserver/").join(path)
 }
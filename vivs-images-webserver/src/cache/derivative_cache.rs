@@ -0,0 +1,108 @@
+use std::fs::File;
+use std::io::{Result, Write};
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use sha2::Digest;
+use sha2::Sha256;
+use tempfile::NamedTempFile;
+
+use crate::models::config::derivatives::{get_derivative_cache_dir, get_derivative_cache_max_bytes};
+use crate::models::query_params::image_resize_params::ImageResizeParams;
+
+
+// Resized copies of images on disk, one file per image and set of resize parameters. A file's modified time
+// is bumped every time it is served, so evicting the oldest files first drops the least recently used ones.
+#[derive(Clone, Debug)]
+pub struct DerivativeCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl DerivativeCache {
+    pub fn new(dir: PathBuf, max_bytes: u64) -> Self {
+        Self { dir, max_bytes }
+    }
+
+    pub fn from_config() -> Self {
+        Self::new(PathBuf::from(get_derivative_cache_dir()), get_derivative_cache_max_bytes())
+    }
+
+    // Covers the original's size and modified time, so editing or replacing the image misses the old copies,
+    // and the encoding quality, so changing it does not serve copies made at the old one
+    pub fn cache_key(image_path: &str, params: &ImageResizeParams, quality: u8) -> Result<String> {
+        let metadata = std::fs::metadata(image_path)?;
+        let modified = metadata.modified()?
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        let mut hasher = Sha256::new();
+        hasher.update(image_path.as_bytes());
+        hasher.update(format!("\0{}\0{}\0{}\0{}", metadata.len(), modified, params, quality).as_bytes());
+        let hash = hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect::<String>();
+        Ok(format!("{}.{}", hash, params.format.extension()))
+    }
+
+    pub fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let path = self.dir.join(key);
+        let data = std::fs::read(&path).ok()?;
+        if let Ok(file) = File::options().write(true).open(&path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+        Some(data)
+    }
+
+    pub fn insert(&self, key: &str, data: &[u8]) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        // written to a temporary file of its own and renamed, so a concurrent request never reads a partial
+        // file and two requests making the same copy do not write over each other
+        let mut partial = NamedTempFile::new_in(&self.dir)?;
+        partial.write_all(data)?;
+        partial.persist(self.dir.join(key)).map_err(|e| e.error)?;
+        self.evict_to_budget()?;
+        Ok(())
+    }
+
+    pub fn total_bytes(&self) -> Result<u64> {
+        Ok(self.list_entries()?.iter().map(|(_, _, len)| len).sum())
+    }
+
+    // Deletes least recently used copies until the cache fits its budget, returning how many bytes were freed
+    pub fn evict_to_budget(&self) -> Result<u64> {
+        let mut entries = self.list_entries()?;
+        let mut total: u64 = entries.iter().map(|(_, _, len)| len).sum();
+        if total <= self.max_bytes {
+            return Ok(0);
+        }
+        entries.sort_by_key(|(_, modified, _)| *modified);
+        let mut freed = 0;
+        for (path, _, len) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            // another request may have evicted it already
+            if std::fs::remove_file(&path).is_ok() {
+                freed += len;
+            }
+            total -= len;
+        }
+        Ok(freed)
+    }
+
+    fn list_entries(&self) -> Result<Vec<(PathBuf, SystemTime, u64)>> {
+        let read_dir = match std::fs::read_dir(&self.dir) {
+            Ok(read_dir) => read_dir,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+        Ok(read_dir
+            .filter_map(|entry| entry.ok())
+            // temporary files being written have no extension
+            .filter(|entry| entry.path().extension().is_some())
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                Some((entry.path(), metadata.modified().ok()?, metadata.len()))
+            })
+            .collect())
+    }
+}
\ No newline at end of file
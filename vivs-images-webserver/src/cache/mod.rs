@@ -1 +1,2 @@
-pub mod thumbnail_cache;
\ No newline at end of file
+pub mod thumbnail_cache;
+pub mod derivative_cache;
\ No newline at end of file
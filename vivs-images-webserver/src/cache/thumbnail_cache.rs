@@ -1,38 +1,145 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::Mutex;
 
+use crate::models::config::thumbnails::get_thumbnail_cache_max_bytes;
 use crate::models::image_thumbnail::ImageThumbnail;
 
+// Bookkeeping per entry on top of the thumbnail data, so many tiny thumbnails still count against the budget
+const THUMBNAIL_CACHE_ENTRY_OVERHEAD_BYTES: usize = 128;
+
+// Keyed by image path and the size asked for, since callers asking for different sizes get different thumbnails
+type ThumbnailCacheKey = (String, u32);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ThumbnailCacheStats {
+    pub entries: usize,
+    pub bytes: usize,
+    pub max_bytes: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl std::fmt::Display for ThumbnailCacheStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lookups = self.hits + self.misses;
+        let hit_percent = if lookups == 0 { 0.0 } else { self.hits as f64 / lookups as f64 * 100.0 };
+        write!(f, "{} thumbnails, {:.1} of {:.1} MB, {} hits and {} misses ({:.1}% hit rate)",
+            self.entries, self.bytes as f64 / 1048576.0, self.max_bytes as f64 / 1048576.0,
+            self.hits, self.misses, hit_percent)
+    }
+}
+
+#[derive(Debug)]
+struct ThumbnailCacheInner {
+    entries: HashMap<ThumbnailCacheKey, (ImageThumbnail, u64)>,
+    // last use tick to key, oldest first
+    recency: BTreeMap<u64, ThumbnailCacheKey>,
+    tick: u64,
+    stats: ThumbnailCacheStats,
+}
+
+impl ThumbnailCacheInner {
+    fn entry_bytes(key: &ThumbnailCacheKey, thumbnail: &ImageThumbnail) -> usize {
+        key.0.len() + thumbnail.thumbnail_data.len() + THUMBNAIL_CACHE_ENTRY_OVERHEAD_BYTES
+    }
+
+    fn touch(&mut self, key: &ThumbnailCacheKey) {
+        self.tick += 1;
+        let tick = self.tick;
+        if let Some((_, last_used)) = self.entries.get_mut(key) {
+            self.recency.remove(last_used);
+            *last_used = tick;
+            self.recency.insert(tick, key.clone());
+        }
+    }
+
+    fn remove(&mut self, key: &ThumbnailCacheKey) {
+        if let Some((thumbnail, last_used)) = self.entries.remove(key) {
+            self.recency.remove(&last_used);
+            self.stats.bytes -= Self::entry_bytes(key, &thumbnail);
+            self.stats.entries -= 1;
+        }
+    }
+
+    fn insert(&mut self, key: ThumbnailCacheKey, thumbnail: ImageThumbnail) {
+        self.remove(&key);
+        let bytes = Self::entry_bytes(&key, &thumbnail);
+        if bytes > self.stats.max_bytes {
+            return;
+        }
+        while self.stats.bytes + bytes > self.stats.max_bytes {
+            let Some(oldest) = self.recency.values().next().cloned() else {
+                break;
+            };
+            self.remove(&oldest);
+        }
+        self.tick += 1;
+        self.recency.insert(self.tick, key.clone());
+        self.entries.insert(key, (thumbnail, self.tick));
+        self.stats.bytes += bytes;
+        self.stats.entries += 1;
+    }
+}
+
+// Thumbnails read from the database, shared between the web server and the worker thread and kept within a
+// memory budget by evicting the least recently used ones
 #[derive(Clone, Debug)]
 pub struct ThumbnailCache {
-    inner: Arc<RwLock<HashMap<String, ImageThumbnail>>>,
+    inner: Arc<Mutex<ThumbnailCacheInner>>,
 }
 
 impl ThumbnailCache {
     pub fn new() -> Self {
+        Self::with_max_bytes(get_thumbnail_cache_max_bytes())
+    }
+
+    pub fn with_max_bytes(max_bytes: usize) -> Self {
         Self {
-            inner: Arc::new(RwLock::new(HashMap::new())),
+            inner: Arc::new(Mutex::new(ThumbnailCacheInner {
+                entries: HashMap::new(),
+                recency: BTreeMap::new(),
+                tick: 0,
+                stats: ThumbnailCacheStats { max_bytes, ..Default::default() },
+            })),
         }
     }
 
-    pub async fn get(&self, image_path: &str) -> Option<ImageThumbnail> {
-        let cache = self.inner.read().await;
-        cache.get(image_path).cloned()
+    pub async fn get(&self, image_path: &str, size: u32) -> Option<ImageThumbnail> {
+        let mut cache = self.inner.lock().await;
+        let key = (image_path.to_string(), size);
+        let thumbnail = cache.entries.get(&key).map(|(thumbnail, _)| thumbnail.clone());
+        if thumbnail.is_some() {
+            cache.touch(&key);
+            cache.stats.hits += 1;
+        } else {
+            cache.stats.misses += 1;
+        }
+        thumbnail
     }
 
-    pub async fn insert(&self, image_path: String, thumbnail: ImageThumbnail) {
-        let mut cache = self.inner.write().await;
-        cache.insert(image_path, thumbnail);
+    // Does not count as a hit or miss, or as a use
+    pub async fn contains(&self, image_path: &str, size: u32) -> bool {
+        let cache = self.inner.lock().await;
+        cache.entries.contains_key(&(image_path.to_string(), size))
     }
 
-    pub async fn batch_insert(&self, thumbnails: Vec<(String, ImageThumbnail)>) {
-        let mut cache = self.inner.write().await;
-        for (path, thumb) in thumbnails {
-            cache.insert(path, thumb);
+    pub async fn insert(&self, image_path: String, size: u32, thumbnail: ImageThumbnail) {
+        let mut cache = self.inner.lock().await;
+        cache.insert((image_path, size), thumbnail);
+    }
+
+    pub async fn batch_insert(&self, thumbnails: Vec<(String, u32, ImageThumbnail)>) {
+        let mut cache = self.inner.lock().await;
+        for (path, size, thumb) in thumbnails {
+            cache.insert((path, size), thumb);
         }
     }
 
+    pub async fn stats(&self) -> ThumbnailCacheStats {
+        self.inner.lock().await.stats
+    }
+
     // Clone is cheap - just increments Arc reference count
     pub fn clone_cache(&self) -> Self {
         Self {
@@ -0,0 +1,56 @@
+// Gaussian falloff for the centre-weighted mean, as a fraction of the image size
+pub const CENTER_WEIGHT_SIGMA: f64 = 0.25;
+
+pub fn srgb_to_linear(c: u8) -> f64 {
+    let c = c as f64 / 255.0;
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+// Relative luminance Y in [0, 1] using the Rec.709 primaries on linear-light values
+pub fn rec709_luminance(rgb: [u8; 3]) -> f64 {
+    0.2126 * srgb_to_linear(rgb[0]) + 0.7152 * srgb_to_linear(rgb[1]) + 0.0722 * srgb_to_linear(rgb[2])
+}
+
+// CIE L* scaled to [0, 1], which tracks how bright the image looks rather than how much light it emits
+pub fn perceptual_lightness(rgb: [u8; 3]) -> f64 {
+    let y = rec709_luminance(rgb);
+    let l = if y > 216.0 / 24389.0 { 116.0 * y.cbrt() - 16.0 } else { y * 24389.0 / 27.0 };
+    l / 100.0
+}
+
+fn mean_of<F: Fn([u8; 3]) -> f64>(pixels: &[[u8; 3]], f: F) -> f64 {
+    if pixels.is_empty() {
+        return 0.0;
+    }
+    pixels.iter().map(|p| f(*p)).sum::<f64>() / pixels.len() as f64
+}
+
+// Unweighted mean of (r + g + b) / 3 on the encoded values
+pub fn mean_channel_average(pixels: &[[u8; 3]]) -> f64 {
+    mean_of(pixels, |p| (p[0] as f64 + p[1] as f64 + p[2] as f64) / (3.0 * 255.0))
+}
+
+pub fn mean_rec709_luminance(pixels: &[[u8; 3]]) -> f64 {
+    mean_of(pixels, rec709_luminance)
+}
+
+pub fn mean_perceptual_lightness(pixels: &[[u8; 3]]) -> f64 {
+    mean_of(pixels, perceptual_lightness)
+}
+
+// Mean L* with a Gaussian weight centred on the image, since the subject is usually near the middle
+pub fn center_weighted_lightness(pixels: &[[u8; 3]], width: usize, height: usize) -> f64 {
+    if pixels.is_empty() || width == 0 || height == 0 || pixels.len() != width * height {
+        return 0.0;
+    }
+    let two_sigma_sq = 2.0 * CENTER_WEIGHT_SIGMA * CENTER_WEIGHT_SIGMA;
+    let (mut total, mut total_weight) = (0.0, 0.0);
+    for (i, p) in pixels.iter().enumerate() {
+        let u = ((i % width) as f64 + 0.5) / width as f64 - 0.5;
+        let v = ((i / width) as f64 + 0.5) / height as f64 - 0.5;
+        let weight = (-(u * u + v * v) / two_sigma_sq).exp();
+        total += weight * perceptual_lightness(*p);
+        total_weight += weight;
+    }
+    total / total_weight
+}
\ No newline at end of file
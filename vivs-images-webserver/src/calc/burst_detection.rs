@@ -0,0 +1,100 @@
+use chrono::Duration;
+
+use crate::models::image_burst::{ImageBurstCandidate, ImageBurstCluster, ImageBurstRun, ImageBurstScoredFrame};
+
+// Sharpness decides between frames whenever it is known, then brightness, then exposure; each part only matters
+// when the ones before it are (close to) tied, since a blurred frame is never the one to keep
+const SHARPNESS_WEIGHT: f64 = 100.0;
+const BRIGHTNESS_WEIGHT: f64 = 10.0;
+const EXPOSURE_WEIGHT: f64 = 1.0;
+
+// Walks the images in the order they were taken and keeps every run of two or more taken no more than max_gap
+// apart. Local time is used since the frames of a burst always come from the same camera
+pub fn split_burst_runs(mut candidates: Vec<ImageBurstCandidate>, max_gap: Duration) -> Vec<ImageBurstRun> {
+    candidates.sort_by(|a, b| a.local_time.cmp(&b.local_time).then_with(|| a.image_path.cmp(&b.image_path)));
+
+    let mut runs = vec![];
+    let mut current: Vec<ImageBurstCandidate> = vec![];
+    for candidate in candidates {
+        if let Some(previous) = current.last() {
+            if candidate.local_time - previous.local_time > max_gap {
+                runs.push(ImageBurstRun { frames: std::mem::take(&mut current) });
+            }
+        }
+        current.push(candidate);
+    }
+    runs.push(ImageBurstRun { frames: current });
+    runs.retain(|r| r.frames.len() > 1);
+    runs
+}
+
+// similarities[i] compares frame i with frame i + 1 of the run; a pair below min_similarity, or one that could not
+// be compared, ends the burst there. Runs of a single frame are left out
+pub fn split_run_into_bursts(run: &ImageBurstRun, similarities: &[Option<f32>], min_similarity: f32) -> Vec<ImageBurstCluster> {
+    let mut bursts = vec![];
+    let mut current: Vec<(&ImageBurstCandidate, Option<f32>)> = vec![];
+    for (i, frame) in run.frames.iter().enumerate() {
+        let mut similarity = match i {
+            0 => None,
+            _ => similarities.get(i - 1).copied().flatten(),
+        };
+        if i > 0 && !similarity.is_some_and(|s| s >= min_similarity) {
+            bursts.push(std::mem::take(&mut current));
+            similarity = None;
+        }
+        current.push((frame, similarity));
+    }
+    bursts.push(current);
+    bursts.into_iter()
+        .filter(|b| b.len() > 1)
+        .map(|b| finish_burst(&b))
+        .collect()
+}
+
+// Scores each frame against the rest of its burst: sharpness relative to the sharpest frame, brightness by how
+// close it is to mid-grey, and exposure by how little of the frame is clipped. Unknown parts score nothing
+pub fn score_burst_frames(frames: &[ImageBurstCandidate]) -> Vec<f64> {
+    let max_sharpness = frames.iter()
+        .filter_map(|f| f.sharpness.filter(|s| s.is_finite()))
+        .fold(0.0, f64::max);
+    frames.iter()
+        .map(|frame| {
+            let sharpness = match frame.sharpness.filter(|s| s.is_finite()) {
+                Some(s) if max_sharpness > 0.0 => s / max_sharpness,
+                _ => 0.0,
+            };
+            let brightness = frame.brightness
+                .filter(|b| b.is_finite())
+                .map(|b| 1.0 - ((b - 0.5).abs() * 2.0).min(1.0))
+                .unwrap_or_default();
+            let exposure = match (frame.clipped_highlights, frame.clipped_shadows) {
+                (Some(h), Some(s)) if (h + s).is_finite() => 1.0 - ((h + s) / 100.0).clamp(0.0, 1.0),
+                _ => 0.0,
+            };
+            sharpness * SHARPNESS_WEIGHT + brightness * BRIGHTNESS_WEIGHT + exposure * EXPOSURE_WEIGHT
+        })
+        .collect()
+}
+
+// The highest scoring frame is the keeper; on a tie, the earliest one
+fn finish_burst(frames: &[(&ImageBurstCandidate, Option<f32>)]) -> ImageBurstCluster {
+    let candidates: Vec<ImageBurstCandidate> = frames.iter().map(|(f, _)| (*f).clone()).collect();
+    let scores = score_burst_frames(&candidates);
+    let mut keeper = 0;
+    for (i, score) in scores.iter().enumerate() {
+        if *score > scores[keeper] {
+            keeper = i;
+        }
+    }
+    ImageBurstCluster {
+        keeper_image_path: candidates[keeper].image_path.clone(),
+        frames: frames.iter().zip(scores)
+            .map(|((frame, similarity), score)| ImageBurstScoredFrame {
+                image_path: frame.image_path.clone(),
+                local_time: frame.local_time,
+                score,
+                similarity: *similarity,
+            })
+            .collect(),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,224 @@
+// Number of dominant colours kept per image
+pub const DEFAULT_PALETTE_SIZE: usize = 5;
+
+// Coarse HSV histogram: 12 hue bins (30 degrees each), then 3 bins (dark, mid, light) for greys
+pub const HSV_HISTOGRAM_HUE_BINS: usize = 12;
+pub const HSV_HISTOGRAM_GREY_BINS: usize = 3;
+pub const HSV_HISTOGRAM_BINS: usize = HSV_HISTOGRAM_HUE_BINS + HSV_HISTOGRAM_GREY_BINS;
+
+// Below this saturation or value a pixel has no meaningful hue
+const GREY_SATURATION_THRESHOLD: f32 = 0.15;
+const GREY_VALUE_THRESHOLD: f32 = 0.15;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaletteColor {
+    pub rgb: [u8; 3],
+    // fraction of the sampled pixels that fell into this colour's bucket
+    pub weight: f32,
+}
+
+impl PaletteColor {
+    pub fn to_hex(&self) -> String {
+        rgb_to_hex(self.rgb)
+    }
+}
+
+pub fn rgb_to_hex(rgb: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2])
+}
+
+pub fn hex_to_rgb(hex: &str) -> Option<[u8; 3]> {
+    let hex = hex.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some([r, g, b])
+}
+
+// Stored as "#rrggbb:weight,#rrggbb:weight,..." so it stays readable and LIKE-searchable
+pub fn format_palette(palette: &[PaletteColor]) -> String {
+    palette.iter()
+        .map(|c| format!("{}:{:.4}", c.to_hex(), c.weight))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+pub fn parse_palette(s: &str) -> Vec<PaletteColor> {
+    s.split(',')
+        .filter_map(|entry| {
+            let (hex, weight) = entry.split_once(':')?;
+            Some(PaletteColor { rgb: hex_to_rgb(hex)?, weight: weight.trim().parse().ok()? })
+        })
+        .collect()
+}
+
+// Median-cut quantisation: repeatedly split the bucket with the widest channel range at its median
+pub fn median_cut_palette(pixels: &[[u8; 3]], max_colors: usize) -> Vec<PaletteColor> {
+    if pixels.is_empty() || max_colors == 0 {
+        return vec![];
+    }
+
+    let mut buckets: Vec<Vec<[u8; 3]>> = vec![pixels.to_vec()];
+    while buckets.len() < max_colors {
+        let widest = buckets.iter()
+            .enumerate()
+            .filter(|(_, b)| b.len() > 1)
+            .map(|(i, b)| (i, widest_channel(b)))
+            .max_by_key(|(_, (_, range))| *range);
+
+        let Some((index, (channel, range))) = widest else { break };
+        if range == 0 {
+            break;
+        }
+
+        let mut bucket = buckets.swap_remove(index);
+        bucket.sort_unstable_by_key(|p| p[channel]);
+        let upper = bucket.split_off(bucket.len() / 2);
+        buckets.push(bucket);
+        buckets.push(upper);
+    }
+
+    let total = pixels.len() as f32;
+    let mut palette: Vec<PaletteColor> = buckets.iter()
+        .map(|b| PaletteColor { rgb: average_rgb(b), weight: b.len() as f32 / total })
+        .collect();
+    palette.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+    palette
+}
+
+fn widest_channel(pixels: &[[u8; 3]]) -> (usize, u8) {
+    (0..3)
+        .map(|c| {
+            let min = pixels.iter().map(|p| p[c]).min().unwrap_or_default();
+            let max = pixels.iter().map(|p| p[c]).max().unwrap_or_default();
+            (c, max - min)
+        })
+        .max_by_key(|(_, range)| *range)
+        .unwrap_or((0, 0))
+}
+
+fn average_rgb(pixels: &[[u8; 3]]) -> [u8; 3] {
+    let mut sum = [0u64; 3];
+    for p in pixels {
+        for c in 0..3 {
+            sum[c] += p[c] as u64;
+        }
+    }
+    let n = pixels.len().max(1) as u64;
+    [(sum[0] / n) as u8, (sum[1] / n) as u8, (sum[2] / n) as u8]
+}
+
+// Hue in degrees [0, 360), saturation and value in [0, 1]
+pub fn rgb_to_hsv(rgb: [u8; 3]) -> (f32, f32, f32) {
+    let r = rgb[0] as f32 / 255.0;
+    let g = rgb[1] as f32 / 255.0;
+    let b = rgb[2] as f32 / 255.0;
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    let saturation = if max == 0.0 { 0.0 } else { delta / max };
+
+    (hue, saturation, max)
+}
+
+pub fn hsv_histogram_bin(rgb: [u8; 3]) -> usize {
+    let (hue, saturation, value) = rgb_to_hsv(rgb);
+    if saturation < GREY_SATURATION_THRESHOLD || value < GREY_VALUE_THRESHOLD {
+        let grey_bin = ((value * HSV_HISTOGRAM_GREY_BINS as f32) as usize).min(HSV_HISTOGRAM_GREY_BINS - 1);
+        HSV_HISTOGRAM_HUE_BINS + grey_bin
+    } else {
+        ((hue / 360.0 * HSV_HISTOGRAM_HUE_BINS as f32) as usize).min(HSV_HISTOGRAM_HUE_BINS - 1)
+    }
+}
+
+// The bin itself and the bins next to it, so a colour close to a bin edge still finds its match;
+// hue bins wrap around, grey bins do not
+pub fn neighbouring_histogram_bins(bin: usize) -> Vec<usize> {
+    if bin < HSV_HISTOGRAM_HUE_BINS {
+        let n = HSV_HISTOGRAM_HUE_BINS;
+        vec![(bin + n - 1) % n, bin, (bin + 1) % n]
+    } else {
+        (bin.saturating_sub(1).max(HSV_HISTOGRAM_HUE_BINS)..=(bin + 1).min(HSV_HISTOGRAM_BINS - 1)).collect()
+    }
+}
+
+// Fraction of pixels in each bin, summing to 1 for a non-empty input
+pub fn hsv_histogram(pixels: &[[u8; 3]]) -> Vec<f32> {
+    let mut bins = [0f32; HSV_HISTOGRAM_BINS];
+    for p in pixels {
+        bins[hsv_histogram_bin(*p)] += 1.0;
+    }
+    let total = pixels.len().max(1) as f32;
+    bins.iter().map(|b| b / total).collect()
+}
+
+// Every bin is written as a 6 character fraction and a comma, so SQL can read one bin with substr
+pub const HISTOGRAM_BIN_TEXT_WIDTH: usize = 7;
+
+pub fn format_histogram(bins: &[f32]) -> String {
+    bins.iter().map(|b| format!("{:.4}", b.clamp(0.0, 1.0))).collect::<Vec<String>>().join(",")
+}
+
+pub fn parse_histogram(s: &str) -> Vec<f32> {
+    s.split(',').filter_map(|b| b.trim().parse().ok()).collect()
+}
+
+pub fn average_saturation(pixels: &[[u8; 3]]) -> f32 {
+    if pixels.is_empty() {
+        return 0.0;
+    }
+    pixels.iter().map(|p| rgb_to_hsv(*p).1).sum::<f32>() / pixels.len() as f32
+}
+
+// Mean (red - blue) balance in [-1, 1]; positive is warm, negative is cool
+pub fn average_warmth(pixels: &[[u8; 3]]) -> f32 {
+    if pixels.is_empty() {
+        return 0.0;
+    }
+    pixels.iter().map(|p| (p[0] as f32 - p[2] as f32) / 255.0).sum::<f32>() / pixels.len() as f32
+}
+
+// sRGB (D65) to CIE L*a*b*
+pub fn rgb_to_lab(rgb: [u8; 3]) -> [f32; 3] {
+    let linear = |c: u8| {
+        let c = c as f32 / 255.0;
+        if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    };
+    let (r, g, b) = (linear(rgb[0]), linear(rgb[1]), linear(rgb[2]));
+
+    let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+    let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+
+    let f = |t: f32| if t > 0.008856 { t.cbrt() } else { 7.787 * t + 16.0 / 116.0 };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+// CIE76 delta E between two colours
+pub fn color_distance(a: [u8; 3], b: [u8; 3]) -> f32 {
+    let a = rgb_to_lab(a);
+    let b = rgb_to_lab(b);
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+// Distance from a target colour to the closest palette entry, discounted by how much of
+// the image that entry covers so a dominant match ranks above a small accent
+pub fn palette_distance(palette: &[PaletteColor], target: [u8; 3]) -> Option<f32> {
+    palette.iter()
+        .map(|c| color_distance(c.rgb, target) * (2.0 - c.weight.clamp(0.0, 1.0)))
+        .min_by(|a, b| a.total_cmp(b))
+}
@@ -0,0 +1,76 @@
+use chrono::{Duration, NaiveDateTime};
+
+use crate::models::image_event::{ImageEventCluster, ImageEventPhoto};
+
+// Walks the photos in the order they were taken and starts a new event at every gap longer than max_time_gap, or
+// when a photo was taken more than max_distance_km from the last located photo of the current event. Local time
+// is used throughout, since mixing cameras that do and do not record a zone would otherwise shift some photos by hours
+pub fn cluster_image_events(mut photos: Vec<ImageEventPhoto>, max_time_gap: Duration, max_distance_km: f64) -> Vec<ImageEventCluster> {
+    photos.sort_by(|a, b| a.local_time.cmp(&b.local_time).then_with(|| a.image_path.cmp(&b.image_path)));
+
+    let mut events = vec![];
+    let mut current: Vec<ImageEventPhoto> = vec![];
+    for photo in photos {
+        if let Some(previous) = current.last() {
+            let last_location = current.iter().rev().find_map(|p| p.location);
+            let time_gap = photo.local_time - previous.local_time > max_time_gap;
+            let location_jump = match (last_location, photo.location) {
+                (Some(a), Some(b)) => a.distance_km(&b) > max_distance_km,
+                _ => false,
+            };
+            if time_gap || location_jump {
+                events.push(finish_event(std::mem::take(&mut current)));
+            }
+        }
+        current.push(photo);
+    }
+    if !current.is_empty() {
+        events.push(finish_event(current));
+    }
+    events
+}
+
+// The sharpest photo is the cover when sharpness is known, otherwise the one in the middle of the event.
+// The location is that of the middle located photo, so it is always somewhere a photo was actually taken
+fn finish_event(photos: Vec<ImageEventPhoto>) -> ImageEventCluster {
+    let middle = &photos[photos.len() / 2];
+    let cover = photos.iter()
+        .filter_map(|p| p.sharpness.filter(|s| s.is_finite()).map(|s| (p, s)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(p, _)| p)
+        .unwrap_or(middle);
+    let located: Vec<_> = photos.iter().filter_map(|p| p.location).collect();
+    ImageEventCluster {
+        image_paths: photos.iter().map(|p| p.image_path.clone()).collect(),
+        start: photos[0].local_time,
+        end: photos[photos.len() - 1].local_time,
+        cover_image_path: cover.image_path.clone(),
+        location: located.get(located.len() / 2).copied(),
+    }
+}
+
+// Pairs each new event with the stored event whose time window overlaps it the most, so an event that is still
+// there after a rerun keeps its id. existing holds (event_id, start, end); each stored event goes to at most one
+// new event, and windows that only touch count as overlapping
+pub fn match_existing_events(existing: &[(i64, NaiveDateTime, NaiveDateTime)], events: &[ImageEventCluster]) -> Vec<Option<i64>> {
+    let mut overlaps = vec![];
+    for (i, event) in events.iter().enumerate() {
+        for (j, (_, start, end)) in existing.iter().enumerate() {
+            let overlap = event.end.min(*end) - event.start.max(*start);
+            if overlap >= Duration::zero() {
+                overlaps.push((overlap, i, j));
+            }
+        }
+    }
+    overlaps.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| (a.1, a.2).cmp(&(b.1, b.2))));
+
+    let mut matched = vec![None; events.len()];
+    let mut taken = vec![false; existing.len()];
+    for (_, i, j) in overlaps {
+        if matched[i].is_none() && !taken[j] {
+            matched[i] = Some(existing[j].0);
+            taken[j] = true;
+        }
+    }
+    matched
+}
\ No newline at end of file
@@ -0,0 +1,79 @@
+// 8-bit luma at or above this counts as a blown highlight, at or below as a crushed shadow
+pub const CLIPPED_HIGHLIGHT_LUMA: u8 = 250;
+pub const CLIPPED_SHADOW_LUMA: u8 = 5;
+
+// Bins kept for the stored histogram; enough to draw a small chart on the image page
+pub const STORED_HISTOGRAM_BINS: usize = 32;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExposureStats {
+    // percentage of pixels at or above CLIPPED_HIGHLIGHT_LUMA
+    pub clipped_highlights: f64,
+    // percentage of pixels at or below CLIPPED_SHADOW_LUMA
+    pub clipped_shadows: f64,
+    // luma percentiles in [0, 255]
+    pub luma_p5: u8,
+    pub luma_p50: u8,
+    pub luma_p95: u8,
+    // RMS contrast: standard deviation of luma normalised to [0, 1]
+    pub contrast: f64,
+}
+
+pub fn luma_histogram(luma: &[u8]) -> [u64; 256] {
+    let mut bins = [0u64; 256];
+    for v in luma {
+        bins[*v as usize] += 1;
+    }
+    bins
+}
+
+// Smallest luma value at or below which `percentile` percent of the pixels fall
+pub fn histogram_percentile(histogram: &[u64; 256], percentile: f64) -> u8 {
+    let total: u64 = histogram.iter().sum();
+    if total == 0 {
+        return 0;
+    }
+    let target = ((percentile / 100.0) * total as f64).ceil().max(1.0) as u64;
+    let mut seen = 0;
+    for (value, count) in histogram.iter().enumerate() {
+        seen += count;
+        if seen >= target {
+            return value as u8;
+        }
+    }
+    255
+}
+
+pub fn exposure_stats(histogram: &[u64; 256]) -> ExposureStats {
+    let total: u64 = histogram.iter().sum();
+    if total == 0 {
+        return ExposureStats { clipped_highlights: 0.0, clipped_shadows: 0.0, luma_p5: 0, luma_p50: 0, luma_p95: 0, contrast: 0.0 };
+    }
+    let total_f = total as f64;
+
+    let highlights: u64 = histogram[CLIPPED_HIGHLIGHT_LUMA as usize..].iter().sum();
+    let shadows: u64 = histogram[..=CLIPPED_SHADOW_LUMA as usize].iter().sum();
+
+    let mean = histogram.iter().enumerate().map(|(v, c)| v as f64 * *c as f64).sum::<f64>() / total_f;
+    let variance = histogram.iter().enumerate().map(|(v, c)| (v as f64 - mean).powi(2) * *c as f64).sum::<f64>() / total_f;
+
+    ExposureStats {
+        clipped_highlights: highlights as f64 / total_f * 100.0,
+        clipped_shadows: shadows as f64 / total_f * 100.0,
+        luma_p5: histogram_percentile(histogram, 5.0),
+        luma_p50: histogram_percentile(histogram, 50.0),
+        luma_p95: histogram_percentile(histogram, 95.0),
+        contrast: variance.sqrt() / 255.0,
+    }
+}
+
+// Merge the 256 luma bins down to `bins` buckets, each as a fraction of all pixels
+pub fn downsample_histogram(histogram: &[u64; 256], bins: usize) -> Vec<f32> {
+    let bins = bins.clamp(1, 256);
+    let total = histogram.iter().sum::<u64>().max(1) as f32;
+    let mut out = vec![0f32; bins];
+    for (value, count) in histogram.iter().enumerate() {
+        out[value * bins / 256] += *count as f32 / total;
+    }
+    out
+}
\ No newline at end of file
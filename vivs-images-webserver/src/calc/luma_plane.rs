@@ -0,0 +1,59 @@
+use image::GrayImage;
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LumaPlane {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<f64>,
+}
+
+impl LumaPlane {
+    pub fn new(width: usize, height: usize, pixels: Vec<f64>) -> Self {
+        assert_eq!(width * height, pixels.len(), "luma plane size does not match pixel count");
+        Self { width, height, pixels }
+    }
+
+    pub fn from_gray_image(img: &GrayImage) -> Self {
+        let pixels = img.pixels().map(|p| p.0[0] as f64).collect();
+        Self::new(img.width() as usize, img.height() as usize, pixels)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> f64 {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn mean(&self) -> f64 {
+        if self.pixels.is_empty() { 0.0 } else { self.pixels.iter().sum::<f64>() / self.pixels.len() as f64 }
+    }
+
+    pub fn variance(&self) -> f64 {
+        if self.pixels.is_empty() {
+            return 0.0;
+        }
+        let mean = self.mean();
+        self.pixels.iter().map(|p| (p - mean) * (p - mean)).sum::<f64>() / self.pixels.len() as f64
+    }
+
+    // 2x2 box average followed by 2x subsampling, the dyadic step used between MS-SSIM scales
+    pub fn downsample_2x(&self) -> Self {
+        let width = self.width / 2;
+        let height = self.height / 2;
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let sum = self.get(2 * x, 2 * y)
+                    + self.get(2 * x + 1, 2 * y)
+                    + self.get(2 * x, 2 * y + 1)
+                    + self.get(2 * x + 1, 2 * y + 1);
+                pixels.push(sum / 4.0);
+            }
+        }
+        Self::new(width, height, pixels)
+    }
+
+    pub fn multiply(&self, other: &LumaPlane) -> Self {
+        let pixels = self.pixels.iter().zip(other.pixels.iter()).map(|(a, b)| a * b).collect();
+        Self::new(self.width, self.height, pixels)
+    }
+}
\ No newline at end of file
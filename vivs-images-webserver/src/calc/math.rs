@@ -18,7 +18,12 @@ pub fn generate_unique_pairs(image_list: &HashSet<String>, log_prog_listener: Op
     let mut pairs = Vec::new();
     for (i, path_a) in image_list.iter().enumerate() {
         for path_b in image_list.iter().skip(i + 1) {
-            pairs.push((path_a.clone(), path_b.clone()));
+            // ordered the way image_similarity stores them, so comparing against the table lines up
+            if path_a <= path_b {
+                pairs.push((path_a.clone(), path_b.clone()));
+            } else {
+                pairs.push((path_b.clone(), path_a.clone()));
+            }
             num_processed += 1;
 
             if num_processed % 1000 == 0 {
@@ -1,3 +1,14 @@
 pub mod file_paths_comparison;
 pub mod image_aspect_ratio;
-pub mod math;
\ No newline at end of file
+pub mod luma_plane;
+pub mod math;
+pub mod ssim;
+pub mod color_palette;
+pub mod sharpness;
+pub mod exposure;
+pub mod brightness;
+pub mod ocr_preprocess;
+pub mod similarity_agreement;
+pub mod reverse_geocode;
+pub mod event_clustering;
+pub mod burst_detection;
\ No newline at end of file
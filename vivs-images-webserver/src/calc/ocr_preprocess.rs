@@ -0,0 +1,172 @@
+use image::metadata::Orientation;
+use image::{GrayImage, Luma};
+
+// Bradley adaptive threshold: a pixel becomes black when it is `bias` darker than the mean of the
+// window around it, which copes with uneven lighting where one global threshold would not
+pub fn adaptive_threshold(img: &GrayImage, window: u32, bias: f64) -> GrayImage {
+    let (width, height) = img.dimensions();
+    let (w, h) = (width as usize, height as usize);
+    let mut integral = vec![0u64; (w + 1) * (h + 1)];
+    for y in 0..h {
+        let mut row_sum = 0u64;
+        for x in 0..w {
+            row_sum += img.get_pixel(x as u32, y as u32)[0] as u64;
+            integral[(y + 1) * (w + 1) + x + 1] = integral[y * (w + 1) + x + 1] + row_sum;
+        }
+    }
+
+    let half = (window / 2).max(1) as usize;
+    GrayImage::from_fn(width, height, |x, y| {
+        let (x, y) = (x as usize, y as usize);
+        let (x0, y0) = (x.saturating_sub(half), y.saturating_sub(half));
+        let (x1, y1) = ((x + half + 1).min(w), (y + half + 1).min(h));
+        let count = ((x1 - x0) * (y1 - y0)) as f64;
+        let sum = integral[y1 * (w + 1) + x1] + integral[y0 * (w + 1) + x0]
+            - integral[y0 * (w + 1) + x1] - integral[y1 * (w + 1) + x0];
+        let mean = sum as f64 / count;
+        let value = img.get_pixel(x as u32, y as u32)[0] as f64;
+        Luma([if value < mean * (1.0 - bias) { 0 } else { 255 }])
+    })
+}
+
+// Projection profile skew estimate: text lines give the sharpest row histogram of dark pixels
+// when the page is level, so try each angle and keep the one with the highest variance
+pub fn estimate_skew_degrees(img: &GrayImage, max_degrees: f64, step_degrees: f64) -> f64 {
+    let (width, height) = img.dimensions();
+    let (cx, cy) = (width as f64 / 2.0, height as f64 / 2.0);
+    let dark: Vec<(f64, f64)> = img.enumerate_pixels()
+        .filter(|(_, _, p)| p[0] < 128)
+        .map(|(x, y, _)| (x as f64 - cx, y as f64 - cy))
+        .collect();
+    if dark.is_empty() || step_degrees <= 0.0 {
+        return 0.0;
+    }
+
+    let diagonal = ((width * width + height * height) as f64).sqrt().ceil() as usize + 2;
+    let steps = (max_degrees / step_degrees).round() as i32;
+    let mut best = (0.0, f64::MIN);
+    for i in -steps..=steps {
+        let angle = i as f64 * step_degrees;
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let mut rows = vec![0f64; diagonal];
+        for (x, y) in &dark {
+            let row = (y * cos - x * sin + diagonal as f64 / 2.0) as usize;
+            if row < diagonal {
+                rows[row] += 1.0;
+            }
+        }
+        let mean = rows.iter().sum::<f64>() / diagonal as f64;
+        let variance = rows.iter().map(|r| (r - mean) * (r - mean)).sum::<f64>();
+        if variance > best.1 + 1e-9 || (variance > best.1 - 1e-9 && angle.abs() < f64::abs(best.0)) {
+            best = (angle, variance);
+        }
+    }
+    best.0
+}
+
+// Rotates about the centre keeping the same size, filling uncovered corners with `fill`
+pub fn rotate_gray(img: &GrayImage, degrees: f64, fill: u8) -> GrayImage {
+    let (width, height) = img.dimensions();
+    let (cx, cy) = (width as f64 / 2.0, height as f64 / 2.0);
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    GrayImage::from_fn(width, height, |x, y| {
+        // inverse mapping with bilinear sampling
+        let (dx, dy) = (x as f64 + 0.5 - cx, y as f64 + 0.5 - cy);
+        let sx = dx * cos + dy * sin + cx - 0.5;
+        let sy = -dx * sin + dy * cos + cy - 0.5;
+        if sx < 0.0 || sy < 0.0 || sx > (width - 1) as f64 || sy > (height - 1) as f64 {
+            return Luma([fill]);
+        }
+        let (x0, y0) = (sx.floor() as u32, sy.floor() as u32);
+        let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+        let (fx, fy) = (sx - x0 as f64, sy - y0 as f64);
+        let p = |x: u32, y: u32| img.get_pixel(x, y)[0] as f64;
+        let top = p(x0, y0) * (1.0 - fx) + p(x1, y0) * fx;
+        let bottom = p(x0, y1) * (1.0 - fx) + p(x1, y1) * fx;
+        Luma([(top * (1.0 - fy) + bottom * fy).round().clamp(0.0, 255.0) as u8])
+    })
+}
+
+// How the page tesseract read relates to the image before it was upscaled and deskewed, and how that image is
+// turned upright, so the word boxes found on the page can be drawn over the image as it is shown
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OcrPageTransform {
+    pub original_width: u32,
+    pub original_height: u32,
+    // size after upscaling; deskewing rotates in place and keeps it
+    pub page_width: u32,
+    pub page_height: u32,
+    // the skew that was corrected, i.e. the page is the upscaled image rotated by minus this
+    pub deskew_degrees: f64,
+    // turns the original upright; NoTransforms when the exif orientation was applied before ocr
+    pub orientation: Orientation,
+}
+
+impl OcrPageTransform {
+    pub fn identity(width: u32, height: u32) -> Self {
+        Self { original_width: width, original_height: height, page_width: width, page_height: height, deskew_degrees: 0.0, orientation: Orientation::NoTransforms }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.original_width == self.page_width && self.original_height == self.page_height && self.deskew_degrees == 0.0
+            && self.orientation == Orientation::NoTransforms
+    }
+
+    // Size of the original once turned upright
+    pub fn upright_size(&self) -> (u32, u32) {
+        match self.orientation {
+            Orientation::Rotate90 | Orientation::Rotate270 | Orientation::Rotate90FlipH | Orientation::Rotate270FlipH => {
+                (self.original_height, self.original_width)
+            }
+            _ => (self.original_width, self.original_height),
+        }
+    }
+
+    // Undoes the rotation the same way rotate_gray samples its source, then the upscale, then turns the box
+    // upright with the image. A box that ends up rotated is replaced by the upright box around its corners,
+    // clamped to the image
+    pub fn box_to_original(&self, left: i32, top: i32, width: i32, height: i32) -> (i32, i32, i32, i32) {
+        if self.page_width == 0 || self.page_height == 0 {
+            return (left, top, width, height);
+        }
+        let (cx, cy) = (self.page_width as f64 / 2.0, self.page_height as f64 / 2.0);
+        let (sin, cos) = (-self.deskew_degrees).to_radians().sin_cos();
+        let scale_x = self.original_width as f64 / self.page_width as f64;
+        let scale_y = self.original_height as f64 / self.page_height as f64;
+
+        let (l, t, r, b) = (left as f64, top as f64, (left + width) as f64, (top + height) as f64);
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+        for (x, y) in [(l, t), (r, t), (l, b), (r, b)] {
+            let (dx, dy) = (x - cx, y - cy);
+            let sx = (dx * cos + dy * sin + cx) * scale_x;
+            let sy = (-dx * sin + dy * cos + cy) * scale_y;
+            min_x = min_x.min(sx);
+            min_y = min_y.min(sy);
+            max_x = max_x.max(sx);
+            max_y = max_y.max(sy);
+        }
+        let clamp_x = |v: f64| v.round().clamp(0.0, self.original_width as f64) as i32;
+        let clamp_y = |v: f64| v.round().clamp(0.0, self.original_height as f64) as i32;
+        let (x0, y0, x1, y1) = (clamp_x(min_x), clamp_y(min_y), clamp_x(max_x), clamp_y(max_y));
+        self.orient_box(x0, y0, x1, y1)
+    }
+
+    // Where the box between two corners on the original lands once it is turned upright, the same way
+    // DynamicImage::apply_orientation moves pixels
+    fn orient_box(&self, x0: i32, y0: i32, x1: i32, y1: i32) -> (i32, i32, i32, i32) {
+        let (w, h) = (self.original_width as i32, self.original_height as i32);
+        let point = |x: i32, y: i32| match self.orientation {
+            Orientation::NoTransforms => (x, y),
+            Orientation::Rotate90 => (h - y, x),
+            Orientation::Rotate180 => (w - x, h - y),
+            Orientation::Rotate270 => (y, w - x),
+            Orientation::FlipHorizontal => (w - x, y),
+            Orientation::FlipVertical => (x, h - y),
+            Orientation::Rotate90FlipH => (y, x),
+            Orientation::Rotate270FlipH => (h - y, w - x),
+        };
+        let (ax, ay) = point(x0, y0);
+        let (bx, by) = point(x1, y1);
+        (ax.min(bx), ay.min(by), (ax - bx).abs(), (ay - by).abs())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+use std::io::ErrorKind;
+
+use crate::models::config::geocoding::{get_geonames_admin1_file, get_geonames_cities_file};
+
+// Compiled in, so place names are available without any download or network access
+const BUNDLED_GEONAMES_CITIES: &str = include_str!("../../data/geonames_cities.tsv");
+const BUNDLED_GEONAMES_COUNTRIES: &str = include_str!("../../data/geonames_countries.tsv");
+pub const BUNDLED_GEONAMES_DATASET_NAME: &str = "bundled";
+
+// Mean earth radius
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+// Column count of a line in a GeoNames cities dump; the bundled layout has six
+const GEONAMES_DUMP_COLUMNS: usize = 19;
+
+// Places are bucketed into cells of one degree of latitude and longitude for lookups
+const GRID_CELL_DEGREES: f64 = 1.0;
+const GRID_LONGITUDE_CELLS: i32 = (360.0 / GRID_CELL_DEGREES) as i32;
+const GRID_LATITUDE_CELLS: i32 = (180.0 / GRID_CELL_DEGREES) as i32;
+// The first lookup radius; it grows fourfold until a place falls within it
+const NEAREST_START_RADIUS_KM: f64 = 50.0;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeoPlace {
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub country_code: String,
+    pub region: Option<String>,
+    pub population: u64,
+}
+
+pub fn haversine_distance_km(lat_a: f64, lon_a: f64, lat_b: f64, lon_b: f64) -> f64 {
+    let d_lat = (lat_b - lat_a).to_radians();
+    let d_lon = (lon_b - lon_a).to_radians();
+    let h = (d_lat / 2.0).sin().powi(2) + lat_a.to_radians().cos() * lat_b.to_radians().cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * h.sqrt().min(1.0).asin()
+}
+
+// Latitude -90..90 and longitude -180..180; exactly 0,0 is what many cameras write when they have no fix
+pub fn is_valid_gps_coordinate(latitude: f64, longitude: f64) -> bool {
+    latitude.is_finite() && longitude.is_finite()
+        && (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude)
+        && !(latitude == 0.0 && longitude == 0.0)
+}
+
+fn parse_error(line_number: usize, message: &str) -> std::io::Error {
+    std::io::Error::new(ErrorKind::InvalidData, format!("line {}: {}", line_number + 1, message))
+}
+
+fn data_lines(text: &str) -> impl Iterator<Item = (usize, Vec<&str>)> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| (i, line.split('\t').collect()))
+}
+
+// Reads either the bundled layout (name, latitude, longitude, country code, region, population) or a GeoNames
+// cities dump, whose admin1 codes are turned into region names with admin1_names ("US.CA" -> "California")
+pub fn parse_geo_places(text: &str, admin1_names: &HashMap<String, String>) -> std::io::Result<Vec<GeoPlace>> {
+    let mut places = vec![];
+    for (line_number, columns) in data_lines(text) {
+        let (name, latitude, longitude, country_code, region, population) = if columns.len() >= GEONAMES_DUMP_COLUMNS {
+            let admin1_code = columns[10].trim();
+            let region = if admin1_code.is_empty() {
+                None
+            } else {
+                Some(admin1_names.get(&format!("{}.{}", columns[8], admin1_code)).cloned().unwrap_or(admin1_code.to_string()))
+            };
+            (columns[1], columns[4], columns[5], columns[8], region, columns[14])
+        } else if columns.len() == 6 {
+            let region = Some(columns[4].trim().to_string()).filter(|r| !r.is_empty());
+            (columns[0], columns[1], columns[2], columns[3], region, columns[5])
+        } else {
+            return Err(parse_error(line_number, &format!("expected 6 or {} tab separated columns, found {}", GEONAMES_DUMP_COLUMNS, columns.len())));
+        };
+        let latitude: f64 = latitude.trim().parse().map_err(|_| parse_error(line_number, "latitude is not a number"))?;
+        let longitude: f64 = longitude.trim().parse().map_err(|_| parse_error(line_number, "longitude is not a number"))?;
+        places.push(GeoPlace {
+            name: name.trim().to_string(),
+            latitude,
+            longitude,
+            country_code: country_code.trim().to_string(),
+            region,
+            population: population.trim().parse().unwrap_or(0),
+        });
+    }
+    Ok(places)
+}
+
+// Country code and name in the first two columns
+pub fn parse_country_names(text: &str) -> HashMap<String, String> {
+    data_lines(text)
+        .filter(|(_, columns)| columns.len() >= 2)
+        .map(|(_, columns)| (columns[0].trim().to_string(), columns[1].trim().to_string()))
+        .collect()
+}
+
+// GeoNames admin1CodesASCII.txt: "US.CA", name, ascii name, geonameid
+pub fn parse_admin1_names(text: &str) -> HashMap<String, String> {
+    data_lines(text)
+        .filter(|(_, columns)| columns.len() >= 2)
+        .map(|(_, columns)| (columns[0].trim().to_string(), columns[1].trim().to_string()))
+        .collect()
+}
+
+// Row counts up from the south pole and column east from the antimeridian
+fn grid_cell(latitude: f64, longitude: f64) -> (i32, i32) {
+    let row = ((latitude + 90.0) / GRID_CELL_DEGREES).floor() as i32;
+    let column = ((longitude + 180.0) / GRID_CELL_DEGREES).floor() as i32;
+    (row.clamp(0, GRID_LATITUDE_CELLS - 1), column.rem_euclid(GRID_LONGITUDE_CELLS))
+}
+
+fn grid_index(row: i32, column: i32) -> usize {
+    (row * GRID_LONGITUDE_CELLS + column) as usize
+}
+
+// Finds the place nearest to a coordinate. Places are kept in a grid of one degree cells, and a lookup only
+// measures the distance to places in the cells that could hold one within the current radius
+pub struct ReverseGeocoder {
+    dataset_name: String,
+    places: Vec<GeoPlace>,
+    country_names: HashMap<String, String>,
+    grid: Vec<Vec<usize>>,
+}
+
+impl ReverseGeocoder {
+    pub fn new(dataset_name: String, places: Vec<GeoPlace>, country_names: HashMap<String, String>) -> Self {
+        let mut grid = vec![vec![]; (GRID_LATITUDE_CELLS * GRID_LONGITUDE_CELLS) as usize];
+        for (i, place) in places.iter().enumerate() {
+            let (row, column) = grid_cell(place.latitude, place.longitude);
+            grid[grid_index(row, column)].push(i);
+        }
+        Self { dataset_name, places, country_names, grid }
+    }
+
+    pub fn bundled() -> Self {
+        let places = parse_geo_places(BUNDLED_GEONAMES_CITIES, &HashMap::new()).unwrap();
+        Self::new(BUNDLED_GEONAMES_DATASET_NAME.to_string(), places, parse_country_names(BUNDLED_GEONAMES_COUNTRIES))
+    }
+
+    pub fn from_geonames_file(cities_path: &str, admin1_path: Option<&str>) -> std::io::Result<Self> {
+        let admin1_names = match admin1_path {
+            Some(path) => parse_admin1_names(&std::fs::read_to_string(path)?),
+            None => HashMap::new(),
+        };
+        let places = parse_geo_places(&std::fs::read_to_string(cities_path)?, &admin1_names)
+            .map_err(|e| std::io::Error::new(e.kind(), format!("{}: {}", cities_path, e)))?;
+        let dataset_name = std::path::Path::new(cities_path).file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or(cities_path.to_string());
+        Ok(Self::new(dataset_name, places, parse_country_names(BUNDLED_GEONAMES_COUNTRIES)))
+    }
+
+    pub fn from_config() -> std::io::Result<Self> {
+        match get_geonames_cities_file() {
+            Some(cities_path) => Self::from_geonames_file(&cities_path, get_geonames_admin1_file().as_deref()),
+            None => Ok(Self::bundled()),
+        }
+    }
+
+    pub fn get_dataset_name(&self) -> &str {
+        &self.dataset_name
+    }
+
+    pub fn len(&self) -> usize {
+        self.places.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.places.is_empty()
+    }
+
+    pub fn get_country_name(&self, country_code: &str) -> Option<&str> {
+        self.country_names.get(country_code).map(|n| n.as_str())
+    }
+
+    pub fn nearest(&self, latitude: f64, longitude: f64) -> Option<(&GeoPlace, f64)> {
+        let mut radius_km = NEAREST_START_RADIUS_KM;
+        // half the earth's circumference is as far apart as two points get
+        while radius_km < std::f64::consts::PI * EARTH_RADIUS_KM {
+            match self.nearest_within(latitude, longitude, radius_km) {
+                Some(Some(nearest)) => return Some(nearest),
+                Some(None) => radius_km *= 4.0,
+                None => break,
+            }
+        }
+        self.places.iter()
+            .map(|place| (place, haversine_distance_km(latitude, longitude, place.latitude, place.longitude)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    // The nearest place no more than radius_km away. A place that close differs in latitude by at most
+    // radius_km / R, and in longitude by an angle that widens towards the poles, so only those cells are read.
+    // None when that is more cells than there are places, in which case a plain scan is cheaper
+    fn nearest_within(&self, latitude: f64, longitude: f64, radius_km: f64) -> Option<Option<(&GeoPlace, f64)>> {
+        let latitude_window = (radius_km / EARTH_RADIUS_KM).to_degrees();
+        let max_abs_latitude = (latitude.abs() + latitude_window).min(90.0);
+        let longitude_bound = (radius_km / (2.0 * EARTH_RADIUS_KM)).sin() / max_abs_latitude.to_radians().cos();
+        let longitude_window = match longitude_bound < 1.0 {
+            true => (2.0 * longitude_bound.asin()).to_degrees(),
+            false => 180.0,
+        };
+
+        let (min_row, min_column) = grid_cell(latitude - latitude_window, longitude - longitude_window);
+        let (max_row, _) = grid_cell(latitude + latitude_window, longitude);
+        let column_count = match longitude_window >= 180.0 {
+            true => GRID_LONGITUDE_CELLS,
+            false => ((2.0 * longitude_window / GRID_CELL_DEGREES).ceil() as i32 + 1).min(GRID_LONGITUDE_CELLS),
+        };
+        if ((max_row - min_row + 1) * column_count) as usize > self.places.len() {
+            return None;
+        }
+
+        let mut nearest: Option<(&GeoPlace, f64)> = None;
+        for row in min_row..=max_row {
+            for column_offset in 0..column_count {
+                let column = (min_column + column_offset).rem_euclid(GRID_LONGITUDE_CELLS);
+                for &i in &self.grid[grid_index(row, column)] {
+                    let place = &self.places[i];
+                    let distance = haversine_distance_km(latitude, longitude, place.latitude, place.longitude);
+                    if distance <= radius_km && nearest.is_none_or(|(_, d)| distance < d) {
+                        nearest = Some((place, distance));
+                    }
+                }
+            }
+        }
+        Some(nearest)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,75 @@
+use crate::calc::luma_plane::LumaPlane;
+
+
+// Variance of the 4-neighbour Laplacian response; low values mean few sharp edges (blurry)
+pub fn laplacian_variance(plane: &LumaPlane) -> f64 {
+    if plane.width < 3 || plane.height < 3 {
+        return 0.0;
+    }
+
+    let mut responses = Vec::with_capacity((plane.width - 2) * (plane.height - 2));
+    for y in 1..plane.height - 1 {
+        for x in 1..plane.width - 1 {
+            let v = plane.get(x, y - 1) + plane.get(x - 1, y) + plane.get(x + 1, y) + plane.get(x, y + 1)
+                - 4.0 * plane.get(x, y);
+            responses.push(v);
+        }
+    }
+
+    let mean = responses.iter().sum::<f64>() / responses.len() as f64;
+    responses.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / responses.len() as f64
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MotionBlurEstimate {
+    // 0 = gradients evenly spread over all directions, 1 = every edge in one direction
+    pub coherence: f64,
+    // direction of the smear in degrees [0, 180), perpendicular to the dominant gradient
+    pub angle_degrees: f64,
+}
+
+// Above this Laplacian variance there are enough sharp edges that one dominant edge direction
+// is the subject (stripes, railings, text) rather than motion blur
+pub const MOTION_BLUR_MAX_LAPLACIAN_VARIANCE: f64 = 100.0;
+
+// Motion blur suppresses gradients along the direction of movement, so the global
+// structure tensor of the Sobel gradients becomes strongly anisotropic
+pub fn estimate_motion_blur(plane: &LumaPlane) -> MotionBlurEstimate {
+    let (mut jxx, mut jxy, mut jyy) = (0.0, 0.0, 0.0);
+    if plane.width >= 3 && plane.height >= 3 {
+        for y in 1..plane.height - 1 {
+            for x in 1..plane.width - 1 {
+                let gx = (plane.get(x + 1, y - 1) + 2.0 * plane.get(x + 1, y) + plane.get(x + 1, y + 1))
+                    - (plane.get(x - 1, y - 1) + 2.0 * plane.get(x - 1, y) + plane.get(x - 1, y + 1));
+                let gy = (plane.get(x - 1, y + 1) + 2.0 * plane.get(x, y + 1) + plane.get(x + 1, y + 1))
+                    - (plane.get(x - 1, y - 1) + 2.0 * plane.get(x, y - 1) + plane.get(x + 1, y - 1));
+                jxx += gx * gx;
+                jxy += gx * gy;
+                jyy += gy * gy;
+            }
+        }
+    }
+
+    let trace = jxx + jyy;
+    if trace <= f64::EPSILON {
+        return MotionBlurEstimate { coherence: 0.0, angle_degrees: 0.0 };
+    }
+
+    let spread = ((jxx - jyy).powi(2) + 4.0 * jxy * jxy).sqrt();
+    let coherence = (spread / trace).clamp(0.0, 1.0);
+
+    // orientation of the dominant gradient, then rotate 90 degrees to get the smear direction
+    let gradient_angle = 0.5 * (2.0 * jxy).atan2(jxx - jyy);
+    let angle_degrees = (gradient_angle.to_degrees() + 90.0).rem_euclid(180.0);
+
+    MotionBlurEstimate { coherence, angle_degrees }
+}
+
+// Coherence only counts as motion blur in an image that is also soft
+pub fn motion_blur_score(estimate: &MotionBlurEstimate, laplacian_variance: f64) -> f64 {
+    if laplacian_variance < MOTION_BLUR_MAX_LAPLACIAN_VARIANCE {
+        estimate.coherence
+    } else {
+        0.0
+    }
+}
\ No newline at end of file
@@ -0,0 +1,103 @@
+// Statistics for comparing the output of two similarity algorithms over the same image pairs.
+// The algorithms do not share a scale (magick stores PSNR in dB, the others SSIM), so most of
+// these work on ranks rather than raw values.
+
+// 1-based ranks in ascending order; tied values share the average of the ranks they span
+pub fn average_ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut i = 0;
+    while i < order.len() {
+        let mut j = i;
+        while j + 1 < order.len() && values[order[j + 1]] == values[order[i]] {
+            j += 1;
+        }
+        let rank = (i + j) as f64 / 2.0 + 1.0;
+        for k in i..=j {
+            ranks[order[k]] = rank;
+        }
+        i = j + 1;
+    }
+    ranks
+}
+
+// Position of each value within its own algorithm, 0 for the least and 1 for the most similar
+pub fn percentile_ranks(values: &[f64]) -> Vec<f64> {
+    if values.len() < 2 {
+        return vec![0.5; values.len()];
+    }
+    let max_rank = (values.len() - 1) as f64;
+    average_ranks(values).into_iter().map(|r| (r - 1.0) / max_rank).collect()
+}
+
+// None when there are fewer than two values, either side is constant or a value is not finite
+pub fn pearson_correlation(a: &[f64], b: &[f64]) -> Option<f64> {
+    if a.len() != b.len() || a.len() < 2 {
+        return None;
+    }
+    let n = a.len() as f64;
+    let mean_a = a.iter().sum::<f64>() / n;
+    let mean_b = b.iter().sum::<f64>() / n;
+
+    let mut covariance = 0.0;
+    let mut variance_a = 0.0;
+    let mut variance_b = 0.0;
+    for (x, y) in a.iter().zip(b) {
+        covariance += (x - mean_a) * (y - mean_b);
+        variance_a += (x - mean_a).powi(2);
+        variance_b += (y - mean_b).powi(2);
+    }
+    if variance_a == 0.0 || variance_b == 0.0 {
+        return None;
+    }
+    let r = covariance / (variance_a.sqrt() * variance_b.sqrt());
+    if r.is_finite() { Some(r) } else { None }
+}
+
+// Pearson correlation of the ranks, so it only cares whether both algorithms order the pairs the same way
+pub fn spearman_correlation(a: &[f64], b: &[f64]) -> Option<f64> {
+    if a.len() != b.len() {
+        return None;
+    }
+    pearson_correlation(&average_ranks(a), &average_ranks(b))
+}
+
+// Linearly interpolated quantile, q in [0, 1]
+pub fn quantile(values: &[f64], q: f64) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let pos = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    let fraction = pos - lower as f64;
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * fraction)
+}
+
+// Each algorithm flags the pairs at or above its own q quantile as similar; of the pairs flagged by
+// either, returns the share flagged by both. None when neither flags anything
+pub fn top_quantile_agreement(a: &[f64], b: &[f64], q: f64) -> Option<f64> {
+    if a.len() != b.len() {
+        return None;
+    }
+    let threshold_a = quantile(a, q)?;
+    let threshold_b = quantile(b, q)?;
+
+    let mut either = 0;
+    let mut both = 0;
+    for (x, y) in a.iter().zip(b) {
+        let flagged_a = *x >= threshold_a;
+        let flagged_b = *y >= threshold_b;
+        if flagged_a || flagged_b {
+            either += 1;
+        }
+        if flagged_a && flagged_b {
+            both += 1;
+        }
+    }
+    if either == 0 { None } else { Some(both as f64 / either as f64) }
+}
\ No newline at end of file
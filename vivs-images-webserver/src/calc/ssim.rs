@@ -0,0 +1,123 @@
+use crate::calc::luma_plane::LumaPlane;
+
+
+// Constants from Wang et al. (2004), for 8-bit luma
+pub const SSIM_K1: f64 = 0.01;
+pub const SSIM_K2: f64 = 0.03;
+pub const SSIM_DYNAMIC_RANGE: f64 = 255.0;
+pub const SSIM_WINDOW_SIZE: usize = 11;
+pub const SSIM_WINDOW_SIGMA: f64 = 1.5;
+
+// Per-scale exponents from Wang et al. (2003), finest scale first
+pub const MS_SSIM_WEIGHTS: [f64; 5] = [0.0448, 0.2856, 0.3001, 0.2363, 0.1333];
+
+// Normalised 1D Gaussian kernel; the 2D window is its outer product
+pub fn gaussian_kernel_1d(size: usize, sigma: f64) -> Vec<f64> {
+    let center = (size as f64 - 1.0) / 2.0;
+    let kernel: Vec<f64> = (0..size)
+        .map(|i| {
+            let d = i as f64 - center;
+            (-(d * d) / (2.0 * sigma * sigma)).exp()
+        })
+        .collect();
+    let sum: f64 = kernel.iter().sum();
+    kernel.into_iter().map(|k| k / sum).collect()
+}
+
+// Separable "valid" convolution: output only where the window fits entirely inside the plane
+fn filter_valid(plane: &LumaPlane, kernel: &[f64]) -> LumaPlane {
+    let n = kernel.len();
+    let out_width = plane.width + 1 - n;
+    let out_height = plane.height + 1 - n;
+
+    let mut horizontal = Vec::with_capacity(out_width * plane.height);
+    for y in 0..plane.height {
+        let row = &plane.pixels[y * plane.width..(y + 1) * plane.width];
+        for x in 0..out_width {
+            horizontal.push(row[x..x + n].iter().zip(kernel).map(|(p, k)| p * k).sum::<f64>());
+        }
+    }
+
+    let mut pixels = Vec::with_capacity(out_width * out_height);
+    for y in 0..out_height {
+        for x in 0..out_width {
+            pixels.push((0..n).map(|i| horizontal[(y + i) * out_width + x] * kernel[i]).sum::<f64>());
+        }
+    }
+    LumaPlane::new(out_width, out_height, pixels)
+}
+
+// Largest odd window no bigger than the standard 11px that still fits in the plane
+fn window_size_for(width: usize, height: usize) -> usize {
+    let size = SSIM_WINDOW_SIZE.min(width).min(height);
+    if size.is_multiple_of(2) { size - 1 } else { size }
+}
+
+// Luminance and contrast-structure terms for every window position that fits inside both planes
+fn local_ssim_terms(a: &LumaPlane, b: &LumaPlane) -> Option<Vec<(f64, f64)>> {
+    if a.width != b.width || a.height != b.height || a.width == 0 || a.height == 0 {
+        return None;
+    }
+
+    let c1 = (SSIM_K1 * SSIM_DYNAMIC_RANGE).powi(2);
+    let c2 = (SSIM_K2 * SSIM_DYNAMIC_RANGE).powi(2);
+    let kernel = gaussian_kernel_1d(window_size_for(a.width, a.height), SSIM_WINDOW_SIGMA);
+
+    let mu_a = filter_valid(a, &kernel);
+    let mu_b = filter_valid(b, &kernel);
+    let a_sq = filter_valid(&a.multiply(a), &kernel);
+    let b_sq = filter_valid(&b.multiply(b), &kernel);
+    let a_b = filter_valid(&a.multiply(b), &kernel);
+
+    Some((0..mu_a.pixels.len())
+        .map(|i| {
+            let (ma, mb) = (mu_a.pixels[i], mu_b.pixels[i]);
+            let var_a = (a_sq.pixels[i] - ma * ma).max(0.0);
+            let var_b = (b_sq.pixels[i] - mb * mb).max(0.0);
+            let cov = a_b.pixels[i] - ma * mb;
+
+            let luminance = (2.0 * ma * mb + c1) / (ma * ma + mb * mb + c1);
+            let contrast_structure = (2.0 * cov + c2) / (var_a + var_b + c2);
+            (luminance, contrast_structure)
+        })
+        .collect())
+}
+
+// Mean SSIM over an 11x11 Gaussian window (sigma 1.5), as in Wang et al. (2004)
+pub fn compute_windowed_ssim(a: &LumaPlane, b: &LumaPlane) -> Option<f64> {
+    let terms = local_ssim_terms(a, b)?;
+    Some(terms.iter().map(|(l, cs)| l * cs).sum::<f64>() / terms.len() as f64)
+}
+
+// Multi-scale SSIM (Wang et al. 2003). Small planes use as many scales as fit a full window,
+// with the weights renormalised so the result stays in the same range.
+pub fn compute_ms_ssim(a: &LumaPlane, b: &LumaPlane) -> Option<f64> {
+    let mut scales = 1;
+    while scales < MS_SSIM_WEIGHTS.len()
+        && (a.width.min(a.height) >> scales) >= SSIM_WINDOW_SIZE {
+        scales += 1;
+    }
+    let weights = &MS_SSIM_WEIGHTS[..scales];
+    let weight_sum: f64 = weights.iter().sum();
+
+    let mut a = a.clone();
+    let mut b = b.clone();
+    let mut result = 1.0;
+    for (i, weight) in weights.iter().enumerate() {
+        let terms = local_ssim_terms(&a, &b)?;
+        let count = terms.len() as f64;
+        let luminance = terms.iter().map(|t| t.0).sum::<f64>() / count;
+        let cs = terms.iter().map(|t| t.1).sum::<f64>() / count;
+        let weight = weight / weight_sum;
+        // negative contrast-structure would make a fractional power undefined
+        result *= cs.max(0.0).powf(weight);
+        if i == scales - 1 {
+            result *= luminance.max(0.0).powf(weight);
+        } else {
+            a = a.downsample_2x();
+            b = b.downsample_2x();
+        }
+    }
+
+    Some(result)
+}
@@ -1,15 +1,19 @@
-use std::path::Path;
-
+use image::DynamicImage;
 use image::ImageError;
 use image::GenericImageView;
 
 use crate::models::image_aspect_ratio::ImageAspectRatio;
 use crate::models::image_aspect_ratio::ImageQuality;
+use crate::converters::open_image::open_image;
 
 
 
 pub fn extract_image_aspect_ratio_model(image_path: &str) -> Result<ImageAspectRatio, ImageError> {
-    let img = image::open(Path::new(image_path)).map_err(|e| e)?;
+    let img = open_image(image_path).map_err(|e| e)?;
+    Ok(extract_image_aspect_ratio_model_from_image(image_path, &img))
+}
+
+pub fn extract_image_aspect_ratio_model_from_image(image_path: &str, img: &DynamicImage) -> ImageAspectRatio {
     let (width, height) = img.dimensions();
     let aspect_ratio = width as f32 / height as f32;
     let quality = if width >= 3840 || height >= 2160 {
@@ -21,11 +25,11 @@ pub fn extract_image_aspect_ratio_model(image_path: &str) -> Result<ImageAspectR
     } else {
         ImageQuality::Low
     };
-    Ok(ImageAspectRatio {
+    ImageAspectRatio {
         image_path: image_path.to_string(),
         width_pixels: width,
         height_pixels: height,
         aspect_ratio,
         quality
-    })
+    }
 }
\ No newline at end of file
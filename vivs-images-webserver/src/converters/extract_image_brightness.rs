@@ -1,15 +1,17 @@
-use std::path::Path;
-
+use image::DynamicImage;
 use image::ImageError;
-use image::GenericImageView;
 
+use crate::calc::brightness::center_weighted_lightness;
+use crate::calc::brightness::mean_channel_average;
+use crate::calc::brightness::mean_perceptual_lightness;
+use crate::calc::brightness::mean_rec709_luminance;
 use crate::models::image_brightness::ImageBrightness;
+use crate::models::image_brightness::ImageToBrightnessAlgo;
+use crate::converters::open_image::open_image;
 
 
-pub enum ImageToBrightnessAlgo {
-    SimpleImageRS,
-    SimpleInHouse,
-}
+// A mean over the whole frame is stable at thumbnail size, so never average the full resolution
+pub const BRIGHTNESS_SAMPLE_MAX_DIMENSION: u32 = 256;
 
 pub struct ImageToBrightnessOptions {
     pub algo: ImageToBrightnessAlgo,
@@ -17,27 +19,50 @@ pub struct ImageToBrightnessOptions {
 
 impl ImageToBrightnessOptions {}
 
-pub fn extract_image_brightness(image_path: &str, _options: &ImageToBrightnessOptions) -> Result<f32, ImageError> {
-    let img = image::open(Path::new(image_path)).map_err(|e| e)?;
-    let gray_img = img.grayscale();
-    let (width, height) = gray_img.dimensions();
-    let total_pixels = (width * height) as f64;
-
-    let mut total_brightness = 0.0;
-    
-    for pixel in gray_img.pixels() {
-        let luma = pixel.2;
-        total_brightness += luma[0] as f64 / 255.0;
+pub fn compute_image_brightness(img: &DynamicImage, options: &ImageToBrightnessOptions) -> f32 {
+    let sample = if img.width() > BRIGHTNESS_SAMPLE_MAX_DIMENSION || img.height() > BRIGHTNESS_SAMPLE_MAX_DIMENSION {
+        img.thumbnail(BRIGHTNESS_SAMPLE_MAX_DIMENSION, BRIGHTNESS_SAMPLE_MAX_DIMENSION)
+    } else {
+        img.clone()
+    };
+
+    if options.algo == ImageToBrightnessAlgo::SimpleImageRS {
+        let gray_img = sample.to_luma8();
+        let total_pixels = gray_img.as_raw().len().max(1) as f64;
+        let total_brightness = gray_img.as_raw().iter().map(|v| *v as f64 / 255.0).sum::<f64>();
+        return (total_brightness / total_pixels) as f32;
     }
 
-    let brightness = total_brightness / total_pixels;
-    Ok(brightness as f32)
+    let rgb = sample.to_rgb8();
+    let (width, height) = (rgb.width() as usize, rgb.height() as usize);
+    let pixels: Vec<[u8; 3]> = rgb.pixels().map(|p| p.0).collect();
+    let brightness = match options.algo {
+        ImageToBrightnessAlgo::SimpleImageRS | ImageToBrightnessAlgo::SimpleInHouse => mean_channel_average(&pixels),
+        ImageToBrightnessAlgo::Rec709LinearLight => mean_rec709_luminance(&pixels),
+        ImageToBrightnessAlgo::PerceptualLightness => mean_perceptual_lightness(&pixels),
+        ImageToBrightnessAlgo::CenterWeighted => center_weighted_lightness(&pixels, width, height),
+    };
+    brightness as f32
+}
+
+pub fn extract_image_brightness(image_path: &str, options: &ImageToBrightnessOptions) -> Result<f32, ImageError> {
+    let img = open_image(image_path)?;
+    Ok(compute_image_brightness(&img, options))
+}
+
+pub fn extract_image_brightness_model_from_image(image_path: &str, img: &DynamicImage, options: &ImageToBrightnessOptions) -> ImageBrightness {
+    ImageBrightness {
+        brightness: compute_image_brightness(img, options),
+        brightness_algo: options.algo,
+        image_path: image_path.to_string(),
+    }
 }
 
 pub fn extract_image_brightness_model(image_path: &str, options: &ImageToBrightnessOptions) -> Result<ImageBrightness, ImageError> {
     let brightness = extract_image_brightness(image_path, options)?;
     Ok(ImageBrightness {
         brightness,
+        brightness_algo: options.algo,
         image_path: image_path.to_string(),
     })
 }
\ No newline at end of file
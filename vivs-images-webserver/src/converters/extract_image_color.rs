@@ -0,0 +1,36 @@
+use image::DynamicImage;
+use image::ImageError;
+
+use crate::calc::color_palette::average_saturation;
+use crate::calc::color_palette::average_warmth;
+use crate::calc::color_palette::format_histogram;
+use crate::calc::color_palette::format_palette;
+use crate::calc::color_palette::hsv_histogram;
+use crate::calc::color_palette::median_cut_palette;
+use crate::calc::color_palette::DEFAULT_PALETTE_SIZE;
+use crate::models::image_color::ImageColor;
+use crate::converters::open_image::open_image;
+
+
+// Colour statistics do not need more than a thumbnail's worth of pixels
+pub const COLOR_SAMPLE_MAX_DIMENSION: u32 = 64;
+
+pub fn extract_image_color_model(image_path: &str, img: &DynamicImage) -> ImageColor {
+    let sample = img.thumbnail(COLOR_SAMPLE_MAX_DIMENSION, COLOR_SAMPLE_MAX_DIMENSION).to_rgb8();
+    let pixels: Vec<[u8; 3]> = sample.pixels().map(|p| p.0).collect();
+
+    let palette = median_cut_palette(&pixels, DEFAULT_PALETTE_SIZE);
+    ImageColor {
+        image_path: image_path.to_string(),
+        dominant_color: palette.first().map(|c| c.to_hex()).unwrap_or_default(),
+        color_palette: format_palette(&palette),
+        hsv_histogram: format_histogram(&hsv_histogram(&pixels)),
+        average_saturation: average_saturation(&pixels),
+        average_warmth: average_warmth(&pixels),
+    }
+}
+
+pub fn open_and_extract_image_color_model(image_path: &str) -> Result<ImageColor, ImageError> {
+    let img = open_image(image_path)?;
+    Ok(extract_image_color_model(image_path, &img))
+}
@@ -0,0 +1,54 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::io::Read;
+
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::models::image_content_hash::ImageContentHash;
+
+
+const CONTENT_HASH_READ_BUFFER_SIZE: usize = 64 * 1024;
+
+// Streams the file through SHA-256 so large images are never held in memory at once
+pub fn hash_reader_sha256<R: Read>(mut reader: R) -> std::io::Result<(String, i64)> {
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; CONTENT_HASH_READ_BUFFER_SIZE];
+    let mut total: i64 = 0;
+    loop {
+        let n = reader.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+        total += n as i64;
+    }
+    let hash = hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect::<String>();
+    Ok((hash, total))
+}
+
+pub fn open_and_extract_image_content_hash_model(image_path: &str) -> std::io::Result<ImageContentHash> {
+    let file = File::open(image_path)?;
+    let (content_hash, file_size) = hash_reader_sha256(BufReader::new(file))?;
+    Ok(ImageContentHash {
+        image_path: image_path.to_string(),
+        content_hash,
+        file_size,
+    })
+}
+
+// A freshly hashed file, plus the missing path it was moved from when one with the same contents is on record
+#[derive(Debug, Clone)]
+pub struct ImageContentHashResult {
+    pub content_hash: ImageContentHash,
+    pub moved_from: Option<String>,
+}
+
+impl std::fmt::Display for ImageContentHashResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.moved_from {
+            Some(old_path) => write!(f, "{} moved from {}", self.content_hash.image_path, old_path),
+            None => write!(f, "{}", self.content_hash),
+        }
+    }
+}
\ No newline at end of file
@@ -10,9 +10,20 @@ pub fn extract_image_exif(image_path: &str) -> actix_web::Result<ImageExif> {
     let file = std::fs::File::open(image_path)?;
     let mut bufreader = std::io::BufReader::new(&file);
     let exifreader = exif::Reader::new();
-    let exif = exifreader.read_from_container(&mut bufreader)
-        .map_err(actix_web::error::ErrorInternalServerError)?;
+    // files without exif still get a row so the capture time can come from xmp, the file name or the file itself
+    let exif = match exifreader.read_from_container(&mut bufreader) {
+        Ok(exif) => Some(exif),
+        Err(exif::Error::NotFound(_)) => None,
+        Err(e) => return Err(actix_web::error::ErrorInternalServerError(e)),
+    };
     let mut image_exif = ImageExif::default(image_path);
+    let taken_at = extract_image_taken_at(image_path, exif.as_ref());
+    image_exif.image_taken_at = taken_at.as_ref().map(|t| t.get_local_time_string());
+    image_exif.image_taken_at_offset = taken_at.as_ref().and_then(|t| t.get_offset_string());
+    image_exif.image_taken_at_source = taken_at.as_ref().map(|t| t.source.to_string());
+    let Some(exif) = exif else {
+        return Ok(image_exif);
+    };
     for enum2 in exif.fields() {
         if let exif::Value::Ascii(strings) = &enum2.value {
             // the strings need to be checked for length before setting. otherwise we will get a lot of empty strings
@@ -41,11 +52,43 @@ pub fn extract_image_exif(image_path: &str) -> actix_web::Result<ImageExif> {
         }
     }
     
-    image_exif.image_taken_at = extract_image_taken_at(image_path)?;
+    image_exif.gps_latitude = extract_gps_decimal_degrees(&exif, Tag::GPSLatitude, Tag::GPSLatitudeRef);
+    image_exif.gps_longitude = extract_gps_decimal_degrees(&exif, Tag::GPSLongitude, Tag::GPSLongitudeRef);
+    image_exif.gps_dest_latitude = extract_gps_decimal_degrees(&exif, Tag::GPSDestLatitude, Tag::GPSDestLatitudeRef);
+    image_exif.gps_dest_longitude = extract_gps_decimal_degrees(&exif, Tag::GPSDestLongitude, Tag::GPSDestLongitudeRef);
 
     Ok(image_exif)
 }
 
+// GPS coordinates are stored as degree, minute and second rationals with a separate N/S or E/W reference, and their
+// display text ("37 deg 46 min 29.64 sec") does not parse as a number, so they are read as signed decimal degrees
+fn extract_gps_decimal_degrees(exif: &exif::Exif, coordinate_tag: Tag, reference_tag: Tag) -> Option<f64> {
+    let dms: Vec<f64> = match &exif.get_field(coordinate_tag, In::PRIMARY)?.value {
+        exif::Value::Rational(values) => values.iter().map(|v| v.to_f64()).collect(),
+        _ => return None,
+    };
+    let reference = exif.get_field(reference_tag, In::PRIMARY).and_then(|f| match &f.value {
+        exif::Value::Ascii(strings) => strings.first().map(|s| String::from_utf8_lossy(s).to_string()),
+        _ => None,
+    });
+    gps_dms_to_decimal_degrees(&dms, reference.as_deref())
+}
+
+// Degrees, then optional minutes and seconds; south and west references make the result negative
+pub fn gps_dms_to_decimal_degrees(dms: &[f64], reference: Option<&str>) -> Option<f64> {
+    if dms.is_empty() {
+        return None;
+    }
+    let degrees: f64 = dms.iter().take(3).zip([1.0, 60.0, 3600.0]).map(|(v, d)| v / d).sum();
+    if !degrees.is_finite() {
+        return None;
+    }
+    match reference.map(|r| r.trim().to_uppercase()).as_deref() {
+        Some("S") | Some("W") => Some(-degrees),
+        _ => Some(degrees),
+    }
+}
+
 pub fn extract_image_exif_tags(image_path: &str) -> std::io::Result<HashSet<String>> {
     let file = std::fs::File::open(image_path)?;
     let mut bufreader = std::io::BufReader::new(&file);
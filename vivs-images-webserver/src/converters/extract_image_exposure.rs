@@ -0,0 +1,41 @@
+use image::imageops::FilterType;
+use image::DynamicImage;
+use image::ImageError;
+
+use crate::calc::color_palette::format_histogram;
+use crate::calc::exposure::downsample_histogram;
+use crate::calc::exposure::exposure_stats;
+use crate::calc::exposure::luma_histogram;
+use crate::calc::exposure::STORED_HISTOGRAM_BINS;
+use crate::models::image_exposure::ImageExposure;
+use crate::converters::open_image::open_image;
+
+
+// Percentiles and clipping are proportions, so a mid-size copy gives the same answer much faster
+pub const EXPOSURE_ANALYSIS_MAX_DIMENSION: u32 = 512;
+
+pub fn extract_image_exposure_model(image_path: &str, img: &DynamicImage) -> ImageExposure {
+    let luma = if img.width() > EXPOSURE_ANALYSIS_MAX_DIMENSION || img.height() > EXPOSURE_ANALYSIS_MAX_DIMENSION {
+        img.resize(EXPOSURE_ANALYSIS_MAX_DIMENSION, EXPOSURE_ANALYSIS_MAX_DIMENSION, FilterType::Triangle).to_luma8()
+    } else {
+        img.to_luma8()
+    };
+
+    let histogram = luma_histogram(luma.as_raw());
+    let stats = exposure_stats(&histogram);
+    ImageExposure {
+        image_path: image_path.to_string(),
+        clipped_highlights: stats.clipped_highlights as f32,
+        clipped_shadows: stats.clipped_shadows as f32,
+        luma_p5: stats.luma_p5 as i32,
+        luma_p50: stats.luma_p50 as i32,
+        luma_p95: stats.luma_p95 as i32,
+        contrast: stats.contrast as f32,
+        luma_histogram: format_histogram(&downsample_histogram(&histogram, STORED_HISTOGRAM_BINS)),
+    }
+}
+
+pub fn open_and_extract_image_exposure_model(image_path: &str) -> Result<ImageExposure, ImageError> {
+    let img = open_image(image_path)?;
+    Ok(extract_image_exposure_model(image_path, &img))
+}
\ No newline at end of file
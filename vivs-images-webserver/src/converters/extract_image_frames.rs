@@ -0,0 +1,137 @@
+use std::fs::File;
+use std::io::{BufReader, Error, ErrorKind, Result};
+
+use image::codecs::gif::GifDecoder;
+use image::codecs::webp::WebPDecoder;
+use image::imageops::FilterType;
+use image::{AnimationDecoder, DynamicImage, Frames, ImageFormat};
+
+use crate::converters::open_image::load_image_from_memory;
+use crate::converters::tiff_structure::{count_tiff_pages, tiff_with_page_first};
+use crate::filesystem::query::images::has_extension;
+use crate::models::image_frames::{ImageFrameThumbnail, ImageFrames, ImageFramesExtraction};
+use crate::models::image_thumbnail::{ImageThumbnail, ThumbnailFormat};
+
+
+pub const FRAME_THUMBNAIL_SIZE: u32 = 128;
+// Long animations keep an evenly spaced subset of their frames rather than one thumbnail per frame
+pub const MAX_FRAME_THUMBNAILS: usize = 48;
+
+// Keeps an evenly spaced subset of a sequence whose length is not known up front: whenever the kept list
+// gets too long every other item is dropped and the stride doubles
+pub struct FrameSampler<T> {
+    max: usize,
+    stride: usize,
+    kept: Vec<(u32, T)>,
+}
+
+impl<T> FrameSampler<T> {
+    pub fn new(max: usize) -> Self {
+        Self { max: max.max(1), stride: 1, kept: vec![] }
+    }
+
+    pub fn wants(&self, index: u32) -> bool {
+        (index as usize).is_multiple_of(self.stride)
+    }
+
+    pub fn push(&mut self, index: u32, item: T) {
+        self.kept.push((index, item));
+        if self.kept.len() > self.max {
+            self.stride *= 2;
+            let stride = self.stride;
+            self.kept.retain(|(i, _)| (*i as usize).is_multiple_of(stride));
+        }
+    }
+
+    pub fn into_kept(self) -> Vec<(u32, T)> {
+        self.kept
+    }
+}
+
+fn frame_thumbnail(image_path: &str, frame_index: u32, frame_delay_ms: u32, frame: &DynamicImage) -> ImageFrameThumbnail {
+    let resized = frame.resize(FRAME_THUMBNAIL_SIZE, FRAME_THUMBNAIL_SIZE, FilterType::Triangle);
+    ImageFrameThumbnail {
+        frame_index,
+        frame_delay_ms,
+        thumbnail: ImageThumbnail::from_image(image_path.to_string(), ThumbnailFormat::PNG, &resized),
+    }
+}
+
+fn extract_animation_frames(image_path: &str, frames: Frames) -> Result<ImageFramesExtraction> {
+    let mut sampler = FrameSampler::new(MAX_FRAME_THUMBNAILS);
+    let mut frame_count = 0;
+    let mut animation_duration_ms = 0;
+    for frame in frames {
+        let frame = frame.map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        let (numerator, denominator) = frame.delay().numer_denom_ms();
+        let frame_delay_ms = numerator / denominator.max(1);
+        if sampler.wants(frame_count) {
+            let thumbnail = frame_thumbnail(image_path, frame_count, frame_delay_ms, &DynamicImage::ImageRgba8(frame.into_buffer()));
+            sampler.push(frame_count, thumbnail);
+        }
+        frame_count += 1;
+        animation_duration_ms += frame_delay_ms;
+    }
+
+    Ok(ImageFramesExtraction {
+        frames: ImageFrames {
+            image_path: image_path.to_string(),
+            frame_count,
+            is_animated: (frame_count > 1) as u32,
+            animation_duration_ms,
+        },
+        // a single frame is already covered by the regular thumbnails
+        thumbnails: if frame_count > 1 { sampler.into_kept().into_iter().map(|(_, t)| t).collect() } else { vec![] },
+    })
+}
+
+fn extract_tiff_pages(image_path: &str) -> Result<ImageFramesExtraction> {
+    let data = std::fs::read(image_path)?;
+    let page_count = count_tiff_pages(&data) as u32;
+    let mut thumbnails = vec![];
+    if page_count > 1 {
+        let stride = (page_count as usize).div_ceil(MAX_FRAME_THUMBNAILS);
+        for page in (0..page_count).step_by(stride) {
+            // pages the image crate cannot decode (an unsupported compression, say) are counted but not shown
+            let Some(page_data) = tiff_with_page_first(&data, page as usize) else {
+                continue;
+            };
+            if let Ok(img) = load_image_from_memory(&page_data, ImageFormat::Tiff) {
+                thumbnails.push(frame_thumbnail(image_path, page, 0, &img));
+            }
+        }
+    }
+
+    Ok(ImageFramesExtraction {
+        frames: ImageFrames {
+            image_path: image_path.to_string(),
+            frame_count: page_count,
+            is_animated: 0,
+            animation_duration_ms: 0,
+        },
+        thumbnails,
+    })
+}
+
+pub fn open_and_extract_image_frames(image_path: &str) -> Result<ImageFramesExtraction> {
+    if has_extension(image_path, &["gif"]) {
+        let decoder = GifDecoder::new(BufReader::new(File::open(image_path)?))
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        extract_animation_frames(image_path, decoder.into_frames())
+    } else if has_extension(image_path, &["webp"]) {
+        let decoder = WebPDecoder::new(BufReader::new(File::open(image_path)?))
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        if decoder.has_animation() {
+            extract_animation_frames(image_path, decoder.into_frames())
+        } else {
+            Ok(ImageFramesExtraction {
+                frames: ImageFrames { image_path: image_path.to_string(), frame_count: 1, is_animated: 0, animation_duration_ms: 0 },
+                thumbnails: vec![],
+            })
+        }
+    } else if has_extension(image_path, &["tif", "tiff"]) {
+        extract_tiff_pages(image_path)
+    } else {
+        Err(Error::new(ErrorKind::Unsupported, format!("{} is not a multi-frame format", image_path)))
+    }
+}
\ No newline at end of file
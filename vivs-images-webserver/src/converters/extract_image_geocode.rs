@@ -0,0 +1,30 @@
+use crate::calc::reverse_geocode::{is_valid_gps_coordinate, ReverseGeocoder};
+use crate::models::image_geocode::{ImageGeocode, ImageGpsCoordinates};
+
+
+// Names the place nearest to the coordinates; invalid coordinates and ones further than max_distance_km from
+// every known place get a row without names
+pub fn extract_image_geocode(coordinates: &ImageGpsCoordinates, geocoder: &ReverseGeocoder, max_distance_km: f64) -> ImageGeocode {
+    let mut geocode = ImageGeocode {
+        image_path: coordinates.image_path.clone(),
+        place_country_code: None,
+        place_country: None,
+        place_region: None,
+        place_city: None,
+        place_city_distance_km: None,
+        place_dataset: geocoder.get_dataset_name().to_string(),
+    };
+    if !is_valid_gps_coordinate(coordinates.latitude, coordinates.longitude) {
+        return geocode;
+    }
+    if let Some((place, distance_km)) = geocoder.nearest(coordinates.latitude, coordinates.longitude) {
+        if distance_km <= max_distance_km {
+            geocode.place_country = Some(geocoder.get_country_name(&place.country_code).unwrap_or(&place.country_code).to_string());
+            geocode.place_country_code = Some(place.country_code.clone());
+            geocode.place_region = place.region.clone();
+            geocode.place_city = Some(place.name.clone());
+            geocode.place_city_distance_km = Some(distance_km as f32);
+        }
+    }
+    geocode
+}
\ No newline at end of file
@@ -1,5 +1,69 @@
+use std::collections::HashMap;
 use std::{io::{Error, ErrorKind}, process::Command};
 
+use image::metadata::Orientation;
+
+use crate::actions::action_registry::ActionOption;
+use crate::calc::ocr_preprocess::OcrPageTransform;
+use crate::converters::ocr_preprocess_image::preprocess_image_for_ocr;
+use crate::converters::ocr_preprocess_image::OcrPreprocessOptions;
+use crate::converters::open_image::read_image_orientation;
+
+use crate::models::config::ocr::get_ocr_languages;
+use crate::models::config::ocr::parse_ocr_languages;
+use crate::models::config::ocr::OCR_LOW_CONFIDENCE_THRESHOLD;
+use crate::models::image_ocr_summary::ImageOcrSummary;
+use crate::models::image_ocr_text::ImageOcrText;
+use crate::models::image_ocr_word::ImageOcrWord;
+
+
+pub struct OcrOptions {
+    pub languages: Vec<String>,
+    pub preprocess: OcrPreprocessOptions,
+    // also read images again whose stored text came from other languages or preprocessing options
+    pub reprocess: bool,
+}
+
+impl OcrOptions {
+    pub fn from_config() -> Self {
+        Self { languages: get_ocr_languages(), preprocess: OcrPreprocessOptions::default(), reprocess: false }
+    }
+
+    pub fn get_action_options() -> Vec<ActionOption> {
+        let mut options = vec![
+            ActionOption::new_string("ocr_languages", "Tesseract languages", &get_ocr_languages().join("+")),
+            ActionOption::new_bool("ocr_reprocess", "Read again images read with other languages or preprocessing", false),
+        ];
+        options.extend(OcrPreprocessOptions::get_action_options());
+        options
+    }
+
+    pub fn from_action_options(action_options: &HashMap<String, String>) -> Self {
+        let languages = action_options.get("ocr_languages")
+            .map(|l| parse_ocr_languages(l))
+            .filter(|l| !l.is_empty())
+            .unwrap_or_else(get_ocr_languages);
+        let reprocess = action_options.get("ocr_reprocess").map(|v| v == "true" || v == "on").unwrap_or(false);
+        Self { languages, preprocess: OcrPreprocessOptions::from_action_options(action_options), reprocess }
+    }
+
+    pub fn get_languages_arg(&self) -> String {
+        self.languages.join("+")
+    }
+}
+
+// Everything one structured tesseract run produces for an image
+pub struct ImageOcrResult {
+    pub text: ImageOcrText,
+    pub summary: ImageOcrSummary,
+    pub words: Vec<ImageOcrWord>,
+}
+
+impl std::fmt::Display for ImageOcrResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.summary.image_path, self.summary)
+    }
+}
 
 pub fn extract_image_ocr_text(image_path: &str) -> Result<String, Box<dyn std::error::Error>> {
     // Run Tesseract OCR compare command
@@ -14,4 +78,149 @@ pub fn extract_image_ocr_text(image_path: &str) -> Result<String, Box<dyn std::e
         let error_msg = String::from_utf8_lossy(&output.stderr);
         return Err(Box::new(Error::new(ErrorKind::Other, format!("Tesseract error: {}", error_msg))));
     }
+}
+
+pub fn extract_image_ocr_structured(image_path: &str, options: &OcrOptions) -> Result<ImageOcrResult, Box<dyn std::error::Error>> {
+    if !options.preprocess.is_enabled() {
+        // tesseract reads the stored pixels and ignores the exif orientation, so the boxes are turned upright here
+        let mut result = run_tesseract_tsv(image_path, image_path, options, vec![])?;
+        let mut transform = OcrPageTransform::identity(result.summary.ocr_page_width as u32, result.summary.ocr_page_height as u32);
+        transform.orientation = read_image_orientation(image_path).unwrap_or(Orientation::NoTransforms);
+        map_ocr_result_to_original(&mut result, &transform);
+        return Ok(result);
+    }
+
+    // tesseract reads from disk, so the preprocessed copy goes to a temporary png
+    let (img, steps, transform) = preprocess_image_for_ocr(image_path, &options.preprocess)?;
+    let temp_file = tempfile::Builder::new().suffix(".png").tempfile()?;
+    img.save_with_format(temp_file.path(), image::ImageFormat::Png)?;
+    let temp_path = temp_file.path().to_string_lossy().to_string();
+    let mut result = run_tesseract_tsv(image_path, &temp_path, options, steps)?;
+    map_ocr_result_to_original(&mut result, &transform);
+    Ok(result)
+}
+
+// The overlay draws word boxes over the image as it is shown, so boxes found on an upscaled, deskewed or sideways
+// page are mapped back, and the page size becomes that of the upright image
+pub fn map_ocr_result_to_original(result: &mut ImageOcrResult, transform: &OcrPageTransform) {
+    if transform.is_identity() {
+        return;
+    }
+    for word in &mut result.words {
+        (word.left, word.top, word.width, word.height) = transform.box_to_original(word.left, word.top, word.width, word.height);
+    }
+    let (width, height) = transform.upright_size();
+    result.summary.ocr_page_width = width as i32;
+    result.summary.ocr_page_height = height as i32;
+}
+
+fn run_tesseract_tsv(image_path: &str, input_path: &str, options: &OcrOptions, preprocessing_steps: Vec<String>) -> Result<ImageOcrResult, Box<dyn std::error::Error>> {
+    let languages = options.get_languages_arg();
+    let mut args = vec![input_path, "-"];
+    if !languages.is_empty() {
+        args.extend_from_slice(&["-l", &languages]);
+    }
+    args.push("tsv");
+    let output = Command::new("tesseract")
+        .args(&args)
+        .output()?;
+
+    if output.status.success() {
+        let tsv = String::from_utf8_lossy(&output.stdout);
+        let mut result = parse_tesseract_tsv(image_path, &languages, &tsv);
+        result.summary.ocr_preprocessing = preprocessing_steps.join(",");
+        result.summary.ocr_options = options.preprocess.get_enabled_names();
+        Ok(result)
+    } else {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        Err(Box::new(Error::other(format!("Tesseract error: {}", error_msg))))
+    }
+}
+
+// Columns: level page_num block_num par_num line_num word_num left top width height conf text
+pub fn parse_tesseract_tsv(image_path: &str, languages: &str, tsv: &str) -> ImageOcrResult {
+    let mut words: Vec<ImageOcrWord> = vec![];
+    let (mut page_width, mut page_height) = (0, 0);
+    let mut text = String::new();
+    let mut confident_text = String::new();
+    let mut last_position: Option<(i32, i32, i32, i32)> = None;
+
+    for line in tsv.lines().skip(1) {
+        let cols: Vec<&str> = line.splitn(12, '\t').collect();
+        if cols.len() < 11 {
+            continue;
+        }
+        let num = |i: usize| cols[i].trim().parse::<i32>().unwrap_or_default();
+        match num(0) {
+            // a multi-page file has a page row per page; the size kept is the first page's, which is the one shown
+            1 if page_width == 0 && page_height == 0 => {
+                page_width = num(8);
+                page_height = num(9);
+            }
+            5 => {
+                let word = cols.get(11).map(|w| w.trim()).unwrap_or_default();
+                let confidence = cols[10].trim().parse::<f32>().unwrap_or(-1.0);
+                if word.is_empty() || confidence < 0.0 {
+                    continue;
+                }
+
+                // block, paragraph and line numbers start again on every page
+                let position = (num(1), num(2), num(3), num(4));
+                let separator = match last_position {
+                    None => "",
+                    Some((page, block, par, _)) if page != position.0 || block != position.1 || par != position.2 => "\n\n",
+                    Some((_, _, _, line)) if line != position.3 => "\n",
+                    _ => " ",
+                };
+                last_position = Some(position);
+                text.push_str(separator);
+                text.push_str(word);
+                if confidence >= OCR_LOW_CONFIDENCE_THRESHOLD {
+                    if !confident_text.is_empty() {
+                        confident_text.push_str(if separator.is_empty() { " " } else { separator });
+                    }
+                    confident_text.push_str(word);
+                }
+
+                words.push(ImageOcrWord {
+                    image_path: image_path.to_string(),
+                    word_index: words.len() as i32,
+                    page_num: position.0,
+                    block_num: position.1,
+                    line_num: position.3,
+                    text: word.to_string(),
+                    confidence,
+                    left: num(6),
+                    top: num(7),
+                    width: num(8),
+                    height: num(9),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let mean_confidence = if words.is_empty() {
+        0.0
+    } else {
+        words.iter().map(|w| w.confidence).sum::<f32>() / words.len() as f32
+    };
+    let low_confidence_count = words.iter().filter(|w| w.confidence < OCR_LOW_CONFIDENCE_THRESHOLD).count();
+
+    ImageOcrResult {
+        text: ImageOcrText { image_path: image_path.to_string(), ocr_text: text },
+        summary: ImageOcrSummary {
+            image_path: image_path.to_string(),
+            ocr_languages: languages.to_string(),
+            ocr_word_count: words.len() as i32,
+            ocr_low_confidence_word_count: low_confidence_count as i32,
+            ocr_mean_confidence: mean_confidence,
+            ocr_confident_text: confident_text,
+            ocr_page_width: page_width,
+            ocr_page_height: page_height,
+            ocr_preprocessing: String::new(),
+            ocr_options: String::new(),
+        },
+        words,
+    }
 }
\ No newline at end of file
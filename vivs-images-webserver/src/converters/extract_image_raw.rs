@@ -0,0 +1,26 @@
+use std::io::Result;
+use std::path::Path;
+
+use image::GenericImageView;
+
+use crate::converters::open_image::open_image;
+use crate::filesystem::query::images::find_jpeg_sibling;
+use crate::models::image_raw::ImageRaw;
+
+
+pub fn open_and_extract_image_raw_model(image_path: &str) -> Result<ImageRaw> {
+    let preview = open_image(image_path).map_err(std::io::Error::other)?;
+    let (preview_width, preview_height) = preview.dimensions();
+    let raw_format = Path::new(image_path).extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_default();
+
+    Ok(ImageRaw {
+        image_path: image_path.to_string(),
+        raw_format,
+        preview_width,
+        preview_height,
+        jpeg_sibling_path: find_jpeg_sibling(image_path),
+    })
+}
\ No newline at end of file
@@ -0,0 +1,38 @@
+use image::imageops::FilterType;
+use image::DynamicImage;
+use image::ImageError;
+
+use crate::calc::luma_plane::LumaPlane;
+use crate::calc::sharpness::estimate_motion_blur;
+use crate::calc::sharpness::laplacian_variance;
+use crate::calc::sharpness::motion_blur_score;
+use crate::models::image_sharpness::ImageSharpness;
+use crate::converters::open_image::open_image;
+
+
+// Laplacian variance depends on resolution, so every image is scaled, up or down, until its longer side is
+// this long; small images come out softer, as they would look shown at that size
+pub const SHARPNESS_ANALYSIS_DIMENSION: u32 = 1024;
+
+pub fn extract_image_sharpness_model(image_path: &str, img: &DynamicImage) -> ImageSharpness {
+    let plane = if img.width().max(img.height()) != SHARPNESS_ANALYSIS_DIMENSION {
+        let resized = img.resize(SHARPNESS_ANALYSIS_DIMENSION, SHARPNESS_ANALYSIS_DIMENSION, FilterType::Triangle);
+        LumaPlane::from_gray_image(&resized.to_luma8())
+    } else {
+        LumaPlane::from_gray_image(&img.to_luma8())
+    };
+
+    let sharpness = laplacian_variance(&plane);
+    let motion_blur = estimate_motion_blur(&plane);
+    ImageSharpness {
+        image_path: image_path.to_string(),
+        sharpness: sharpness as f32,
+        motion_blur: motion_blur_score(&motion_blur, sharpness) as f32,
+        motion_blur_angle: motion_blur.angle_degrees as f32,
+    }
+}
+
+pub fn open_and_extract_image_sharpness_model(image_path: &str) -> Result<ImageSharpness, ImageError> {
+    let img = open_image(image_path)?;
+    Ok(extract_image_sharpness_model(image_path, &img))
+}
\ No newline at end of file
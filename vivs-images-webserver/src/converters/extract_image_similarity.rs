@@ -1,19 +1,24 @@
 use std::io::ErrorKind;
-use std::path::Path;
 use std::io::Result;
 use std::io::Error;
 use std::process::Command;
 
 use image::imageops::FilterType;
+use image::metadata::Orientation;
 use image::DynamicImage;
 use image::GenericImageView;
 use tempfile::NamedTempFile;
 
+use crate::calc::ssim::compute_ms_ssim;
+use crate::calc::ssim::compute_windowed_ssim;
+use crate::calc::luma_plane::LumaPlane;
 use crate::converters::convert_images_same_size_max::resize_to_common_dimensions;
-use crate::converters::string_to_hashcode::string_hashcode_java_style;
 use crate::core::data_context::WebServerActionDataContext;
 use crate::models::image_similarity::ImageComparisonAlgorithm;
 use crate::models::image_similarity::ImageSimilarity;
+use crate::converters::open_image::{open_image, read_image_orientation};
+use crate::filesystem::query::images::is_raw_image_path;
+use crate::models::config::similarity::SIMILARITY_THUMBNAIL_SIZE;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ComputeImageSimilarityOptions {
@@ -43,8 +48,9 @@ impl std::cmp::Eq for ComputeImageSimilarityOptions {
 
 impl std::hash::Hash for ComputeImageSimilarityOptions {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        let key = compute_comparison_key(&self.image_path_a, &self.image_path_b);
+        let key = order_comparison_pair(&self.image_path_a, &self.image_path_b);
         key.hash(state);
+        self.algo.hash(state);
     }
 }
 
@@ -59,13 +65,15 @@ pub async fn extract_image_similarity(options: &ComputeImageSimilarityOptions, p
         ImageComparisonAlgorithm::Magick => extract_image_similarity_using_magick(options)?,
         ImageComparisonAlgorithm::CustomV1 => extract_image_similarity_using_custom_v1(options)?,
         ImageComparisonAlgorithm::CustomV2Thumbnails => extract_image_similarity_using_custom_v2_thumbnails(options, pool).await?,
+        ImageComparisonAlgorithm::WindowedSsim |
+        ImageComparisonAlgorithm::MultiScaleSsim => extract_image_similarity_using_windowed_ssim_from_disk(options)?,
     };
-    let image_comparison_key = compute_comparison_key(&options.image_path_a, &options.image_path_b);
+    let (image_path_a, image_path_b) = order_comparison_pair(&options.image_path_a, &options.image_path_b);
     let image_comparison_algorithm = options.algo.clone();
     Ok(ImageSimilarity {
-        image_comparison_key, image_comparison_algorithm, 
-        image_path_a: options.image_path_a.clone(),
-        image_path_b: options.image_path_b.clone(),
+        image_comparison_algorithm, 
+        image_path_a,
+        image_path_b,
         similarity_value, similarity_confidence
     })
 }
@@ -73,7 +81,7 @@ pub async fn extract_image_similarity(options: &ComputeImageSimilarityOptions, p
 async fn extract_image_similarity_using_custom_v2_thumbnails(options: &ComputeImageSimilarityOptions, pool: WebServerActionDataContext) -> Result<(f32, f32)> {
     let img_a = pool.get_thumbnail_at_most_width_length(
         &options.image_path_a, 
-        options.max_dimension.unwrap_or(32),
+        options.max_dimension.unwrap_or(SIMILARITY_THUMBNAIL_SIZE),
         ).await
         .map_err(|e| Error::new(ErrorKind::InvalidData, format!("{}", e)))?;
 
@@ -83,7 +91,7 @@ async fn extract_image_similarity_using_custom_v2_thumbnails(options: &ComputeIm
 
     let img_b = pool.get_thumbnail_at_most_width_length(
         &options.image_path_b, 
-        options.max_dimension.unwrap_or(32),
+        options.max_dimension.unwrap_or(SIMILARITY_THUMBNAIL_SIZE),
         ).await
         .map_err(|e| Error::new(ErrorKind::InvalidData, format!("{}", e)))?;
 
@@ -97,14 +105,30 @@ async fn extract_image_similarity_using_custom_v2_thumbnails(options: &ComputeIm
     extract_image_similarity_using_ssim_confidence(img_a, img_b, options)
 }
 
-pub fn compute_comparison_key(image_path_a: &str, image_path_b: &str) -> i32 {
-    string_hashcode_java_style(image_path_a).wrapping_add(string_hashcode_java_style(image_path_b))
+// The small thumbnail every thumbnail-based comparison works on, decoded; None when it has not been generated yet
+pub async fn get_similarity_thumbnail_image(image_path: &str, pool: &WebServerActionDataContext) -> std::result::Result<Option<DynamicImage>, Box<dyn std::error::Error + Send>> {
+    match pool.get_thumbnail_at_most_width_length(image_path, SIMILARITY_THUMBNAIL_SIZE).await? {
+        Some(thumb) => thumb.to_image()
+            .map(Some)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send>),
+        None => Ok(None),
+    }
+}
+
+// Similarity is symmetric, so a pair is always stored with the smaller path first; together with the
+// algorithm this is the image_similarity primary key
+pub fn order_comparison_pair(image_path_a: &str, image_path_b: &str) -> (String, String) {
+    if image_path_a <= image_path_b {
+        (image_path_a.to_string(), image_path_b.to_string())
+    } else {
+        (image_path_b.to_string(), image_path_a.to_string())
+    }
 }
 
 fn extract_image_similarity_using_custom_v1(options: &ComputeImageSimilarityOptions) -> Result<(f32, f32)> {
-    let img_a = image::open(Path::new(&options.image_path_a))
+    let img_a = open_image(&options.image_path_a)
         .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
-    let img_b = image::open(Path::new(&options.image_path_b))
+    let img_b = open_image(&options.image_path_b)
         .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
 
     extract_image_similarity_using_ssim_confidence(img_a, img_b, options)
@@ -175,15 +199,74 @@ pub fn extract_image_similarity_using_ssim_confidence(img_a: DynamicImage, img_b
     Ok((similarity, confidence))
 }
 
+fn extract_image_similarity_using_windowed_ssim_from_disk(options: &ComputeImageSimilarityOptions) -> Result<(f32, f32)> {
+    let img_a = open_image(&options.image_path_a)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    let img_b = open_image(&options.image_path_b)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+
+    extract_image_similarity_using_windowed_ssim(img_a, img_b, options)
+}
+
+// Gaussian-windowed SSIM or MS-SSIM (depending on options.algo) over the resized luma planes
+pub fn extract_image_similarity_using_windowed_ssim(img_a: DynamicImage, img_b: DynamicImage, options: &ComputeImageSimilarityOptions) -> Result<(f32, f32)> {
+    let (resized_a, resized_b) = resize_to_common_dimensions(
+        &img_a.grayscale(),
+        &img_b.grayscale(),
+        options.max_dimension,
+        options.filter_type.unwrap_or(FilterType::Lanczos3)
+    );
+
+    let luma_a = LumaPlane::from_gray_image(&resized_a.to_luma8());
+    let luma_b = LumaPlane::from_gray_image(&resized_b.to_luma8());
+
+    let similarity = match options.algo {
+        ImageComparisonAlgorithm::MultiScaleSsim => compute_ms_ssim(&luma_a, &luma_b),
+        _ => compute_windowed_ssim(&luma_a, &luma_b),
+    }.ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("cannot compute SSIM for {}", options)))?;
+
+    // Same variance-based confidence as the global SSIM approach
+    let avg_variance = (luma_a.variance() + luma_b.variance()) / 2.0;
+    let confidence = (avg_variance / 65025.0).clamp(0.0, 1.0);
+
+    Ok((similarity as f32, confidence as f32))
+}
+
+// ImageMagick compares pixels as stored and needs a delegate to read RAW files, so RAW and rotated images are
+// handed to it upright, through open_image, as a temp file instead
+fn magick_input_file(image_path: &str) -> Result<Option<NamedTempFile>> {
+    let orientation = read_image_orientation(image_path).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    if !is_raw_image_path(image_path) && orientation == Orientation::NoTransforms {
+        return Ok(None);
+    }
+    let img = open_image(image_path).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    let preview_file = tempfile::Builder::new().suffix(".png").tempfile()?;
+    img.save_with_format(preview_file.path(), image::ImageFormat::Png)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    Ok(Some(preview_file))
+}
+
+fn magick_input_path<'a>(image_path: &'a str, preview_file: &'a Option<NamedTempFile>) -> Result<&'a str> {
+    match preview_file {
+        Some(f) => f.path().to_str().ok_or_else(|| Error::other("Invalid temp path")),
+        None => Ok(image_path),
+    }
+}
+
 fn extract_image_similarity_using_magick(options: &ComputeImageSimilarityOptions) -> Result<(f32, f32)> {
     // Create a temporary file
     let temp_diff_image = NamedTempFile::new().unwrap();
     let temp_path = temp_diff_image.path().to_str()
         .ok_or_else(|| Error::new(ErrorKind::Other, "Invalid temp path"))?;
+    let preview_a = magick_input_file(&options.image_path_a)?;
+    let preview_b = magick_input_file(&options.image_path_b)?;
 
     // Run ImageMagick compare command
     let output = Command::new("magick")
-        .args(["compare", "-metric", "PSNR", &options.image_path_a, &options.image_path_b, temp_path])
+        .args(["compare", "-metric", "PSNR",
+            magick_input_path(&options.image_path_a, &preview_a)?,
+            magick_input_path(&options.image_path_b, &preview_b)?,
+            temp_path])
         .output()?;
 
     // println!("output.status.success(): {}", output.status.success());
@@ -1,121 +1,212 @@
-use chrono::{DateTime, Datelike, NaiveDateTime, Utc};
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Offset, Timelike};
 use exif::{In, Tag};
 use regex::Regex;
 
-pub fn extract_image_taken_at(image_path: &str) -> actix_web::Result<Option<String>> {
-    let mut taken_at: Option<String> = None;
+use crate::models::image_taken_at::{ImageTakenAt, TakenAtSource};
+
+// Capture times are stored in this sortable ISO-8601 form, as the local wall-clock time the camera recorded
+pub const TAKEN_AT_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+// used instead when the camera recorded fractions of a second, so shots within the same second keep their order
+pub const TAKEN_AT_FORMAT_MILLIS: &str = "%Y-%m-%dT%H:%M:%S%.3f";
+
+const TAKEN_AT_INPUT_FORMATS: &[&str] = &[
+    "%Y:%m:%d %H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S UTC",
+    "%Y-%m-%dT%H:%M",
+];
+
+// Each date tag with the sub-second and offset tags that belong to it, most specific first
+const EXIF_TAKEN_AT_TAGS: [(Tag, Tag, Tag); 3] = [
+    (Tag::DateTimeOriginal, Tag::SubSecTimeOriginal, Tag::OffsetTimeOriginal),
+    (Tag::DateTimeDigitized, Tag::SubSecTimeDigitized, Tag::OffsetTimeDigitized),
+    (Tag::DateTime, Tag::SubSecTime, Tag::OffsetTime),
+];
+
+const XMP_TAKEN_AT_TAGS: [&str; 4] = [
+    "Xmp.exif.DateTimeOriginal",
+    "Xmp.photoshop.DateCreated",
+    "Xmp.xmp.CreateDate",
+    "Xmp.exif.DateTimeDigitized",
+];
+
+// How far the camera clock may be from the GPS clock once the time zone is taken out, for the zone to be trusted
+const GPS_OFFSET_TOLERANCE_MINUTES: i64 = 5;
+
+// Tries exif date tags, then xmp, then the exif GPS clock, then the file name and finally the file's own times
+pub fn extract_image_taken_at(image_path: &str, exif: Option<&exif::Exif>) -> Option<ImageTakenAt> {
+    exif.and_then(extract_taken_at_from_exif)
+        .or_else(|| extract_taken_at_from_xmp_file(image_path))
+        .or_else(|| exif.and_then(extract_taken_at_from_gps))
+        .or_else(|| extract_taken_at_from_file_name(image_path))
+        .or_else(|| extract_taken_at_from_file_times(image_path))
+}
+
+pub fn format_taken_at(local_time: &NaiveDateTime) -> String {
+    let format = if local_time.nanosecond() == 0 { TAKEN_AT_FORMAT } else { TAKEN_AT_FORMAT_MILLIS };
+    local_time.format(format).to_string()
+}
 
-    // try exif first
-    taken_at = extract_image_date_from_exif(image_path)?;
-    // println!("extract_image_taken_at exif_string = {:?}", taken_at);
-    if taken_at.is_some() {
-        return Ok(taken_at)
+// Reads the date forms exif tags, xmp, file names and earlier versions of this server produced, with the zone
+// offset when the text has one
+pub fn parse_taken_at(value: &str) -> Option<(NaiveDateTime, Option<FixedOffset>)> {
+    let value = value.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value).or_else(|_| DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M%:z")) {
+        return Some((dt.naive_local(), Some(*dt.offset())));
     }
+    let naive = TAKEN_AT_INPUT_FORMATS.iter().find_map(|f| NaiveDateTime::parse_from_str(value, f).ok())
+        .or_else(|| ["%Y:%m:%d", "%Y-%m-%d"].iter()
+            .find_map(|f| NaiveDate::parse_from_str(value, f).ok())
+            .and_then(|d| d.and_hms_opt(0, 0, 0)))?;
+    Some((naive, None))
+}
+
+// The offset is dropped so the time stays the one shown on the camera
+pub fn normalize_taken_at(value: &str) -> Option<String> {
+    parse_taken_at(value).map(|(local_time, _)| format_taken_at(&local_time))
+}
 
-    // try the file name
-    taken_at = extract_image_date_from_file_name(image_path);
-    // println!("extract_image_taken_at file_name = {:?}", taken_at);    
-    if taken_at.is_some() {
-        return Ok(taken_at)
+fn exif_ascii(exif: &exif::Exif, tag: Tag) -> Option<String> {
+    match &exif.get_field(tag, In::PRIMARY)?.value {
+        exif::Value::Ascii(strings) => strings.iter()
+            .map(|s| String::from_utf8_lossy(s).trim_matches(|c: char| c == '\0' || c.is_whitespace()).to_string())
+            .find(|s| !s.is_empty()),
+        _ => None,
     }
+}
 
-    // final attempt is to get file creation time
-    // println!("extract_image_taken_at file_creation_time = {:?}", taken_at);    
+// "+02:00" style; cameras without a zone set write blanks
+pub fn parse_exif_offset(value: &str) -> Option<FixedOffset> {
+    value.trim().parse::<FixedOffset>().ok()
+}
 
-    Ok(taken_at)
+// SubSecTime holds the digits after the decimal point, so "5" is half a second and "050" is 50 milliseconds
+pub fn parse_exif_sub_seconds(value: &str) -> Option<u32> {
+    let digits = value.trim();
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let nine_digits: String = digits.chars().chain(std::iter::repeat('0')).take(9).collect();
+    nine_digits.parse().ok()
 }
 
-fn extract_image_date_from_exif(image_path: &str) -> actix_web::Result<Option<String>> {
-    let file = std::fs::File::open(image_path)?;
-    let mut bufreader = std::io::BufReader::new(&file);
-    let exifreader = exif::Reader::new();
-    let exif = exifreader.read_from_container(&mut bufreader)
-        .map_err(|e| actix_web::error::ErrorInternalServerError(format!("{}", e)))?;
-    
-    // Try various EXIF date fields (different cameras use different fields)
-    for tag in [Tag::DateTimeOriginal, Tag::DateTimeDigitized, Tag::DateTime] {
-        if let Some(x) = exif.get_field(tag, In::PRIMARY) {
-            return Ok(Some(x.display_value().to_string()))
+// Cameras without a clock set write placeholders like 0000:00:00 00:00:00, so those fall through to the next tag
+pub fn extract_taken_at_from_exif(exif: &exif::Exif) -> Option<ImageTakenAt> {
+    EXIF_TAKEN_AT_TAGS.iter().find_map(|(date_tag, sub_seconds_tag, offset_tag)| {
+        let (mut local_time, _) = parse_taken_at(&exif_ascii(exif, *date_tag)?)?;
+        if let Some(nanos) = exif_ascii(exif, *sub_seconds_tag).and_then(|v| parse_exif_sub_seconds(&v)) {
+            local_time = local_time.with_nanosecond(nanos).unwrap_or(local_time);
         }
+        let offset = exif_ascii(exif, *offset_tag).and_then(|v| parse_exif_offset(&v))
+            .or_else(|| offset_from_gps(&local_time, &extract_gps_utc(exif)?));
+        Some(ImageTakenAt { local_time, offset, source: TakenAtSource::Exif })
+    })
+}
+
+// The GPS clock is UTC, so with no other date the local time is unknown and UTC is used
+pub fn extract_taken_at_from_gps(exif: &exif::Exif) -> Option<ImageTakenAt> {
+    let utc = extract_gps_utc(exif)?;
+    Some(ImageTakenAt { local_time: utc, offset: FixedOffset::east_opt(0), source: TakenAtSource::Gps })
+}
+
+pub fn extract_gps_utc(exif: &exif::Exif) -> Option<NaiveDateTime> {
+    let date_stamp = exif_ascii(exif, Tag::GPSDateStamp)?;
+    let date = ["%Y:%m:%d", "%Y-%m-%d"].iter().find_map(|f| NaiveDate::parse_from_str(&date_stamp, f).ok())?;
+    let hms: Vec<f64> = match &exif.get_field(Tag::GPSTimeStamp, In::PRIMARY)?.value {
+        exif::Value::Rational(values) => values.iter().map(|v| v.to_f64()).collect(),
+        _ => return None,
+    };
+    let [hours, minutes, seconds] = hms[..] else {
+        return None;
+    };
+    if !(hours.is_finite() && minutes.is_finite() && seconds.is_finite()) || hours < 0.0 || minutes < 0.0 || seconds < 0.0 {
+        return None;
+    }
+    let time = NaiveTime::from_hms_nano_opt(hours as u32, minutes as u32, seconds as u32, (seconds.fract() * 1e9) as u32)?;
+    Some(date.and_time(time))
+}
+
+// Zones are whole quarter hours, so the camera's local time less the GPS time gives the zone if the clocks agree
+fn offset_from_gps(local_time: &NaiveDateTime, gps_utc: &NaiveDateTime) -> Option<FixedOffset> {
+    let minutes = (*local_time - *gps_utc).num_seconds() as f64 / 60.0;
+    let zone_minutes = ((minutes / 15.0).round() * 15.0) as i64;
+    if zone_minutes.abs() > 14 * 60 || (minutes - zone_minutes as f64).abs() > GPS_OFFSET_TOLERANCE_MINUTES as f64 {
+        return None;
     }
+    FixedOffset::east_opt((zone_minutes * 60) as i32)
+}
+
+// Takes the xmp tags as the xmp import stores them, keyed like Xmp.exif.DateTimeOriginal
+pub fn extract_taken_at_from_xmp(tags: &HashMap<String, String>) -> Option<ImageTakenAt> {
+    XMP_TAKEN_AT_TAGS.iter().find_map(|tag| {
+        let (local_time, offset) = parse_taken_at(tags.get(*tag)?)?;
+        Some(ImageTakenAt { local_time, offset, source: TakenAtSource::Xmp })
+    })
+}
 
-    Ok(None)
+fn extract_taken_at_from_xmp_file(image_path: &str) -> Option<ImageTakenAt> {
+    let metadata = rexiv2::Metadata::new_from_path(Path::new(image_path)).ok()?;
+    let tags: HashMap<String, String> = XMP_TAKEN_AT_TAGS.iter()
+        .filter_map(|tag| Some((tag.to_string(), metadata.get_tag_string(tag).ok()?)))
+        .collect();
+    extract_taken_at_from_xmp(&tags)
 }
 
-// Function to extract date from filename (common camera naming patterns)
-// Common camera filename patterns:
-// IMG_YYYYMMDD_HHMMSS.jpg
-// DSC_YYYYMMDD_HHMMSS.jpg
-// YYYYMMDD_HHMMSS.jpg
-// IMG-YYYYMMDD-WA0000.jpg (WhatsApp)
-fn extract_image_date_from_file_name(image_path: &str) -> Option<String> {
-    let filename = std::path::Path::new(image_path)
-        .file_name().unwrap()
-        .to_str().unwrap()
+// Common camera and phone file name patterns, all in local time except Pixel phones which name files in UTC:
+// IMG_YYYYMMDD_HHMMSS.jpg, DSC_YYYYMMDD_HHMMSS.jpg, YYYYMMDD_HHMMSS.jpg, Screenshot_YYYYMMDD-HHMMSS.png,
+// PXL_YYYYMMDD_HHMMSSmmm.jpg, YYYY-MM-DD HH.MM.SS.jpg (Dropbox), signal-YYYY-MM-DD-HHMMSS.jpg and
+// IMG-YYYYMMDD-WA0000.jpg (WhatsApp, date only)
+pub fn extract_taken_at_from_file_name(image_path: &str) -> Option<ImageTakenAt> {
+    let filename = Path::new(image_path)
+        .file_name()?
+        .to_str()?
         .to_lowercase();
-    
-    let patterns = [
-        // Pattern 1: IMG_YYYYMMDD_HHMMSS or DSC_YYYYMMDD_HHMMSS
-        r"(?:img|dsc)_(\d{4})(\d{2})(\d{2})_(\d{2})(\d{2})(\d{2})",
-        // Pattern 2: YYYYMMDD_HHMMSS (standalone)
-        r"(\d{4})(\d{2})(\d{2})_(\d{2})(\d{2})(\d{2})",
-        // Pattern 3: IMG-YYYYMMDD-WA0000 (WhatsApp)
-        r"(?:img|dsc)-(\d{4})(\d{2})(\d{2})-wa\d+",
-        // Pattern 4: YYYYMMDD_HHMMSS with various separators
-        r"(\d{4})(\d{2})(\d{2})[-_](\d{2})(\d{2})(\d{2})",
+
+    let patterns: [(&str, Option<FixedOffset>); 4] = [
+        (r"pxl_(\d{4})(\d{2})(\d{2})_(\d{2})(\d{2})(\d{2})(\d{3})", FixedOffset::east_opt(0)),
+        (r"(?:^|\D)(\d{4})(\d{2})(\d{2})[-_ ]?(\d{2})(\d{2})(\d{2})(?:\D|$)", None),
+        (r"(?:^|\D)(\d{4})-(\d{2})-(\d{2})[-_ ](\d{2})[.\-:]?(\d{2})[.\-:]?(\d{2})(?:\D|$)", None),
+        (r"(?:img|vid)-(\d{4})(\d{2})(\d{2})-wa\d+", None),
     ];
 
-    for pattern in patterns.iter() {
-        if let Some(dt) = extract_datetime_from_pattern(&filename, pattern) {
-            return Some(dt.to_string());
-        }
-    }
-    
-    None
+    patterns.iter().find_map(|(pattern, offset)| {
+        let local_time = extract_datetime_from_pattern(&filename, pattern)?;
+        Some(ImageTakenAt { local_time, offset: *offset, source: TakenAtSource::FileName })
+    })
 }
 
-
-/// Extract datetime using a specific regex pattern
-fn extract_datetime_from_pattern(text: &str, pattern: &str) -> Option<DateTime<Utc>> {
+/// Extract datetime using a specific regex pattern; groups are year, month, day, then optional hour, minute,
+/// second and milliseconds
+fn extract_datetime_from_pattern(text: &str, pattern: &str) -> Option<NaiveDateTime> {
     let re = Regex::new(pattern).ok()?;
     let captures = re.captures(text)?;
-    
-    if captures.len() >= 7 { // 6 groups + full match
-        let year = captures.get(1)?.as_str().parse::<i32>().ok()?;
-        let month = captures.get(2)?.as_str().parse::<u32>().ok()?;
-        let day = captures.get(3)?.as_str().parse::<u32>().ok()?;
-        let hour = captures.get(4)?.as_str().parse::<u32>().ok()?;
-        let minute = captures.get(5)?.as_str().parse::<u32>().ok()?;
-        let second = captures.get(6)?.as_str().parse::<u32>().ok()?;
-        
-        // Try to create a NaiveDateTime first
-        if let Some(naive_dt) = NaiveDateTime::from_timestamp_opt(
-            create_timestamp(year, month, day, hour, minute, second)?, 
-            0
-        ) {
-            return Some(DateTime::from_naive_utc_and_offset(naive_dt, Utc));
-        }
-    }
-    
-    None
-}
-
+    let group = |i: usize| captures.get(i).and_then(|m| m.as_str().parse::<u32>().ok());
 
-/// Helper function to create timestamp from components
-fn create_timestamp(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> Option<i64> {
-    // Basic validation
-    if month < 1 || month > 12 || day < 1 || day > 31 || hour > 23 || minute > 59 || second > 59 {
+    let year = captures.get(1)?.as_str().parse::<i32>().ok()?;
+    // long digit runs in hashes and counters can look like dates
+    if !(1970..=2100).contains(&year) {
         return None;
     }
-    
-    // Use chrono to create proper timestamp
-    NaiveDateTime::from_timestamp_opt(0, 0)
-        .and_then(|base| {
-            base.date()
-                .with_year(year)
-                .and_then(|d| d.with_month(month))
-                .and_then(|d| d.with_day(day))
-                .and_then(|d| d.and_hms_opt(hour, minute, second))
-        })
-        .map(|dt| dt.timestamp())
+    let date = NaiveDate::from_ymd_opt(year, group(2)?, group(3)?)?;
+    let time = match (group(4), group(5), group(6)) {
+        (Some(hour), Some(minute), Some(second)) => NaiveTime::from_hms_milli_opt(hour, minute, second, group(7).unwrap_or_default())?,
+        _ => NaiveTime::MIN,
+    };
+    Some(date.and_time(time))
+}
+
+// Last resort: the earlier of the file's creation and modification times, in the server's time zone
+fn extract_taken_at_from_file_times(image_path: &str) -> Option<ImageTakenAt> {
+    let metadata = std::fs::metadata(image_path).ok()?;
+    let earliest = [metadata.created().ok(), metadata.modified().ok()].into_iter().flatten().min()?;
+    let local: DateTime<Local> = earliest.into();
+    Some(ImageTakenAt {
+        local_time: local.naive_local().with_nanosecond(0)?,
+        offset: Some(local.offset().fix()),
+        source: TakenAtSource::FileModified,
+    })
 }
@@ -1,12 +1,14 @@
-use std::path::Path;
 use std::io::{Error, ErrorKind, Result};
 
 use image::codecs::bmp::BmpEncoder;
 use image::codecs::jpeg::JpegEncoder;
-use image::{DynamicImage, ImageEncoder};
+use image::codecs::webp::WebPEncoder;
+use image::{DynamicImage, ExtendedColorType, ImageEncoder};
 use image::{codecs::png::PngEncoder, imageops::FilterType};
 
 use crate::models::image_thumbnail::{ImageThumbnail, ThumbnailFormat};
+use crate::converters::open_image::open_image;
+use crate::models::config::thumbnails::{get_thumbnail_sizes, DEFAULT_THUMBNAIL_QUALITY};
 
 
 
@@ -14,6 +16,8 @@ pub struct ExtractImageThumbnailOptions {
     pub width_and_length: u32,
     pub filter: FilterType,
     pub output_format: ThumbnailFormat,
+    // JPEG only
+    pub quality: u8,
 }
 
 pub fn convert_image_to_thumbnail_image(img: &DynamicImage, options: &ExtractImageThumbnailOptions) -> Result<DynamicImage> {
@@ -34,12 +38,13 @@ pub fn convert_image_to_vec_u8(img: &DynamicImage, options: &ExtractImageThumbna
             ).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
         },
         ThumbnailFormat::JPG => {
-            let encoder = JpegEncoder::new(&mut buf);
+            // JPEG has no alpha channel
+            let encoder = JpegEncoder::new_with_quality(&mut buf, options.quality);
             encoder.write_image(
-                img.as_bytes(),
+                img.to_rgb8().as_raw(),
                 img.width(),
                 img.height(),
-                img.color().into(),
+                ExtendedColorType::Rgb8,
             ).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
         },
         ThumbnailFormat::BMP => {
@@ -51,6 +56,15 @@ pub fn convert_image_to_vec_u8(img: &DynamicImage, options: &ExtractImageThumbna
                 img.color().into(),
             ).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
         },
+        ThumbnailFormat::WEBP => {
+            let encoder = WebPEncoder::new_lossless(&mut buf);
+            encoder.write_image(
+                img.to_rgba8().as_raw(),
+                img.width(),
+                img.height(),
+                ExtendedColorType::Rgba8,
+            ).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        },
         ThumbnailFormat::RAW_rgb8 => {
             // For raw format, just return the raw bytes
             buf = img.to_rgb8().into_raw();
@@ -66,7 +80,7 @@ pub fn convert_image_to_thumbnail(img: &DynamicImage, options: &ExtractImageThum
 }
 
 pub async fn extract_image_thumbnail(path: &str, options: ExtractImageThumbnailOptions) -> Result<ImageThumbnail> {
-    let img = image::open(Path::new(path))
+    let img = open_image(path)
         .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
 
     let buf = convert_image_to_thumbnail(&img, &options)?;
@@ -105,16 +119,17 @@ pub fn extract_multiple_image_thumbnails(size_list: &[u32], img: &DynamicImage,
 }
 
 pub fn open_and_extract_multiple_image_thumbnails_standard_sizes(path: &str) -> Result<Vec<DynamicImage>> {
-    let img = image::open(Path::new(path))
+    let img = open_image(path)
         .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
     extract_multiple_image_thumbnails_standard_sizes(&img)
 }
 
 pub const DEFAULT_THUMBNAIL_SIZE_LIST: [u32;5] = [8, 16, 32, 64, 128];
 
+// The sizes configured with VIVS_THUMBNAIL_SIZES, DEFAULT_THUMBNAIL_SIZE_LIST when unset
 pub fn extract_multiple_image_thumbnails_standard_sizes(img: &DynamicImage) -> Result<Vec<DynamicImage>> {
     let filter = image::imageops::FilterType::Lanczos3;
-    extract_multiple_image_thumbnails(&DEFAULT_THUMBNAIL_SIZE_LIST, img, filter)
+    extract_multiple_image_thumbnails(&get_thumbnail_sizes(), img, filter)
 }
 
 pub fn extract_multiple_image_thumbnails_standard_sizes_to_png_vec_u8(img: &DynamicImage) -> Result<Vec<Vec<u8>>> {
@@ -128,7 +143,8 @@ pub fn convert_thumbnails_to_vec_vec_u8(thumbs: Vec<DynamicImage>) -> std::resul
         let options = ExtractImageThumbnailOptions {
             filter: image::imageops::FilterType::Lanczos3,
             width_and_length: img.width(),
-            output_format: ThumbnailFormat::PNG
+            output_format: ThumbnailFormat::PNG,
+            quality: DEFAULT_THUMBNAIL_QUALITY,
         };
         let s = convert_image_to_vec_u8(&img, &options)?;
         imgs.push(s);
@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+use std::io::{Error, ErrorKind, Result};
+use std::ops::Range;
+
+use crate::converters::tiff_structure::TiffReader;
+
+// CR2, NEF, ARW and DNG are TIFF containers. Besides the sensor data they carry one or more JPEG previews,
+// pointed to from an IFD either as a JPEGInterchangeFormat offset and length or as a single strip.
+const TAG_COMPRESSION: u16 = 0x0103;
+const TAG_STRIP_OFFSETS: u16 = 0x0111;
+const TAG_STRIP_BYTE_COUNTS: u16 = 0x0117;
+const TAG_SUB_IFDS: u16 = 0x014A;
+const TAG_JPEG_INTERCHANGE_FORMAT: u16 = 0x0201;
+const TAG_JPEG_INTERCHANGE_FORMAT_LENGTH: u16 = 0x0202;
+const TAG_EXIF_IFD: u16 = 0x8769;
+
+// old-style and new-style JPEG compression
+const COMPRESSION_JPEG: &[u32] = &[6, 7];
+const MAX_IFDS: usize = 64;
+
+// Byte ranges of every JPEG stream referenced from the TIFF structure, in IFD order
+pub fn find_embedded_jpeg_ranges(data: &[u8]) -> Vec<Range<usize>> {
+    let Some(reader) = TiffReader::new(data) else {
+        return vec![];
+    };
+    let mut ranges = vec![];
+    let mut pending: Vec<usize> = reader.first_ifd().into_iter().collect();
+    let mut visited = HashSet::new();
+
+    while let Some(ifd) = pending.pop() {
+        if ifd == 0 || visited.len() >= MAX_IFDS || !visited.insert(ifd) {
+            continue;
+        }
+
+        let mut compression = None;
+        let mut strip_offsets = vec![];
+        let mut strip_byte_counts = vec![];
+        let mut jpeg_offset = None;
+        let mut jpeg_length = None;
+        for (tag, values) in reader.ifd_entries(ifd) {
+            match tag {
+                TAG_COMPRESSION => compression = values.first().copied(),
+                TAG_STRIP_OFFSETS => strip_offsets = values,
+                TAG_STRIP_BYTE_COUNTS => strip_byte_counts = values,
+                TAG_JPEG_INTERCHANGE_FORMAT => jpeg_offset = values.first().copied(),
+                TAG_JPEG_INTERCHANGE_FORMAT_LENGTH => jpeg_length = values.first().copied(),
+                TAG_SUB_IFDS | TAG_EXIF_IFD => pending.extend(values.iter().map(|v| *v as usize)),
+                _ => {},
+            }
+        }
+
+        if let (Some(offset), Some(length)) = (jpeg_offset, jpeg_length) {
+            ranges.extend(checked_range(offset, length, data.len()));
+        }
+        if compression.is_some_and(|c| COMPRESSION_JPEG.contains(&c)) && strip_offsets.len() == 1 && strip_byte_counts.len() == 1 {
+            ranges.extend(checked_range(strip_offsets[0], strip_byte_counts[0], data.len()));
+        }
+
+        pending.extend(reader.next_ifd(ifd));
+    }
+    ranges
+}
+
+// Offsets and lengths come straight from the file, so a malformed one must not overflow or point past the end
+fn checked_range(offset: u32, length: u32, data_len: usize) -> Option<Range<usize>> {
+    let start = usize::try_from(offset).ok()?;
+    let end = start.checked_add(usize::try_from(length).ok()?)?;
+    (start < end && end <= data_len).then_some(start..end)
+}
+
+// True for baseline, extended and progressive JPEG; lossless JPEG (how DNG and CR2 often store the sensor
+// data itself) has a different start-of-frame marker and cannot be decoded by the image crate
+pub fn is_decodable_jpeg(jpeg: &[u8]) -> bool {
+    if !jpeg.starts_with(&[0xFF, 0xD8]) {
+        return false;
+    }
+    let mut i = 2;
+    while i + 4 <= jpeg.len() {
+        if jpeg[i] != 0xFF {
+            return false;
+        }
+        let marker = jpeg[i + 1];
+        match marker {
+            0xC0..=0xC2 => return true,
+            0xC3 | 0xC5..=0xC7 | 0xC9..=0xCB | 0xCD..=0xCF => return false,
+            0xD9 | 0xDA => return false,
+            _ => {},
+        }
+        let length = u16::from_be_bytes([jpeg[i + 2], jpeg[i + 3]]) as usize;
+        i += 2 + length;
+    }
+    false
+}
+
+// The largest decodable JPEG preview inside a RAW file
+pub fn extract_largest_embedded_jpeg(data: &[u8]) -> Option<&[u8]> {
+    find_embedded_jpeg_ranges(data).into_iter()
+        .map(|r| &data[r])
+        .filter(|jpeg| is_decodable_jpeg(jpeg))
+        .max_by_key(|jpeg| jpeg.len())
+}
+
+pub fn extract_raw_preview_jpeg(raw_path: &str) -> Result<Vec<u8>> {
+    let data = std::fs::read(raw_path)?;
+    extract_largest_embedded_jpeg(&data)
+        .map(|jpeg| jpeg.to_vec())
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("no embedded JPEG preview found in {}", raw_path)))
+}
\ No newline at end of file
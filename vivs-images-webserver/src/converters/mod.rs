@@ -9,4 +9,16 @@ pub mod extract_image_similarity;
 pub mod extract_image_thumbnail;
 pub mod extract_image_xmp;
 pub mod comparison;
-pub mod string_to_hashcode;
\ No newline at end of file
+pub mod string_to_hashcode;
+pub mod extract_image_color;
+pub mod extract_image_sharpness;
+pub mod extract_image_exposure;
+pub mod ocr_preprocess_image;
+pub mod extract_image_content_hash;
+pub mod extract_raw_preview;
+pub mod open_image;
+pub mod extract_image_raw;
+pub mod tiff_structure;
+pub mod extract_image_frames;
+pub mod resize_image;
+pub mod extract_image_geocode;
\ No newline at end of file
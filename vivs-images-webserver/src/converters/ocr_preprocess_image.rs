@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use image::imageops::FilterType;
+use image::DynamicImage;
+
+use crate::actions::action_registry::ActionOption;
+use crate::calc::ocr_preprocess::adaptive_threshold;
+use crate::calc::ocr_preprocess::estimate_skew_degrees;
+use crate::calc::ocr_preprocess::OcrPageTransform;
+use crate::calc::ocr_preprocess::rotate_gray;
+use crate::converters::open_image::open_image_with_orientation;
+
+
+// Tesseract wants text at least ~20px tall, so small images are scaled up until the short side reaches this
+pub const OCR_UPSCALE_MIN_DIMENSION: u32 = 1000;
+pub const OCR_UPSCALE_MAX_FACTOR: f32 = 4.0;
+// Skew search range and resolution, measured on a copy no larger than OCR_DESKEW_SAMPLE_DIMENSION
+pub const OCR_DESKEW_MAX_DEGREES: f64 = 10.0;
+pub const OCR_DESKEW_STEP_DEGREES: f64 = 0.25;
+pub const OCR_DESKEW_SAMPLE_DIMENSION: u32 = 800;
+// Adaptive threshold window as a fraction of the image width, and how much darker than the window mean is ink
+pub const OCR_THRESHOLD_WINDOW_FRACTION: f32 = 0.125;
+pub const OCR_THRESHOLD_BIAS: f64 = 0.15;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OcrPreprocessOptions {
+    pub exif_orientation: bool,
+    pub greyscale: bool,
+    pub upscale: bool,
+    pub deskew: bool,
+    pub adaptive_threshold: bool,
+}
+
+impl OcrPreprocessOptions {
+    pub fn get_action_options() -> Vec<ActionOption> {
+        vec![
+            ActionOption::new_bool("ocr_exif_orientation", "Rotate using EXIF orientation", false),
+            ActionOption::new_bool("ocr_greyscale", "Convert to greyscale", false),
+            ActionOption::new_bool("ocr_upscale", "Upscale small images", false),
+            ActionOption::new_bool("ocr_deskew", "Deskew", false),
+            ActionOption::new_bool("ocr_adaptive_threshold", "Adaptive threshold", false),
+        ]
+    }
+
+    pub fn from_action_options(action_options: &HashMap<String, String>) -> Self {
+        let flag = |name: &str| action_options.get(name).map(|v| v == "true" || v == "on").unwrap_or(false);
+        Self {
+            exif_orientation: flag("ocr_exif_orientation"),
+            greyscale: flag("ocr_greyscale"),
+            upscale: flag("ocr_upscale"),
+            deskew: flag("ocr_deskew"),
+            adaptive_threshold: flag("ocr_adaptive_threshold"),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.exif_orientation || self.greyscale || self.upscale || self.deskew || self.adaptive_threshold
+    }
+
+    // The enabled options in the order they run, e.g. "greyscale,deskew"; empty when none are
+    pub fn get_enabled_names(&self) -> String {
+        [
+            ("exif_orientation", self.exif_orientation),
+            ("greyscale", self.greyscale),
+            ("upscale", self.upscale),
+            ("deskew", self.deskew),
+            ("adaptive_threshold", self.adaptive_threshold),
+        ].iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| *name)
+            .collect::<Vec<&str>>()
+            .join(",")
+    }
+}
+
+// Runs the enabled steps in a fixed order and returns the result along with the steps that
+// actually changed something, e.g. ["exif_orientation", "upscale:2.50", "deskew:-1.25"], and how to map
+// positions on the result back onto the image as it is shown, i.e. upright
+pub fn preprocess_image_for_ocr(image_path: &str, options: &OcrPreprocessOptions) -> Result<(DynamicImage, Vec<String>, OcrPageTransform), image::ImageError> {
+    let mut steps = vec![];
+    let (mut img, orientation) = open_image_with_orientation(image_path)?;
+
+    if options.exif_orientation && orientation != image::metadata::Orientation::NoTransforms {
+        img.apply_orientation(orientation);
+        steps.push("exif_orientation".to_string());
+    }
+
+    let mut transform = OcrPageTransform::identity(img.width(), img.height());
+    // otherwise the boxes found on the sideways page are turned upright afterwards
+    if !options.exif_orientation {
+        transform.orientation = orientation;
+    }
+
+    if options.greyscale || options.deskew || options.adaptive_threshold {
+        img = DynamicImage::ImageLuma8(img.to_luma8());
+        steps.push("greyscale".to_string());
+    }
+
+    if options.upscale {
+        let short_side = img.width().min(img.height()).max(1);
+        let factor = (OCR_UPSCALE_MIN_DIMENSION as f32 / short_side as f32).min(OCR_UPSCALE_MAX_FACTOR);
+        if factor > 1.0 {
+            let (w, h) = ((img.width() as f32 * factor) as u32, (img.height() as f32 * factor) as u32);
+            img = img.resize_exact(w, h, FilterType::CatmullRom);
+            transform.page_width = w;
+            transform.page_height = h;
+            steps.push(format!("upscale:{:.2}", factor));
+        }
+    }
+
+    if options.deskew {
+        let sample = img.resize(OCR_DESKEW_SAMPLE_DIMENSION, OCR_DESKEW_SAMPLE_DIMENSION, FilterType::Triangle).to_luma8();
+        let sample = adaptive_threshold(&sample, (sample.width() as f32 * OCR_THRESHOLD_WINDOW_FRACTION) as u32, OCR_THRESHOLD_BIAS);
+        let skew = estimate_skew_degrees(&sample, OCR_DESKEW_MAX_DEGREES, OCR_DESKEW_STEP_DEGREES);
+        if skew != 0.0 {
+            img = DynamicImage::ImageLuma8(rotate_gray(&img.to_luma8(), -skew, 255));
+            transform.deskew_degrees = skew;
+            steps.push(format!("deskew:{:.2}", skew));
+        }
+    }
+
+    if options.adaptive_threshold {
+        let gray = img.to_luma8();
+        let window = (gray.width() as f32 * OCR_THRESHOLD_WINDOW_FRACTION) as u32;
+        img = DynamicImage::ImageLuma8(adaptive_threshold(&gray, window, OCR_THRESHOLD_BIAS));
+        steps.push("adaptive_threshold".to_string());
+    }
+
+    Ok((img, steps, transform))
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+use std::io::{BufRead, Cursor, Error, ErrorKind, Seek};
+use std::path::Path;
+
+use image::metadata::Orientation;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, ImageResult};
+
+use crate::converters::extract_raw_preview::extract_largest_embedded_jpeg;
+use crate::converters::tiff_structure::tiff_orientation;
+use crate::filesystem::query::images::is_raw_image_path;
+
+
+// Decodes the pixels as stored, along with the EXIF orientation that says how to turn them upright
+pub fn decode_image_with_orientation<R: BufRead + Seek>(reader: ImageReader<R>) -> ImageResult<(DynamicImage, Orientation)> {
+    let mut decoder = reader.into_decoder()?;
+    let orientation = decoder.orientation()?;
+    Ok((DynamicImage::from_decoder(decoder)?, orientation))
+}
+
+// Like open_image but leaves the orientation to the caller; camera RAW files are decoded from their embedded
+// JPEG preview, which often has no EXIF of its own, so the RAW file's orientation is used instead
+pub fn open_image_with_orientation(path: &str) -> ImageResult<(DynamicImage, Orientation)> {
+    if is_raw_image_path(path) {
+        let data = std::fs::read(path)?;
+        let jpeg = extract_largest_embedded_jpeg(&data)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("no embedded JPEG preview found in {}", path)))?;
+        let (img, orientation) = decode_image_with_orientation(ImageReader::with_format(Cursor::new(jpeg), ImageFormat::Jpeg))?;
+        let orientation = match orientation {
+            Orientation::NoTransforms => tiff_orientation(&data).and_then(Orientation::from_exif).unwrap_or(orientation),
+            _ => orientation,
+        };
+        Ok((img, orientation))
+    } else {
+        decode_image_with_orientation(ImageReader::open(Path::new(path))?.with_guessed_format()?)
+    }
+}
+
+// Reads only as far as the orientation, without decoding the pixels
+pub fn read_image_orientation(path: &str) -> ImageResult<Orientation> {
+    if is_raw_image_path(path) {
+        return open_image_with_orientation(path).map(|(_, orientation)| orientation);
+    }
+    ImageReader::open(Path::new(path))?.with_guessed_format()?.into_decoder()?.orientation()
+}
+
+// Opens an image file for processing, turned upright according to its EXIF orientation so portrait shots
+// come out portrait; camera RAW files are decoded from their embedded JPEG preview
+pub fn open_image(path: &str) -> ImageResult<DynamicImage> {
+    let (mut img, orientation) = open_image_with_orientation(path)?;
+    img.apply_orientation(orientation);
+    Ok(img)
+}
+
+// The in-memory counterpart of open_image
+pub fn load_image_from_memory(data: &[u8], format: ImageFormat) -> ImageResult<DynamicImage> {
+    let (mut img, orientation) = decode_image_with_orientation(ImageReader::with_format(Cursor::new(data), format))?;
+    img.apply_orientation(orientation);
+    Ok(img)
+}
\ No newline at end of file
@@ -0,0 +1,50 @@
+use std::io::{Error, ErrorKind, Result};
+
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImageView};
+
+use crate::converters::extract_image_thumbnail::{convert_image_to_vec_u8, ExtractImageThumbnailOptions};
+use crate::converters::open_image::open_image;
+use crate::models::query_params::image_resize_params::{ImageFit, ImageResizeParams};
+
+
+// Never enlarges: a box bigger than the image is treated as the image's own size. Cover and fill keep the
+// box's shape, so a box that does not fit is scaled down as a whole rather than clamped side by side
+pub fn resize_image(img: &DynamicImage, params: &ImageResizeParams) -> DynamicImage {
+    let filter = FilterType::Lanczos3;
+    let (original_width, original_height) = img.dimensions();
+    let width = params.width.unwrap_or(original_width).min(original_width);
+    let height = params.height.unwrap_or(original_height).min(original_height);
+    match (params.fit, params.width, params.height) {
+        (ImageFit::Cover, Some(w), Some(h)) => {
+            let (width, height) = fit_box_within(w, h, original_width, original_height);
+            img.resize_to_fill(width, height, filter)
+        },
+        (ImageFit::Fill, Some(w), Some(h)) => {
+            let (width, height) = fit_box_within(w, h, original_width, original_height);
+            img.resize_exact(width, height, filter)
+        },
+        _ if width >= original_width && height >= original_height => img.clone(),
+        _ => img.resize(width, height, filter),
+    }
+}
+
+fn fit_box_within(width: u32, height: u32, max_width: u32, max_height: u32) -> (u32, u32) {
+    let scale = (max_width as f64 / width as f64).min(max_height as f64 / height as f64).min(1.0);
+    let scaled = |v: u32| ((v as f64 * scale).round() as u32).max(1);
+    (scaled(width), scaled(height))
+}
+
+// Opened through open_image, so the result is upright and RAW files are resized from their preview
+pub fn open_and_encode_resized_image(image_path: &str, params: &ImageResizeParams, quality: u8) -> Result<Vec<u8>> {
+    let img = open_image(image_path)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    let resized = resize_image(&img, params);
+    let options = ExtractImageThumbnailOptions {
+        width_and_length: resized.width().max(resized.height()),
+        filter: FilterType::Lanczos3,
+        output_format: params.format,
+        quality,
+    };
+    convert_image_to_vec_u8(&resized, &options)
+}
\ No newline at end of file
@@ -0,0 +1,124 @@
+use std::collections::HashSet;
+
+// Just enough of the TIFF container format to walk its IFDs (image file directories), for the cases the image
+// crate does not cover: the JPEG previews inside camera RAW files and the pages after the first one
+const MAX_PAGES: usize = 4096;
+const TAG_ORIENTATION: u16 = 0x0112;
+const MAX_VALUES_PER_ENTRY: usize = 1024;
+
+pub struct TiffReader<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+}
+
+impl<'a> TiffReader<'a> {
+    pub fn new(data: &'a [u8]) -> Option<Self> {
+        let little_endian = match data.get(0..4)? {
+            [b'I', b'I', 0x2A, 0x00] => true,
+            [b'M', b'M', 0x00, 0x2A] => false,
+            _ => return None,
+        };
+        Some(Self { data, little_endian })
+    }
+
+    pub fn u16_at(&self, offset: usize) -> Option<u16> {
+        let b: [u8; 2] = self.data.get(offset..offset.checked_add(2)?)?.try_into().ok()?;
+        Some(if self.little_endian { u16::from_le_bytes(b) } else { u16::from_be_bytes(b) })
+    }
+
+    pub fn u32_at(&self, offset: usize) -> Option<u32> {
+        let b: [u8; 4] = self.data.get(offset..offset.checked_add(4)?)?.try_into().ok()?;
+        Some(if self.little_endian { u32::from_le_bytes(b) } else { u32::from_be_bytes(b) })
+    }
+
+    pub fn first_ifd(&self) -> Option<usize> {
+        self.u32_at(4).map(|o| o as usize).filter(|o| *o != 0)
+    }
+
+    pub fn next_ifd(&self, ifd: usize) -> Option<usize> {
+        let entry_count = self.u16_at(ifd)? as usize;
+        self.u32_at(ifd.checked_add(2 + entry_count * 12)?).map(|o| o as usize).filter(|o| *o != 0)
+    }
+
+    // Each entry's tag with its SHORT or LONG values; other field types come back without values
+    pub fn ifd_entries(&self, ifd: usize) -> Vec<(u16, Vec<u32>)> {
+        let Some(entry_count) = self.u16_at(ifd) else {
+            return vec![];
+        };
+        (0..entry_count as usize)
+            .map_while(|i| ifd.checked_add(2 + i * 12))
+            .map_while(|entry| Some((self.u16_at(entry)?, self.entry_values(entry))))
+            .collect()
+    }
+
+    // Values of a SHORT or LONG entry; they sit inline when they fit in four bytes
+    fn entry_values(&self, entry: usize) -> Vec<u32> {
+        let (Some(field_type), Some(count)) = (entry.checked_add(2).and_then(|o| self.u16_at(o)), entry.checked_add(4).and_then(|o| self.u32_at(o))) else {
+            return vec![];
+        };
+        let size = match field_type {
+            3 => 2,
+            4 | 13 => 4,
+            _ => return vec![],
+        };
+        let count = count as usize;
+        let Some(value_offset) = entry.checked_add(8) else {
+            return vec![];
+        };
+        let start = if count.checked_mul(size).is_some_and(|bytes| bytes <= 4) {
+            value_offset
+        } else {
+            match self.u32_at(value_offset) {
+                Some(offset) => offset as usize,
+                None => return vec![],
+            }
+        };
+        (0..count.min(MAX_VALUES_PER_ENTRY))
+            .map_while(|i| start.checked_add(i * size))
+            .filter_map(|offset| if size == 2 {
+                self.u16_at(offset).map(u32::from)
+            } else {
+                self.u32_at(offset)
+            })
+            .collect()
+    }
+
+    // Offsets of the top-level IFDs, one per page, following the next-IFD chain from the header
+    pub fn page_ifds(&self) -> Vec<usize> {
+        let mut pages = vec![];
+        let mut visited = HashSet::new();
+        let mut next = self.first_ifd();
+        while let Some(ifd) = next {
+            if pages.len() >= MAX_PAGES || !visited.insert(ifd) || self.u16_at(ifd).is_none() {
+                break;
+            }
+            pages.push(ifd);
+            next = self.next_ifd(ifd);
+        }
+        pages
+    }
+}
+
+pub fn count_tiff_pages(data: &[u8]) -> usize {
+    TiffReader::new(data).map(|r| r.page_ifds().len()).unwrap_or_default()
+}
+
+// The EXIF orientation (1-8) stored in the first IFD
+pub fn tiff_orientation(data: &[u8]) -> Option<u8> {
+    let reader = TiffReader::new(data)?;
+    reader.ifd_entries(reader.first_ifd()?).into_iter()
+        .find(|(tag, _)| *tag == TAG_ORIENTATION)
+        .and_then(|(_, values)| values.first().copied())
+        .and_then(|v| u8::try_from(v).ok())
+}
+
+// A copy of the file whose header points at the given page, so decoders that only read the first page read that one
+pub fn tiff_with_page_first(data: &[u8], page: usize) -> Option<Vec<u8>> {
+    let reader = TiffReader::new(data)?;
+    let ifd = *reader.page_ifds().get(page)?;
+    let ifd = u32::try_from(ifd).ok()?;
+    let mut copy = data.to_vec();
+    let offset = if reader.little_endian { ifd.to_le_bytes() } else { ifd.to_be_bytes() };
+    copy[4..8].copy_from_slice(&offset);
+    Some(copy)
+}
\ No newline at end of file
@@ -1,7 +1,10 @@
 use std::error::Error;
+use std::str::FromStr;
 
+use sqlx::sqlite::SqliteConnectOptions;
 use sqlx::SqlitePool;
 
+use crate::database::migration::apply_migrations::apply_migrations;
 use crate::{cache::thumbnail_cache::ThumbnailCache, database::query::query_image_thumbnail::query_thumbnail_table_at_most_width_length, models::image_thumbnail::ImageThumbnail};
 
 
@@ -21,19 +24,30 @@ impl WebServerActionDataContext {
 
     pub async fn open() -> anyhow::Result<Self> {
         // Connect to SQLite database
-        let pool = SqlitePool::connect(&format!("sqlite:{}", crate::models::config::paths::DB_FILE))
+        // foreign keys are what cascade deleting an image_paths row to every feature table
+        let options = SqliteConnectOptions::from_str(&format!("sqlite:{}", crate::models::config::paths::DB_FILE))
+            .map_err(|e| anyhow::anyhow!("Invalid database path: {}", e))?
+            .foreign_keys(true);
+        let pool = SqlitePool::connect_with(options)
             .await
             .map_err(|e| anyhow::anyhow!("Failed to connect to database: {}", e))?;
 
+        let migrations = apply_migrations(&pool)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to migrate database: {}", e))?;
+        for m in migrations {
+            println!("Applied migration {}: {}", m.version, m.description);
+        }
+
         Ok(Self::new(pool, ThumbnailCache::new()))
     }
     
     pub async fn get_thumbnail_at_most_width_length(&self, path: &str, arg: u32) -> Result<Option<ImageThumbnail>, Box<dyn Error + Send>> {
-        if let Some(thumb) = self.thumbnail_cache.get(path).await {
+        if let Some(thumb) = self.thumbnail_cache.get(path, arg).await {
             Ok(Some(thumb))
         } else if let Some(thumb) = query_thumbnail_table_at_most_width_length(path, arg, &self.pool).await
             .map_err(|e| anyhow::anyhow!("failed to query_thumbnail_table_at_most_width_length: {}", e))? {
-            self.thumbnail_cache.insert(path.to_string(), thumb.clone()).await;
+            self.thumbnail_cache.insert(path.to_string(), arg, thumb.clone()).await;
             Ok(Some(thumb))
         } else {
             Ok(None)
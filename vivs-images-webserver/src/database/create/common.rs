@@ -1,3 +1,4 @@
+use crate::database::create::create_image_paths::SQL_CREATE_IMAGE_PATHS;
 use crate::database::create::create_image_aspect_ratio::SQL_CREATE_IMAGE_ASPECT_RATIO;
 use crate::database::create::create_image_iptc::SQL_CREATE_IMAGE_IPTC;
 use crate::database::create::create_image_ocr_text::SQL_CREATE_IMAGE_OCR_TEXT;
@@ -5,14 +6,77 @@ use crate::database::create::create_image_similarity::SQL_CREATE_IMAGE_SIMILARIT
 use crate::database::create::create_image_exif::SQL_CREATE_IMAGE_EXIF;
 use crate::database::create::create_image_brightness::SQL_CREATE_IMAGE_BRIGHTNESS;
 use crate::database::create::create_image_thumbnail::SQL_CREATE_IMAGE_THUMBNAIL;
+use crate::database::create::create_image_color::SQL_CREATE_IMAGE_COLOR;
+use crate::database::create::create_image_sharpness::SQL_CREATE_IMAGE_SHARPNESS;
+use crate::database::create::create_image_exposure::SQL_CREATE_IMAGE_EXPOSURE;
+use crate::database::create::create_image_ocr_summary::SQL_CREATE_IMAGE_OCR_SUMMARY;
+use crate::database::create::create_image_ocr_word::SQL_CREATE_IMAGE_OCR_WORD;
+use crate::database::create::create_image_content_hash::SQL_CREATE_IMAGE_CONTENT_HASH;
+use crate::database::create::create_image_tags::SQL_CREATE_IMAGE_TAGS;
+use crate::database::create::create_image_xmp::SQL_CREATE_IMAGE_XMP;
+use crate::database::create::create_image_similarity_neighbors::SQL_CREATE_IMAGE_SIMILARITY_NEIGHBORS;
+use crate::database::create::create_image_raw::SQL_CREATE_IMAGE_RAW;
+use crate::database::create::create_image_frames::SQL_CREATE_IMAGE_FRAMES;
+use crate::database::create::create_image_orientation_pending::SQL_CREATE_IMAGE_ORIENTATION_PENDING;
+use crate::database::create::create_image_geocode::SQL_CREATE_IMAGE_GEOCODE;
+use crate::database::create::create_image_event::SQL_CREATE_IMAGE_EVENT;
+use crate::database::create::create_image_burst::SQL_CREATE_IMAGE_BURST;
+use crate::database::create::create_image_exif_pending::SQL_CREATE_IMAGE_EXIF_PENDING;
 
 
+// image_paths comes first since every other table references its image_id
 pub const SQL_CREATE_IMAGE_TABLES: &[&str] = &[
+    SQL_CREATE_IMAGE_PATHS,
     SQL_CREATE_IMAGE_BRIGHTNESS,
     SQL_CREATE_IMAGE_EXIF,
     SQL_CREATE_IMAGE_SIMILARITY,
     SQL_CREATE_IMAGE_ASPECT_RATIO,
     SQL_CREATE_IMAGE_OCR_TEXT,
     SQL_CREATE_IMAGE_THUMBNAIL,
-    SQL_CREATE_IMAGE_IPTC
+    SQL_CREATE_IMAGE_IPTC,
+    SQL_CREATE_IMAGE_COLOR,
+    SQL_CREATE_IMAGE_SHARPNESS,
+    SQL_CREATE_IMAGE_EXPOSURE,
+    SQL_CREATE_IMAGE_OCR_SUMMARY,
+    SQL_CREATE_IMAGE_OCR_WORD,
+    SQL_CREATE_IMAGE_CONTENT_HASH,
+    SQL_CREATE_IMAGE_TAGS,
+    SQL_CREATE_IMAGE_XMP,
+    SQL_CREATE_IMAGE_SIMILARITY_NEIGHBORS,
+    SQL_CREATE_IMAGE_RAW,
+    SQL_CREATE_IMAGE_FRAMES,
+    SQL_CREATE_IMAGE_ORIENTATION_PENDING,
+    SQL_CREATE_IMAGE_GEOCODE,
+    SQL_CREATE_IMAGE_EVENT,
+    SQL_CREATE_IMAGE_BURST,
+    SQL_CREATE_IMAGE_EXIF_PENDING
 ];
+
+
+// Every feature table keyed by a single image_path column, each with an image_id referencing image_paths
+pub const SQL_IMAGE_PATH_KEYED_TABLES: &[&str] = &[
+    "image_brightness",
+    "image_exif",
+    "image_aspect_ratio",
+    "image_ocr_text",
+    "image_thumbnail",
+    "image_iptc",
+    "image_color",
+    "image_sharpness",
+    "image_exposure",
+    "image_ocr_summary",
+    "image_ocr_word",
+    "image_tags",
+    "image_xmp",
+    "image_content_hash",
+    "image_similarity_neighbors",
+    "image_similarity_neighbor_scan",
+    "image_raw",
+    "image_frames",
+    "image_frame_thumbnail",
+    "image_orientation_pending",
+    "image_geocode",
+    "image_event_member",
+    "image_burst_frame",
+    "image_exif_pending"
+];
\ No newline at end of file
@@ -2,6 +2,7 @@
 pub const SQL_CREATE_IMAGE_ASPECT_RATIO: &str = r#"
 CREATE TABLE IF NOT EXISTS image_aspect_ratio (
     image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
     width_pixels INTEGER NOT NULL,
     height_pixels INTEGER NOT NULL,
     aspect_ratio REAL NOT NULL,
@@ -2,7 +2,9 @@
 pub const SQL_CREATE_IMAGE_BRIGHTNESS: &str = r#"
 CREATE TABLE IF NOT EXISTS image_brightness (
     image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
     brightness REAL NOT NULL,
+    brightness_algo INTEGER NOT NULL DEFAULT 0,
     created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
     updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
 );
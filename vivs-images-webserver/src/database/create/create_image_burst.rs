@@ -0,0 +1,27 @@
+pub const SQL_CREATE_IMAGE_BURST: &str = r#"
+-- AUTOINCREMENT so a re-detected burst never hands an old burst id, e.g. in a saved search, to different frames
+CREATE TABLE IF NOT EXISTS image_burst (
+    burst_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    burst_start TEXT NOT NULL,
+    burst_end TEXT NOT NULL,
+    burst_frame_count INTEGER NOT NULL,
+    burst_keeper_image_path TEXT NOT NULL,
+    -- set once the burst was submitted on the review page, even if every frame was kept
+    burst_reviewed INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_burst_burst_start ON image_burst(burst_start);
+
+-- burst_id is NULL for images that were checked and are not part of a burst
+CREATE TABLE IF NOT EXISTS image_burst_frame (
+    image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    burst_id INTEGER NULL REFERENCES image_burst(burst_id) ON DELETE CASCADE,
+    burst_frame_score REAL NULL,
+    burst_frame_similarity REAL NULL,
+    burst_is_keeper INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_burst_frame_burst_id ON image_burst_frame(burst_id);
+
+"#;
\ No newline at end of file
@@ -0,0 +1,15 @@
+
+pub const SQL_CREATE_IMAGE_COLOR: &str = r#"
+CREATE TABLE IF NOT EXISTS image_color (
+    image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    dominant_color TEXT NOT NULL,
+    color_palette TEXT NOT NULL,
+    hsv_histogram TEXT NOT NULL,
+    average_saturation REAL NOT NULL,
+    average_warmth REAL NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_color_dominant_color ON image_color(dominant_color);
+
+"#;
\ No newline at end of file
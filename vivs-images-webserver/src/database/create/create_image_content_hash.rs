@@ -0,0 +1,11 @@
+pub const SQL_CREATE_IMAGE_CONTENT_HASH: &str = r#"
+CREATE TABLE IF NOT EXISTS image_content_hash (
+    image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    content_hash TEXT NOT NULL,
+    file_size INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_content_hash_content_hash ON image_content_hash(content_hash);
+
+"#;
\ No newline at end of file
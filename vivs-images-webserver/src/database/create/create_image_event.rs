@@ -0,0 +1,23 @@
+pub const SQL_CREATE_IMAGE_EVENT: &str = r#"
+-- AUTOINCREMENT so a rebuilt clustering never hands an old event id, e.g. in a saved search, to a different event
+CREATE TABLE IF NOT EXISTS image_event (
+    event_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_start TEXT NOT NULL,
+    event_end TEXT NOT NULL,
+    event_image_count INTEGER NOT NULL,
+    event_cover_image_path TEXT NOT NULL,
+    event_latitude REAL NULL,
+    event_longitude REAL NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_event_event_start ON image_event(event_start);
+
+CREATE TABLE IF NOT EXISTS image_event_member (
+    image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    event_id INTEGER NOT NULL REFERENCES image_event(event_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_event_member_event_id ON image_event_member(event_id);
+
+"#;
\ No newline at end of file
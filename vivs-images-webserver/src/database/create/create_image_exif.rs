@@ -2,7 +2,10 @@
 pub const SQL_CREATE_IMAGE_EXIF: &str = r#"
 CREATE TABLE IF NOT EXISTS image_exif (
     image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
     image_taken_at TIMESTAMP NULL,
+    image_taken_at_offset TEXT NULL,
+    image_taken_at_source TEXT NULL,
     
     -- Date and time fields
     date_time TIMESTAMP NULL,
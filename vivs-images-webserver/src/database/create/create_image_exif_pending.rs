@@ -0,0 +1,9 @@
+// Images whose exif row is kept but has to be read from the file again, filled in by migrations that could not
+// repair a column in SQL and worked off by the exif import, which updates the row in place
+pub const SQL_CREATE_IMAGE_EXIF_PENDING: &str = r#"
+CREATE TABLE IF NOT EXISTS image_exif_pending (
+    image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE
+);
+
+"#;
\ No newline at end of file
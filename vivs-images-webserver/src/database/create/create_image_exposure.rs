@@ -0,0 +1,18 @@
+
+pub const SQL_CREATE_IMAGE_EXPOSURE: &str = r#"
+CREATE TABLE IF NOT EXISTS image_exposure (
+    image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    clipped_highlights REAL NOT NULL,
+    clipped_shadows REAL NOT NULL,
+    luma_p5 INTEGER NOT NULL,
+    luma_p50 INTEGER NOT NULL,
+    luma_p95 INTEGER NOT NULL,
+    contrast REAL NOT NULL,
+    luma_histogram TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_exposure_clipped_highlights ON image_exposure(clipped_highlights);
+CREATE INDEX IF NOT EXISTS idx_image_exposure_contrast ON image_exposure(contrast);
+
+"#;
\ No newline at end of file
@@ -0,0 +1,23 @@
+pub const SQL_CREATE_IMAGE_FRAMES: &str = r#"
+CREATE TABLE IF NOT EXISTS image_frames (
+    image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    frame_count INTEGER NOT NULL,
+    is_animated INTEGER NOT NULL,
+    animation_duration_ms INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_frames_frame_count ON image_frames(frame_count);
+
+CREATE TABLE IF NOT EXISTS image_frame_thumbnail (
+    image_path TEXT NOT NULL,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    frame_index INTEGER NOT NULL,
+    frame_delay_ms INTEGER NOT NULL,
+    width_and_length INTEGER NOT NULL,
+    thumbnail_format INTEGER NOT NULL,
+    thumbnail_data BLOB NOT NULL,
+    PRIMARY KEY (image_path, frame_index)
+);
+
+"#;
\ No newline at end of file
@@ -0,0 +1,17 @@
+pub const SQL_CREATE_IMAGE_GEOCODE: &str = r#"
+CREATE TABLE IF NOT EXISTS image_geocode (
+    image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    place_country_code TEXT,
+    place_country TEXT,
+    place_region TEXT,
+    place_city TEXT,
+    place_city_distance_km REAL,
+    place_dataset TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_geocode_place_country ON image_geocode(place_country);
+CREATE INDEX IF NOT EXISTS idx_image_geocode_place_region ON image_geocode(place_region);
+CREATE INDEX IF NOT EXISTS idx_image_geocode_place_city ON image_geocode(place_city);
+
+"#;
\ No newline at end of file
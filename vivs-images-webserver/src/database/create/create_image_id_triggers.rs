@@ -0,0 +1,107 @@
+use crate::database::create::common::SQL_IMAGE_PATH_KEYED_TABLES;
+
+
+// The insert code writes image_path only; these triggers keep image_id in step with image_paths so
+// joins and ON DELETE CASCADE work without every update function looking the id up itself.
+pub fn sql_create_image_id_index_and_triggers(table: &str) -> String {
+    format!(r#"
+CREATE INDEX IF NOT EXISTS idx_{table}_image_id ON {table}(image_id);
+
+CREATE TRIGGER IF NOT EXISTS trg_{table}_image_id_insert AFTER INSERT ON {table}
+WHEN NEW.image_id IS NULL
+BEGIN
+    UPDATE {table} SET image_id = (SELECT image_id FROM image_paths WHERE image_path = NEW.image_path) WHERE rowid = NEW.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_{table}_image_id_update AFTER UPDATE OF image_path ON {table}
+BEGIN
+    UPDATE {table} SET image_id = (SELECT image_id FROM image_paths WHERE image_path = NEW.image_path) WHERE rowid = NEW.rowid;
+END;
+"#, table = table)
+}
+
+pub const SQL_CREATE_IMAGE_SIMILARITY_IMAGE_ID_TRIGGERS: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_image_similarity_image_id_a ON image_similarity(image_id_a);
+CREATE INDEX IF NOT EXISTS idx_image_similarity_image_id_b ON image_similarity(image_id_b);
+
+CREATE TRIGGER IF NOT EXISTS trg_image_similarity_image_id_insert AFTER INSERT ON image_similarity
+BEGIN
+    UPDATE image_similarity SET
+        image_id_a = (SELECT image_id FROM image_paths WHERE image_path = NEW.image_path_a),
+        image_id_b = (SELECT image_id FROM image_paths WHERE image_path = NEW.image_path_b)
+    WHERE rowid = NEW.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_image_similarity_image_id_update AFTER UPDATE OF image_path_a, image_path_b ON image_similarity
+BEGIN
+    UPDATE image_similarity SET
+        image_id_a = (SELECT image_id FROM image_paths WHERE image_path = NEW.image_path_a),
+        image_id_b = (SELECT image_id FROM image_paths WHERE image_path = NEW.image_path_b)
+    WHERE rowid = NEW.rowid;
+END;
+"#;
+
+// The image_path side is covered by the per-table triggers; this keeps the neighbour's id in step
+pub const SQL_CREATE_IMAGE_SIMILARITY_NEIGHBORS_IMAGE_ID_TRIGGERS: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_image_similarity_neighbors_neighbor_image_id ON image_similarity_neighbors(neighbor_image_id);
+
+CREATE TRIGGER IF NOT EXISTS trg_image_similarity_neighbors_neighbor_image_id_insert AFTER INSERT ON image_similarity_neighbors
+WHEN NEW.neighbor_image_id IS NULL
+BEGIN
+    UPDATE image_similarity_neighbors SET neighbor_image_id = (SELECT image_id FROM image_paths WHERE image_path = NEW.neighbor_path) WHERE rowid = NEW.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_image_similarity_neighbors_neighbor_image_id_update AFTER UPDATE OF neighbor_path ON image_similarity_neighbors
+BEGIN
+    UPDATE image_similarity_neighbors SET neighbor_image_id = (SELECT image_id FROM image_paths WHERE image_path = NEW.neighbor_path) WHERE rowid = NEW.rowid;
+END;
+"#;
+
+// Feature rows are often added before the path itself, so a new (or renamed) image_paths row claims them
+pub fn sql_create_image_paths_triggers() -> String {
+    let mut body = String::new();
+    for table in SQL_IMAGE_PATH_KEYED_TABLES {
+        body.push_str(&format!("    UPDATE {} SET image_id = NEW.image_id WHERE image_path = NEW.image_path;\n", table));
+    }
+    body.push_str("    UPDATE image_similarity SET image_id_a = NEW.image_id WHERE image_path_a = NEW.image_path;\n");
+    body.push_str("    UPDATE image_similarity SET image_id_b = NEW.image_id WHERE image_path_b = NEW.image_path;\n");
+    body.push_str("    UPDATE image_similarity_neighbors SET neighbor_image_id = NEW.image_id WHERE neighbor_path = NEW.image_path;\n");
+
+    format!(r#"
+CREATE TRIGGER IF NOT EXISTS trg_image_paths_image_id_insert AFTER INSERT ON image_paths
+BEGIN
+{body}END;
+
+CREATE TRIGGER IF NOT EXISTS trg_image_paths_image_id_update AFTER UPDATE OF image_path ON image_paths
+BEGIN
+{body}END;
+"#, body = body)
+}
+
+pub fn get_sql_create_image_id_triggers() -> Vec<String> {
+    let mut statements: Vec<String> = SQL_IMAGE_PATH_KEYED_TABLES.iter()
+        .map(|table| sql_create_image_id_index_and_triggers(table))
+        .collect();
+    statements.push(SQL_CREATE_IMAGE_SIMILARITY_IMAGE_ID_TRIGGERS.to_string());
+    statements.push(SQL_CREATE_IMAGE_SIMILARITY_NEIGHBORS_IMAGE_ID_TRIGGERS.to_string());
+    statements.push(sql_create_image_paths_triggers());
+    statements
+}
+
+// The triggers are created with IF NOT EXISTS, so they are dropped first whenever the schema is brought up to
+// date; that way a table added to SQL_IMAGE_PATH_KEYED_TABLES is covered without a migration of its own, and
+// migrations that rebuild a table do not trip over triggers naming it
+pub fn get_sql_drop_image_id_triggers() -> Vec<String> {
+    let mut triggers: Vec<String> = SQL_IMAGE_PATH_KEYED_TABLES.iter()
+        .flat_map(|table| [format!("trg_{}_image_id_insert", table), format!("trg_{}_image_id_update", table)])
+        .collect();
+    triggers.extend([
+        "trg_image_similarity_image_id_insert",
+        "trg_image_similarity_image_id_update",
+        "trg_image_similarity_neighbors_neighbor_image_id_insert",
+        "trg_image_similarity_neighbors_neighbor_image_id_update",
+        "trg_image_paths_image_id_insert",
+        "trg_image_paths_image_id_update",
+    ].map(String::from));
+    triggers.iter().map(|t| format!("DROP TRIGGER IF EXISTS {};", t)).collect()
+}
\ No newline at end of file
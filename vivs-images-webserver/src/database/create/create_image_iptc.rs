@@ -1,8 +1,9 @@
 
 pub const SQL_CREATE_IMAGE_IPTC: &str = r#"
 
-CREATE TABLE image_iptc (
+CREATE TABLE IF NOT EXISTS image_iptc (
     image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
     model_version TEXT,
     date_sent TEXT,
     time_sent TEXT,
@@ -69,9 +70,9 @@ CREATE TABLE image_iptc (
     owner_id TEXT
 );
 -- Optional indexes for frequently queried fields
-CREATE INDEX idx_iptc_keywords ON image_iptc(keywords);
-CREATE INDEX idx_iptc_by_line ON image_iptc(by_line);
-CREATE INDEX idx_iptc_category ON image_iptc(category);
-CREATE INDEX idx_iptc_date_created ON image_iptc(date_created);
+CREATE INDEX IF NOT EXISTS idx_iptc_keywords ON image_iptc(keywords);
+CREATE INDEX IF NOT EXISTS idx_iptc_by_line ON image_iptc(by_line);
+CREATE INDEX IF NOT EXISTS idx_iptc_category ON image_iptc(category);
+CREATE INDEX IF NOT EXISTS idx_iptc_date_created ON image_iptc(date_created);
 
 "#;
\ No newline at end of file
@@ -0,0 +1,19 @@
+
+pub const SQL_CREATE_IMAGE_OCR_SUMMARY: &str = r#"
+CREATE TABLE IF NOT EXISTS image_ocr_summary (
+    image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    ocr_languages TEXT NOT NULL,
+    ocr_word_count INTEGER NOT NULL,
+    ocr_low_confidence_word_count INTEGER NOT NULL,
+    ocr_mean_confidence REAL NOT NULL,
+    ocr_confident_text TEXT NOT NULL,
+    ocr_page_width INTEGER NOT NULL,
+    ocr_page_height INTEGER NOT NULL,
+    ocr_preprocessing TEXT NOT NULL DEFAULT '',
+    ocr_options TEXT NOT NULL DEFAULT ''
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_ocr_summary_mean_confidence ON image_ocr_summary(ocr_mean_confidence);
+
+"#;
\ No newline at end of file
@@ -2,6 +2,7 @@
 pub const SQL_CREATE_IMAGE_OCR_TEXT: &str = r#"
 CREATE TABLE IF NOT EXISTS image_ocr_text (
     image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
     ocr_text TEXT NOT NULL
 );
 
@@ -0,0 +1,21 @@
+
+pub const SQL_CREATE_IMAGE_OCR_WORD: &str = r#"
+CREATE TABLE IF NOT EXISTS image_ocr_word (
+    image_path TEXT NOT NULL,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    word_index INTEGER NOT NULL,
+    page_num INTEGER NOT NULL DEFAULT 1,
+    block_num INTEGER NOT NULL,
+    line_num INTEGER NOT NULL,
+    text TEXT NOT NULL,
+    confidence REAL NOT NULL,
+    left INTEGER NOT NULL,
+    top INTEGER NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    PRIMARY KEY (image_path, word_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_ocr_word_text ON image_ocr_word(text);
+
+"#;
\ No newline at end of file
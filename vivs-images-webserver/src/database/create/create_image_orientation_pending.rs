@@ -0,0 +1,9 @@
+// Images whose derived rows were computed before EXIF orientation was applied when decoding, filled in by a
+// migration and worked off by the recompute_oriented_images action
+pub const SQL_CREATE_IMAGE_ORIENTATION_PENDING: &str = r#"
+CREATE TABLE IF NOT EXISTS image_orientation_pending (
+    image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE
+);
+
+"#;
\ No newline at end of file
@@ -1,7 +1,8 @@
 
 pub const SQL_CREATE_IMAGE_PATHS: &str = r#"
 CREATE TABLE IF NOT EXISTS image_paths (
-    image_path TEXT PRIMARY KEY
+    image_id INTEGER PRIMARY KEY,
+    image_path TEXT NOT NULL UNIQUE
 );
 
 CREATE UNIQUE INDEX IF NOT EXISTS idx_image_paths_image_path ON image_paths(image_path);
@@ -0,0 +1,13 @@
+pub const SQL_CREATE_IMAGE_RAW: &str = r#"
+CREATE TABLE IF NOT EXISTS image_raw (
+    image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    raw_format TEXT NOT NULL,
+    preview_width INTEGER NOT NULL,
+    preview_height INTEGER NOT NULL,
+    jpeg_sibling_path TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_raw_jpeg_sibling_path ON image_raw(jpeg_sibling_path);
+
+"#;
\ No newline at end of file
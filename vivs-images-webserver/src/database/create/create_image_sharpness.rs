@@ -0,0 +1,13 @@
+
+pub const SQL_CREATE_IMAGE_SHARPNESS: &str = r#"
+CREATE TABLE IF NOT EXISTS image_sharpness (
+    image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    sharpness REAL NOT NULL,
+    motion_blur REAL NOT NULL,
+    motion_blur_angle REAL NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_sharpness_sharpness ON image_sharpness(sharpness);
+
+"#;
\ No newline at end of file
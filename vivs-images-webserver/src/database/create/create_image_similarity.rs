@@ -1,15 +1,17 @@
 
 pub const SQL_CREATE_IMAGE_SIMILARITY: &str = r#"
 CREATE TABLE IF NOT EXISTS image_similarity (
-    image_comparison_key INTEGER NOT NULL PRIMARY KEY,
-    image_comparison_algorithm: INTEGER NOT NULL,
-    image_path_a TEXT,
-    image_path_b TEXT,
+    image_comparison_algorithm INTEGER NOT NULL,
+    image_path_a TEXT NOT NULL,
+    image_path_b TEXT NOT NULL,
+    image_id_a INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    image_id_b INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
     similarity_value REAL NOT NULL,
     similarity_confidence REAL NOT NULL,
+    PRIMARY KEY (image_path_a, image_path_b, image_comparison_algorithm),
+    CHECK (image_path_a <= image_path_b)
 );
 
-CREATE INDEX IF NOT EXISTS idx_image_similarity_comparison_key ON image_similarity(image_comparison_key);
-CREATE UNIQUE INDEX IF NOT EXISTS idx_image_similarity_image_path ON image_similarity(image_path_a, image_path_b);
+CREATE INDEX IF NOT EXISTS idx_image_similarity_image_path_b ON image_similarity(image_path_b);
 
 "#;
\ No newline at end of file
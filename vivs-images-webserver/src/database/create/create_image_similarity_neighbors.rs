@@ -0,0 +1,26 @@
+
+// Top-K storage: each image keeps its most similar neighbours per algorithm, so rows grow with K instead of
+// with every pair. The scan table records which images have been compared against everything else.
+pub const SQL_CREATE_IMAGE_SIMILARITY_NEIGHBORS: &str = r#"
+CREATE TABLE IF NOT EXISTS image_similarity_neighbors (
+    image_path TEXT NOT NULL,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    neighbor_path TEXT NOT NULL,
+    neighbor_image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    image_comparison_algorithm INTEGER NOT NULL,
+    similarity_value REAL NOT NULL,
+    similarity_confidence REAL NOT NULL,
+    PRIMARY KEY (image_path, neighbor_path, image_comparison_algorithm)
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_similarity_neighbors_neighbor_path ON image_similarity_neighbors(neighbor_path);
+
+CREATE TABLE IF NOT EXISTS image_similarity_neighbor_scan (
+    image_path TEXT NOT NULL,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    image_comparison_algorithm INTEGER NOT NULL,
+    compared_count INTEGER NOT NULL,
+    PRIMARY KEY (image_path, image_comparison_algorithm)
+);
+
+"#;
\ No newline at end of file
@@ -3,9 +3,11 @@ pub const SQL_CREATE_IMAGE_THUMBNAIL: &str = r#"
 CREATE TABLE IF NOT EXISTS image_thumbnail (
     image_thumbnail_key INTEGER NOT NULL PRIMARY KEY,
     image_path TEXT NOT NULL,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
     width_and_length INTEGER NOT NULL,
     thumbnail_format INTEGER NOT NULL,
-    thumbnail_data BLOB NOT NULL
+    thumbnail_data BLOB NOT NULL,
+    thumbnail_storage_key TEXT
 );
 
 CREATE UNIQUE INDEX IF NOT EXISTS idx_image_thumbnail_key ON image_thumbnail(image_thumbnail_key);
@@ -2,6 +2,7 @@
 pub const SQL_CREATE_IMAGE_XMP: &str = r#"
 CREATE TABLE IF NOT EXISTS image_xmp (
     image_path TEXT PRIMARY KEY,
+    image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
     xmp TEXT NOT NULL
 );
 
@@ -8,4 +8,19 @@ pub mod create_image_brightness;
 pub mod create_image_ocr_text;
 pub mod create_image_iptc;
 pub mod create_image_tags;
-pub mod create_image_xmp;
\ No newline at end of file
+pub mod create_image_xmp;
+pub mod create_image_color;
+pub mod create_image_sharpness;
+pub mod create_image_exposure;
+pub mod create_image_ocr_summary;
+pub mod create_image_ocr_word;
+pub mod create_image_content_hash;
+pub mod create_image_id_triggers;
+pub mod create_image_similarity_neighbors;
+pub mod create_image_raw;
+pub mod create_image_frames;
+pub mod create_image_orientation_pending;
+pub mod create_image_geocode;
+pub mod create_image_event;
+pub mod create_image_burst;
+pub mod create_image_exif_pending;
\ No newline at end of file
@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+use std::error::Error;
+
+use sqlx::{Row, SqlitePool};
+
+use crate::database::common::{execute_query, execute_update_or_insert};
+use crate::database::create::common::SQL_CREATE_IMAGE_TABLES;
+use crate::database::create::create_image_id_triggers::{get_sql_create_image_id_triggers, get_sql_drop_image_id_triggers};
+use crate::database::migration::common::Migration;
+use crate::database::migration::migrations::SQL_MIGRATIONS;
+
+
+pub const SQL_CREATE_SCHEMA_MIGRATIONS: &str = r#"
+CREATE TABLE IF NOT EXISTS schema_migrations (
+    version INTEGER PRIMARY KEY,
+    description TEXT NOT NULL,
+    applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+"#;
+
+pub async fn query_applied_migrations(pool: &SqlitePool) -> Result<Vec<Migration>, Box<dyn Error + Send>> {
+    let rows = execute_query(pool, "SELECT version, description, applied_at FROM schema_migrations ORDER BY version", vec![]).await?;
+    Ok(rows.iter().map(Migration::new_from_db).collect())
+}
+
+fn to_send_error(e: sqlx::Error) -> Box<dyn Error + Send> {
+    Box::new(e) as Box<dyn Error + Send>
+}
+
+// Creates any missing tables, then runs every migration that has not been recorded yet, each in its own
+// transaction so one that fails part way (like the image_paths rebuild) leaves nothing half done. The image_id
+// triggers are dropped beforehand and recreated afterwards, since they need the columns the migrations add
+pub async fn apply_migrations(pool: &SqlitePool) -> Result<Vec<Migration>, Box<dyn Error + Send>> {
+    for sql in SQL_CREATE_IMAGE_TABLES {
+        if let Err(e) = execute_update_or_insert(pool, sql, vec![]).await {
+            println!("could not create table: {}", e);
+        }
+    }
+    execute_update_or_insert(pool, SQL_CREATE_SCHEMA_MIGRATIONS, vec![]).await?;
+
+    let applied: HashSet<i32> = execute_query(pool, "SELECT version FROM schema_migrations", vec![]).await?
+        .iter()
+        .filter_map(|r| r.try_get("version").ok())
+        .collect();
+
+    for sql in get_sql_drop_image_id_triggers() {
+        execute_update_or_insert(pool, &sql, vec![]).await?;
+    }
+
+    let mut newly_applied = vec![];
+    for (version, description, sql) in SQL_MIGRATIONS {
+        if applied.contains(version) {
+            continue;
+        }
+        let mut tx = pool.begin().await.map_err(to_send_error)?;
+        match sqlx::query(sql).execute(&mut tx).await {
+            Ok(_) => {},
+            Err(e) if e.to_string().contains("duplicate column name") => {},
+            Err(e) => return Err(to_send_error(e)),
+        }
+        sqlx::query("INSERT INTO schema_migrations (version, description) VALUES (?, ?)")
+            .bind(version).bind(description)
+            .execute(&mut tx).await.map_err(to_send_error)?;
+        tx.commit().await.map_err(to_send_error)?;
+        newly_applied.push(Migration::new_from_file(*version, description));
+    }
+
+    for sql in get_sql_create_image_id_triggers() {
+        execute_update_or_insert(pool, &sql, vec![]).await?;
+    }
+    Ok(newly_applied)
+}
\ No newline at end of file
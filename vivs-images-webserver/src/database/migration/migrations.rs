@@ -0,0 +1,218 @@
+// Schema changes for databases created before a column existed. Fresh databases get the
+// column from the CREATE TABLE statement, so "duplicate column" just means already applied.
+// The image_id triggers are not migrations; apply_migrations recreates them on every start.
+// Adds the image_id column to a feature table and fills it in from image_paths. Its own migration per
+// table, so a table created after this change with the column already present does not block the rest.
+macro_rules! sql_migrate_image_id {
+    ($table:literal) => {
+        concat!(
+            "ALTER TABLE ", $table, " ADD COLUMN image_id INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE;\n",
+            "UPDATE ", $table, " SET image_id = (SELECT image_paths.image_id FROM image_paths WHERE image_paths.image_path = ", $table, ".image_path);\n"
+        )
+    };
+}
+
+pub const SQL_MIGRATIONS: &[(i32, &str, &str)] = &[
+    (1, "store which algorithm produced each brightness value", SQL_MIGRATE_IMAGE_BRIGHTNESS_ALGO),
+    (2, "record the preprocessing steps and options applied before ocr", SQL_MIGRATE_IMAGE_OCR_SUMMARY_PREPROCESSING),
+    (3, "give every image path an integer image_id", SQL_MIGRATE_IMAGE_PATHS_IMAGE_ID),
+    (4, "reference image_paths by image_id from image_brightness", sql_migrate_image_id!("image_brightness")),
+    (5, "reference image_paths by image_id from image_exif", sql_migrate_image_id!("image_exif")),
+    (6, "reference image_paths by image_id from image_aspect_ratio", sql_migrate_image_id!("image_aspect_ratio")),
+    (7, "reference image_paths by image_id from image_ocr_text", sql_migrate_image_id!("image_ocr_text")),
+    (8, "reference image_paths by image_id from image_thumbnail", sql_migrate_image_id!("image_thumbnail")),
+    (9, "reference image_paths by image_id from image_iptc", sql_migrate_image_id!("image_iptc")),
+    (10, "reference image_paths by image_id from image_color", sql_migrate_image_id!("image_color")),
+    (11, "reference image_paths by image_id from image_sharpness", sql_migrate_image_id!("image_sharpness")),
+    (12, "reference image_paths by image_id from image_exposure", sql_migrate_image_id!("image_exposure")),
+    (13, "reference image_paths by image_id from image_ocr_summary", sql_migrate_image_id!("image_ocr_summary")),
+    (14, "reference image_paths by image_id from image_ocr_word", sql_migrate_image_id!("image_ocr_word")),
+    (15, "reference image_paths by image_id from image_tags", sql_migrate_image_id!("image_tags")),
+    (16, "reference image_paths by image_id from image_xmp", sql_migrate_image_id!("image_xmp")),
+    (17, "reference image_paths by image_id from image_content_hash", sql_migrate_image_id!("image_content_hash")),
+    (18, "reference image_paths by image_id from image_similarity", SQL_MIGRATE_IMAGE_SIMILARITY_IMAGE_IDS),
+    (19, "key image_similarity by ordered path pair and algorithm, recording duplicates", SQL_MIGRATE_IMAGE_SIMILARITY_PAIR_KEY),
+    (20, "queue images decoded before exif orientation was applied for recomputing", SQL_MIGRATE_IMAGE_ORIENTATION_PENDING),
+    (21, "record where thumbnail data kept outside image_thumbnail is stored", SQL_MIGRATE_IMAGE_THUMBNAIL_STORAGE_KEY),
+    (22, "re-read exif of images whose gps coordinates were not stored as numbers", SQL_MIGRATE_IMAGE_EXIF_GPS_COORDINATES),
+    (23, "store image_taken_at as sortable ISO-8601 local time", SQL_MIGRATE_IMAGE_EXIF_TAKEN_AT_ISO),
+    (24, "record the utc offset and source of image_taken_at", SQL_MIGRATE_IMAGE_EXIF_TAKEN_AT_SOURCE),
+];
+
+pub const SQL_MIGRATE_IMAGE_BRIGHTNESS_ALGO: &str = r#"
+ALTER TABLE image_brightness ADD COLUMN brightness_algo INTEGER NOT NULL DEFAULT 0;
+"#;
+
+pub const SQL_MIGRATE_IMAGE_OCR_SUMMARY_PREPROCESSING: &str = r#"
+ALTER TABLE image_ocr_summary ADD COLUMN ocr_preprocessing TEXT NOT NULL DEFAULT '';
+ALTER TABLE image_ocr_summary ADD COLUMN ocr_options TEXT NOT NULL DEFAULT '';
+"#;
+
+pub const SQL_MIGRATE_IMAGE_THUMBNAIL_STORAGE_KEY: &str = r#"
+ALTER TABLE image_thumbnail ADD COLUMN thumbnail_storage_key TEXT;
+"#;
+
+// GPS coordinates used to be parsed from their "deg min sec" display text, which never succeeded, so only the
+// N/S and E/W references were kept. The coordinates are not in the database, so those rows are queued for the
+// exif import to read again as decimal degrees; the rest of each row stays as it is until then
+pub const SQL_MIGRATE_IMAGE_EXIF_GPS_COORDINATES: &str = r#"
+INSERT OR IGNORE INTO image_exif_pending (image_path)
+SELECT image_path FROM image_exif WHERE gps_latitude IS NULL AND gps_longitude IS NULL AND (gps_latitude_ref IS NOT NULL OR gps_longitude_ref IS NOT NULL);
+UPDATE image_exif_pending SET image_id = (SELECT image_paths.image_id FROM image_paths WHERE image_paths.image_path = image_exif_pending.image_path);
+"#;
+
+// exif and file name dates differ only in separators. Anything else is cleared and the image queued for the
+// exif import to read again; the rest of each row stays as it is until then
+pub const SQL_MIGRATE_IMAGE_EXIF_TAKEN_AT_ISO: &str = r#"
+UPDATE image_exif SET image_taken_at = replace(substr(image_taken_at, 1, 10), ':', '-') || 'T' || substr(image_taken_at, 12, 8)
+WHERE image_taken_at GLOB '[0-9][0-9][0-9][0-9][:-][0-9][0-9][:-][0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]*'
+    AND date(replace(substr(image_taken_at, 1, 10), ':', '-')) IS NOT NULL;
+INSERT OR IGNORE INTO image_exif_pending (image_path)
+SELECT image_path FROM image_exif WHERE image_taken_at IS NOT NULL
+    AND image_taken_at NOT GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9]T[0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+UPDATE image_exif SET image_taken_at = NULL WHERE image_taken_at IS NOT NULL
+    AND image_taken_at NOT GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9]T[0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+UPDATE image_exif_pending SET image_id = (SELECT image_paths.image_id FROM image_paths WHERE image_paths.image_path = image_exif_pending.image_path);
+"#;
+
+// Capture times stored so far all came from exif. Images without one are queued for the exif import to read
+// again, so the xmp, gps, file name and modified time fallbacks can fill them in
+pub const SQL_MIGRATE_IMAGE_EXIF_TAKEN_AT_SOURCE: &str = r#"
+ALTER TABLE image_exif ADD COLUMN image_taken_at_offset TEXT NULL;
+ALTER TABLE image_exif ADD COLUMN image_taken_at_source TEXT NULL;
+UPDATE image_exif SET image_taken_at_source = 'exif' WHERE image_taken_at IS NOT NULL;
+INSERT OR IGNORE INTO image_exif_pending (image_path) SELECT image_path FROM image_exif WHERE image_taken_at IS NULL;
+UPDATE image_exif_pending SET image_id = (SELECT image_paths.image_id FROM image_paths WHERE image_paths.image_path = image_exif_pending.image_path);
+"#;
+
+// sqlite cannot change a primary key in place, so the table is rebuilt with image_id as its rowid alias
+pub const SQL_MIGRATE_IMAGE_PATHS_IMAGE_ID: &str = r#"
+CREATE TABLE image_paths_with_id (
+    image_id INTEGER PRIMARY KEY,
+    image_path TEXT NOT NULL UNIQUE
+);
+INSERT INTO image_paths_with_id (image_path) SELECT image_path FROM image_paths ORDER BY image_path;
+DROP TABLE image_paths;
+ALTER TABLE image_paths_with_id RENAME TO image_paths;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_image_paths_image_path ON image_paths(image_path);
+"#;
+
+pub const SQL_MIGRATE_IMAGE_SIMILARITY_IMAGE_IDS: &str = r#"
+ALTER TABLE image_similarity ADD COLUMN image_id_a INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE;
+ALTER TABLE image_similarity ADD COLUMN image_id_b INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE;
+UPDATE image_similarity SET
+    image_id_a = (SELECT image_paths.image_id FROM image_paths WHERE image_paths.image_path = image_similarity.image_path_a),
+    image_id_b = (SELECT image_paths.image_id FROM image_paths WHERE image_paths.image_path = image_similarity.image_path_b);
+"#;
+
+// The old image_comparison_key summed two 32-bit string hashes, so different pairs could share a key.
+// Rows are rebuilt under (image_path_a, image_path_b, algorithm) with the smaller path first; when one
+// pair ends up with several rows the most confident is kept and the group is logged to image_similarity_duplicates.
+// A pair whose key was already taken by another pair was never stored, since the old lookup matched on the key;
+// those are found by hashing every known path the same way and logged to image_similarity_key_collisions, and
+// the similarity action compares them again now they are missing. The search walks stored rows times known paths
+pub const SQL_MIGRATE_IMAGE_SIMILARITY_PAIR_KEY: &str = r#"
+CREATE TABLE image_similarity_by_pair (
+    image_comparison_algorithm INTEGER NOT NULL,
+    image_path_a TEXT NOT NULL,
+    image_path_b TEXT NOT NULL,
+    image_id_a INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    image_id_b INTEGER REFERENCES image_paths(image_id) ON DELETE CASCADE,
+    similarity_value REAL NOT NULL,
+    similarity_confidence REAL NOT NULL,
+    PRIMARY KEY (image_path_a, image_path_b, image_comparison_algorithm),
+    CHECK (image_path_a <= image_path_b)
+);
+
+CREATE TABLE IF NOT EXISTS image_similarity_duplicates (
+    image_path_a TEXT NOT NULL,
+    image_path_b TEXT NOT NULL,
+    image_comparison_algorithm INTEGER NOT NULL,
+    duplicate_count INTEGER NOT NULL,
+    min_similarity_value REAL NOT NULL,
+    max_similarity_value REAL NOT NULL,
+    detected_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO image_similarity_duplicates (image_path_a, image_path_b, image_comparison_algorithm, duplicate_count, min_similarity_value, max_similarity_value)
+SELECT MIN(image_path_a, image_path_b), MAX(image_path_a, image_path_b), image_comparison_algorithm, COUNT(*), MIN(similarity_value), MAX(similarity_value)
+FROM image_similarity
+WHERE image_path_a IS NOT NULL AND image_path_b IS NOT NULL
+GROUP BY MIN(image_path_a, image_path_b), MAX(image_path_a, image_path_b), image_comparison_algorithm
+HAVING COUNT(*) > 1;
+
+INSERT OR IGNORE INTO image_similarity_by_pair (image_comparison_algorithm, image_path_a, image_path_b, image_id_a, image_id_b, similarity_value, similarity_confidence)
+SELECT
+    image_comparison_algorithm,
+    MIN(image_path_a, image_path_b),
+    MAX(image_path_a, image_path_b),
+    CASE WHEN image_path_a <= image_path_b THEN image_id_a ELSE image_id_b END,
+    CASE WHEN image_path_a <= image_path_b THEN image_id_b ELSE image_id_a END,
+    similarity_value,
+    similarity_confidence
+FROM image_similarity
+WHERE image_path_a IS NOT NULL AND image_path_b IS NOT NULL
+ORDER BY similarity_confidence DESC;
+
+CREATE TABLE IF NOT EXISTS image_similarity_key_collisions (
+    image_path_a TEXT NOT NULL,
+    image_path_b TEXT NOT NULL,
+    collided_path_a TEXT NOT NULL,
+    collided_path_b TEXT NOT NULL,
+    image_comparison_key INTEGER NOT NULL,
+    detected_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+-- string_hashcode_java_style: hash = hash * 31 + char, wrapping at 32 bits
+CREATE TEMP TABLE similarity_path_hash AS
+WITH RECURSIVE known_paths(image_path) AS (
+    SELECT image_path FROM image_paths
+    UNION SELECT image_path_a FROM image_similarity WHERE image_path_a IS NOT NULL
+    UNION SELECT image_path_b FROM image_similarity WHERE image_path_b IS NOT NULL
+),
+path_hash(image_path, position, hash) AS (
+    SELECT image_path, 1, 0 FROM known_paths
+    UNION ALL
+    SELECT image_path, position + 1,
+        ((hash * 31 + unicode(substr(image_path, position, 1)) + 2147483648) % 4294967296 + 4294967296) % 4294967296 - 2147483648
+    FROM path_hash WHERE position <= length(image_path)
+)
+SELECT image_path, hash FROM path_hash WHERE position = length(image_path) + 1;
+CREATE INDEX temp.idx_similarity_path_hash_path ON similarity_path_hash(image_path);
+CREATE INDEX temp.idx_similarity_path_hash_hash ON similarity_path_hash(hash);
+
+CREATE TEMP TABLE similarity_pair_key AS
+SELECT DISTINCT image_similarity_by_pair.image_path_a, image_similarity_by_pair.image_path_b,
+    ((hash_a.hash + hash_b.hash + 2147483648) % 4294967296 + 4294967296) % 4294967296 - 2147483648 AS comparison_key
+FROM image_similarity_by_pair
+INNER JOIN similarity_path_hash hash_a ON hash_a.image_path = image_similarity_by_pair.image_path_a
+INNER JOIN similarity_path_hash hash_b ON hash_b.image_path = image_similarity_by_pair.image_path_b;
+
+INSERT INTO image_similarity_key_collisions (image_path_a, image_path_b, collided_path_a, collided_path_b, image_comparison_key)
+SELECT stored.image_path_a, stored.image_path_b, path_a.image_path, path_b.image_path, stored.comparison_key
+FROM similarity_pair_key stored
+CROSS JOIN similarity_path_hash path_a
+INNER JOIN similarity_path_hash path_b ON path_b.hash = ((stored.comparison_key - path_a.hash + 2147483648) % 4294967296 + 4294967296) % 4294967296 - 2147483648
+    AND path_b.image_path > path_a.image_path
+WHERE NOT EXISTS (SELECT 1 FROM similarity_pair_key other
+    WHERE other.image_path_a = path_a.image_path AND other.image_path_b = path_b.image_path);
+
+DROP TABLE similarity_pair_key;
+DROP TABLE similarity_path_hash;
+DROP TABLE image_similarity;
+ALTER TABLE image_similarity_by_pair RENAME TO image_similarity;
+CREATE INDEX IF NOT EXISTS idx_image_similarity_image_path_b ON image_similarity(image_path_b);
+"#;
+
+// Every image with rows that depend on which way up it was decoded; the recompute action checks each file's
+// orientation and only redoes those that were not already upright.
+pub const SQL_MIGRATE_IMAGE_ORIENTATION_PENDING: &str = r#"
+INSERT OR IGNORE INTO image_orientation_pending (image_path)
+SELECT image_path FROM image_aspect_ratio
+UNION SELECT image_path FROM image_thumbnail
+UNION SELECT image_path FROM image_sharpness
+UNION SELECT image_path_a FROM image_similarity
+UNION SELECT image_path_b FROM image_similarity
+UNION SELECT image_path FROM image_similarity_neighbor_scan;
+UPDATE image_orientation_pending SET image_id = (SELECT image_paths.image_id FROM image_paths WHERE image_paths.image_path = image_orientation_pending.image_path);
+"#;
\ No newline at end of file
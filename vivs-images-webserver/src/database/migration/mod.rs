@@ -1 +1,3 @@
-pub mod common;
\ No newline at end of file
+pub mod common;
+pub mod migrations;
+pub mod apply_migrations;
\ No newline at end of file
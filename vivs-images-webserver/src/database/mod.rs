@@ -4,4 +4,5 @@ pub mod create;
 pub mod destroy;
 pub mod migration;
 pub mod query;
-pub mod update;
\ No newline at end of file
+pub mod update;
+pub mod thumbnail_store;
\ No newline at end of file
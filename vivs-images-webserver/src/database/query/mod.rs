@@ -9,4 +9,18 @@ pub mod query_image_iptc;
 pub mod query_image_tag;
 pub mod query_top_level_metrics;
 pub mod query_image_xmp;
-pub mod search;
\ No newline at end of file
+pub mod search;
+pub mod query_image_color;
+pub mod query_image_sharpness;
+pub mod query_image_exposure;
+pub mod query_image_ocr_summary;
+pub mod query_image_ocr_word;
+pub mod query_image_content_hash;
+pub mod query_image_similarity_neighbors;
+pub mod query_image_raw;
+pub mod query_image_frames;
+pub mod query_image_orientation_pending;
+pub mod query_image_geocode;
+pub mod query_image_timeline;
+pub mod query_image_event;
+pub mod query_image_burst;
\ No newline at end of file
@@ -3,6 +3,7 @@ use std::{collections::HashSet, error::Error};
 use sqlx::{Row, SqlitePool};
 
 use crate::database::common::execute_query;
+use crate::models::image_brightness::ImageToBrightnessAlgo;
 
 
 // Retrieves brightness image paths from the brightness table in the database
@@ -22,4 +23,23 @@ pub async fn query_brightness_table_count(image_path: &str, pool: &SqlitePool) -
     let v: Option<u32> = rows.iter().nth(0).map(|r| r.get("ct"));
     let v: usize = v.unwrap_or_default() as usize;
     Ok(v)
+}
+
+// Rows produced by a different algorithm, which need recomputing when switching to `brightness_algo`
+pub async fn get_image_paths_with_other_brightness_algo_from_db(brightness_algo: &ImageToBrightnessAlgo, pool: &SqlitePool) -> Result<HashSet<String>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT image_path FROM image_brightness WHERE brightness_algo <> ?"#;
+    let algo = brightness_algo.to_string();
+    let rows = execute_query(pool, sql, vec![ &algo ]).await?;
+
+    Ok(rows.iter()
+        .filter_map(|r| r.try_get("image_path").ok())
+        .collect())
+}
+
+pub async fn query_brightness_table_count_for_algo(image_path: &str, brightness_algo: &ImageToBrightnessAlgo, pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let sql = r#"SELECT COUNT(*) 'ct' FROM image_brightness WHERE image_path = ? AND brightness_algo = ?"#;
+    let algo = brightness_algo.to_string();
+    let rows = execute_query(pool, sql, vec![ image_path, &algo ]).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    Ok(v.unwrap_or_default() as usize)
 }
\ No newline at end of file
@@ -0,0 +1,86 @@
+use std::error::Error;
+
+use sqlx::{Row, SqlitePool};
+
+use crate::converters::extract_image_taken_at::parse_taken_at;
+use crate::database::common::execute_query;
+use crate::models::config::bursts::BURST_DELETE_TAG_NAME;
+use crate::models::image_burst::{ImageBurst, ImageBurstCandidate, ImageBurstFrame, ImageBurstReview, ImageBurstReviewFrame};
+
+
+// Every image still on record with a capture time, with what scoring needs to pick the keeper of a burst
+pub async fn query_image_burst_candidates(pool: &SqlitePool) -> Result<Vec<ImageBurstCandidate>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT image_exif.image_path, image_exif.image_taken_at, image_sharpness.sharpness, image_brightness.brightness,
+            image_exposure.clipped_highlights, image_exposure.clipped_shadows
+        FROM image_exif
+        INNER JOIN image_paths ON image_paths.image_id = image_exif.image_id
+        LEFT JOIN image_sharpness ON image_sharpness.image_id = image_exif.image_id
+        LEFT JOIN image_brightness ON image_brightness.image_id = image_exif.image_id
+        LEFT JOIN image_exposure ON image_exposure.image_id = image_exif.image_id
+        WHERE image_exif.image_taken_at IS NOT NULL"#;
+    let rows = execute_query(pool, sql, vec![]).await?;
+    Ok(rows.iter()
+        .filter_map(|r| {
+            let taken_at: String = r.try_get("image_taken_at").ok()?;
+            Some(ImageBurstCandidate {
+                image_path: r.try_get("image_path").ok()?,
+                local_time: parse_taken_at(&taken_at)?.0,
+                sharpness: r.try_get("sharpness").unwrap_or_default(),
+                brightness: r.try_get("brightness").unwrap_or_default(),
+                clipped_highlights: r.try_get("clipped_highlights").unwrap_or_default(),
+                clipped_shadows: r.try_get("clipped_shadows").unwrap_or_default(),
+            })
+        })
+        .collect())
+}
+
+// How many of the images already went through burst detection, whether or not they ended up in a burst
+pub async fn query_burst_checked_image_count(image_paths: &[String], pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    if image_paths.is_empty() {
+        return Ok(0);
+    }
+    let placeholders = image_paths.iter().map(|_| "?").collect::<Vec<&str>>().join(", ");
+    let sql = format!(r#"SELECT COUNT(*) 'ct' FROM image_burst_frame WHERE image_path IN ({})"#, placeholders);
+    let rows = execute_query(pool, &sql, image_paths.iter().map(String::as_str).collect()).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    Ok(v.unwrap_or_default() as usize)
+}
+
+pub async fn query_image_burst_count(pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let rows = execute_query(pool, r#"SELECT COUNT(*) 'ct' FROM image_burst"#, vec![]).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    Ok(v.unwrap_or_default() as usize)
+}
+
+// The newest bursts with their frames in the order they were taken. Unless show_reviewed is set, bursts already
+// submitted on the review page are left out, so the page moves on to the next ones as they are reviewed
+pub async fn query_image_burst_reviews(show_reviewed: bool, limit: usize, pool: &SqlitePool) -> Result<Vec<ImageBurstReview>, Box<dyn Error + Send>> {
+    let tagged_sql = r#"EXISTS (SELECT 1 FROM image_tags WHERE image_tags.image_id = image_burst_frame.image_id AND image_tags.tag_name = ?)"#;
+    let reviewed_filter = match show_reviewed {
+        true => "",
+        false => "WHERE burst_reviewed = 0",
+    };
+    let sql = format!(r#"SELECT image_burst.burst_start, image_burst.burst_end, image_burst.burst_frame_count, image_burst.burst_keeper_image_path,
+            image_burst.burst_reviewed, image_burst_frame.*, image_exif.image_taken_at, {} AS tagged_for_deletion
+        FROM image_burst
+        INNER JOIN image_burst_frame ON image_burst_frame.burst_id = image_burst.burst_id
+        LEFT JOIN image_exif ON image_exif.image_id = image_burst_frame.image_id
+        WHERE image_burst.burst_id IN (SELECT burst_id FROM image_burst {} ORDER BY burst_start DESC, burst_id DESC LIMIT {})
+        ORDER BY image_burst.burst_start DESC, image_burst.burst_id DESC, image_exif.image_taken_at, image_burst_frame.image_path"#,
+        tagged_sql, reviewed_filter, limit);
+    let rows = execute_query(pool, &sql, vec![ BURST_DELETE_TAG_NAME ]).await?;
+
+    let mut reviews: Vec<ImageBurstReview> = vec![];
+    for row in &rows {
+        let frame = ImageBurstReviewFrame {
+            frame: ImageBurstFrame::new(row),
+            taken_at: row.try_get("image_taken_at").unwrap_or_default(),
+            tagged_for_deletion: row.try_get("tagged_for_deletion").unwrap_or_default(),
+        };
+        match reviews.last_mut() {
+            Some(review) if Some(review.burst.burst_id) == frame.frame.burst_id => review.frames.push(frame),
+            _ => reviews.push(ImageBurstReview { burst: ImageBurst::new(row), frames: vec![frame] }),
+        }
+    }
+    Ok(reviews)
+}
\ No newline at end of file
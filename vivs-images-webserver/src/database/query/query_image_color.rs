@@ -0,0 +1,45 @@
+use std::error::Error;
+use std::collections::HashSet;
+
+use sqlx::{Row, SqlitePool};
+
+use crate::calc::color_palette::HISTOGRAM_BIN_TEXT_WIDTH;
+use crate::database::common::execute_query;
+use crate::models::image_color::ImageColor;
+
+
+// Retrieves colour image paths from the color table in the database
+pub async fn get_image_paths_from_db(pool: &SqlitePool) -> Result<HashSet<String>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT image_path FROM image_color"#;
+    let rows = execute_query(pool, sql, vec![]).await?;
+    
+    Ok(rows.iter()
+        .filter_map(|r| r.try_get("image_path").ok())
+        .collect())
+}
+
+
+pub async fn query_color_table_count(image_path: &str, pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let sql = r#"SELECT COUNT(*) 'ct' FROM image_color WHERE image_path = ?"#;
+    let rows = execute_query(pool, sql, vec![ image_path ]).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    let v: usize = v.unwrap_or_default() as usize;
+    Ok(v)
+}
+
+
+// Colour rows with at least min_weight of their pixels in the given histogram bins, so only
+// those are ranked by palette distance
+pub async fn query_image_colors_in_histogram_bins(bins: &[usize], min_weight: f32, pool: &SqlitePool) -> Result<Vec<ImageColor>, Box<dyn Error + Send>> {
+    if bins.is_empty() {
+        return Ok(vec![]);
+    }
+    let bin_weights_sql = bins.iter()
+        .map(|b| format!("CAST(substr(hsv_histogram, {}, 6) AS REAL)", b * HISTOGRAM_BIN_TEXT_WIDTH + 1))
+        .collect::<Vec<String>>()
+        .join(" + ");
+    let sql = format!(r#"SELECT * FROM image_color WHERE {} >= CAST(? AS REAL)"#, bin_weights_sql);
+    let min_weight = min_weight.to_string();
+    let rows = execute_query(pool, &sql, vec![ &min_weight ]).await?;
+    Ok(rows.iter().map(ImageColor::new).collect())
+}
\ No newline at end of file
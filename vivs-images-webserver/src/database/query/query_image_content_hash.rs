@@ -0,0 +1,35 @@
+use std::error::Error;
+use std::collections::HashSet;
+
+use sqlx::{Row, SqlitePool};
+
+use crate::database::common::execute_query;
+
+
+// Retrieves content_hash image paths from the content_hash table in the database
+pub async fn get_image_paths_from_db(pool: &SqlitePool) -> Result<HashSet<String>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT image_path FROM image_content_hash"#;
+    let rows = execute_query(pool, sql, vec![]).await?;
+    
+    Ok(rows.iter()
+        .filter_map(|r| r.try_get("image_path").ok())
+        .collect())
+}
+
+
+pub async fn query_content_hash_table_count(image_path: &str, pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let sql = r#"SELECT COUNT(*) 'ct' FROM image_content_hash WHERE image_path = ?"#;
+    let rows = execute_query(pool, sql, vec![ image_path ]).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    let v: usize = v.unwrap_or_default() as usize;
+    Ok(v)
+}
+
+// The other paths that were stored with the same file contents
+pub async fn query_image_paths_with_content_hash(content_hash: &str, except_image_path: &str, pool: &SqlitePool) -> Result<Vec<String>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT image_path FROM image_content_hash WHERE content_hash = ? AND image_path != ? ORDER BY image_path"#;
+    let rows = execute_query(pool, sql, vec![ content_hash, except_image_path ]).await?;
+    Ok(rows.iter()
+        .filter_map(|r| r.try_get("image_path").ok())
+        .collect())
+}
\ No newline at end of file
@@ -0,0 +1,83 @@
+use std::error::Error;
+
+use sqlx::{Row, SqlitePool};
+
+use crate::calc::reverse_geocode::is_valid_gps_coordinate;
+use crate::converters::extract_image_taken_at::parse_taken_at;
+use crate::database::common::execute_query;
+use crate::models::image_event::{ImageEvent, ImageEventPhoto};
+use crate::models::image_timeline::TimelineBucket;
+use crate::models::query_params::geo_search_params::GeoPoint;
+
+
+// Every image still on record with a capture time, with what clustering needs to split and describe the events
+pub async fn query_image_event_photos(pool: &SqlitePool) -> Result<Vec<ImageEventPhoto>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT image_exif.image_path, image_exif.image_taken_at, image_exif.gps_latitude, image_exif.gps_longitude, image_sharpness.sharpness
+        FROM image_exif
+        INNER JOIN image_paths ON image_paths.image_id = image_exif.image_id
+        LEFT JOIN image_sharpness ON image_sharpness.image_id = image_exif.image_id
+        WHERE image_exif.image_taken_at IS NOT NULL"#;
+    let rows = execute_query(pool, sql, vec![]).await?;
+    Ok(rows.iter()
+        .filter_map(|r| {
+            let taken_at: String = r.try_get("image_taken_at").ok()?;
+            let latitude: Option<f64> = r.try_get("gps_latitude").unwrap_or_default();
+            let longitude: Option<f64> = r.try_get("gps_longitude").unwrap_or_default();
+            let location = match (latitude, longitude) {
+                (Some(lat), Some(lon)) if is_valid_gps_coordinate(lat, lon) => GeoPoint::new(lat, lon),
+                _ => None,
+            };
+            Some(ImageEventPhoto {
+                image_path: r.try_get("image_path").ok()?,
+                local_time: parse_taken_at(&taken_at)?.0,
+                location,
+                sharpness: r.try_get("sharpness").unwrap_or_default(),
+            })
+        })
+        .collect())
+}
+
+const SQL_SELECT_IMAGE_EVENT: &str = r#"SELECT image_event.*, (
+        SELECT COALESCE(image_geocode.place_city, image_geocode.place_country) FROM image_event_member
+        INNER JOIN image_geocode ON image_geocode.image_id = image_event_member.image_id
+        WHERE image_event_member.event_id = image_event.event_id AND COALESCE(image_geocode.place_city, image_geocode.place_country) IS NOT NULL
+        ORDER BY image_event_member.image_path LIMIT 1
+    ) AS event_place
+    FROM image_event"#;
+
+// Events that started in the period the prefix of their ISO-8601 start time names, newest first
+pub async fn query_image_events(start_prefix: &str, pool: &SqlitePool) -> Result<Vec<ImageEvent>, Box<dyn Error + Send>> {
+    let sql = format!(r#"{} WHERE event_start LIKE ? ORDER BY event_start DESC, event_id DESC"#, SQL_SELECT_IMAGE_EVENT);
+    let pattern = format!("{}%", start_prefix);
+    let rows = execute_query(pool, &sql, vec![ pattern.as_str() ]).await?;
+    Ok(rows.iter().map(ImageEvent::new).collect())
+}
+
+pub async fn query_image_event(event_id: i64, pool: &SqlitePool) -> Result<Option<ImageEvent>, Box<dyn Error + Send>> {
+    let sql = format!(r#"{} WHERE event_id = ?"#, SQL_SELECT_IMAGE_EVENT);
+    let event_id = event_id.to_string();
+    let rows = execute_query(pool, &sql, vec![ event_id.as_str() ]).await?;
+    Ok(rows.first().map(ImageEvent::new))
+}
+
+// Number of events per start year, newest first
+pub async fn query_image_event_year_counts(pool: &SqlitePool) -> Result<Vec<TimelineBucket>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT substr(event_start, 1, 4) AS bucket, COUNT(*) 'ct' FROM image_event GROUP BY bucket ORDER BY bucket DESC"#;
+    let rows = execute_query(pool, sql, vec![]).await?;
+    Ok(rows.iter()
+        .filter_map(|r| Some(TimelineBucket {
+            key: r.try_get("bucket").ok()?,
+            count: r.try_get::<u32, _>("ct").ok()? as usize,
+        }))
+        .collect())
+}
+
+pub async fn query_images_missing_event_count(pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let sql = r#"SELECT COUNT(*) 'ct' FROM image_exif
+        INNER JOIN image_paths ON image_paths.image_id = image_exif.image_id
+        LEFT JOIN image_event_member ON image_event_member.image_id = image_exif.image_id
+        WHERE image_exif.image_taken_at IS NOT NULL AND image_event_member.image_id IS NULL"#;
+    let rows = execute_query(pool, sql, vec![]).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    Ok(v.unwrap_or_default() as usize)
+}
\ No newline at end of file
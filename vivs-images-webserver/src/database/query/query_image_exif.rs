@@ -23,4 +23,20 @@ pub async fn query_exif_table_count(image_path: &str, pool: &SqlitePool) -> Resu
     let v: Option<u32> = rows.iter().nth(0).map(|r| r.get("ct"));
     let v: usize = v.unwrap_or_default() as usize;
     Ok(v)
+}
+
+// Images with an exif row that has to be read from the file again
+pub async fn get_exif_pending_image_paths(pool: &SqlitePool) -> Result<HashSet<String>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT image_path FROM image_exif_pending"#;
+    let rows = execute_query(pool, sql, vec![]).await?;
+    Ok(rows.iter()
+        .filter_map(|r| r.try_get("image_path").ok())
+        .collect())
+}
+
+pub async fn query_exif_pending_count(image_path: &str, pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let sql = r#"SELECT COUNT(*) 'ct' FROM image_exif_pending WHERE image_path = ?"#;
+    let rows = execute_query(pool, sql, vec![ image_path ]).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    Ok(v.unwrap_or_default() as usize)
 }
\ No newline at end of file
@@ -0,0 +1,38 @@
+use std::error::Error;
+use std::collections::HashSet;
+
+use sqlx::{Row, SqlitePool};
+
+use crate::database::common::execute_query;
+use crate::models::image_frames::ImageFrameThumbnail;
+
+
+// Retrieves frames image paths from the frames table in the database
+pub async fn get_image_paths_from_db(pool: &SqlitePool) -> Result<HashSet<String>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT image_path FROM image_frames"#;
+    let rows = execute_query(pool, sql, vec![]).await?;
+    
+    Ok(rows.iter()
+        .filter_map(|r| r.try_get("image_path").ok())
+        .collect())
+}
+
+
+pub async fn query_frames_table_count(image_path: &str, pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let sql = r#"SELECT COUNT(*) 'ct' FROM image_frames WHERE image_path = ?"#;
+    let rows = execute_query(pool, sql, vec![ image_path ]).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    let v: usize = v.unwrap_or_default() as usize;
+    Ok(v)
+}
+
+// The stored frame thumbnails of a multi-frame file, in frame order
+pub async fn query_frame_thumbnails(image_path: &str, pool: &SqlitePool) -> Result<Vec<ImageFrameThumbnail>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT * FROM image_frame_thumbnail WHERE image_path = ? ORDER BY frame_index"#;
+    let rows = execute_query(pool, sql, vec![ image_path ]).await?;
+    let mut items = vec![];
+    for r in rows {
+        items.push(ImageFrameThumbnail::new_from_row(&r).map_err(|e| Box::new(e) as Box<dyn Error + Send>)?);
+    }
+    Ok(items)
+}
\ No newline at end of file
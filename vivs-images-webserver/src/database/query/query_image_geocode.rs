@@ -0,0 +1,51 @@
+use std::error::Error;
+use std::collections::HashSet;
+
+use sqlx::{Row, SqlitePool};
+
+use crate::database::common::execute_query;
+use crate::models::image_geocode::ImageGpsCoordinates;
+
+
+// Retrieves geocoded image paths from the geocode table in the database
+pub async fn get_image_paths_from_db(pool: &SqlitePool) -> Result<HashSet<String>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT image_path FROM image_geocode"#;
+    let rows = execute_query(pool, sql, vec![]).await?;
+    
+    Ok(rows.iter()
+        .filter_map(|r| r.try_get("image_path").ok())
+        .collect())
+}
+
+const SQL_GPS_COORDINATES_MISSING_GEOCODE: &str = r#"
+FROM image_exif
+INNER JOIN image_paths ON image_paths.image_id = image_exif.image_id
+LEFT JOIN image_geocode ON image_geocode.image_id = image_exif.image_id
+WHERE image_exif.gps_latitude IS NOT NULL AND image_exif.gps_longitude IS NOT NULL AND image_geocode.image_id IS NULL"#;
+
+pub async fn query_gps_coordinates_missing_geocode(pool: &SqlitePool) -> Result<Vec<ImageGpsCoordinates>, Box<dyn Error + Send>> {
+    let sql = format!(r#"SELECT image_exif.image_path, image_exif.gps_latitude, image_exif.gps_longitude {} ORDER BY image_exif.image_path"#, SQL_GPS_COORDINATES_MISSING_GEOCODE);
+    let rows = execute_query(pool, &sql, vec![]).await?;
+    Ok(rows.iter()
+        .filter_map(|r| Some(ImageGpsCoordinates {
+            image_path: r.try_get("image_path").ok()?,
+            latitude: r.try_get("gps_latitude").ok()?,
+            longitude: r.try_get("gps_longitude").ok()?,
+        }))
+        .collect())
+}
+
+pub async fn query_gps_coordinates_missing_geocode_count(pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let sql = format!(r#"SELECT COUNT(*) 'ct' {}"#, SQL_GPS_COORDINATES_MISSING_GEOCODE);
+    let rows = execute_query(pool, &sql, vec![]).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    Ok(v.unwrap_or_default() as usize)
+}
+
+pub async fn query_geocode_table_count(image_path: &str, pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let sql = r#"SELECT COUNT(*) 'ct' FROM image_geocode WHERE image_path = ?"#;
+    let rows = execute_query(pool, sql, vec![ image_path ]).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    let v: usize = v.unwrap_or_default() as usize;
+    Ok(v)
+}
\ No newline at end of file
@@ -0,0 +1,44 @@
+use std::error::Error;
+use std::collections::HashSet;
+
+use sqlx::{Row, SqlitePool};
+
+use crate::database::common::execute_query;
+
+
+// Retrieves ocr_summary image paths from the ocr_summary table in the database
+pub async fn get_image_paths_from_db(pool: &SqlitePool) -> Result<HashSet<String>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT image_path FROM image_ocr_summary"#;
+    let rows = execute_query(pool, sql, vec![]).await?;
+    
+    Ok(rows.iter()
+        .filter_map(|r| r.try_get("image_path").ok())
+        .collect())
+}
+
+
+pub async fn query_ocr_summary_table_count(image_path: &str, pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let sql = r#"SELECT COUNT(*) 'ct' FROM image_ocr_summary WHERE image_path = ?"#;
+    let rows = execute_query(pool, sql, vec![ image_path ]).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    let v: usize = v.unwrap_or_default() as usize;
+    Ok(v)
+}
+
+// Images still on record whose text was read with other languages or preprocessing options than these
+pub async fn query_ocr_summary_stale_image_paths(languages: &str, options: &str, pool: &SqlitePool) -> Result<HashSet<String>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT image_ocr_summary.image_path FROM image_ocr_summary
+        INNER JOIN image_paths ON image_paths.image_id = image_ocr_summary.image_id
+        WHERE image_ocr_summary.ocr_languages != ? OR image_ocr_summary.ocr_options != ?"#;
+    let rows = execute_query(pool, sql, vec![ languages, options ]).await?;
+    Ok(rows.iter()
+        .filter_map(|r| r.try_get("image_path").ok())
+        .collect())
+}
+
+pub async fn query_ocr_summary_current_count(image_path: &str, languages: &str, options: &str, pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let sql = r#"SELECT COUNT(*) 'ct' FROM image_ocr_summary WHERE image_path = ? AND ocr_languages = ? AND ocr_options = ?"#;
+    let rows = execute_query(pool, sql, vec![ image_path, languages, options ]).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    Ok(v.unwrap_or_default() as usize)
+}
\ No newline at end of file
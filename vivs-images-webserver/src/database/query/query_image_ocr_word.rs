@@ -0,0 +1,16 @@
+use std::error::Error;
+
+use sqlx::SqlitePool;
+
+use crate::database::common::execute_query;
+use crate::models::image_ocr_word::ImageOcrWord;
+
+
+// Word boxes on the first page of an image, the one that is shown, in reading order, leaving out words below
+// `min_confidence`
+pub async fn query_ocr_words_from_db(image_path: &str, min_confidence: f32, pool: &SqlitePool) -> Result<Vec<ImageOcrWord>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT * FROM image_ocr_word WHERE image_path = ? AND page_num = 1 AND confidence >= ? ORDER BY word_index"#;
+    let min_confidence = min_confidence.to_string();
+    let rows = execute_query(pool, sql, vec![ image_path, &min_confidence ]).await?;
+    Ok(rows.iter().map(ImageOcrWord::new).collect())
+}
\ No newline at end of file
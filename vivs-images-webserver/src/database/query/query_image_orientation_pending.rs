@@ -0,0 +1,28 @@
+use std::error::Error;
+
+use sqlx::{Row, SqlitePool};
+
+use crate::database::common::execute_query;
+
+
+pub async fn query_orientation_pending_paths(pool: &SqlitePool) -> Result<Vec<String>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT image_path FROM image_orientation_pending ORDER BY image_path"#;
+    let rows = execute_query(pool, sql, vec![]).await?;
+    Ok(rows.iter()
+        .filter_map(|r| r.try_get("image_path").ok())
+        .collect())
+}
+
+pub async fn query_orientation_pending_count(image_path: &str, pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let sql = r#"SELECT COUNT(*) 'ct' FROM image_orientation_pending WHERE image_path = ?"#;
+    let rows = execute_query(pool, sql, vec![ image_path ]).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    Ok(v.unwrap_or_default() as usize)
+}
+
+pub async fn query_orientation_pending_total(pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let sql = r#"SELECT COUNT(*) 'ct' FROM image_orientation_pending"#;
+    let rows = execute_query(pool, sql, vec![]).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    Ok(v.unwrap_or_default() as usize)
+}
\ No newline at end of file
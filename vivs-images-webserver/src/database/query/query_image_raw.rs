@@ -0,0 +1,35 @@
+use std::error::Error;
+use std::collections::HashSet;
+
+use sqlx::{Row, SqlitePool};
+
+use crate::database::common::execute_query;
+
+
+// Retrieves raw image paths from the raw table in the database
+pub async fn get_image_paths_from_db(pool: &SqlitePool) -> Result<HashSet<String>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT image_path FROM image_raw"#;
+    let rows = execute_query(pool, sql, vec![]).await?;
+    
+    Ok(rows.iter()
+        .filter_map(|r| r.try_get("image_path").ok())
+        .collect())
+}
+
+
+pub async fn query_raw_table_count(image_path: &str, pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let sql = r#"SELECT COUNT(*) 'ct' FROM image_raw WHERE image_path = ?"#;
+    let rows = execute_query(pool, sql, vec![ image_path ]).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    let v: usize = v.unwrap_or_default() as usize;
+    Ok(v)
+}
+
+// The RAW files a JPEG was written alongside, for linking back from the JPEG
+pub async fn query_raw_paths_for_jpeg_sibling(jpeg_path: &str, pool: &SqlitePool) -> Result<Vec<String>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT image_path FROM image_raw WHERE jpeg_sibling_path = ? ORDER BY image_path"#;
+    let rows = execute_query(pool, sql, vec![ jpeg_path ]).await?;
+    Ok(rows.iter()
+        .filter_map(|r| r.try_get("image_path").ok())
+        .collect())
+}
\ No newline at end of file
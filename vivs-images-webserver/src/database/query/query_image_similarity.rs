@@ -3,25 +3,23 @@ use std::error::Error;
 
 use sqlx::{Row, SqlitePool};
 
-use crate::models::image_similarity::ImageComparisonAlgorithm;
+use crate::models::image_similarity::{ImageComparisonAlgorithm, ImageSimilarity};
 use crate::database::common::execute_query;
-use crate::converters::extract_image_similarity::compute_comparison_key;
+use crate::converters::extract_image_similarity::order_comparison_pair;
 
 
 
-pub async fn get_image_similarity_value_exists_in_db(path_a: &str, path_b: &str, pool: &SqlitePool) -> Result<bool, Box<dyn Error + Send>> {
-    let hash = compute_comparison_key(path_a, path_b).to_string();
+pub async fn get_image_similarity_value_exists_in_db(path_a: &str, path_b: &str, algo: &ImageComparisonAlgorithm, pool: &SqlitePool) -> Result<bool, Box<dyn Error + Send>> {
+    let (path_a, path_b) = order_comparison_pair(path_a, path_b);
+    let algo = algo.to_string();
     let rows = execute_query(pool,
         r#"
         SELECT COUNT(*) 'ct'
         FROM image_similarity
-        WHERE 
-            (image_path_a = ? AND image_path_b = ?)
-            OR (image_path_b = ? AND image_path_a = ?)
-            OR image_comparison_key = ?;
+        WHERE image_path_a = ? AND image_path_b = ? AND image_comparison_algorithm = ?;
         "#,
         vec![
-            path_a, path_b, path_a, path_b, &hash
+            &path_a, &path_b, &algo
         ]
     ).await?;
 
@@ -143,4 +141,30 @@ pub async fn get_count_of_comparisons_per_image_path(pool: &SqlitePool) -> Resul
             }
         })
         .collect())
+}
+
+// Random sample of pairs compared by at least two of the given algorithms, with one row per algorithm for each pair
+pub async fn query_similarity_sample_for_algorithms(algos: &[ImageComparisonAlgorithm], sample_size: usize, pool: &SqlitePool) -> Result<Vec<ImageSimilarity>, Box<dyn Error + Send>> {
+    if algos.is_empty() {
+        return Ok(vec![]);
+    }
+    let algos: Vec<String> = algos.iter().map(|a| a.to_string()).collect();
+    let placeholders = vec!["?"; algos.len()].join(", ");
+    let sql = format!(r#"
+    SELECT s.image_comparison_algorithm, s.image_path_a, s.image_path_b, s.similarity_value, s.similarity_confidence
+    FROM image_similarity s
+    INNER JOIN (
+        SELECT image_path_a, image_path_b FROM image_similarity
+        WHERE image_comparison_algorithm IN ({placeholders})
+        GROUP BY image_path_a, image_path_b
+        HAVING COUNT(DISTINCT image_comparison_algorithm) >= 2
+        ORDER BY RANDOM()
+        LIMIT {sample_size}
+    ) sample ON sample.image_path_a = s.image_path_a AND sample.image_path_b = s.image_path_b
+    WHERE s.image_comparison_algorithm IN ({placeholders});"#);
+
+    let params: Vec<&str> = algos.iter().chain(algos.iter()).map(|a| a.as_str()).collect();
+    let rows = execute_query(pool, &sql, params).await?;
+
+    Ok(rows.iter().map(ImageSimilarity::new).collect())
 }
\ No newline at end of file
@@ -0,0 +1,76 @@
+use std::collections::HashSet;
+use std::error::Error;
+
+use sqlx::{Row, SqlitePool};
+
+use crate::database::common::execute_query;
+use crate::models::image_similarity::ImageComparisonAlgorithm;
+use crate::models::image_similarity_neighbor::{ImageSimilarityNeighbor, ImageSimilarityPrunePlan};
+
+
+// Images whose neighbours have been found by comparing them against every other image
+pub async fn query_similarity_neighbor_scanned_paths(algo: &ImageComparisonAlgorithm, pool: &SqlitePool) -> Result<HashSet<String>, Box<dyn Error + Send>> {
+    let algo = algo.to_string();
+    let rows = execute_query(pool, "SELECT image_path FROM image_similarity_neighbor_scan WHERE image_comparison_algorithm = ?;", vec![&algo]).await?;
+    Ok(rows.iter()
+        .filter_map(|r| r.try_get("image_path").ok())
+        .collect())
+}
+
+pub async fn query_similarity_neighbor_scan_count(path: &str, algo: &ImageComparisonAlgorithm, pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let algo = algo.to_string();
+    let rows = execute_query(pool,
+        "SELECT COUNT(*) 'ct' FROM image_similarity_neighbor_scan WHERE image_path = ? AND image_comparison_algorithm = ?;",
+        vec![path, &algo]
+    ).await?;
+    let v: u32 = rows.iter()
+        .filter_map(|r| r.try_get("ct").ok())
+        .next()
+        .unwrap_or_default();
+    Ok(v as usize)
+}
+
+// Most similar first
+pub async fn query_similarity_neighbors_for_image(path: &str, algo: &ImageComparisonAlgorithm, pool: &SqlitePool) -> Result<Vec<ImageSimilarityNeighbor>, Box<dyn Error + Send>> {
+    let algo = algo.to_string();
+    let rows = execute_query(pool,
+        r#"
+        SELECT image_path, neighbor_path, image_comparison_algorithm, similarity_value, similarity_confidence
+        FROM image_similarity_neighbors
+        WHERE image_path = ? AND image_comparison_algorithm = ?
+        ORDER BY similarity_value DESC, neighbor_path;
+        "#,
+        vec![path, &algo]
+    ).await?;
+    Ok(rows.iter().map(ImageSimilarityNeighbor::new).collect())
+}
+
+// One plan per algorithm present in image_similarity
+pub async fn query_similarity_prune_plans(top_k: usize, min_value: f32, pool: &SqlitePool) -> Result<Vec<ImageSimilarityPrunePlan>, Box<dyn Error + Send>> {
+    let sql = r#"
+    WITH sides AS (
+        SELECT image_comparison_algorithm, image_path_a 'image_path' FROM image_similarity
+        UNION ALL
+        SELECT image_comparison_algorithm, image_path_b 'image_path' FROM image_similarity
+    )
+    SELECT image_comparison_algorithm, COUNT(*) / 2 'row_count', COUNT(DISTINCT image_path) 'image_count'
+    FROM sides
+    GROUP BY image_comparison_algorithm
+    ORDER BY image_comparison_algorithm;"#;
+    let rows = execute_query(pool, sql, vec![]).await?;
+
+    Ok(rows.iter()
+        .filter_map(|r| {
+            let algo: u8 = r.try_get("image_comparison_algorithm").ok()?;
+            let row_count: u32 = r.try_get("row_count").ok()?;
+            let image_count: u32 = r.try_get("image_count").ok()?;
+            Some(ImageSimilarityPrunePlan {
+                image_comparison_algorithm: algo.try_into().ok()?,
+                row_count: row_count as usize,
+                image_count: image_count as usize,
+                top_k,
+                min_value,
+            })
+        })
+        .collect())
+}
\ No newline at end of file
@@ -5,6 +5,8 @@ use sqlx::{Row, SqlitePool};
 
 use crate::models::image_thumbnail::ImageThumbnail;
 use crate::database::common::execute_query;
+use crate::database::thumbnail_store::read_thumbnail_data;
+use crate::models::config::thumbnails::ThumbnailStorage;
 
 
 
@@ -17,12 +19,21 @@ pub async fn get_thumbnail_image_paths_from_db(pool: &SqlitePool) -> Result<Hash
         .collect())
 }
 
+// Rows written while thumbnails were kept in a separate store get their data read back from there
+async fn thumbnail_from_row(row: &sqlx::sqlite::SqliteRow, pool: &SqlitePool) -> Result<ImageThumbnail, Box<dyn Error + Send>> {
+    let mut thumbnail = ImageThumbnail::new_from_row(row).map_err(|e| Box::new(e) as Box<dyn Error + Send>)?;
+    if let Ok(Some(storage_key)) = row.try_get::<Option<String>, _>("thumbnail_storage_key") {
+        thumbnail.thumbnail_data = read_thumbnail_data(&storage_key, pool).await?;
+    }
+    Ok(thumbnail)
+}
+
 pub async fn query_thumbnail_table(image_path: &str, pool: &SqlitePool) -> Result<Vec<ImageThumbnail>, Box<dyn Error + Send>> {
     let sql = r#"SELECT * FROM image_thumbnail WHERE image_path = ?"#;
     let rows = execute_query(pool, sql, vec![ image_path ]).await?;
     let mut items = vec![];
     for r in rows {
-        items.push(ImageThumbnail::new_from_row(&r).map_err(|e| Box::new(e) as Box<dyn Error + Send>)?);
+        items.push(thumbnail_from_row(&r, pool).await?);
     }
     Ok(items)
 }
@@ -58,7 +69,7 @@ pub async fn query_thumbnail_table_width_length_operator(
     let dim = dim.to_string();
     let rows = execute_query(pool, &sql, vec![ image_path, &dim ]).await?;
     Ok(if let Some(r) = rows.iter().nth(0) {
-        Some(ImageThumbnail::new_from_row(r).map_err(|e| Box::new(e) as Box<dyn Error + Send>)?)
+        Some(thumbnail_from_row(r, pool).await?)
     } else {
         None
     })
@@ -68,6 +79,60 @@ pub async fn query_thumbnail_table_width_length(image_path: &str, dim: u32, pool
     query_thumbnail_table_width_length_operator(image_path, "=", dim, None, None, pool).await
 }
 
+// The smallest stored thumbnail at least as large as asked for, or the largest one when none are
+pub async fn query_thumbnail_table_nearest_width_length(image_path: &str, dim: u32, pool: &SqlitePool) -> Result<Option<ImageThumbnail>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT * FROM image_thumbnail WHERE image_path = ?
+        ORDER BY CASE WHEN width_and_length >= ? THEN 0 ELSE 1 END, ABS(width_and_length - ?) LIMIT 1"#;
+    let dim = dim.to_string();
+    let rows = execute_query(pool, sql, vec![ image_path, &dim, &dim ]).await?;
+    Ok(if let Some(r) = rows.first() {
+        Some(thumbnail_from_row(r, pool).await?)
+    } else {
+        None
+    })
+}
+
 pub async fn query_thumbnail_table_at_most_width_length(image_path: &str, dim: u32, pool: &SqlitePool) -> Result<Option<ImageThumbnail>, Box<dyn Error + Send>> {
     query_thumbnail_table_width_length_operator(image_path, "<=", dim, Some("width_and_length DESC"), Some(1), pool).await
+}
+
+// Thumbnails whose data is kept somewhere other than the given storage, optionally just the one row
+fn sql_thumbnails_not_in_storage(select: &str, storage: &ThumbnailStorage, image_thumbnail_key: Option<i64>) -> (String, Vec<String>) {
+    let (mut sql, mut params) = if *storage == ThumbnailStorage::Database {
+        (format!("SELECT {} FROM image_thumbnail WHERE thumbnail_storage_key IS NOT NULL", select), vec![])
+    } else {
+        (format!("SELECT {} FROM image_thumbnail WHERE (thumbnail_storage_key IS NULL OR thumbnail_storage_key NOT LIKE ?)", select),
+            vec![ format!("{}:%", storage.get_name()) ])
+    };
+    if let Some(image_thumbnail_key) = image_thumbnail_key {
+        sql.push_str(" AND image_thumbnail_key = ?");
+        params.push(image_thumbnail_key.to_string());
+    }
+    (sql, params)
+}
+
+pub async fn query_thumbnail_keys_not_in_storage(storage: &ThumbnailStorage, pool: &SqlitePool) -> Result<Vec<i64>, Box<dyn Error + Send>> {
+    let (sql, params) = sql_thumbnails_not_in_storage("image_thumbnail_key", storage, None);
+    let rows = execute_query(pool, &sql, params.iter().map(|p| p.as_str()).collect()).await?;
+    Ok(rows.iter()
+        .filter_map(|r| r.try_get("image_thumbnail_key").ok())
+        .collect())
+}
+
+pub async fn query_thumbnail_count_not_in_storage(storage: &ThumbnailStorage, image_thumbnail_key: Option<i64>, pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let (sql, params) = sql_thumbnails_not_in_storage("COUNT(*) 'ct'", storage, image_thumbnail_key);
+    let rows = execute_query(pool, &sql, params.iter().map(|p| p.as_str()).collect()).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    Ok(v.unwrap_or_default() as usize)
+}
+
+pub async fn query_thumbnail_by_key(image_thumbnail_key: i64, pool: &SqlitePool) -> Result<Option<ImageThumbnail>, Box<dyn Error + Send>> {
+    let sql = r#"SELECT * FROM image_thumbnail WHERE image_thumbnail_key = ?"#;
+    let image_thumbnail_key = image_thumbnail_key.to_string();
+    let rows = execute_query(pool, sql, vec![ &image_thumbnail_key ]).await?;
+    Ok(if let Some(r) = rows.first() {
+        Some(thumbnail_from_row(r, pool).await?)
+    } else {
+        None
+    })
 }
\ No newline at end of file
@@ -0,0 +1,34 @@
+use std::error::Error;
+
+use sqlx::{Row, SqlitePool};
+
+use crate::database::common::execute_query;
+use crate::models::image_timeline::TimelineBucket;
+
+
+// Capture times not in the normalized YYYY-MM-DDTHH:MM:SS form cannot be placed on the timeline
+const TAKEN_AT_NORMALIZED_GLOB: &str = "[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9]T*";
+
+// Number of images per year (bucket_len 4), month (7) or day (10) whose capture time starts with prefix
+pub async fn query_taken_at_bucket_counts(pool: &SqlitePool, prefix: &str, bucket_len: usize) -> Result<Vec<TimelineBucket>, Box<dyn Error + Send>> {
+    let sql = format!(r#"SELECT substr(image_taken_at, 1, {}) AS bucket, COUNT(*) AS ct FROM image_exif
+        WHERE image_taken_at GLOB '{}' AND image_taken_at LIKE ?
+        GROUP BY bucket ORDER BY bucket"#, bucket_len, TAKEN_AT_NORMALIZED_GLOB);
+    let pattern = format!("{}%", prefix);
+    let rows = execute_query(pool, &sql, vec![ pattern.as_str() ]).await?;
+    Ok(rows.iter()
+        .filter_map(|r| Some(TimelineBucket {
+            key: r.try_get("bucket").ok()?,
+            count: r.try_get::<u32, _>("ct").ok()? as usize,
+        }))
+        .collect())
+}
+
+pub async fn query_undated_image_count(pool: &SqlitePool) -> Result<usize, Box<dyn Error + Send>> {
+    let sql = format!(r#"SELECT COUNT(*) 'ct' FROM image_paths
+        LEFT JOIN image_exif ON image_exif.image_id = image_paths.image_id
+        WHERE image_exif.image_taken_at IS NULL OR image_exif.image_taken_at NOT GLOB '{}'"#, TAKEN_AT_NORMALIZED_GLOB);
+    let rows = execute_query(pool, &sql, vec![]).await?;
+    let v: Option<u32> = rows.first().map(|r| r.get("ct"));
+    Ok(v.unwrap_or_default() as usize)
+}
\ No newline at end of file
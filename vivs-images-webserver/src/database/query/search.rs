@@ -5,17 +5,31 @@ use std::sync::Arc;
 use sqlx::{Row, SqlitePool};
 
 use crate::core::data_context::WebServerActionDataContext;
-use crate::models::image::{Image, ImageFieldMeta};
+use crate::models::image::{Image, ImageFieldMeta, HAS_THUMBNAIL_COLUMN};
 use crate::database::common::execute_query;
 use crate::models::image_aspect_ratio::ImageAspectRatio;
 use crate::models::image_brightness::ImageBrightness;
+use crate::models::image_color::ImageColor;
+use crate::models::image_sharpness::ImageSharpness;
 use crate::models::image_exif::ImageExif;
 use crate::models::image_iptc::ImageIptc;
 use crate::models::image_ocr_text::ImageOcrText;
 use crate::models::image_paths::ImagePaths;
 use crate::models::image_similarity::ImageSimilarity;
 use crate::models::image_xmp::ImageXmp;
-use crate::models::query_params::search_params::SearchParams;
+use crate::models::image_exposure::ImageExposure;
+use crate::models::image_ocr_summary::ImageOcrSummary;
+use crate::models::image_content_hash::ImageContentHash;
+use crate::models::image_raw::ImageRaw;
+use crate::models::image_frames::ImageFrames;
+use crate::models::image_geocode::ImageGeocode;
+use crate::models::image_event::ImageEventMember;
+use crate::models::image_burst::ImageBurstFrame;
+use crate::models::query_params::geo_search_params::{GeoBoundingBox, GeoPoint, GeoSearchFilter, KM_PER_DEGREE_LATITUDE};
+use crate::models::query_params::search_params::{SearchParams, GEO_DISTANCE_ORDER_COLUMN};
+
+const GPS_LATITUDE_COLUMN: &str = "[image_exif].[gps_latitude]";
+const GPS_LONGITUDE_COLUMN: &str = "[image_exif].[gps_longitude]";
 
 pub struct SearchBuilderImageFeature {
     pub table_name: String,
@@ -114,9 +128,10 @@ impl SearchBuilder {
     
     pub fn with_tables(mut self, join_tables: Vec<SearchBuilderImageFeature>) -> Self {
         let base_table = self.base_table;
+        // every base, including the similarity sub-selects, carries the integer image_id of the image it lists
         for table in join_tables {
             self = self.with_field_meta_columns(table.columns);
-            self = self.with_join(&format!("LEFT JOIN {} ON {}.image_path = {}.image_path", 
+            self = self.with_join(&format!("LEFT JOIN {} ON {}.image_id = {}.image_id", 
                 table.table_name, base_table, table.table_name));
         }
         self
@@ -143,8 +158,42 @@ impl SearchBuilder {
             SearchBuilderImageFeature::from_meta("image_aspect_ratio", &ImageAspectRatio::get_meta()[1..]),
             SearchBuilderImageFeature::from_meta("image_iptc", &ImageIptc::get_meta()[1..]),
             SearchBuilderImageFeature::from_meta("image_xmp", &ImageXmp::get_meta()[1..]),
+            SearchBuilderImageFeature::from_meta("image_color", &ImageColor::get_meta()[1..]),
+            SearchBuilderImageFeature::from_meta("image_sharpness", &ImageSharpness::get_meta()[1..]),
+            SearchBuilderImageFeature::from_meta("image_exposure", &ImageExposure::get_meta()[1..]),
+            SearchBuilderImageFeature::from_meta("image_ocr_summary", &ImageOcrSummary::get_meta()[1..]),
+            SearchBuilderImageFeature::from_meta("image_content_hash", &ImageContentHash::get_meta()[1..]),
+            SearchBuilderImageFeature::from_meta("image_raw", &ImageRaw::get_meta()[1..]),
+            SearchBuilderImageFeature::from_meta("image_frames", &ImageFrames::get_meta()[1..]),
+            SearchBuilderImageFeature::from_meta("image_geocode", &ImageGeocode::get_meta()[1..]),
+            SearchBuilderImageFeature::from_meta("image_event_member", &ImageEventMember::get_meta()[1..]),
+            SearchBuilderImageFeature::from_meta("image_burst_frame", &ImageBurstFrame::get_meta()[1..]),
         ];
-        self.with_field_meta_columns(base_table_meta).with_tables(default_tables)
+        // result tables link to /thumb when there is a thumbnail, so only whether one exists is selected
+        let has_thumbnail = format!("EXISTS (SELECT 1 FROM image_thumbnail WHERE image_thumbnail.image_id = {}.image_id) AS {}", self.base_table, HAS_THUMBNAIL_COLUMN);
+        self.with_field_meta_columns(base_table_meta).with_tables(default_tables).with_select_columns(vec![has_thumbnail])
+    }
+
+    // Radius and bounding box become criteria on the EXIF coordinates; a near point also adds a squared distance
+    // column for ordering. Must come after with_criteria, which replaces the criteria
+    pub fn with_geo_filter(mut self, filter: &GeoSearchFilter) -> Self {
+        if let Some(near) = &filter.near {
+            self.select_columns.push(format!("{} AS {}", geo_distance_squared_sql(near), GEO_DISTANCE_ORDER_COLUMN));
+        }
+        if let Some((center, radius_km)) = filter.get_radius() {
+            let radius_degrees = radius_km / KM_PER_DEGREE_LATITUDE;
+            let mut params = HashMap::new();
+            // the latitude band lets SQLite use the coordinate index before working out distances
+            params.insert(format!("{} >= ?", GPS_LATITUDE_COLUMN), (center.latitude - radius_degrees).to_string());
+            params.insert(format!("{} <= ?", GPS_LATITUDE_COLUMN), (center.latitude + radius_degrees).to_string());
+            // the expression has no column affinity, so the text parameter is cast to compare as a number
+            params.insert(format!("{} <= CAST(? AS REAL)", geo_distance_squared_sql(&center)), (radius_degrees * radius_degrees).to_string());
+            self.criteria.push(("AND".to_string(), params));
+        }
+        if let Some(bounding_box) = &filter.bounding_box {
+            self.criteria.extend(geo_bounding_box_criteria(bounding_box));
+        }
+        self
     }
 
     pub fn with_select_columns(mut self, columns: Vec<String>) -> Self {
@@ -332,21 +381,52 @@ impl SearchBuilder {
     }
 }
 
+// Equirectangular approximation in squared degrees of latitude; SQLite has no trig functions by default, so the
+// centre's cosine is worked out here and the longitude difference wraps around the antimeridian
+fn geo_distance_squared_sql(center: &GeoPoint) -> String {
+    let d_lat = format!("({} - {:?})", GPS_LATITUDE_COLUMN, center.latitude);
+    let d_lon = format!("ABS({} - {:?})", GPS_LONGITUDE_COLUMN, center.longitude);
+    let d_x = format!("(MIN({}, 360.0 - {}) * {:?})", d_lon, d_lon, center.latitude.to_radians().cos());
+    format!("({} * {} + {} * {})", d_lat, d_lat, d_x, d_x)
+}
+
+fn geo_bounding_box_criteria(bounding_box: &GeoBoundingBox) -> Vec<(String, HashMap<String, String>)> {
+    let mut latitude_params = HashMap::new();
+    latitude_params.insert(format!("{} >= ?", GPS_LATITUDE_COLUMN), bounding_box.min_latitude.to_string());
+    latitude_params.insert(format!("{} <= ?", GPS_LATITUDE_COLUMN), bounding_box.max_latitude.to_string());
+
+    let mut longitude_params = HashMap::new();
+    longitude_params.insert(format!("{} >= ?", GPS_LONGITUDE_COLUMN), bounding_box.min_longitude.to_string());
+    longitude_params.insert(format!("{} <= ?", GPS_LONGITUDE_COLUMN), bounding_box.max_longitude.to_string());
+    let longitude_op = if bounding_box.crosses_antimeridian() { "OR" } else { "AND" };
+
+    vec![
+        ("AND".to_string(), latitude_params),
+        (longitude_op.to_string(), longitude_params),
+    ]
+}
+
 // get count without fetching full image data
 pub async fn count_sql_db_images_by_criteria(
     pool: &SqlitePool,
     criteria: &Vec<(String, HashMap<String, String>)>,
+    geo_filter: Option<&GeoSearchFilter>,
 ) -> Result<i64, Box<dyn Error + Send>> {
-    let builder = SearchBuilder::new()
+    let mut builder = SearchBuilder::new()
         .with_default_tables()
         .with_criteria(criteria.clone());
 
+    if let Some(geo_filter) = geo_filter {
+        builder = builder.with_geo_filter(geo_filter);
+    }
+
     builder.execute_count(pool).await
 }
 
 pub async fn execute_search_images_query_with_criteria(
     pool: WebServerActionDataContext,
     criteria: &Vec<(String, HashMap<String, String>)>,
+    geo_filter: Option<&GeoSearchFilter>,
     order_by: Option<&str>,
     limit: Option<i32>,
     offset: Option<i32>,
@@ -355,6 +435,10 @@ pub async fn execute_search_images_query_with_criteria(
         .with_default_tables()
         .with_criteria(criteria.clone());
 
+    if let Some(geo_filter) = geo_filter {
+        builder = builder.with_geo_filter(geo_filter);
+    }
+
     if let Some(order) = order_by {
         builder = builder.with_order_by(order);
     }
@@ -374,17 +458,11 @@ pub async fn execute_search_images_query_with_criteria(
 // this needs to be fixed to allow adding additional "features" / tables / other things
 // for instance, similarity
 async fn transform_sql_row_to_image(
-    pool: WebServerActionDataContext, 
+    _pool: WebServerActionDataContext, 
     row: &sqlx::sqlite::SqliteRow,
     tables_selected: Vec<String>
 ) -> Result<Image, Box<dyn Error + Send>> {
-    let mut img = Image::new(&row, tables_selected);
-    
-    if let Some(thumb) = pool.get_thumbnail_at_most_width_length(&img.path, 64).await? {
-        img.assign_thumbnail(thumb);
-    }
-    
-    Ok(img)
+    Ok(Image::new(row, tables_selected))
 }
 
 async fn transform_results_to_output(
@@ -406,10 +484,11 @@ pub async fn search_images_by_criteria(
     order_by: Option<&str>,
 ) -> Result<SearchImagesPageModel, Box<dyn Error + Send>> {
     let criteria = params.into_sql_query_params();
+    let geo_filter = params.get_geo_filter();
     
-    let total_count = count_sql_db_images_by_criteria(&pool.pool, &criteria).await? as usize;
+    let total_count = count_sql_db_images_by_criteria(&pool.pool, &criteria, Some(&geo_filter)).await? as usize;
     
-    let items = execute_search_images_query_with_criteria(pool, &criteria, order_by, params.get_limit(), params.get_offset())
+    let items = execute_search_images_query_with_criteria(pool, &criteria, Some(&geo_filter), order_by, params.get_limit(), params.get_offset())
         .await?;
     
     Ok(SearchImagesPageModel { total_count, items })
@@ -424,7 +503,7 @@ pub async fn find_image_by_path(pool: WebServerActionDataContext, path: &str) ->
     let mut params = HashMap::new();
     params.insert("image_paths.image_path = ?".to_string(), path.to_string());
     let criteria = vec![ ("".to_string(), params) ];
-    let results = execute_search_images_query_with_criteria(pool, &criteria, None, Some(1), None).await?;
+    let results = execute_search_images_query_with_criteria(pool, &criteria, None, None, Some(1), None).await?;
     match results.first() {
         Some(Ok(item)) => {
             Ok(Some(item.clone()))
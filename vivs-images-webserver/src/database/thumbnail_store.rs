@@ -0,0 +1,142 @@
+use std::error::Error;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use sha2::Digest;
+use sha2::Sha256;
+use sqlx::pool::PoolConnection;
+use sqlx::{Row, Sqlite, SqlitePool};
+use tempfile::NamedTempFile;
+
+use crate::models::config::thumbnails::ThumbnailStorage;
+use crate::models::image_thumbnail::ThumbnailFormat;
+
+
+// Thumbnail data kept outside image_thumbnail.thumbnail_data is content-addressed: it is stored under the
+// SHA-256 of the encoded bytes, so identical thumbnails are written once. The image_thumbnail row keeps an
+// empty blob and a storage key "<storage name>:<sha256>.<extension>".
+const THUMBNAIL_STORE_SCHEMA: &str = "thumbnail_store";
+
+const SQL_CREATE_THUMBNAIL_STORE: &str = r#"
+CREATE TABLE IF NOT EXISTS thumbnail_store.thumbnail_blob (
+    thumbnail_name TEXT PRIMARY KEY,
+    thumbnail_data BLOB NOT NULL
+);
+"#;
+
+fn to_send_error<E: Error + Send + 'static>(e: E) -> Box<dyn Error + Send> {
+    Box::new(e) as Box<dyn Error + Send>
+}
+
+pub fn thumbnail_content_hash(data: &[u8]) -> String {
+    Sha256::digest(data).iter().map(|b| format!("{:02x}", b)).collect::<String>()
+}
+
+// None when the storage keeps data inline
+pub fn thumbnail_storage_key(storage: &ThumbnailStorage, format: &ThumbnailFormat, data: &[u8]) -> Option<String> {
+    if *storage == ThumbnailStorage::Database {
+        return None;
+    }
+    Some(format!("{}:{}.{}", storage.get_name(), thumbnail_content_hash(data), format.extension()))
+}
+
+fn parse_thumbnail_storage_key(storage_key: &str) -> Result<(ThumbnailStorage, &str), Box<dyn Error + Send>> {
+    let invalid = || Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("invalid thumbnail storage key {}", storage_key))) as Box<dyn Error + Send>;
+    let (storage_name, thumbnail_name) = storage_key.split_once(':').ok_or_else(invalid)?;
+    // the name becomes a file name, so it must not be able to leave the store directory
+    if thumbnail_name.is_empty() || !thumbnail_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '.') || thumbnail_name.starts_with('.') {
+        return Err(invalid());
+    }
+    let storage = ThumbnailStorage::from_name(storage_name).filter(|s| *s != ThumbnailStorage::Database).ok_or_else(invalid)?;
+    Ok((storage, thumbnail_name))
+}
+
+// Spread over 256 subdirectories by the first two hex digits so no single directory gets huge
+pub fn thumbnail_store_file_path(dir: &str, thumbnail_name: &str) -> PathBuf {
+    Path::new(dir).join(&thumbnail_name[..2.min(thumbnail_name.len())]).join(thumbnail_name)
+}
+
+// A pool connection with the store database attached. Attaching is per connection, so it is done the first
+// time each connection is used for the store and "already in use" means it was.
+async fn acquire_thumbnail_store_connection(db_file: &str, pool: &SqlitePool) -> Result<PoolConnection<Sqlite>, Box<dyn Error + Send>> {
+    let mut conn = pool.acquire().await.map_err(to_send_error)?;
+    let sql = format!("ATTACH DATABASE ? AS {};", THUMBNAIL_STORE_SCHEMA);
+    match sqlx::query(&sql).bind(db_file).execute(&mut conn).await {
+        Ok(_) => {},
+        Err(e) if e.to_string().contains("already in use") => {},
+        Err(e) => return Err(to_send_error(e)),
+    }
+    sqlx::query(SQL_CREATE_THUMBNAIL_STORE).execute(&mut conn).await.map_err(to_send_error)?;
+    Ok(conn)
+}
+
+pub async fn write_thumbnail_data(storage_key: &str, data: &[u8], pool: &SqlitePool) -> Result<(), Box<dyn Error + Send>> {
+    let (storage, thumbnail_name) = parse_thumbnail_storage_key(storage_key)?;
+    match storage {
+        ThumbnailStorage::Database => Ok(()),
+        ThumbnailStorage::SqliteFile(db_file) => {
+            let mut conn = acquire_thumbnail_store_connection(&db_file, pool).await?;
+            sqlx::query("INSERT OR IGNORE INTO thumbnail_store.thumbnail_blob (thumbnail_name, thumbnail_data) VALUES (?, ?);")
+                .bind(thumbnail_name)
+                .bind(data)
+                .execute(&mut conn).await
+                .map_err(to_send_error)?;
+            Ok(())
+        },
+        ThumbnailStorage::Directory(dir) => {
+            let path = thumbnail_store_file_path(&dir, thumbnail_name);
+            if path.exists() {
+                return Ok(());
+            }
+            let parent = path.parent().unwrap_or_else(|| Path::new(&dir));
+            std::fs::create_dir_all(parent).map_err(to_send_error)?;
+            // written to a temporary file of its own and renamed, so a reader never sees a partial file and
+            // writers of the same thumbnail do not write over each other's data
+            let mut partial = NamedTempFile::new_in(parent).map_err(to_send_error)?;
+            partial.write_all(data).map_err(to_send_error)?;
+            partial.persist(&path).map_err(|e| to_send_error(e.error))?;
+            Ok(())
+        },
+    }
+}
+
+pub async fn read_thumbnail_data(storage_key: &str, pool: &SqlitePool) -> Result<Vec<u8>, Box<dyn Error + Send>> {
+    let (storage, thumbnail_name) = parse_thumbnail_storage_key(storage_key)?;
+    match storage {
+        ThumbnailStorage::Database => Ok(vec![]),
+        ThumbnailStorage::SqliteFile(db_file) => {
+            let mut conn = acquire_thumbnail_store_connection(&db_file, pool).await?;
+            let row = sqlx::query("SELECT thumbnail_data FROM thumbnail_store.thumbnail_blob WHERE thumbnail_name = ?;")
+                .bind(thumbnail_name)
+                .fetch_optional(&mut conn).await
+                .map_err(to_send_error)?
+                .ok_or_else(|| Box::new(std::io::Error::new(std::io::ErrorKind::NotFound, format!("thumbnail {} not in {}", thumbnail_name, db_file))) as Box<dyn Error + Send>)?;
+            row.try_get::<Vec<u8>, _>("thumbnail_data").map_err(to_send_error)
+        },
+        ThumbnailStorage::Directory(dir) => {
+            std::fs::read(thumbnail_store_file_path(&dir, thumbnail_name)).map_err(to_send_error)
+        },
+    }
+}
+
+// Removes data no image_thumbnail row refers to any more; data that is already gone is not an error
+pub async fn delete_thumbnail_data(storage_key: &str, pool: &SqlitePool) -> Result<(), Box<dyn Error + Send>> {
+    let (storage, thumbnail_name) = parse_thumbnail_storage_key(storage_key)?;
+    match storage {
+        ThumbnailStorage::Database => Ok(()),
+        ThumbnailStorage::SqliteFile(db_file) => {
+            let mut conn = acquire_thumbnail_store_connection(&db_file, pool).await?;
+            sqlx::query("DELETE FROM thumbnail_store.thumbnail_blob WHERE thumbnail_name = ?;")
+                .bind(thumbnail_name)
+                .execute(&mut conn).await
+                .map_err(to_send_error)?;
+            Ok(())
+        },
+        ThumbnailStorage::Directory(dir) => {
+            match std::fs::remove_file(thumbnail_store_file_path(&dir, thumbnail_name)) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(to_send_error(e)),
+                _ => Ok(()),
+            }
+        },
+    }
+}
\ No newline at end of file
@@ -7,4 +7,19 @@ pub mod update_image_ocr_text;
 pub mod update_image_tags;
 pub mod update_image_iptc;
 pub mod update_image_image_paths;
-pub mod update_image_xmp;
\ No newline at end of file
+pub mod update_image_xmp;
+pub mod update_image_color;
+pub mod update_image_sharpness;
+pub mod update_image_exposure;
+pub mod update_image_ocr_summary;
+pub mod update_image_ocr_word;
+pub mod update_image_content_hash;
+pub mod update_image_path_rekey;
+pub mod update_image_similarity_neighbors;
+pub mod update_image_raw;
+pub mod update_image_frames;
+pub mod update_image_orientation;
+pub mod update_image_geocode;
+pub mod update_image_event;
+pub mod update_image_burst;
+pub mod update_image_ocr_result;
\ No newline at end of file
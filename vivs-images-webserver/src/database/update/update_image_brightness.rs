@@ -3,15 +3,16 @@ use std::error::Error;
 use sqlx::{Pool, Sqlite};
 
 use crate::database::common::execute_update_or_insert;
+use crate::models::image_brightness::ImageToBrightnessAlgo;
 
 
-pub async fn execute_update_image_brightness_sql(image_path: &String, brightness: f32, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+pub async fn execute_update_image_brightness_sql(image_path: &String, brightness: f32, brightness_algo: &ImageToBrightnessAlgo, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
     let query = r#"
         UPDATE image_brightness
-        SET brightness = ?, updated_at = CURRENT_TIMESTAMP
+        SET brightness = ?, brightness_algo = ?, updated_at = CURRENT_TIMESTAMP
         WHERE image_path = ?;
     "#;
-    let r = execute_update_or_insert(pool, query, vec![ brightness.to_string().as_str(), image_path ]).await?;
+    let r = execute_update_or_insert(pool, query, vec![ brightness.to_string().as_str(), brightness_algo.to_string().as_str(), image_path ]).await?;
     let r = r.rows_affected();
     if r == 1 {
         Ok(())
@@ -20,9 +21,10 @@ pub async fn execute_update_image_brightness_sql(image_path: &String, brightness
     }
 }
 
-pub async fn execute_insert_image_brightness_sql(image_path: &String, brightness: f32, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
-    let query = r#"INSERT INTO image_brightness (image_path, brightness) VALUES (?, ?);"#;
-    let r = execute_update_or_insert(pool, query, vec![ image_path, brightness.to_string().as_str() ]).await?;
+// Replaces any row from a different algorithm so there is only ever one brightness per image
+pub async fn execute_insert_image_brightness_sql(image_path: &String, brightness: f32, brightness_algo: &ImageToBrightnessAlgo, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    let query = r#"INSERT OR REPLACE INTO image_brightness (image_path, brightness, brightness_algo) VALUES (?, ?, ?);"#;
+    let r = execute_update_or_insert(pool, query, vec![ image_path, brightness.to_string().as_str(), brightness_algo.to_string().as_str() ]).await?;
     let r = r.rows_affected();
     if r == 1 {
         Ok(())
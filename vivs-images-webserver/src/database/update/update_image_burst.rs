@@ -0,0 +1,110 @@
+use std::collections::HashSet;
+use std::error::Error;
+
+use sqlx::{Pool, Sqlite};
+
+use crate::converters::extract_image_taken_at::format_taken_at;
+use crate::models::config::bursts::BURST_DELETE_TAG_NAME;
+use crate::models::image_burst::ImageBurstScan;
+
+
+fn to_send_error(e: sqlx::Error) -> Box<dyn Error + Send> {
+    Box::new(e) as Box<dyn Error + Send>
+}
+
+// images whose burst was already reviewed; a re-detected burst made up only of these stays reviewed
+const SQL_SELECT_REVIEWED_BURST_FRAME: &str = r#"
+SELECT 1 FROM image_burst_frame INNER JOIN image_burst ON image_burst.burst_id = image_burst_frame.burst_id
+WHERE image_burst_frame.image_path = ? AND image_burst.burst_reviewed != 0;"#;
+
+const SQL_DELETE_BURST_OF_IMAGE: &str = r#"
+DELETE FROM image_burst WHERE burst_id IN (SELECT burst_id FROM image_burst_frame WHERE image_path = ?);"#;
+
+const SQL_DELETE_BURST_FRAME: &str = r#"
+DELETE FROM image_burst_frame WHERE image_path = ?;"#;
+
+// frames of a deleted burst that lie outside the scanned run lose their row too, so they are checked again
+const SQL_DELETE_ORPHANED_BURST_FRAMES: &str = r#"
+DELETE FROM image_burst_frame WHERE burst_id IS NOT NULL AND burst_id NOT IN (SELECT burst_id FROM image_burst);"#;
+
+const SQL_INSERT_BURST: &str = r#"
+INSERT INTO image_burst (burst_start, burst_end, burst_frame_count, burst_keeper_image_path, burst_reviewed)
+VALUES (?, ?, ?, ?, ?);"#;
+
+const SQL_INSERT_BURST_FRAME: &str = r#"
+INSERT INTO image_burst_frame (image_path, burst_id, burst_frame_score, burst_frame_similarity, burst_is_keeper)
+VALUES (?, ?, ?, ?, ?);"#;
+
+const SQL_INSERT_DELETE_TAG: &str = r#"
+INSERT OR IGNORE INTO tags (tag_name, tag_label, tag_description) VALUES (?, 'To delete', 'Frames picked for deletion on the burst review page');"#;
+
+const SQL_INSERT_IMAGE_DELETE_TAG: &str = r#"
+INSERT OR IGNORE INTO image_tags (image_path, tag_name) VALUES (?, ?);"#;
+
+const SQL_UPDATE_BURST_REVIEWED: &str = r#"
+UPDATE image_burst SET burst_reviewed = 1 WHERE burst_id = ?;"#;
+
+// Replaces whatever was stored for the scanned images with the bursts found among them; the images that are not in
+// any burst get a row without one, so they count as checked
+pub async fn execute_replace_image_bursts_sql(scan: &ImageBurstScan, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    let mut tx = pool.begin().await.map_err(to_send_error)?;
+
+    let mut reviewed = HashSet::new();
+    for image_path in &scan.image_paths {
+        if sqlx::query(SQL_SELECT_REVIEWED_BURST_FRAME).bind(image_path).fetch_optional(&mut tx).await.map_err(to_send_error)?.is_some() {
+            reviewed.insert(image_path.as_str());
+        }
+    }
+    for image_path in &scan.image_paths {
+        sqlx::query(SQL_DELETE_BURST_OF_IMAGE).bind(image_path).execute(&mut tx).await.map_err(to_send_error)?;
+        sqlx::query(SQL_DELETE_BURST_FRAME).bind(image_path).execute(&mut tx).await.map_err(to_send_error)?;
+    }
+    sqlx::query(SQL_DELETE_ORPHANED_BURST_FRAMES).execute(&mut tx).await.map_err(to_send_error)?;
+
+    let mut in_burst = HashSet::new();
+    for burst in &scan.bursts {
+        let burst_reviewed = burst.frames.iter().all(|f| reviewed.contains(f.image_path.as_str()));
+        let burst_id = sqlx::query(SQL_INSERT_BURST)
+            .bind(burst.get_start().map(|t| format_taken_at(&t)).unwrap_or_default())
+            .bind(burst.get_end().map(|t| format_taken_at(&t)).unwrap_or_default())
+            .bind(burst.frames.len() as i64).bind(&burst.keeper_image_path).bind(burst_reviewed as i64)
+            .execute(&mut tx).await.map_err(to_send_error)?
+            .last_insert_rowid();
+        for frame in &burst.frames {
+            sqlx::query(SQL_INSERT_BURST_FRAME)
+                .bind(&frame.image_path).bind(burst_id).bind(frame.score)
+                .bind(frame.similarity.map(|s| s as f64)).bind((frame.image_path == burst.keeper_image_path) as i64)
+                .execute(&mut tx).await.map_err(to_send_error)?;
+            in_burst.insert(frame.image_path.as_str());
+        }
+    }
+    for image_path in scan.image_paths.iter().filter(|p| !in_burst.contains(p.as_str())) {
+        sqlx::query(SQL_INSERT_BURST_FRAME)
+            .bind(image_path).bind(None::<i64>).bind(None::<f64>).bind(None::<f64>).bind(0i64)
+            .execute(&mut tx).await.map_err(to_send_error)?;
+    }
+
+    tx.commit().await.map_err(to_send_error)?;
+    Ok(())
+}
+
+// Tags the images for deletion, skipping those already tagged, and marks the bursts they were picked from as
+// reviewed, including those where every frame was kept; returns how many images were newly tagged
+pub async fn execute_tag_images_for_deletion_sql(image_paths: &[String], burst_ids: &[i64], pool: &Pool<Sqlite>) -> Result<u64, Box<dyn Error + Send>> {
+    let mut tx = pool.begin().await.map_err(to_send_error)?;
+
+    sqlx::query(SQL_INSERT_DELETE_TAG).bind(BURST_DELETE_TAG_NAME).execute(&mut tx).await.map_err(to_send_error)?;
+    let mut tagged = 0;
+    for image_path in image_paths {
+        tagged += sqlx::query(SQL_INSERT_IMAGE_DELETE_TAG)
+            .bind(image_path).bind(BURST_DELETE_TAG_NAME)
+            .execute(&mut tx).await.map_err(to_send_error)?
+            .rows_affected();
+    }
+    for burst_id in burst_ids {
+        sqlx::query(SQL_UPDATE_BURST_REVIEWED).bind(burst_id).execute(&mut tx).await.map_err(to_send_error)?;
+    }
+
+    tx.commit().await.map_err(to_send_error)?;
+    Ok(tagged)
+}
\ No newline at end of file
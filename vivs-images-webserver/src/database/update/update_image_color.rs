@@ -0,0 +1,34 @@
+use std::error::Error;
+
+use sqlx::{Pool, Sqlite};
+
+use crate::models::image_color::ImageColor;
+use crate::database::common::execute_update_or_insert;
+
+
+pub async fn execute_insert_image_color_sql(item: &ImageColor, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    let column_names = ImageColor::get_meta().iter().map(|c| c.name.to_string()).collect::<Vec<String>>();
+    let column_names_sql = column_names.join(", ");
+    let column_var_placeholders_sql = column_names.iter().map(|_| "?").collect::<Vec<&str>>().join(", ");
+    let query = format!(r#"INSERT INTO image_color ({}) VALUES ({});"#, column_names_sql, column_var_placeholders_sql);
+    let params: Vec<String> = column_names.iter().map(|c| item.get_field(c).unwrap()).collect();
+    let params: Vec<&str> = params.iter().map(|c| c.as_str()).collect();
+    let r = execute_update_or_insert(pool, &query, params).await?;
+    let r = r.rows_affected();
+    if r == 1 {
+        Ok(())
+    } else {
+        Err(Box::new(std::io::Error::other(format!("SQL insert returned {} rows", r))))
+    }
+}
+
+pub async fn execute_delete_image_color_sql(image_path: &str, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    let query = r#"DELETE FROM image_color WHERE image_path = ?;"#;
+    let r = execute_update_or_insert(pool, query, vec![ image_path ]).await?;
+    let r = r.rows_affected();
+    if r == 1 {
+        Ok(())
+    } else {
+        Err(Box::new(std::io::Error::other(format!("SQL delete returned {} rows", r))))
+    }
+}
\ No newline at end of file
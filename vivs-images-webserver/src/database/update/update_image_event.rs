@@ -0,0 +1,78 @@
+use std::collections::HashSet;
+use std::error::Error;
+
+use chrono::NaiveDateTime;
+use sqlx::{Pool, Row, Sqlite};
+
+use crate::calc::event_clustering::match_existing_events;
+use crate::converters::extract_image_taken_at::{format_taken_at, parse_taken_at};
+use crate::models::image_event::ImageEventCluster;
+
+
+fn to_send_error(e: sqlx::Error) -> Box<dyn Error + Send> {
+    Box::new(e) as Box<dyn Error + Send>
+}
+
+const SQL_INSERT_EVENT: &str = r#"
+INSERT INTO image_event (event_start, event_end, event_image_count, event_cover_image_path, event_latitude, event_longitude)
+VALUES (?, ?, ?, ?, ?, ?);"#;
+
+const SQL_UPDATE_EVENT: &str = r#"
+UPDATE image_event SET event_start = ?, event_end = ?, event_image_count = ?, event_cover_image_path = ?, event_latitude = ?, event_longitude = ?
+WHERE event_id = ?;"#;
+
+const SQL_INSERT_EVENT_MEMBER: &str = r#"
+INSERT OR REPLACE INTO image_event_member (image_path, event_id) VALUES (?, ?);"#;
+
+// Clustering looks at every image at once, so a new run replaces all stored events rather than adding to them.
+// A new event that overlaps a stored one in time takes over its row and id, so links to it keep working
+pub async fn execute_replace_image_events_sql(events: &[ImageEventCluster], pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    let mut tx = pool.begin().await.map_err(to_send_error)?;
+
+    let existing: Vec<(i64, NaiveDateTime, NaiveDateTime)> = sqlx::query("SELECT event_id, event_start, event_end FROM image_event;")
+        .fetch_all(&mut tx).await.map_err(to_send_error)?
+        .iter()
+        .filter_map(|row| {
+            let start = parse_taken_at(&row.try_get::<String, _>("event_start").ok()?)?.0;
+            let end = parse_taken_at(&row.try_get::<String, _>("event_end").ok()?)?.0;
+            Some((row.try_get("event_id").ok()?, start, end))
+        })
+        .collect();
+    let matched = match_existing_events(&existing, events);
+
+    sqlx::query("DELETE FROM image_event_member;").execute(&mut tx).await.map_err(to_send_error)?;
+    let kept: HashSet<i64> = matched.iter().flatten().copied().collect();
+    let removed_event_ids: Vec<i64> = sqlx::query("SELECT event_id FROM image_event;")
+        .fetch_all(&mut tx).await.map_err(to_send_error)?
+        .iter()
+        .filter_map(|row| row.try_get::<i64, _>("event_id").ok())
+        .filter(|event_id| !kept.contains(event_id))
+        .collect();
+    for event_id in removed_event_ids {
+        sqlx::query("DELETE FROM image_event WHERE event_id = ?;").bind(event_id).execute(&mut tx).await.map_err(to_send_error)?;
+    }
+
+    for (event, existing_event_id) in events.iter().zip(matched) {
+        let sql = match existing_event_id {
+            Some(_) => SQL_UPDATE_EVENT,
+            None => SQL_INSERT_EVENT,
+        };
+        let mut query = sqlx::query(sql)
+            .bind(format_taken_at(&event.start)).bind(format_taken_at(&event.end))
+            .bind(event.image_paths.len() as i64).bind(&event.cover_image_path)
+            .bind(event.location.map(|l| l.latitude)).bind(event.location.map(|l| l.longitude));
+        if let Some(event_id) = existing_event_id {
+            query = query.bind(event_id);
+        }
+        let result = query.execute(&mut tx).await.map_err(to_send_error)?;
+        let event_id = existing_event_id.unwrap_or_else(|| result.last_insert_rowid());
+        for image_path in &event.image_paths {
+            sqlx::query(SQL_INSERT_EVENT_MEMBER)
+                .bind(image_path).bind(event_id)
+                .execute(&mut tx).await.map_err(to_send_error)?;
+        }
+    }
+
+    tx.commit().await.map_err(to_send_error)?;
+    Ok(())
+}
\ No newline at end of file
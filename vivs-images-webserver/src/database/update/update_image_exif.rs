@@ -63,4 +63,9 @@ pub async fn execute_delete_image_exif_sql(image_path: &String, pool: &Pool<Sqli
     } else {
         Err(Box::new(std::io::Error::other(format!("SQL delete returned {} rows", r))))
     }
+}
+
+pub async fn execute_delete_image_exif_pending_sql(image_path: &str, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    execute_update_or_insert(pool, "DELETE FROM image_exif_pending WHERE image_path = ?;", vec![ image_path ]).await?;
+    Ok(())
 }
\ No newline at end of file
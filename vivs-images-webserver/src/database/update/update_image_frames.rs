@@ -0,0 +1,49 @@
+use std::error::Error;
+
+use sqlx::{Pool, Sqlite};
+
+use crate::models::image_frames::ImageFramesExtraction;
+
+
+fn to_send_error(e: sqlx::Error) -> Box<dyn Error + Send> {
+    Box::new(e) as Box<dyn Error + Send>
+}
+
+// The frame counts and the frame thumbnails go in together, so a file never shows up half extracted
+pub async fn execute_insert_image_frames_sql(item: &ImageFramesExtraction, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    let mut tx = pool.begin().await.map_err(to_send_error)?;
+    sqlx::query("INSERT INTO image_frames (image_path, frame_count, is_animated, animation_duration_ms) VALUES (?, ?, ?, ?);")
+        .bind(&item.frames.image_path)
+        .bind(item.frames.frame_count)
+        .bind(item.frames.is_animated)
+        .bind(item.frames.animation_duration_ms)
+        .execute(&mut tx).await.map_err(to_send_error)?;
+
+    for frame in &item.thumbnails {
+        let thumbnail_format: u8 = (&frame.thumbnail.thumbnail_format).try_into().map_err(|e| Box::new(e) as Box<dyn Error + Send>)?;
+        sqlx::query("INSERT OR REPLACE INTO image_frame_thumbnail (image_path, frame_index, frame_delay_ms, width_and_length, thumbnail_format, thumbnail_data) VALUES (?, ?, ?, ?, ?, ?);")
+            .bind(&item.frames.image_path)
+            .bind(frame.frame_index)
+            .bind(frame.frame_delay_ms)
+            .bind(frame.thumbnail.width_and_length)
+            .bind(thumbnail_format)
+            .bind(&frame.thumbnail.thumbnail_data)
+            .execute(&mut tx).await.map_err(to_send_error)?;
+    }
+
+    tx.commit().await.map_err(to_send_error)
+}
+
+pub async fn execute_delete_image_frames_sql(image_path: &str, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    let mut tx = pool.begin().await.map_err(to_send_error)?;
+    sqlx::query("DELETE FROM image_frame_thumbnail WHERE image_path = ?;")
+        .bind(image_path)
+        .execute(&mut tx).await.map_err(to_send_error)?;
+    let r = sqlx::query("DELETE FROM image_frames WHERE image_path = ?;")
+        .bind(image_path)
+        .execute(&mut tx).await.map_err(to_send_error)?;
+    if r.rows_affected() != 1 {
+        return Err(Box::new(std::io::Error::other(format!("SQL delete returned {} rows", r.rows_affected()))));
+    }
+    tx.commit().await.map_err(to_send_error)
+}
\ No newline at end of file
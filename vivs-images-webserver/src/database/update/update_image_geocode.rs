@@ -0,0 +1,38 @@
+use std::error::Error;
+
+use sqlx::{Pool, Sqlite};
+
+use crate::models::image_geocode::ImageGeocode;
+use crate::database::common::execute_update_or_insert;
+
+
+pub async fn execute_insert_image_geocode_sql(item: &ImageGeocode, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    // coordinates far from every known place have no names, which are stored as NULL rather than empty strings
+    let query = r#"INSERT INTO image_geocode (image_path, place_country_code, place_country, place_region, place_city, place_city_distance_km, place_dataset)
+        VALUES (?, NULLIF(?, ''), NULLIF(?, ''), NULLIF(?, ''), NULLIF(?, ''), NULLIF(?, ''), ?);"#;
+    let place_country_code = item.place_country_code.clone().unwrap_or_default();
+    let place_country = item.place_country.clone().unwrap_or_default();
+    let place_region = item.place_region.clone().unwrap_or_default();
+    let place_city = item.place_city.clone().unwrap_or_default();
+    let place_city_distance_km = item.place_city_distance_km.map(|d| d.to_string()).unwrap_or_default();
+    let params = vec![ item.image_path.as_str(), place_country_code.as_str(), place_country.as_str(), place_region.as_str(),
+        place_city.as_str(), place_city_distance_km.as_str(), item.place_dataset.as_str() ];
+    let r = execute_update_or_insert(pool, query, params).await?;
+    let r = r.rows_affected();
+    if r == 1 {
+        Ok(())
+    } else {
+        Err(Box::new(std::io::Error::other(format!("SQL insert returned {} rows", r))))
+    }
+}
+
+pub async fn execute_delete_image_geocode_sql(image_path: &str, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    let query = r#"DELETE FROM image_geocode WHERE image_path = ?;"#;
+    let r = execute_update_or_insert(pool, query, vec![ image_path ]).await?;
+    let r = r.rows_affected();
+    if r == 1 {
+        Ok(())
+    } else {
+        Err(Box::new(std::io::Error::other(format!("SQL delete returned {} rows", r))))
+    }
+}
\ No newline at end of file
@@ -17,4 +17,16 @@ pub async fn execute_insert_image_path_sql(image_path: &String, pool: &SqlitePoo
     } else {
         Err(Box::new(std::io::Error::other(format!("SQL update returned {} rows", r))))
     }
+}
+
+// Every feature table references image_paths with ON DELETE CASCADE, so this removes the image everywhere
+pub async fn execute_delete_image_path_sql(image_path: &str, pool: &SqlitePool) -> Result<(), Box<dyn Error + Send>> {
+    let query = r#"DELETE FROM image_paths WHERE image_path = ?;"#;
+    let r = execute_update_or_insert(pool, query, vec![ image_path ]).await?;
+    let r = r.rows_affected();
+    if r == 1 {
+        Ok(())
+    } else {
+        Err(Box::new(std::io::Error::other(format!("SQL delete returned {} rows", r))))
+    }
 }
\ No newline at end of file
@@ -0,0 +1,52 @@
+use std::error::Error;
+
+use sqlx::{Pool, Sqlite};
+
+use crate::converters::extract_image_ocr_text::ImageOcrResult;
+use crate::models::image_ocr_summary::ImageOcrSummary;
+
+
+fn to_send_error(e: sqlx::Error) -> Box<dyn Error + Send> {
+    Box::new(e) as Box<dyn Error + Send>
+}
+
+const SQL_REPLACE_OCR_TEXT: &str = r#"
+INSERT OR REPLACE INTO image_ocr_text (image_path, ocr_text) VALUES (?, ?);"#;
+
+const SQL_INSERT_OCR_WORD: &str = r#"
+INSERT INTO image_ocr_word (image_path, word_index, page_num, block_num, line_num, text, confidence, left, top, width, height)
+VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?);"#;
+
+// The text, the word boxes and the summary of one run go in together. The summary is what marks the image as
+// read, so a run that fails part way leaves the image to be read again rather than with half its words
+pub async fn execute_replace_image_ocr_result_sql(result: &ImageOcrResult, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    let image_path = &result.summary.image_path;
+    let mut tx = pool.begin().await.map_err(to_send_error)?;
+
+    sqlx::query(SQL_REPLACE_OCR_TEXT)
+        .bind(&result.text.image_path).bind(&result.text.ocr_text)
+        .execute(&mut tx).await.map_err(to_send_error)?;
+
+    sqlx::query("DELETE FROM image_ocr_word WHERE image_path = ?;")
+        .bind(image_path)
+        .execute(&mut tx).await.map_err(to_send_error)?;
+    for word in &result.words {
+        sqlx::query(SQL_INSERT_OCR_WORD)
+            .bind(&word.image_path).bind(word.word_index).bind(word.page_num).bind(word.block_num).bind(word.line_num)
+            .bind(&word.text).bind(word.confidence)
+            .bind(word.left).bind(word.top).bind(word.width).bind(word.height)
+            .execute(&mut tx).await.map_err(to_send_error)?;
+    }
+
+    let column_names = ImageOcrSummary::get_meta().iter().map(|c| c.name.to_string()).collect::<Vec<String>>();
+    let column_var_placeholders_sql = column_names.iter().map(|_| "?").collect::<Vec<&str>>().join(", ");
+    let sql = format!("INSERT OR REPLACE INTO image_ocr_summary ({}) VALUES ({});", column_names.join(", "), column_var_placeholders_sql);
+    let mut query = sqlx::query(&sql);
+    for column_name in &column_names {
+        query = query.bind(result.summary.get_field(column_name));
+    }
+    query.execute(&mut tx).await.map_err(to_send_error)?;
+
+    tx.commit().await.map_err(to_send_error)?;
+    Ok(())
+}
\ No newline at end of file
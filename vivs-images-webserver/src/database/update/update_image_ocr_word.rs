@@ -0,0 +1,12 @@
+use std::error::Error;
+
+use sqlx::{Pool, Sqlite};
+
+use crate::database::common::execute_update_or_insert;
+
+
+pub async fn execute_delete_image_ocr_words_sql(image_path: &str, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    let query = r#"DELETE FROM image_ocr_word WHERE image_path = ?;"#;
+    execute_update_or_insert(pool, query, vec![ image_path ]).await?;
+    Ok(())
+}
\ No newline at end of file
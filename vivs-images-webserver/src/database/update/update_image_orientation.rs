@@ -0,0 +1,55 @@
+use std::error::Error;
+
+use sqlx::{Pool, Sqlite};
+
+use crate::database::common::execute_update_or_insert;
+use crate::database::update::update_image_aspect_ratio::execute_insert_image_aspect_ratio_sql;
+use crate::database::update::update_image_sharpness::execute_insert_image_sharpness_sql;
+use crate::database::update::update_image_thumbnail::execute_insert_image_thumbnail_sql;
+use crate::models::image_orientation::ImageOrientationRecompute;
+
+
+fn to_send_error(e: sqlx::Error) -> Box<dyn Error + Send> {
+    Box::new(e) as Box<dyn Error + Send>
+}
+
+// Rows of an image decoded sideways. Aspect ratio, thumbnails and sharpness are put back straight away;
+// similarity needs the other image of each pair, so it is left for the similarity actions to fill in again
+const SQL_DELETE_ORIENTATION_DEPENDENT_ROWS: &[&str] = &[
+    "DELETE FROM image_aspect_ratio WHERE image_path = ?1;",
+    "DELETE FROM image_thumbnail WHERE image_path = ?1;",
+    "DELETE FROM image_sharpness WHERE image_path = ?1;",
+    "DELETE FROM image_raw WHERE image_path = ?1;",
+    "DELETE FROM image_similarity WHERE image_path_a = ?1 OR image_path_b = ?1;",
+    "DELETE FROM image_similarity_neighbors WHERE image_path = ?1 OR neighbor_path = ?1;",
+    "DELETE FROM image_similarity_neighbor_scan WHERE image_path = ?1;",
+];
+
+// The pending marker is cleared last, so an interrupted recompute is simply done again
+pub async fn execute_apply_image_orientation_recompute_sql(item: &ImageOrientationRecompute, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    if !item.is_upright() {
+        let mut tx = pool.begin().await.map_err(to_send_error)?;
+        for sql in SQL_DELETE_ORIENTATION_DEPENDENT_ROWS {
+            match sqlx::query(sql).bind(&item.image_path).execute(&mut tx).await {
+                Ok(_) => {},
+                // optional tables are only created once something is imported into them
+                Err(e) if e.to_string().contains("no such table") => {},
+                Err(e) => return Err(to_send_error(e)),
+            }
+        }
+        tx.commit().await.map_err(to_send_error)?;
+
+        if let Some(aspect_ratio) = &item.aspect_ratio {
+            execute_insert_image_aspect_ratio_sql(aspect_ratio, pool).await?;
+        }
+        for thumbnail in &item.thumbnails {
+            execute_insert_image_thumbnail_sql(thumbnail, pool).await?;
+        }
+        if let Some(sharpness) = &item.sharpness {
+            execute_insert_image_sharpness_sql(sharpness, pool).await?;
+        }
+    }
+
+    execute_update_or_insert(pool, "DELETE FROM image_orientation_pending WHERE image_path = ?;", vec![ &item.image_path ]).await?;
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,79 @@
+use std::error::Error;
+
+use sqlx::{Pool, Row, Sqlite};
+
+use crate::converters::extract_image_similarity::order_comparison_pair;
+use crate::database::create::common::SQL_IMAGE_PATH_KEYED_TABLES;
+
+
+fn to_send_error(e: sqlx::Error) -> Box<dyn Error + Send> {
+    Box::new(e) as Box<dyn Error + Send>
+}
+
+// Moves every row stored for old_path over to new_path in one transaction, so extracted data follows a moved file.
+// Rows that already exist for new_path are kept and the old ones dropped. Returns the number of rows moved.
+pub async fn execute_rekey_image_path_sql(old_path: &str, new_path: &str, pool: &Pool<Sqlite>) -> Result<u64, Box<dyn Error + Send>> {
+    let mut tx = pool.begin().await.map_err(to_send_error)?;
+    let mut moved = 0;
+
+    for table in SQL_IMAGE_PATH_KEYED_TABLES {
+        let update = format!("UPDATE OR IGNORE {} SET image_path = ? WHERE image_path = ?;", table);
+        match sqlx::query(&update).bind(new_path).bind(old_path).execute(&mut tx).await {
+            Ok(r) => moved += r.rows_affected(),
+            // optional tables are only created once something is imported into them
+            Err(e) if e.to_string().contains("no such table") => continue,
+            Err(e) => return Err(to_send_error(e)),
+        }
+        let delete = format!("DELETE FROM {} WHERE image_path = ?;", table);
+        sqlx::query(&delete).bind(old_path).execute(&mut tx).await.map_err(to_send_error)?;
+    }
+
+    // the image_path side of top-k neighbours moved with the tables above, this is the other side
+    match sqlx::query("UPDATE OR IGNORE image_similarity_neighbors SET neighbor_path = ? WHERE neighbor_path = ?;")
+        .bind(new_path).bind(old_path)
+        .execute(&mut tx).await {
+        Ok(r) => {
+            moved += r.rows_affected();
+            sqlx::query("DELETE FROM image_similarity_neighbors WHERE neighbor_path = ?;")
+                .bind(old_path)
+                .execute(&mut tx).await.map_err(to_send_error)?;
+        },
+        Err(e) if e.to_string().contains("no such table") => {},
+        Err(e) => return Err(to_send_error(e)),
+    }
+
+    // similarity pairs are stored in path order, which the new path may change
+    let rows = match sqlx::query("SELECT image_comparison_algorithm, image_path_a, image_path_b FROM image_similarity WHERE image_path_a = ? OR image_path_b = ?;")
+        .bind(old_path).bind(old_path)
+        .fetch_all(&mut tx).await {
+        Ok(rows) => rows,
+        Err(e) if e.to_string().contains("no such table") => vec![],
+        Err(e) => return Err(to_send_error(e)),
+    };
+    for row in rows {
+        let algo: i64 = row.try_get("image_comparison_algorithm").unwrap_or_default();
+        let old_a: String = row.try_get("image_path_a").unwrap_or_default();
+        let old_b: String = row.try_get("image_path_b").unwrap_or_default();
+        let (new_a, new_b) = order_comparison_pair(
+            if old_a == old_path { new_path } else { &old_a },
+            if old_b == old_path { new_path } else { &old_b },
+        );
+        let r = sqlx::query("UPDATE OR REPLACE image_similarity SET image_path_a = ?, image_path_b = ? WHERE image_path_a = ? AND image_path_b = ? AND image_comparison_algorithm = ?;")
+            .bind(&new_a).bind(&new_b).bind(&old_a).bind(&old_b).bind(algo)
+            .execute(&mut tx).await.map_err(to_send_error)?;
+        moved += r.rows_affected();
+    }
+
+    // image_paths goes last: its image_id triggers re-point the feature rows above, and deleting the
+    // old path afterwards only cascades to rows that were already replaced
+    let r = sqlx::query("UPDATE OR IGNORE image_paths SET image_path = ? WHERE image_path = ?;")
+        .bind(new_path).bind(old_path)
+        .execute(&mut tx).await.map_err(to_send_error)?;
+    moved += r.rows_affected();
+    sqlx::query("DELETE FROM image_paths WHERE image_path = ?;")
+        .bind(old_path)
+        .execute(&mut tx).await.map_err(to_send_error)?;
+
+    tx.commit().await.map_err(to_send_error)?;
+    Ok(moved)
+}
\ No newline at end of file
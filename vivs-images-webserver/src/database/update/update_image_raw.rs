@@ -0,0 +1,34 @@
+use std::error::Error;
+
+use sqlx::{Pool, Sqlite};
+
+use crate::models::image_raw::ImageRaw;
+use crate::database::common::execute_update_or_insert;
+
+
+pub async fn execute_insert_image_raw_sql(item: &ImageRaw, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    // most RAW files have no JPEG sibling, which is stored as NULL rather than an empty path
+    let query = r#"INSERT INTO image_raw (image_path, raw_format, preview_width, preview_height, jpeg_sibling_path) VALUES (?, ?, ?, ?, NULLIF(?, ''));"#;
+    let preview_width = item.preview_width.to_string();
+    let preview_height = item.preview_height.to_string();
+    let jpeg_sibling_path = item.jpeg_sibling_path.clone().unwrap_or_default();
+    let params = vec![ item.image_path.as_str(), item.raw_format.as_str(), preview_width.as_str(), preview_height.as_str(), jpeg_sibling_path.as_str() ];
+    let r = execute_update_or_insert(pool, query, params).await?;
+    let r = r.rows_affected();
+    if r == 1 {
+        Ok(())
+    } else {
+        Err(Box::new(std::io::Error::other(format!("SQL insert returned {} rows", r))))
+    }
+}
+
+pub async fn execute_delete_image_raw_sql(image_path: &str, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    let query = r#"DELETE FROM image_raw WHERE image_path = ?;"#;
+    let r = execute_update_or_insert(pool, query, vec![ image_path ]).await?;
+    let r = r.rows_affected();
+    if r == 1 {
+        Ok(())
+    } else {
+        Err(Box::new(std::io::Error::other(format!("SQL delete returned {} rows", r))))
+    }
+}
\ No newline at end of file
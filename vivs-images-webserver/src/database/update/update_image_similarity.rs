@@ -4,18 +4,19 @@ use sqlx::{Pool, Sqlite};
 
 use crate::models::image_similarity::ImageSimilarity;
 use crate::database::common::execute_update_or_insert;
-use crate::converters::extract_image_similarity::compute_comparison_key;
+use crate::converters::extract_image_similarity::order_comparison_pair;
 
 
 pub async fn execute_update_image_similarity_sql(v: ImageSimilarity, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
     let query = r#"
         UPDATE image_similarity
         SET similarity_value = ?
-        WHERE image_comparison_key = ?;
+        WHERE image_path_a = ? AND image_path_b = ? AND image_comparison_algorithm = ?;
     "#;
     let similarity_value = v.similarity_value.to_string();
-    let hash = compute_comparison_key(&v.image_path_a, &v.image_path_b).to_string();
-    let params: Vec<&str> = vec![ &similarity_value, &hash ];
+    let (path_a, path_b) = order_comparison_pair(&v.image_path_a, &v.image_path_b);
+    let algo = v.image_comparison_algorithm.to_string();
+    let params: Vec<&str> = vec![ &similarity_value, &path_a, &path_b, &algo ];
     let r = execute_update_or_insert(pool, query, params).await?;
     let r = r.rows_affected();
     if r == 1 {
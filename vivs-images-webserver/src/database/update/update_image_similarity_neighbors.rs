@@ -0,0 +1,132 @@
+use std::error::Error;
+
+use sqlx::{Pool, Sqlite, Transaction};
+
+use crate::models::image_similarity_neighbor::{ImageSimilarityNeighborScan, ImageSimilarityPrunePlan};
+
+
+fn to_send_error(e: sqlx::Error) -> Box<dyn Error + Send> {
+    Box::new(e) as Box<dyn Error + Send>
+}
+
+const SQL_UPSERT_NEIGHBOR: &str = r#"
+INSERT OR REPLACE INTO image_similarity_neighbors (image_path, neighbor_path, image_comparison_algorithm, similarity_value, similarity_confidence)
+VALUES (?, ?, ?, ?, ?);"#;
+
+// Drops everything past an image's K best neighbours
+const SQL_TRIM_NEIGHBORS: &str = r#"
+DELETE FROM image_similarity_neighbors
+WHERE image_path = ?1 AND image_comparison_algorithm = ?2 AND neighbor_path NOT IN (
+    SELECT neighbor_path FROM image_similarity_neighbors
+    WHERE image_path = ?1 AND image_comparison_algorithm = ?2
+    ORDER BY similarity_value DESC, neighbor_path
+    LIMIT ?3
+);"#;
+
+async fn trim_neighbors(image_path: &str, algo: i64, top_k: i64, tx: &mut Transaction<'_, Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    sqlx::query(SQL_TRIM_NEIGHBORS)
+        .bind(image_path).bind(algo).bind(top_k)
+        .execute(&mut *tx).await.map_err(to_send_error)?;
+    Ok(())
+}
+
+// Replaces the image's neighbour list with the first K of the scan and offers the image to each neighbour's
+// own list, so images scanned earlier pick up newer images without being scanned again
+pub async fn execute_replace_image_similarity_neighbors_sql(scan: &ImageSimilarityNeighborScan, top_k: usize, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    let algo: u8 = (&scan.image_comparison_algorithm).try_into().unwrap_or_default();
+    let algo = algo as i64;
+    let top_k = top_k as i64;
+    let mut tx = pool.begin().await.map_err(to_send_error)?;
+
+    sqlx::query("DELETE FROM image_similarity_neighbors WHERE image_path = ? AND image_comparison_algorithm = ?;")
+        .bind(&scan.image_path).bind(algo)
+        .execute(&mut tx).await.map_err(to_send_error)?;
+
+    for (i, neighbor) in scan.neighbors.iter().enumerate() {
+        if (i as i64) < top_k {
+            sqlx::query(SQL_UPSERT_NEIGHBOR)
+                .bind(&scan.image_path).bind(&neighbor.neighbor_path).bind(algo)
+                .bind(neighbor.similarity_value).bind(neighbor.similarity_confidence)
+                .execute(&mut tx).await.map_err(to_send_error)?;
+        }
+        sqlx::query(SQL_UPSERT_NEIGHBOR)
+            .bind(&neighbor.neighbor_path).bind(&scan.image_path).bind(algo)
+            .bind(neighbor.similarity_value).bind(neighbor.similarity_confidence)
+            .execute(&mut tx).await.map_err(to_send_error)?;
+        trim_neighbors(&neighbor.neighbor_path, algo, top_k, &mut tx).await?;
+    }
+
+    sqlx::query("INSERT OR REPLACE INTO image_similarity_neighbor_scan (image_path, image_comparison_algorithm, compared_count) VALUES (?, ?, ?);")
+        .bind(&scan.image_path).bind(algo).bind(scan.compared_count as i64)
+        .execute(&mut tx).await.map_err(to_send_error)?;
+
+    tx.commit().await.map_err(to_send_error)?;
+    Ok(())
+}
+
+// Both directions of every pair above the minimum, ranked per image; existing neighbours are merged in
+const SQL_PRUNE_INSERT_NEIGHBORS: &str = r#"
+INSERT OR REPLACE INTO image_similarity_neighbors (image_path, neighbor_path, image_comparison_algorithm, similarity_value, similarity_confidence)
+SELECT image_path, neighbor_path, image_comparison_algorithm, similarity_value, similarity_confidence FROM (
+    SELECT *, ROW_NUMBER() OVER (PARTITION BY image_path ORDER BY similarity_value DESC, neighbor_path) 'neighbor_rank'
+    FROM (
+        SELECT image_path_a 'image_path', image_path_b 'neighbor_path', image_comparison_algorithm, similarity_value, similarity_confidence
+        FROM image_similarity WHERE image_comparison_algorithm = ?1 AND similarity_value >= ?2
+        UNION ALL
+        SELECT image_path_b 'image_path', image_path_a 'neighbor_path', image_comparison_algorithm, similarity_value, similarity_confidence
+        FROM image_similarity WHERE image_comparison_algorithm = ?1 AND similarity_value >= ?2
+    )
+)
+WHERE neighbor_rank <= ?3;"#;
+
+const SQL_PRUNE_TRIM_NEIGHBORS: &str = r#"
+DELETE FROM image_similarity_neighbors WHERE rowid IN (
+    SELECT rowid FROM (
+        SELECT rowid, ROW_NUMBER() OVER (PARTITION BY image_path ORDER BY similarity_value DESC, neighbor_path) 'neighbor_rank'
+        FROM image_similarity_neighbors WHERE image_comparison_algorithm = ?1
+    )
+    WHERE neighbor_rank > ?3
+);"#;
+
+// Only images compared against every other image for this algorithm have a complete neighbour list
+const SQL_PRUNE_MARK_SCANNED: &str = r#"
+WITH sides AS (
+    SELECT image_path_a 'image_path' FROM image_similarity WHERE image_comparison_algorithm = ?1
+    UNION ALL
+    SELECT image_path_b 'image_path' FROM image_similarity WHERE image_comparison_algorithm = ?1
+)
+INSERT OR REPLACE INTO image_similarity_neighbor_scan (image_path, image_comparison_algorithm, compared_count)
+SELECT image_path, ?1, COUNT(*) FROM sides
+GROUP BY image_path
+HAVING COUNT(*) >= (SELECT COUNT(DISTINCT image_path) FROM sides) - 1;"#;
+
+// Keeps the pairs that are still somebody's neighbour
+const SQL_PRUNE_DELETE_PAIRS: &str = r#"
+DELETE FROM image_similarity
+WHERE image_comparison_algorithm = ?1 AND NOT EXISTS (
+    SELECT 1 FROM image_similarity_neighbors n
+    WHERE n.image_comparison_algorithm = image_similarity.image_comparison_algorithm
+    AND ((n.image_path = image_similarity.image_path_a AND n.neighbor_path = image_similarity.image_path_b)
+        OR (n.image_path = image_similarity.image_path_b AND n.neighbor_path = image_similarity.image_path_a))
+);"#;
+
+// Moves one algorithm's pairs into top-K neighbour lists and deletes the pairs no image kept.
+// Returns the number of image_similarity rows deleted.
+pub async fn execute_prune_image_similarity_to_top_k_sql(plan: &ImageSimilarityPrunePlan, pool: &Pool<Sqlite>) -> Result<u64, Box<dyn Error + Send>> {
+    let algo: u8 = (&plan.image_comparison_algorithm).try_into().unwrap_or_default();
+    let mut tx = pool.begin().await.map_err(to_send_error)?;
+
+    // each statement uses the parameters it needs out of ?1 algorithm, ?2 minimum value and ?3 K
+    for sql in [SQL_PRUNE_MARK_SCANNED, SQL_PRUNE_INSERT_NEIGHBORS, SQL_PRUNE_TRIM_NEIGHBORS] {
+        sqlx::query(sql)
+            .bind(algo as i64).bind(plan.min_value).bind(plan.top_k as i64)
+            .execute(&mut tx).await.map_err(to_send_error)?;
+    }
+    let deleted = sqlx::query(SQL_PRUNE_DELETE_PAIRS)
+        .bind(algo as i64)
+        .execute(&mut tx).await.map_err(to_send_error)?
+        .rows_affected();
+
+    tx.commit().await.map_err(to_send_error)?;
+    Ok(deleted)
+}
\ No newline at end of file
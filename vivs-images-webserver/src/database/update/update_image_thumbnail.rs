@@ -1,10 +1,12 @@
 use std::error::Error;
 
 use actix_web::Either;
-use sqlx::{Pool, Sqlite};
+use sqlx::{Pool, Row, Sqlite};
 
-use crate::models::image_thumbnail::ImageThumbnail;
-use crate::database::common::{execute_update_or_insert, execute_update_or_insert_with_blob};
+use crate::models::image_thumbnail::{ImageThumbnail, ImageThumbnailRelocation};
+use crate::database::common::{execute_query, execute_update_or_insert, execute_update_or_insert_with_blob};
+use crate::database::thumbnail_store::{delete_thumbnail_data, thumbnail_storage_key, write_thumbnail_data};
+use crate::models::config::thumbnails::{get_thumbnail_storage, ThumbnailStorage};
 
 
 pub async fn execute_update_image_thumbnail_sql(image_path: &String, thumbnail: f64, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
@@ -22,22 +24,32 @@ pub async fn execute_update_image_thumbnail_sql(image_path: &String, thumbnail:
     }
 }
 
+// With VIVS_THUMBNAIL_STORAGE set to a separate store the data is written there first, and the row only
+// records its storage key
 pub async fn execute_insert_image_thumbnail_sql(thumbnail: &ImageThumbnail, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    let storage_key = thumbnail_storage_key(&get_thumbnail_storage(), &thumbnail.thumbnail_format, &thumbnail.thumbnail_data);
+    if let Some(storage_key) = &storage_key {
+        write_thumbnail_data(storage_key, &thumbnail.thumbnail_data, pool).await?;
+    }
+
     let regular_column_names = ImageThumbnail::get_meta().iter().filter(|c| c.field_type != "blob").map(|c| c.name.to_string()).collect::<Vec<String>>();
     let blob_column_names = ImageThumbnail::get_meta().iter().filter(|c| c.field_type == "blob").map(|c| c.name.to_string()).collect::<Vec<String>>();
     let column_names_sql = regular_column_names.join(", ");
     let blob_column_names_sql = blob_column_names.join(", ");
     let column_var_placeholders_sql = std::iter::repeat_n("?", ImageThumbnail::get_meta().len()).collect::<Vec<&str>>().join(", ");
-    let query = format!(r#"INSERT INTO image_thumbnail ({},{}) VALUES ({});"#, column_names_sql, blob_column_names_sql, column_var_placeholders_sql);
+    let query = format!(r#"INSERT INTO image_thumbnail ({},{},thumbnail_storage_key) VALUES ({}, NULLIF(?, ''));"#, column_names_sql, blob_column_names_sql, column_var_placeholders_sql);
     let params: Vec<String> = regular_column_names.iter().filter_map(|c| thumbnail.get_field(c)).collect();
     let mut params: Vec<Either<&str, Vec<u8>>> = params.iter().map(|v| Either::Left(v.as_str())).collect();
     for blob_c in blob_column_names.iter() {
-        if let Some(blob) = thumbnail.get_field_blob(blob_c) {
+        if storage_key.is_some() {
+            params.push(Either::Right(vec![]));
+        } else if let Some(blob) = thumbnail.get_field_blob(blob_c) {
             params.push(Either::Right(blob));
         } else {
             return Err(Box::new(std::io::Error::other(format!("Could not get blob from data object"))));
         }
     }
+    params.push(Either::Left(storage_key.as_deref().unwrap_or_default()));
     let r = execute_update_or_insert_with_blob(&pool, &query, params).await?;
     let r = r.rows_affected();
     if r == 1 {
@@ -47,13 +59,48 @@ pub async fn execute_insert_image_thumbnail_sql(thumbnail: &ImageThumbnail, pool
     }
 }
 
+// Deletes every size of the image's thumbnail, then any stored data that no other thumbnail shares
 pub async fn execute_delete_image_thumbnail_sql(image_path: &String, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    let storage_keys: Vec<String> = execute_query(pool, "SELECT DISTINCT thumbnail_storage_key FROM image_thumbnail WHERE image_path = ? AND thumbnail_storage_key IS NOT NULL;", vec![ image_path ]).await?
+        .iter()
+        .filter_map(|row| row.try_get("thumbnail_storage_key").ok())
+        .collect();
+
     let query = r#"DELETE FROM image_thumbnail WHERE image_path = ?;"#;
     let r = execute_update_or_insert(pool, query, vec![ image_path ]).await?;
     let r = r.rows_affected();
+    if r == 0 {
+        return Err(Box::new(std::io::Error::other(format!("SQL delete returned {} rows", r))));
+    }
+
+    for storage_key in storage_keys {
+        let still_used = execute_query(pool, "SELECT 1 FROM image_thumbnail WHERE thumbnail_storage_key = ? LIMIT 1;", vec![ &storage_key ]).await?;
+        if still_used.is_empty() {
+            delete_thumbnail_data(&storage_key, pool).await?;
+        }
+    }
+    Ok(())
+}
+
+// Moves one thumbnail's data into the given storage, leaving a copy in the old one
+pub async fn execute_relocate_image_thumbnail_sql(relocation: &ImageThumbnailRelocation, storage: &ThumbnailStorage, pool: &Pool<Sqlite>) -> Result<(), Box<dyn Error + Send>> {
+    let thumbnail = &relocation.thumbnail;
+    let storage_key = thumbnail_storage_key(storage, &thumbnail.thumbnail_format, &thumbnail.thumbnail_data);
+    if let Some(storage_key) = &storage_key {
+        write_thumbnail_data(storage_key, &thumbnail.thumbnail_data, pool).await?;
+    }
+    let query = r#"UPDATE image_thumbnail SET thumbnail_data = ?, thumbnail_storage_key = NULLIF(?, '') WHERE image_thumbnail_key = ?;"#;
+    let image_thumbnail_key = relocation.image_thumbnail_key.to_string();
+    let params = vec![
+        Either::Right(if storage_key.is_some() { vec![] } else { thumbnail.thumbnail_data.clone() }),
+        Either::Left(storage_key.as_deref().unwrap_or_default()),
+        Either::Left(image_thumbnail_key.as_str()),
+    ];
+    let r = execute_update_or_insert_with_blob(pool, query, params).await?;
+    let r = r.rows_affected();
     if r == 1 {
         Ok(())
     } else {
-        Err(Box::new(std::io::Error::other(format!("SQL delete returned {} rows", r))))
+        Err(Box::new(std::io::Error::other(format!("SQL update returned {} rows", r))))
     }
 }
\ No newline at end of file
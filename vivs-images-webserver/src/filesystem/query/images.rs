@@ -4,7 +4,12 @@ use std::path::Path;
 use homedir::my_home;
 
 // Common image extensions for reuse
-pub const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "bmp", "gif", "webp", "tif", "tiff"];
+pub const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "bmp", "gif", "webp", "tif", "tiff", "cr2", "nef", "arw", "dng"];
+// Camera RAW formats, read through the JPEG preview they embed
+pub const RAW_IMAGE_EXTENSIONS: &[&str] = &["cr2", "nef", "arw", "dng"];
+pub const JPEG_EXTENSIONS: &[&str] = &["jpg", "jpeg"];
+// Formats that can hold more than one frame (GIF, WebP) or page (TIFF)
+pub const MULTI_FRAME_IMAGE_EXTENSIONS: &[&str] = &["gif", "webp", "tif", "tiff"];
 pub const JPEG_TIFF_EXTENSIONS: &[&str] = &["jpg", "jpeg", "tif", "tiff"];
 pub const TEXT_EXTENSIONS: &[&str] = &["txt"];
 
@@ -14,6 +19,32 @@ pub fn has_extension(file_name: &str, extensions: &[&str]) -> bool {
     extensions.iter().any(|&ext| file_name_lower.ends_with(&format!(".{}", ext)))
 }
 
+pub fn is_raw_image_path(path: &str) -> bool {
+    has_extension(path, RAW_IMAGE_EXTENSIONS)
+}
+
+// A JPEG with the same stem next to a RAW file, as written by cameras shooting RAW+JPEG
+pub fn find_jpeg_sibling(raw_path: &str) -> Option<String> {
+    let path = Path::new(raw_path);
+    let stem = path.file_stem()?.to_str()?;
+    let parent = path.parent()?;
+    let mut siblings: Vec<String> = std::fs::read_dir(parent).ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let sibling = entry.path();
+            let sibling_stem = sibling.file_stem()?.to_str()?;
+            let file_name = sibling.file_name()?.to_str()?;
+            if sibling_stem == stem && has_extension(file_name, JPEG_EXTENSIONS) {
+                sibling.canonicalize().ok().map(|p| p.to_string_lossy().into_owned())
+            } else {
+                None
+            }
+        })
+        .collect();
+    siblings.sort();
+    siblings.into_iter().next()
+}
+
 // Generic function to get files with specific extensions in a folder
 pub fn get_files_in_folder<P: AsRef<Path>>(folder: P, extensions: &[&str]) -> HashSet<String> {
     let mut results = HashSet::new();
@@ -75,6 +106,16 @@ pub fn get_images_in_photo_sync_path() -> Result<HashSet<String>, Box<dyn Error
     Ok(get_images_in_folder(images_path))
 }
 
+pub fn get_raw_images_in_photo_sync_path() -> Result<HashSet<String>, Box<dyn Error + Send>> {
+    let images_path = get_photo_sync_path()?;
+    Ok(get_files_in_folder(images_path, RAW_IMAGE_EXTENSIONS))
+}
+
+pub fn get_multi_frame_images_in_photo_sync_path() -> Result<HashSet<String>, Box<dyn Error + Send>> {
+    let images_path = get_photo_sync_path()?;
+    Ok(get_files_in_folder(images_path, MULTI_FRAME_IMAGE_EXTENSIONS))
+}
+
 pub fn get_jpg_tiff_in_photo_sync_path() -> Result<HashSet<String>, Box<dyn Error + Send>> {
     let images_path = get_photo_sync_path()?;
     Ok(get_files_in_folder(images_path, JPEG_TIFF_EXTENSIONS))
@@ -36,14 +36,22 @@ async fn main() -> anyhow::Result<()> {
             .route("/actions/start/{action_name}", web::post().to(view::html::pages::action_detail::view_page_action_detail_post))
             .route("/actions/task/{action_task_id}", web::get().to(view::html::pages::task_detail::view_page_task_detail_get))
             .route("/search", web::get().to(view::html::pages::search::search_images))
+            .route("/search/map", web::get().to(view::html::pages::search::search_images_map))
             .route("/search/wallpapers", web::get().to(view::html::pages::search::search_wallpapers))
+            .route("/search/color", web::get().to(view::html::pages::search_by_color::search_images_by_color))
             .route("/browse/filesystem", web::get().to(view::html::pages::browse_filesystem::view_page_browse_filesystem))
             .route("/browse/by-property", web::get().to(view::html::pages::browse_by_property::view_page_browse_properties))
             .route("/browse/by-property/{property}", web::get().to(view::html::pages::browse_by_property_detail::view_page_property_details))
+            .route("/browse/timeline", web::get().to(view::html::pages::browse_timeline::view_page_timeline))
+            .route("/browse/events", web::get().to(view::html::pages::browse_events::view_page_events))
+            .route("/browse/bursts", web::get().to(view::html::pages::browse_bursts::view_page_bursts))
+            .route("/browse/bursts/tag", web::post().to(view::html::pages::browse_bursts::view_page_bursts_tag_post))
             .route("/browse/tags", web::get().to(view::html::pages::browse_tags::view_page_tags))
             .route("/browse/tags/{tag}", web::get().to(view::html::pages::browse_tags::view_page_tag_details))
+            .route("/analysis/similarity-algorithms", web::get().to(view::html::pages::similarity_algorithms::view_page_similarity_algorithms))
             .route("/image", web::get().to(view::html::pages::image::view_image))
             .route("/img", web::get().to(api::web::get_image))
+            .route("/thumb", web::get().to(api::web::get_thumbnail))
             .route("/style.css", web::get().to(api::web::get_style))
             .route("/api/wallpaper", web::get().to(api::api_get_wallpaper_image_path::api_get_wallpaper_image_path))
     })
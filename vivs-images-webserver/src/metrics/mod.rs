@@ -7,4 +7,11 @@ pub mod thumbnail_metrics;
 pub mod ocr_text_metrics;
 pub mod tag_metrics;
 pub mod iptc_metrics;
-pub mod xmp_metrics;
\ No newline at end of file
+pub mod xmp_metrics;
+pub mod color_metrics;
+pub mod sharpness_metrics;
+pub mod exposure_metrics;
+pub mod ocr_summary_metrics;
+pub mod content_hash_metrics;
+pub mod raw_metrics;
+pub mod frames_metrics;
\ No newline at end of file
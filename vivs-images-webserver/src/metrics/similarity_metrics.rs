@@ -4,8 +4,12 @@ use sqlx::SqlitePool;
 
 use crate::actions::analysis_task_item_processor::LogProgListenerPair;
 use crate::calc::file_paths_comparison::{CrossFilePathComparisonModel, FilePathComparisonModel};
-use crate::database::query::query_image_similarity::{get_image_path_pairs_from_db, get_image_paths_from_db};
+use crate::database::query::query_image_similarity_neighbors::query_similarity_neighbor_scanned_paths;
+use crate::database::query::query_image_thumbnail::get_thumbnail_image_paths_from_db;
+use crate::database::query::query_image_similarity::{get_image_paths_from_db, query_similarity_table_pairs_using_thumbnail_algo};
 use crate::filesystem::query::images::get_images_in_photo_sync_path;
+use crate::models::config::similarity::SIMILARITY_TOP_K_ALGORITHM;
+use crate::models::image_similarity::ImageComparisonAlgorithm;
 
 
 pub async fn get_image_paths_simple_difference_similarity_analysis(pool: &SqlitePool) -> Result<FilePathComparisonModel, Box<dyn Error + Send>> {
@@ -32,8 +36,9 @@ pub async fn get_simple_similarity_missing_on_disk_count(pool: &SqlitePool) -> R
 
 
 
+// Pairs are compared against the rows of a single algorithm, so every algorithm can be filled in independently
 pub async fn get_image_paths_full_difference_similarity_analysis(
-    pool: &SqlitePool, log_prog_listener: Option<LogProgListenerPair>
+    algo: &ImageComparisonAlgorithm, pool: &SqlitePool, log_prog_listener: Option<LogProgListenerPair>
 ) -> Result<CrossFilePathComparisonModel, Box<dyn Error + Send>> {
     if let Some(x) = &log_prog_listener {
         x.1("getting image paths in photo sync path");
@@ -45,7 +50,7 @@ pub async fn get_image_paths_full_difference_similarity_analysis(
         x.1("getting image pairs from db");
         x.0(0.6);
     }
-    let image_paths_in_sql = get_image_path_pairs_from_db(pool).await?;
+    let image_paths_in_sql = query_similarity_table_pairs_using_thumbnail_algo(algo.clone(), pool).await?;
     Ok(CrossFilePathComparisonModel::new_easy_2(
         image_paths_on_disk, "images on disk",
         image_paths_in_sql, "similarity sql list",
@@ -54,13 +59,42 @@ pub async fn get_image_paths_full_difference_similarity_analysis(
 }
 
 pub async fn get_full_similarity_missing_in_sql_count(pool: &SqlitePool) -> Result<(usize, String), Box<dyn Error + Send>> {
-    let analysis = get_image_paths_full_difference_similarity_analysis(pool, None).await?;
+    let analysis = get_image_paths_full_difference_similarity_analysis(&ImageComparisonAlgorithm::Magick, pool, None).await?;
     let v = analysis.pairs_missing_from_a.len();
     Ok((v, format!("There are {} image pairs on disk without a known similarity", v)))
 }
 
 pub async fn get_full_similarity_missing_on_disk_count(pool: &SqlitePool) -> Result<(usize, String), Box<dyn Error + Send>> {
-    let analysis = get_image_paths_full_difference_similarity_analysis(pool, None).await?;
+    let analysis = get_image_paths_full_difference_similarity_analysis(&ImageComparisonAlgorithm::Magick, pool, None).await?;
     let v = analysis.pairs_missing_from_b.len();
     Ok((v, format!("There are {} image pairs in SQL without a valid image on disk", v)))
 }
+
+
+
+// Images with a thumbnail that have not yet been compared against every other image for their top-k neighbours
+pub async fn get_similarity_neighbor_scan_analysis(
+    algo: &ImageComparisonAlgorithm, pool: &SqlitePool, log_prog_listener: Option<LogProgListenerPair>
+) -> Result<FilePathComparisonModel, Box<dyn Error + Send>> {
+    if let Some(x) = &log_prog_listener {
+        x.1("getting thumbnail image paths from db");
+        x.0(0.3);
+    }
+    let thumbnail_paths = get_thumbnail_image_paths_from_db(pool).await?;
+
+    if let Some(x) = &log_prog_listener {
+        x.1("getting scanned image paths from db");
+        x.0(0.6);
+    }
+    let scanned_paths = query_similarity_neighbor_scanned_paths(algo, pool).await?;
+    Ok(FilePathComparisonModel::new(
+        thumbnail_paths, "thumbnail paths",
+        scanned_paths, "top-k neighbour scans", log_prog_listener)
+    )
+}
+
+pub async fn get_similarity_neighbor_scan_missing_count(pool: &SqlitePool) -> Result<(usize, String), Box<dyn Error + Send>> {
+    let analysis = get_similarity_neighbor_scan_analysis(&SIMILARITY_TOP_K_ALGORITHM, pool, None).await?;
+    let v = analysis.files_missing_from_b.len();
+    Ok((v, format!("There are {} images whose top-k similar neighbours have not been found", v)))
+}
\ No newline at end of file
@@ -0,0 +1,25 @@
+// Frames taken further apart than this are never part of the same burst; override with VIVS_BURST_MAX_GAP_SECONDS
+pub const DEFAULT_BURST_MAX_GAP_SECONDS: f64 = 2.0;
+pub const BURST_MAX_GAP_SECONDS_ENV_VAR: &str = "VIVS_BURST_MAX_GAP_SECONDS";
+
+// Consecutive frames whose thumbnails are less similar than this start a new burst, even a second apart;
+// override with VIVS_BURST_MIN_SIMILARITY
+pub const DEFAULT_BURST_MIN_SIMILARITY: f32 = 0.8;
+pub const BURST_MIN_SIMILARITY_ENV_VAR: &str = "VIVS_BURST_MIN_SIMILARITY";
+
+// The tag the burst review page puts on the frames picked for deletion
+pub const BURST_DELETE_TAG_NAME: &str = "to-delete";
+
+pub fn get_burst_max_gap_seconds() -> f64 {
+    std::env::var(BURST_MAX_GAP_SECONDS_ENV_VAR).ok()
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|v| v.is_finite() && *v > 0.0)
+        .unwrap_or(DEFAULT_BURST_MAX_GAP_SECONDS)
+}
+
+pub fn get_burst_min_similarity() -> f32 {
+    std::env::var(BURST_MIN_SIMILARITY_ENV_VAR).ok()
+        .and_then(|v| v.trim().parse::<f32>().ok())
+        .filter(|v| v.is_finite())
+        .unwrap_or(DEFAULT_BURST_MIN_SIMILARITY)
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+// Resized copies served by /img?w=&h= are kept in this directory; override with VIVS_DERIVATIVE_CACHE_DIR
+pub const DEFAULT_DERIVATIVE_CACHE_DIR: &str = "/home/viveret/vivs-images-derivatives";
+pub const DERIVATIVE_CACHE_DIR_ENV_VAR: &str = "VIVS_DERIVATIVE_CACHE_DIR";
+
+// Least recently served copies are deleted once the directory grows past this; override with VIVS_DERIVATIVE_CACHE_MAX_MB
+pub const DEFAULT_DERIVATIVE_CACHE_MAX_MB: u64 = 512;
+pub const DERIVATIVE_CACHE_MAX_MB_ENV_VAR: &str = "VIVS_DERIVATIVE_CACHE_MAX_MB";
+
+// JPEG quality (1-100) of resized copies; override with VIVS_DERIVATIVE_QUALITY
+pub const DEFAULT_DERIVATIVE_QUALITY: u8 = 85;
+pub const DERIVATIVE_QUALITY_ENV_VAR: &str = "VIVS_DERIVATIVE_QUALITY";
+
+// Requested widths and heights are clamped to this, so a query string cannot ask for a huge image
+pub const DERIVATIVE_MAX_DIMENSION: u32 = 4096;
+
+// Browsers revalidate resized copies with their ETag after this many seconds
+pub const DERIVATIVE_CACHE_MAX_AGE_SECONDS: u32 = 7 * 24 * 60 * 60;
+
+pub fn get_derivative_cache_dir() -> String {
+    std::env::var(DERIVATIVE_CACHE_DIR_ENV_VAR).ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| DEFAULT_DERIVATIVE_CACHE_DIR.to_string())
+}
+
+pub fn get_derivative_cache_max_bytes() -> u64 {
+    std::env::var(DERIVATIVE_CACHE_MAX_MB_ENV_VAR).ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_DERIVATIVE_CACHE_MAX_MB) * 1024 * 1024
+}
+
+pub fn get_derivative_quality() -> u8 {
+    std::env::var(DERIVATIVE_QUALITY_ENV_VAR).ok()
+        .and_then(|v| v.trim().parse::<u8>().ok())
+        .map(|q| q.clamp(1, 100))
+        .unwrap_or(DEFAULT_DERIVATIVE_QUALITY)
+}
\ No newline at end of file
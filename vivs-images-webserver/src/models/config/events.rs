@@ -0,0 +1,22 @@
+// Photos further apart in time than this start a new event; override with VIVS_EVENT_MAX_TIME_GAP_HOURS
+pub const DEFAULT_EVENT_MAX_TIME_GAP_HOURS: f64 = 3.0;
+pub const EVENT_MAX_TIME_GAP_HOURS_ENV_VAR: &str = "VIVS_EVENT_MAX_TIME_GAP_HOURS";
+
+// A photo taken further than this from the last located photo of an event starts a new one, even straight after it;
+// override with VIVS_EVENT_MAX_DISTANCE_KM
+pub const DEFAULT_EVENT_MAX_DISTANCE_KM: f64 = 50.0;
+pub const EVENT_MAX_DISTANCE_KM_ENV_VAR: &str = "VIVS_EVENT_MAX_DISTANCE_KM";
+
+fn get_env_positive_f64(name: &str) -> Option<f64> {
+    std::env::var(name).ok()
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|v| v.is_finite() && *v > 0.0)
+}
+
+pub fn get_event_max_time_gap_hours() -> f64 {
+    get_env_positive_f64(EVENT_MAX_TIME_GAP_HOURS_ENV_VAR).unwrap_or(DEFAULT_EVENT_MAX_TIME_GAP_HOURS)
+}
+
+pub fn get_event_max_distance_km() -> f64 {
+    get_env_positive_f64(EVENT_MAX_DISTANCE_KM_ENV_VAR).unwrap_or(DEFAULT_EVENT_MAX_DISTANCE_KM)
+}
\ No newline at end of file
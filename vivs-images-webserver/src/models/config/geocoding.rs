@@ -0,0 +1,32 @@
+// Place names come from the bundled data/geonames_cities.tsv unless this points at a GeoNames cities dump
+// (cities500.txt, cities15000.txt, ...) or a file in the bundled six-column layout
+pub const GEONAMES_CITIES_FILE_ENV_VAR: &str = "VIVS_GEONAMES_CITIES_FILE";
+
+// GeoNames dumps only carry admin1 codes; admin1CodesASCII.txt turns them into region names
+pub const GEONAMES_ADMIN1_FILE_ENV_VAR: &str = "VIVS_GEONAMES_ADMIN1_FILE";
+
+// Coordinates further than this from every known place get no place names, e.g. photos taken at sea;
+// override with VIVS_GEOCODE_MAX_DISTANCE_KM
+pub const DEFAULT_GEOCODE_MAX_DISTANCE_KM: f64 = 300.0;
+pub const GEOCODE_MAX_DISTANCE_KM_ENV_VAR: &str = "VIVS_GEOCODE_MAX_DISTANCE_KM";
+
+fn get_env_path(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+pub fn get_geonames_cities_file() -> Option<String> {
+    get_env_path(GEONAMES_CITIES_FILE_ENV_VAR)
+}
+
+pub fn get_geonames_admin1_file() -> Option<String> {
+    get_env_path(GEONAMES_ADMIN1_FILE_ENV_VAR)
+}
+
+pub fn get_geocode_max_distance_km() -> f64 {
+    std::env::var(GEOCODE_MAX_DISTANCE_KM_ENV_VAR).ok()
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|v| *v > 0.0)
+        .unwrap_or(DEFAULT_GEOCODE_MAX_DISTANCE_KM)
+}
\ No newline at end of file
@@ -1 +1,8 @@
-pub mod paths;
\ No newline at end of file
+pub mod paths;
+pub mod ocr;
+pub mod similarity;
+pub mod thumbnails;
+pub mod derivatives;
+pub mod geocoding;
+pub mod events;
+pub mod bursts;
\ No newline at end of file
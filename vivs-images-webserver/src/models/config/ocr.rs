@@ -0,0 +1,21 @@
+// Languages passed to tesseract as `-l eng+deu`; override with a '+' or ',' separated list in VIVS_OCR_LANGUAGES
+pub const DEFAULT_OCR_LANGUAGES: &[&str] = &["eng"];
+pub const OCR_LANGUAGES_ENV_VAR: &str = "VIVS_OCR_LANGUAGES";
+
+// Tesseract word confidence (0-100) below which a word is treated as noise
+pub const OCR_LOW_CONFIDENCE_THRESHOLD: f32 = 60.0;
+
+pub fn get_ocr_languages() -> Vec<String> {
+    std::env::var(OCR_LANGUAGES_ENV_VAR).ok()
+        .map(|v| parse_ocr_languages(&v))
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| DEFAULT_OCR_LANGUAGES.iter().map(|s| s.to_string()).collect())
+}
+
+pub fn parse_ocr_languages(s: &str) -> Vec<String> {
+    s.split(['+', ',', ' '])
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty() && l.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+        .map(|l| l.to_string())
+        .collect()
+}
\ No newline at end of file
@@ -0,0 +1,54 @@
+use crate::models::image_similarity::ImageComparisonAlgorithm;
+
+// How similarity is stored: "full" keeps every compared pair in image_similarity, "top_k" keeps only each
+// image's K most similar neighbours in image_similarity_neighbors; set with VIVS_SIMILARITY_STORAGE
+pub const SIMILARITY_STORAGE_ENV_VAR: &str = "VIVS_SIMILARITY_STORAGE";
+
+// Neighbours kept per image and algorithm in top_k mode; override with VIVS_SIMILARITY_TOP_K
+pub const DEFAULT_SIMILARITY_TOP_K: usize = 20;
+pub const SIMILARITY_TOP_K_ENV_VAR: &str = "VIVS_SIMILARITY_TOP_K";
+
+// Neighbours below this similarity value are not kept at all; override with VIVS_SIMILARITY_TOP_K_MIN_VALUE
+pub const DEFAULT_SIMILARITY_TOP_K_MIN_VALUE: f32 = 0.5;
+pub const SIMILARITY_TOP_K_MIN_VALUE_ENV_VAR: &str = "VIVS_SIMILARITY_TOP_K_MIN_VALUE";
+
+// Top-K scans compare each image against every other one, so they use the small stored thumbnails
+pub const SIMILARITY_TOP_K_ALGORITHM: ImageComparisonAlgorithm = ImageComparisonAlgorithm::CustomV2Thumbnails;
+
+// Largest stored thumbnail size CustomV2Thumbnails compares, unless a comparison asks for another one
+pub const SIMILARITY_THUMBNAIL_SIZE: u32 = 32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimilarityStorageMode {
+    Full,
+    TopK,
+}
+
+impl SimilarityStorageMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "full" => Some(Self::Full),
+            "top_k" | "topk" => Some(Self::TopK),
+            _ => None,
+        }
+    }
+}
+
+pub fn get_similarity_storage_mode() -> SimilarityStorageMode {
+    std::env::var(SIMILARITY_STORAGE_ENV_VAR).ok()
+        .and_then(|v| SimilarityStorageMode::from_name(&v))
+        .unwrap_or(SimilarityStorageMode::Full)
+}
+
+pub fn get_similarity_top_k() -> usize {
+    std::env::var(SIMILARITY_TOP_K_ENV_VAR).ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|k| *k > 0)
+        .unwrap_or(DEFAULT_SIMILARITY_TOP_K)
+}
+
+pub fn get_similarity_top_k_min_value() -> f32 {
+    std::env::var(SIMILARITY_TOP_K_MIN_VALUE_ENV_VAR).ok()
+        .and_then(|v| v.trim().parse::<f32>().ok())
+        .unwrap_or(DEFAULT_SIMILARITY_TOP_K_MIN_VALUE)
+}
\ No newline at end of file
@@ -0,0 +1,108 @@
+use crate::converters::extract_image_thumbnail::DEFAULT_THUMBNAIL_SIZE_LIST;
+use crate::models::image_thumbnail::ThumbnailFormat;
+
+// Thumbnail edge lengths generated per image, as a ',' separated list in VIVS_THUMBNAIL_SIZES. Colour, brightness
+// and similarity read the largest stored thumbnail no bigger than 32-128 px, so keep a size at or below those.
+pub const THUMBNAIL_SIZES_ENV_VAR: &str = "VIVS_THUMBNAIL_SIZES";
+
+// Encoding of new thumbnails: png (default) or jpg; set with VIVS_THUMBNAIL_FORMAT. WebP is left out since its
+// encoder is lossless only, which makes thumbnails larger than png without using the quality setting
+pub const DEFAULT_THUMBNAIL_FORMAT: ThumbnailFormat = ThumbnailFormat::PNG;
+pub const THUMBNAIL_FORMAT_ENV_VAR: &str = "VIVS_THUMBNAIL_FORMAT";
+
+// JPEG quality (1-100) of new thumbnails; override with VIVS_THUMBNAIL_QUALITY
+pub const DEFAULT_THUMBNAIL_QUALITY: u8 = 80;
+pub const THUMBNAIL_QUALITY_ENV_VAR: &str = "VIVS_THUMBNAIL_QUALITY";
+
+// Where new thumbnail data is written: "db" keeps it in image_thumbnail.thumbnail_data, "sqlite" in a separate
+// database file and "dir" in a content-addressed directory; set with VIVS_THUMBNAIL_STORAGE. The file and the
+// directory are set on their own, so thumbnails written before switching storage can still be read.
+pub const THUMBNAIL_STORAGE_ENV_VAR: &str = "VIVS_THUMBNAIL_STORAGE";
+pub const DEFAULT_THUMBNAIL_STORE_DB_FILE: &str = "/home/viveret/vivs-images-thumbnails.db";
+pub const THUMBNAIL_STORE_DB_FILE_ENV_VAR: &str = "VIVS_THUMBNAIL_STORE_DB_FILE";
+pub const DEFAULT_THUMBNAIL_STORE_DIR: &str = "/home/viveret/vivs-images-thumbnails";
+pub const THUMBNAIL_STORE_DIR_ENV_VAR: &str = "VIVS_THUMBNAIL_STORE_DIR";
+
+// Memory the in-process thumbnail cache may use before evicting; override with VIVS_THUMBNAIL_CACHE_MAX_MB
+pub const DEFAULT_THUMBNAIL_CACHE_MAX_MB: usize = 64;
+pub const THUMBNAIL_CACHE_MAX_MB_ENV_VAR: &str = "VIVS_THUMBNAIL_CACHE_MAX_MB";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ThumbnailStorage {
+    Database,
+    SqliteFile(String),
+    Directory(String),
+}
+
+impl ThumbnailStorage {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "db" | "inline" => Some(Self::Database),
+            "sqlite" => Some(Self::SqliteFile(get_env_path(THUMBNAIL_STORE_DB_FILE_ENV_VAR, DEFAULT_THUMBNAIL_STORE_DB_FILE))),
+            "dir" | "directory" => Some(Self::Directory(get_env_path(THUMBNAIL_STORE_DIR_ENV_VAR, DEFAULT_THUMBNAIL_STORE_DIR))),
+            _ => None,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Database => "db",
+            Self::SqliteFile(_) => "sqlite",
+            Self::Directory(_) => "dir",
+        }
+    }
+}
+
+fn get_env_path(env_var: &str, default: &str) -> String {
+    std::env::var(env_var).ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| default.to_string())
+}
+
+pub fn get_thumbnail_sizes() -> Vec<u32> {
+    std::env::var(THUMBNAIL_SIZES_ENV_VAR).ok()
+        .map(|v| parse_thumbnail_sizes(&v))
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| DEFAULT_THUMBNAIL_SIZE_LIST.to_vec())
+}
+
+// Sorted ascending without duplicates; anything that is not a positive number is skipped
+pub fn parse_thumbnail_sizes(s: &str) -> Vec<u32> {
+    let mut sizes: Vec<u32> = s.split([',', ' '])
+        .filter_map(|v| v.trim().parse::<u32>().ok())
+        .filter(|v| *v > 0)
+        .collect();
+    sizes.sort();
+    sizes.dedup();
+    sizes
+}
+
+pub fn get_thumbnail_format() -> ThumbnailFormat {
+    std::env::var(THUMBNAIL_FORMAT_ENV_VAR).ok()
+        .and_then(|v| parse_thumbnail_format(&v))
+        .unwrap_or(DEFAULT_THUMBNAIL_FORMAT)
+}
+
+pub fn parse_thumbnail_format(s: &str) -> Option<ThumbnailFormat> {
+    ThumbnailFormat::from_name(s).filter(|f| matches!(f, ThumbnailFormat::PNG | ThumbnailFormat::JPG))
+}
+
+pub fn get_thumbnail_quality() -> u8 {
+    std::env::var(THUMBNAIL_QUALITY_ENV_VAR).ok()
+        .and_then(|v| v.trim().parse::<u8>().ok())
+        .map(|q| q.clamp(1, 100))
+        .unwrap_or(DEFAULT_THUMBNAIL_QUALITY)
+}
+
+pub fn get_thumbnail_storage() -> ThumbnailStorage {
+    std::env::var(THUMBNAIL_STORAGE_ENV_VAR).ok()
+        .and_then(|v| ThumbnailStorage::from_name(&v))
+        .unwrap_or(ThumbnailStorage::Database)
+}
+
+pub fn get_thumbnail_cache_max_bytes() -> usize {
+    std::env::var(THUMBNAIL_CACHE_MAX_MB_ENV_VAR).ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .unwrap_or(DEFAULT_THUMBNAIL_CACHE_MAX_MB) * 1024 * 1024
+}
\ No newline at end of file
@@ -8,8 +8,18 @@ use crate::models::image_paths::ImagePaths;
 use crate::models::image_similarity::ImageSimilarity;
 use crate::models::image_exif::ImageExif;
 use crate::models::image_brightness::ImageBrightness;
+use crate::models::image_color::ImageColor;
+use crate::models::image_sharpness::ImageSharpness;
 use crate::models::image_thumbnail::ImageThumbnail;
 use crate::models::image_xmp::ImageXmp;
+use crate::models::image_exposure::ImageExposure;
+use crate::models::image_ocr_summary::ImageOcrSummary;
+use crate::models::image_content_hash::ImageContentHash;
+use crate::models::image_raw::ImageRaw;
+use crate::models::image_frames::ImageFrames;
+use crate::models::image_geocode::ImageGeocode;
+use crate::models::image_event::ImageEventMember;
+use crate::models::image_burst::ImageBurstFrame;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct ImageFieldMeta {
@@ -23,6 +33,9 @@ pub struct ImageFieldMeta {
     pub category: Option<String>,
 }
 
+// Selected by searches instead of the thumbnail itself, which pages load from /thumb
+pub const HAS_THUMBNAIL_COLUMN: &str = "has_thumbnail";
+
 #[derive(Debug, Clone)]
 pub struct Image {
     pub path: String,
@@ -30,10 +43,21 @@ pub struct Image {
     pub brightness: Option<ImageBrightness>,
     pub similarity: Option<ImageSimilarity>,
     pub thumbnail: Option<ImageThumbnail>,
+    pub has_thumbnail: bool,
     pub ocr_text: Option<ImageOcrText>,
     pub aspect_ratio: Option<ImageAspectRatio>,
     pub xmp: Option<ImageXmp>,
     pub iptc: Option<ImageIptc>,
+    pub color: Option<ImageColor>,
+    pub sharpness: Option<ImageSharpness>,
+    pub exposure: Option<ImageExposure>,
+    pub ocr_summary: Option<ImageOcrSummary>,
+    pub content_hash: Option<ImageContentHash>,
+    pub raw: Option<ImageRaw>,
+    pub frames: Option<ImageFrames>,
+    pub geocode: Option<ImageGeocode>,
+    pub event: Option<ImageEventMember>,
+    pub burst: Option<ImageBurstFrame>,
 }
 
 impl Image {
@@ -55,6 +79,16 @@ impl Image {
         let aspect_ratio = ImageAspectRatio::new(row);
         let xmp = ImageXmp::new(row);
         let iptc = ImageIptc::new(row);
+        let color = ImageColor::new(row);
+        let sharpness = ImageSharpness::new(row);
+        let exposure = ImageExposure::new(row);
+        let ocr_summary = ImageOcrSummary::new(row);
+        let content_hash = ImageContentHash::new(row);
+        let raw = ImageRaw::new(row);
+        let frames = ImageFrames::new(row);
+        let geocode = ImageGeocode::new(row);
+        let event = ImageEventMember::new(row);
+        let burst = ImageBurstFrame::new(row);
         let similarity = if tables_selected.contains(&"image_similarity".to_string()) {
             Some(ImageSimilarity::new(row))
         } else {
@@ -74,7 +108,18 @@ impl Image {
             aspect_ratio: Some(aspect_ratio),
             xmp: Some(xmp),
             iptc: Some(iptc),
+            color: Some(color),
+            sharpness: Some(sharpness),
+            exposure: Some(exposure),
+            ocr_summary: Some(ocr_summary),
+            content_hash: Some(content_hash),
+            raw: Some(raw).filter(|r| !r.raw_format.is_empty()),
+            frames: Some(frames).filter(|f| f.frame_count > 0),
+            geocode: Some(geocode).filter(|g| !g.place_dataset.is_empty()),
+            event: Some(event).filter(|e| e.event_id.is_some()),
+            burst: Some(burst).filter(|b| b.burst_id.is_some()),
             similarity,
+            has_thumbnail: thumbnail.is_some() || row.try_get::<bool, _>(HAS_THUMBNAIL_COLUMN).unwrap_or(false),
             thumbnail,
         }
     }
@@ -87,6 +132,16 @@ impl Image {
         x.extend_from_slice(&ImageAspectRatio::get_meta()[1..]);
         x.extend_from_slice(&ImageIptc::get_meta()[1..]);
         x.extend_from_slice(&ImageXmp::get_meta()[1..]);
+        x.extend_from_slice(&ImageColor::get_meta()[1..]);
+        x.extend_from_slice(&ImageSharpness::get_meta()[1..]);
+        x.extend_from_slice(&ImageExposure::get_meta()[1..]);
+        x.extend_from_slice(&ImageOcrSummary::get_meta()[1..]);
+        x.extend_from_slice(&ImageContentHash::get_meta()[1..]);
+        x.extend_from_slice(&ImageRaw::get_meta()[1..]);
+        x.extend_from_slice(&ImageFrames::get_meta()[1..]);
+        x.extend_from_slice(&ImageGeocode::get_meta()[1..]);
+        x.extend_from_slice(&ImageEventMember::get_meta()[1..]);
+        x.extend_from_slice(&ImageBurstFrame::get_meta()[1..]);
         x
     }
 
@@ -125,6 +180,36 @@ impl Image {
         if let Some(v) = self.xmp.as_ref().and_then(|s| s.get_field(field)) {
             return Some(v);
         }
+        if let Some(v) = self.color.as_ref().and_then(|s| s.get_field(field)) {
+            return Some(v);
+        }
+        if let Some(v) = self.sharpness.as_ref().and_then(|s| s.get_field(field)) {
+            return Some(v);
+        }
+        if let Some(v) = self.exposure.as_ref().and_then(|s| s.get_field(field)) {
+            return Some(v);
+        }
+        if let Some(v) = self.ocr_summary.as_ref().and_then(|s| s.get_field(field)) {
+            return Some(v);
+        }
+        if let Some(v) = self.content_hash.as_ref().and_then(|s| s.get_field(field)) {
+            return Some(v);
+        }
+        if let Some(v) = self.raw.as_ref().and_then(|s| s.get_field(field)) {
+            return Some(v);
+        }
+        if let Some(v) = self.frames.as_ref().and_then(|s| s.get_field(field)) {
+            return Some(v);
+        }
+        if let Some(v) = self.geocode.as_ref().and_then(|s| s.get_field(field)) {
+            return Some(v);
+        }
+        if let Some(v) = self.event.as_ref().and_then(|s| s.get_field(field)) {
+            return Some(v);
+        }
+        if let Some(v) = self.burst.as_ref().and_then(|s| s.get_field(field)) {
+            return Some(v);
+        }
         None
     }
     
@@ -1,28 +1,108 @@
+use std::fmt::Display;
+
 use serde::Deserialize;
 use sqlx::Row;
 
 use crate::models::image::ImageFieldMeta;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+pub enum ImageToBrightnessAlgo {
+    // mean of the image crate's greyscale conversion (gamma-encoded luma)
+    SimpleImageRS,
+    // mean of (r + g + b) / 3
+    SimpleInHouse,
+    // mean Rec.709 relative luminance of linear-light values
+    Rec709LinearLight,
+    // mean CIE L*
+    PerceptualLightness,
+    // mean CIE L* weighted towards the centre of the frame
+    CenterWeighted,
+}
+
+impl ImageToBrightnessAlgo {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::SimpleImageRS,
+            Self::SimpleInHouse,
+            Self::Rec709LinearLight,
+            Self::PerceptualLightness,
+            Self::CenterWeighted,
+        ]
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::SimpleImageRS => "simple_image_rs",
+            Self::SimpleInHouse => "simple_in_house",
+            Self::Rec709LinearLight => "rec709_linear_light",
+            Self::PerceptualLightness => "perceptual_lightness",
+            Self::CenterWeighted => "center_weighted",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all().into_iter().find(|a| a.get_name() == name)
+    }
+}
+
+impl TryFrom<u8> for ImageToBrightnessAlgo {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::SimpleImageRS),
+            1 => Ok(Self::SimpleInHouse),
+            2 => Ok(Self::Rec709LinearLight),
+            3 => Ok(Self::PerceptualLightness),
+            4 => Ok(Self::CenterWeighted),
+            _ => Err(())
+        }
+    }
+}
+
+impl From<&ImageToBrightnessAlgo> for u8 {
+    fn from(value: &ImageToBrightnessAlgo) -> Self {
+        match value {
+            ImageToBrightnessAlgo::SimpleImageRS => 0,
+            ImageToBrightnessAlgo::SimpleInHouse => 1,
+            ImageToBrightnessAlgo::Rec709LinearLight => 2,
+            ImageToBrightnessAlgo::PerceptualLightness => 3,
+            ImageToBrightnessAlgo::CenterWeighted => 4,
+        }
+    }
+}
+
+impl Display for ImageToBrightnessAlgo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let v: u8 = self.into();
+        f.write_str(&format!("{}", v))
+    }
+}
+
 // Struct to hold mapping of an image path to calculated brightness
 #[derive(Debug, Clone, Deserialize)]
 pub struct ImageBrightness {
     pub image_path: String,
     pub brightness: f32,
+    pub brightness_algo: ImageToBrightnessAlgo,
 }
 
 pub const IMAGE_BRIGHTNESS_COLUMNS_JSON: &str = r#"
 [
     {"name": "image_path", "label": "Image Path", "description": "The file path of the image", "field_type": "string", "example": "/images/photo.jpg", "category": "general", "table_name": "image_brightness"},
-    {"name": "brightness", "label": "Brightness", "description": "The brightness of the image", "field_type": "f32", "example":"0.4", "category": "general", "table_name": "image_brightness"}
+    {"name": "brightness", "label": "Brightness", "description": "The brightness of the image", "field_type": "f32", "example":"0.4", "category": "general", "table_name": "image_brightness"},
+    {"name": "brightness_algo", "label": "Brightness Algorithm", "description": "The algorithm that produced the brightness: 0 = greyscale mean, 1 = RGB mean, 2 = Rec.709 linear-light luminance, 3 = perceptual L*, 4 = centre-weighted L*", "field_type": "u8", "example": "3", "category": "general", "table_name": "image_brightness"}
 ]"#;
 
 impl ImageBrightness {
     pub fn new(row: &sqlx::sqlite::SqliteRow) -> Self {
         let image_path: String = row.try_get("image_path").unwrap_or_default();
         let brightness: f32 = row.try_get("brightness").unwrap_or(0.0);
+        let brightness_algo: u8 = row.try_get("brightness_algo").unwrap_or(0);
         ImageBrightness {
             image_path,
             brightness,
+            brightness_algo: brightness_algo.try_into().unwrap_or(ImageToBrightnessAlgo::SimpleImageRS),
         }
     }
 
@@ -30,6 +110,7 @@ impl ImageBrightness {
         match field {
             "image_path" => Some(self.image_path.clone()),
             "brightness" => Some(format!("{:.2}", self.brightness)),
+            "brightness_algo" => Some(self.brightness_algo.to_string()),
             _ => None,
         }
     }
@@ -41,6 +122,6 @@ impl ImageBrightness {
 
 impl std::fmt::Display for ImageBrightness {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "brightness: {}", self.brightness)
+        write!(f, "brightness: {} ({})", self.brightness, self.brightness_algo.get_name())
     }
 }
\ No newline at end of file
@@ -0,0 +1,201 @@
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use sqlx::Row;
+
+use crate::models::image::ImageFieldMeta;
+
+// A run of near-identical frames taken seconds apart, as stored by the burst detection action
+#[derive(Debug, Clone)]
+pub struct ImageBurst {
+    pub burst_id: i64,
+    pub burst_start: String,
+    pub burst_end: String,
+    pub burst_frame_count: i64,
+    pub burst_keeper_image_path: String,
+    pub burst_reviewed: i64,
+}
+
+impl ImageBurst {
+    pub fn new(row: &sqlx::sqlite::SqliteRow) -> Self {
+        ImageBurst {
+            burst_id: row.try_get("burst_id").unwrap_or_default(),
+            burst_start: row.try_get("burst_start").unwrap_or_default(),
+            burst_end: row.try_get("burst_end").unwrap_or_default(),
+            burst_frame_count: row.try_get("burst_frame_count").unwrap_or_default(),
+            burst_keeper_image_path: row.try_get("burst_keeper_image_path").unwrap_or_default(),
+            burst_reviewed: row.try_get("burst_reviewed").unwrap_or_default(),
+        }
+    }
+
+    pub fn is_reviewed(&self) -> bool {
+        self.burst_reviewed != 0
+    }
+
+    pub fn get_search_href(&self) -> String {
+        get_burst_search_href(self.burst_id)
+    }
+}
+
+// The search listing a burst's frames in the order they were taken
+fn get_burst_search_href(burst_id: i64) -> String {
+    format!("/search?burst_id={}&order_by=image_taken_at+ASC", burst_id)
+}
+
+impl std::fmt::Display for ImageBurst {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} to {} ({} frames)", self.burst_start, self.burst_end, self.burst_frame_count)
+    }
+}
+
+// How an image fared in burst detection. Images that were checked but turned out not to be part of a burst keep a
+// row without a burst_id, so they are not checked again
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageBurstFrame {
+    pub image_path: String,
+    pub burst_id: Option<i64>,
+    pub burst_frame_score: Option<f64>,
+    pub burst_frame_similarity: Option<f64>,
+    pub burst_is_keeper: Option<i64>,
+}
+
+pub const IMAGE_BURST_FRAME_COLUMNS_JSON: &str = r#"
+[
+    {"name": "image_path", "label": "Image Path", "description": "The file path of the image", "field_type": "string", "example": "/images/photo.jpg", "category": "general", "table_name": "image_burst_frame"},
+    {"name": "burst_id", "label": "Burst", "description": "The burst of near-identical frames the image was taken in", "field_type": "integer", "example": "7", "category": "general", "table_name": "image_burst_frame"},
+    {"name": "burst_frame_score", "label": "Burst Frame Score", "description": "How good the frame is compared to the rest of its burst, from sharpness, brightness and exposure", "field_type": "float", "example": "92.5", "category": "general", "table_name": "image_burst_frame"},
+    {"name": "burst_frame_similarity", "label": "Burst Frame Similarity", "description": "Thumbnail similarity to the previous frame of the burst", "field_type": "float", "example": "0.93", "category": "general", "table_name": "image_burst_frame"},
+    {"name": "burst_is_keeper", "label": "Burst Keeper", "description": "1 for the frame suggested to keep from its burst, 0 for the rest", "field_type": "integer", "example": "1", "category": "general", "table_name": "image_burst_frame"}
+]"#;
+
+impl ImageBurstFrame {
+    pub fn new(row: &sqlx::sqlite::SqliteRow) -> Self {
+        ImageBurstFrame {
+            image_path: row.try_get("image_path").unwrap_or_default(),
+            burst_id: row.try_get("burst_id").unwrap_or_default(),
+            burst_frame_score: row.try_get("burst_frame_score").unwrap_or_default(),
+            burst_frame_similarity: row.try_get("burst_frame_similarity").unwrap_or_default(),
+            burst_is_keeper: row.try_get("burst_is_keeper").unwrap_or_default(),
+        }
+    }
+
+    pub fn is_keeper(&self) -> bool {
+        self.burst_is_keeper.unwrap_or_default() != 0
+    }
+
+    pub fn get_search_href(&self) -> Option<String> {
+        self.burst_id.map(get_burst_search_href)
+    }
+
+    pub fn get_field(&self, field: &str) -> Option<String> {
+        match field {
+            "image_path" => Some(self.image_path.clone()),
+            "burst_id" => self.burst_id.map(|id| id.to_string()),
+            "burst_frame_score" => self.burst_frame_score.map(|s| format!("{:.1}", s)),
+            "burst_frame_similarity" => self.burst_frame_similarity.map(|s| format!("{:.3}", s)),
+            "burst_is_keeper" => self.burst_is_keeper.map(|k| k.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn get_meta() -> Vec<ImageFieldMeta> {
+        serde_json::from_str::<Vec<ImageFieldMeta>>(IMAGE_BURST_FRAME_COLUMNS_JSON).unwrap()
+    }
+}
+
+// What burst detection needs to know about each image with a capture time. Brightness is 0 to 1 and the clipped
+// values are percentages, as stored by the brightness and exposure actions
+#[derive(Debug, Clone)]
+pub struct ImageBurstCandidate {
+    pub image_path: String,
+    pub local_time: NaiveDateTime,
+    pub sharpness: Option<f64>,
+    pub brightness: Option<f64>,
+    pub clipped_highlights: Option<f64>,
+    pub clipped_shadows: Option<f64>,
+}
+
+// Consecutive images taken no more than the burst gap apart, which thumbnail similarity may split further
+#[derive(Debug, Clone)]
+pub struct ImageBurstRun {
+    pub frames: Vec<ImageBurstCandidate>,
+}
+
+impl ImageBurstRun {
+    pub fn get_image_paths(&self) -> Vec<String> {
+        self.frames.iter().map(|f| f.image_path.clone()).collect()
+    }
+}
+
+impl std::fmt::Display for ImageBurstRun {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.frames.first(), self.frames.last()) {
+            (Some(first), Some(last)) => write!(f, "{} to {} ({} images)", first.local_time, last.local_time, self.frames.len()),
+            _ => write!(f, "empty run"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageBurstAnalysis {
+    pub candidate_count: usize,
+    pub runs: Vec<ImageBurstRun>,
+}
+
+impl std::fmt::Display for ImageBurstAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} runs of images taken close together from {} images with a capture time", self.runs.len(), self.candidate_count)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageBurstScoredFrame {
+    pub image_path: String,
+    pub local_time: NaiveDateTime,
+    pub score: f64,
+    // to the previous frame; None for the first frame of a burst
+    pub similarity: Option<f32>,
+}
+
+// One burst found by detection, before it is stored and given an id
+#[derive(Debug, Clone)]
+pub struct ImageBurstCluster {
+    pub frames: Vec<ImageBurstScoredFrame>,
+    pub keeper_image_path: String,
+}
+
+impl ImageBurstCluster {
+    pub fn get_start(&self) -> Option<NaiveDateTime> {
+        self.frames.first().map(|f| f.local_time)
+    }
+
+    pub fn get_end(&self) -> Option<NaiveDateTime> {
+        self.frames.last().map(|f| f.local_time)
+    }
+}
+
+// The bursts found in one run; they replace whatever was stored for the run's images
+#[derive(Debug, Clone)]
+pub struct ImageBurstScan {
+    pub image_paths: Vec<String>,
+    pub bursts: Vec<ImageBurstCluster>,
+}
+
+impl std::fmt::Display for ImageBurstScan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} bursts in {} images", self.bursts.len(), self.image_paths.len())
+    }
+}
+
+// A stored burst with its frames in the order they were taken, for the review page
+#[derive(Debug, Clone)]
+pub struct ImageBurstReview {
+    pub burst: ImageBurst,
+    pub frames: Vec<ImageBurstReviewFrame>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageBurstReviewFrame {
+    pub frame: ImageBurstFrame,
+    pub taken_at: String,
+    pub tagged_for_deletion: bool,
+}
\ No newline at end of file
@@ -0,0 +1,75 @@
+use serde::Deserialize;
+use sqlx::Row;
+
+use crate::calc::color_palette::{parse_histogram, parse_palette, PaletteColor};
+use crate::models::image::ImageFieldMeta;
+
+// Struct to hold mapping of an image path to its calculated colour information
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageColor {
+    pub image_path: String,
+    pub dominant_color: String,
+    pub color_palette: String,
+    pub hsv_histogram: String,
+    pub average_saturation: f32,
+    pub average_warmth: f32,
+}
+
+pub const IMAGE_COLOR_COLUMNS_JSON: &str = r##"
+[
+    {"name": "image_path", "label": "Image Path", "description": "The file path of the image", "field_type": "string", "example": "/images/photo.jpg", "category": "general", "table_name": "image_color"},
+    {"name": "dominant_color", "label": "Dominant Color", "description": "The most common colour of the image as a hex code", "field_type": "string", "example": "#3a6ea5", "category": "color", "table_name": "image_color"},
+    {"name": "color_palette", "label": "Color Palette", "description": "Dominant colours (median cut over a thumbnail) with the fraction of the image each covers", "field_type": "string", "example": "#3a6ea5:0.4200,#d9c8a0:0.2100", "category": "color", "table_name": "image_color"},
+    {"name": "hsv_histogram", "label": "HSV Histogram", "description": "Fraction of pixels in 12 hue bins followed by dark, mid and light grey bins", "field_type": "string", "example": "0.1000,0.0500,...", "category": "color", "table_name": "image_color"},
+    {"name": "average_saturation", "label": "Average Saturation", "description": "The mean HSV saturation of the image, from 0 (grey) to 1 (vivid)", "field_type": "f32", "example": "0.4", "category": "color", "table_name": "image_color"},
+    {"name": "average_warmth", "label": "Average Warmth", "description": "The mean red/blue balance of the image, from -1 (cool) to 1 (warm)", "field_type": "f32", "example": "0.1", "category": "color", "table_name": "image_color"}
+]"##;
+
+impl ImageColor {
+    pub fn new(row: &sqlx::sqlite::SqliteRow) -> Self {
+        let image_path: String = row.try_get("image_path").unwrap_or_default();
+        let dominant_color: String = row.try_get("dominant_color").unwrap_or_default();
+        let color_palette: String = row.try_get("color_palette").unwrap_or_default();
+        let hsv_histogram: String = row.try_get("hsv_histogram").unwrap_or_default();
+        let average_saturation: f32 = row.try_get("average_saturation").unwrap_or(0.0);
+        let average_warmth: f32 = row.try_get("average_warmth").unwrap_or(0.0);
+        ImageColor {
+            image_path,
+            dominant_color,
+            color_palette,
+            hsv_histogram,
+            average_saturation,
+            average_warmth,
+        }
+    }
+
+    pub fn get_field(&self, field: &str) -> Option<String> {
+        match field {
+            "image_path" => Some(self.image_path.clone()),
+            "dominant_color" => Some(self.dominant_color.clone()),
+            "color_palette" => Some(self.color_palette.clone()),
+            "hsv_histogram" => Some(self.hsv_histogram.clone()),
+            "average_saturation" => Some(format!("{:.4}", self.average_saturation)),
+            "average_warmth" => Some(format!("{:.4}", self.average_warmth)),
+            _ => None,
+        }
+    }
+
+    pub fn get_meta() -> Vec<ImageFieldMeta> {
+        serde_json::from_str::<Vec<ImageFieldMeta>>(IMAGE_COLOR_COLUMNS_JSON).unwrap()
+    }
+
+    pub fn get_palette(&self) -> Vec<PaletteColor> {
+        parse_palette(&self.color_palette)
+    }
+
+    pub fn get_histogram(&self) -> Vec<f32> {
+        parse_histogram(&self.hsv_histogram)
+    }
+}
+
+impl std::fmt::Display for ImageColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "dominant_color: {}", self.dominant_color)
+    }
+}
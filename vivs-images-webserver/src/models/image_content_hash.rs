@@ -0,0 +1,51 @@
+use serde::Deserialize;
+use sqlx::Row;
+
+use crate::models::image::ImageFieldMeta;
+
+// Struct to hold mapping of an image path to a hash of the file contents, used to follow files that are moved or renamed
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageContentHash {
+    pub image_path: String,
+    pub content_hash: String,
+    pub file_size: i64,
+}
+
+pub const IMAGE_CONTENT_HASH_COLUMNS_JSON: &str = r#"
+[
+    {"name": "image_path", "label": "Image Path", "description": "The file path of the image", "field_type": "string", "example": "/images/photo.jpg", "category": "general", "table_name": "image_content_hash"},
+    {"name": "content_hash", "label": "Content Hash", "description": "SHA-256 of the file bytes as lowercase hex; the same file keeps the same hash when moved or renamed", "field_type": "string", "example": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08", "category": "general", "table_name": "image_content_hash"},
+    {"name": "file_size", "label": "File Size", "description": "The size of the file in bytes when it was hashed", "field_type": "integer", "example": "2048000", "category": "general", "table_name": "image_content_hash"}
+]"#;
+
+impl ImageContentHash {
+    pub fn new(row: &sqlx::sqlite::SqliteRow) -> Self {
+        let image_path: String = row.try_get("image_path").unwrap_or_default();
+        let content_hash: String = row.try_get("content_hash").unwrap_or_default();
+        let file_size: i64 = row.try_get("file_size").unwrap_or(0);
+        ImageContentHash {
+            image_path,
+            content_hash,
+            file_size,
+        }
+    }
+
+    pub fn get_field(&self, field: &str) -> Option<String> {
+        match field {
+            "image_path" => Some(self.image_path.clone()),
+            "content_hash" => Some(self.content_hash.clone()),
+            "file_size" => Some(self.file_size.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn get_meta() -> Vec<ImageFieldMeta> {
+        serde_json::from_str::<Vec<ImageFieldMeta>>(IMAGE_CONTENT_HASH_COLUMNS_JSON).unwrap()
+    }
+}
+
+impl std::fmt::Display for ImageContentHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "content hash: {} ({} bytes)", self.content_hash, self.file_size)
+    }
+}
\ No newline at end of file
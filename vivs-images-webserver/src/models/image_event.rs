@@ -0,0 +1,121 @@
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use sqlx::Row;
+
+use crate::models::image::ImageFieldMeta;
+use crate::models::query_params::geo_search_params::GeoPoint;
+
+// A run of photos taken close together in time and place, as stored by the event clustering action
+#[derive(Debug, Clone)]
+pub struct ImageEvent {
+    pub event_id: i64,
+    pub event_start: String,
+    pub event_end: String,
+    pub event_image_count: i64,
+    pub event_cover_image_path: String,
+    pub event_latitude: Option<f64>,
+    pub event_longitude: Option<f64>,
+    // the nearest city or country of the first member image that has one, when the images were geocoded
+    pub event_place: Option<String>,
+}
+
+impl ImageEvent {
+    pub fn new(row: &sqlx::sqlite::SqliteRow) -> Self {
+        ImageEvent {
+            event_id: row.try_get("event_id").unwrap_or_default(),
+            event_start: row.try_get("event_start").unwrap_or_default(),
+            event_end: row.try_get("event_end").unwrap_or_default(),
+            event_image_count: row.try_get("event_image_count").unwrap_or_default(),
+            event_cover_image_path: row.try_get("event_cover_image_path").unwrap_or_default(),
+            event_latitude: row.try_get("event_latitude").unwrap_or_default(),
+            event_longitude: row.try_get("event_longitude").unwrap_or_default(),
+            event_place: row.try_get("event_place").unwrap_or_default(),
+        }
+    }
+
+    pub fn get_location(&self) -> Option<GeoPoint> {
+        GeoPoint::new(self.event_latitude?, self.event_longitude?)
+    }
+
+    // The same search the events page links to, listing the event's images in the order they were taken
+    pub fn get_search_href(&self) -> String {
+        format!("/search?event_id={}&order_by=image_taken_at+ASC", self.event_id)
+    }
+}
+
+impl std::fmt::Display for ImageEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} to {} ({} images)", self.event_start, self.event_end, self.event_image_count)
+    }
+}
+
+// Which event an image belongs to
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageEventMember {
+    pub image_path: String,
+    pub event_id: Option<i64>,
+}
+
+pub const IMAGE_EVENT_MEMBER_COLUMNS_JSON: &str = r#"
+[
+    {"name": "image_path", "label": "Image Path", "description": "The file path of the image", "field_type": "string", "example": "/images/photo.jpg", "category": "general", "table_name": "image_event_member"},
+    {"name": "event_id", "label": "Event", "description": "The event the image was grouped into by capture time and location", "field_type": "integer", "example": "12", "category": "general", "table_name": "image_event_member"}
+]"#;
+
+impl ImageEventMember {
+    pub fn new(row: &sqlx::sqlite::SqliteRow) -> Self {
+        let image_path: String = row.try_get("image_path").unwrap_or_default();
+        let event_id: Option<i64> = row.try_get("event_id").unwrap_or_default();
+        ImageEventMember { image_path, event_id }
+    }
+
+    pub fn get_field(&self, field: &str) -> Option<String> {
+        match field {
+            "image_path" => Some(self.image_path.clone()),
+            "event_id" => self.event_id.map(|id| id.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn get_meta() -> Vec<ImageFieldMeta> {
+        serde_json::from_str::<Vec<ImageFieldMeta>>(IMAGE_EVENT_MEMBER_COLUMNS_JSON).unwrap()
+    }
+}
+
+// What clustering needs to know about each image with a capture time
+#[derive(Debug, Clone)]
+pub struct ImageEventPhoto {
+    pub image_path: String,
+    pub local_time: NaiveDateTime,
+    pub location: Option<GeoPoint>,
+    pub sharpness: Option<f64>,
+}
+
+// One event found by clustering, before it is stored and given an id
+#[derive(Debug, Clone)]
+pub struct ImageEventCluster {
+    pub image_paths: Vec<String>,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    pub cover_image_path: String,
+    pub location: Option<GeoPoint>,
+}
+
+impl std::fmt::Display for ImageEventCluster {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} to {} ({} images)", self.start, self.end, self.image_paths.len())
+    }
+}
+
+// Every event found in one clustering run; they replace the stored events as a whole
+#[derive(Debug, Clone)]
+pub struct ImageEventClustering {
+    pub photo_count: usize,
+    pub events: Vec<ImageEventCluster>,
+}
+
+impl std::fmt::Display for ImageEventClustering {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} events from {} images with a capture time", self.events.len(), self.photo_count)
+    }
+}
\ No newline at end of file
@@ -8,6 +8,8 @@ use crate::models::image::ImageFieldMeta;
 pub struct ImageExif {
     pub image_path: String,
     pub image_taken_at: Option<String>,
+    pub image_taken_at_offset: Option<String>,
+    pub image_taken_at_source: Option<String>,
     pub camera_make: Option<String>,
     pub camera_model: Option<String>,
     pub lens_model: Option<String>,
@@ -157,7 +159,9 @@ pub struct ImageExif {
 pub const IMAGE_EXIF_COLUMNS_JSON: &str = r#"
 [
     {"name": "image_path", "label": "Image Path", "description": "The file path of the image", "field_type": "string", "example": "/images/photo.jpg", "category": "general", "table_name": "image_exif"},
-    {"name": "image_taken_at", "label": "Taken At", "description": "The date and time when the image was taken", "field_type": "datetime", "example": "2023-01-01T12:00:00Z", "category": "exif", "table_name": "image_exif"},
+    {"name": "image_taken_at", "label": "Taken At", "description": "The date and time when the image was taken", "field_type": "datetime", "example": "2023-01-01T12:00:00", "category": "exif", "table_name": "image_exif"},
+    {"name": "image_taken_at_offset", "label": "Taken At Offset", "description": "The offset from UTC of the local time the image was taken at, when known", "field_type": "string", "example": "+02:00", "category": "exif", "table_name": "image_exif"},
+    {"name": "image_taken_at_source", "label": "Taken At Source", "description": "Where the time the image was taken was read from: exif, gps, xmp, filename or mtime", "field_type": "string", "example": "exif", "category": "exif", "table_name": "image_exif"},
     {"name": "camera_make", "label": "Camera Make", "description": "The manufacturer of the camera", "field_type": "string", "example": "Canon", "category": "exif", "table_name": "image_exif"},
     {"name": "camera_model", "label": "Camera Model", "description": "The model of the camera", "field_type": "string", "example": "EOS 5D Mark IV", "category": "exif", "table_name": "image_exif"},
     {"name": "lens_model", "label": "Lens Model", "description": "The model of the lens used", "field_type": "string", "example": "EF24-70mm f/2.8L II USM", "category": "exif", "table_name": "image_exif"},
@@ -330,16 +334,19 @@ impl ImageExif {
                 row.try_get(stringify!($field)).ok()
             };
             ($field:ident, parse $type:ty) => {
-                row.try_get::<Option<String>, _>(stringify!($field))
-                    .ok()
-                    .flatten()
-                    .and_then(|v| if v.is_empty() { None } else { v.parse().ok() })
+                match row.try_get::<Option<String>, _>(stringify!($field)) {
+                    Ok(v) => v.and_then(|v| if v.is_empty() { None } else { v.parse().ok() }),
+                    // REAL and INTEGER values don't decode as text
+                    Err(_) => row.try_get::<Option<$type>, _>(stringify!($field)).ok().flatten(),
+                }
             };
         }
 
         ImageExif {
             image_path: extract_field!(image_path, String).unwrap_or_default(),
             image_taken_at: extract_field!(image_taken_at, String),
+            image_taken_at_offset: extract_field!(image_taken_at_offset, String),
+            image_taken_at_source: extract_field!(image_taken_at_source, String),
             camera_make: extract_field!(camera_make, String),
             camera_model: extract_field!(camera_model, String),
             lens_model: extract_field!(lens_model, String),
@@ -491,6 +498,8 @@ impl ImageExif {
         match field {
             "image_path" => Some(self.image_path.clone()),
             "image_taken_at" => self.image_taken_at.clone(),
+            "image_taken_at_offset" => self.image_taken_at_offset.clone(),
+            "image_taken_at_source" => self.image_taken_at_source.clone(),
             "camera_make" => self.camera_make.clone(),
             "camera_model" => self.camera_model.clone(),
             "lens_model" => self.lens_model.clone(),
@@ -646,6 +655,8 @@ impl ImageExif {
 
         match key {
             "image_taken_at" => set_string(&mut self.image_taken_at),
+            "image_taken_at_offset" => set_string(&mut self.image_taken_at_offset),
+            "image_taken_at_source" => set_string(&mut self.image_taken_at_source),
             "camera_make" => set_string(&mut self.camera_make),
             "camera_model" => set_string(&mut self.camera_model),
             "lens_model" => set_string(&mut self.lens_model),
@@ -966,6 +977,8 @@ impl ImageExif {
         Self {
             image_path: image_path.to_string(),
             image_taken_at: None,
+            image_taken_at_offset: None,
+            image_taken_at_source: None,
             camera_make: None,
             camera_model: None,
             lens_model: None,
@@ -1120,6 +1133,8 @@ impl std::fmt::Display for ImageExif {
         write!(f, "camera_make: {:?}, , ", self.camera_make)?;
         write!(f, "orientation: {:?}, ", self.orientation)?;
         write!(f, "image_taken_at: {:?}, ", self.image_taken_at)?;
+        write!(f, "image_taken_at_offset: {:?}, ", self.image_taken_at_offset)?;
+        write!(f, "image_taken_at_source: {:?}, ", self.image_taken_at_source)?;
         write!(f, "camera_make: {:?}, ", self.camera_make)?;
         write!(f, "camera_model: {:?}, ", self.camera_model)?;
         write!(f, "lens_model: {:?}, ", self.lens_model)?;
@@ -0,0 +1,81 @@
+use serde::Deserialize;
+use sqlx::Row;
+
+use crate::calc::color_palette::parse_histogram;
+use crate::models::image::ImageFieldMeta;
+
+// Struct to hold mapping of an image path to its clipping, luma percentiles and contrast
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageExposure {
+    pub image_path: String,
+    pub clipped_highlights: f32,
+    pub clipped_shadows: f32,
+    pub luma_p5: i32,
+    pub luma_p50: i32,
+    pub luma_p95: i32,
+    pub contrast: f32,
+    pub luma_histogram: String,
+}
+
+pub const IMAGE_EXPOSURE_COLUMNS_JSON: &str = r#"
+[
+    {"name": "image_path", "label": "Image Path", "description": "The file path of the image", "field_type": "string", "example": "/images/photo.jpg", "category": "general", "table_name": "image_exposure"},
+    {"name": "clipped_highlights", "label": "Clipped Highlights %", "description": "Percentage of pixels that are blown out to (near) white", "field_type": "f32", "example": "2.5", "category": "general", "table_name": "image_exposure"},
+    {"name": "clipped_shadows", "label": "Clipped Shadows %", "description": "Percentage of pixels that are crushed to (near) black", "field_type": "f32", "example": "1.0", "category": "general", "table_name": "image_exposure"},
+    {"name": "luma_p5", "label": "Luma 5th Percentile", "description": "Luma value (0-255) that 5% of the pixels are darker than", "field_type": "i32", "example": "12", "category": "general", "table_name": "image_exposure"},
+    {"name": "luma_p50", "label": "Luma Median", "description": "Median luma value (0-255)", "field_type": "i32", "example": "118", "category": "general", "table_name": "image_exposure"},
+    {"name": "luma_p95", "label": "Luma 95th Percentile", "description": "Luma value (0-255) that 95% of the pixels are darker than", "field_type": "i32", "example": "235", "category": "general", "table_name": "image_exposure"},
+    {"name": "contrast", "label": "Contrast", "description": "RMS contrast, the standard deviation of luma from 0 (flat) to 0.5 (black and white only)", "field_type": "f32", "example": "0.22", "category": "general", "table_name": "image_exposure"},
+    {"name": "luma_histogram", "label": "Luma Histogram", "description": "Comma separated fraction of pixels in each of 32 luma bins, darkest first", "field_type": "string", "example": "0.0100,0.0250,...", "category": "general", "table_name": "image_exposure"}
+]"#;
+
+impl ImageExposure {
+    pub fn new(row: &sqlx::sqlite::SqliteRow) -> Self {
+        let image_path: String = row.try_get("image_path").unwrap_or_default();
+        let clipped_highlights: f32 = row.try_get("clipped_highlights").unwrap_or(0.0);
+        let clipped_shadows: f32 = row.try_get("clipped_shadows").unwrap_or(0.0);
+        let luma_p5: i32 = row.try_get("luma_p5").unwrap_or(0);
+        let luma_p50: i32 = row.try_get("luma_p50").unwrap_or(0);
+        let luma_p95: i32 = row.try_get("luma_p95").unwrap_or(0);
+        let contrast: f32 = row.try_get("contrast").unwrap_or(0.0);
+        let luma_histogram: String = row.try_get("luma_histogram").unwrap_or_default();
+        ImageExposure {
+            image_path,
+            clipped_highlights,
+            clipped_shadows,
+            luma_p5,
+            luma_p50,
+            luma_p95,
+            contrast,
+            luma_histogram,
+        }
+    }
+
+    pub fn get_histogram(&self) -> Vec<f32> {
+        parse_histogram(&self.luma_histogram)
+    }
+
+    pub fn get_field(&self, field: &str) -> Option<String> {
+        match field {
+            "image_path" => Some(self.image_path.clone()),
+            "clipped_highlights" => Some(format!("{:.2}", self.clipped_highlights)),
+            "clipped_shadows" => Some(format!("{:.2}", self.clipped_shadows)),
+            "luma_p5" => Some(self.luma_p5.to_string()),
+            "luma_p50" => Some(self.luma_p50.to_string()),
+            "luma_p95" => Some(self.luma_p95.to_string()),
+            "contrast" => Some(format!("{:.4}", self.contrast)),
+            "luma_histogram" => Some(self.luma_histogram.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn get_meta() -> Vec<ImageFieldMeta> {
+        serde_json::from_str::<Vec<ImageFieldMeta>>(IMAGE_EXPOSURE_COLUMNS_JSON).unwrap()
+    }
+}
+
+impl std::fmt::Display for ImageExposure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "highlights: {:.2}%, shadows: {:.2}%, median: {}", self.clipped_highlights, self.clipped_shadows, self.luma_p50)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,94 @@
+use serde::Deserialize;
+use sqlx::Row;
+
+use crate::models::image::ImageFieldMeta;
+use crate::models::image_thumbnail::ImageThumbnail;
+
+// Struct to hold how many frames (GIF/WebP) or pages (TIFF) a file has and, when animated, how long it plays for
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageFrames {
+    pub image_path: String,
+    pub frame_count: u32,
+    pub is_animated: u32,
+    pub animation_duration_ms: u32,
+}
+
+pub const IMAGE_FRAMES_COLUMNS_JSON: &str = r#"
+[
+    {"name": "image_path", "label": "Image Path", "description": "The file path of the image", "field_type": "string", "example": "/images/photo.gif", "category": "general", "table_name": "image_frames"},
+    {"name": "frame_count", "label": "Frame Count", "description": "The number of animation frames, or pages for a TIFF", "field_type": "u32", "example": "12", "category": "general", "table_name": "image_frames"},
+    {"name": "is_animated", "label": "Animated", "description": "1 for an animated GIF or WebP with more than one frame, otherwise 0", "field_type": "u32", "example": "1", "category": "general", "table_name": "image_frames"},
+    {"name": "animation_duration_ms", "label": "Animation Duration (ms)", "description": "The sum of the frame delays of one loop of the animation", "field_type": "u32", "example": "1200", "category": "general", "table_name": "image_frames"}
+]"#;
+
+impl ImageFrames {
+    pub fn new(row: &sqlx::sqlite::SqliteRow) -> Self {
+        let image_path: String = row.try_get("image_path").unwrap_or_default();
+        let frame_count: u32 = row.try_get("frame_count").unwrap_or_default();
+        let is_animated: u32 = row.try_get("is_animated").unwrap_or_default();
+        let animation_duration_ms: u32 = row.try_get("animation_duration_ms").unwrap_or_default();
+        ImageFrames {
+            image_path,
+            frame_count,
+            is_animated,
+            animation_duration_ms,
+        }
+    }
+
+    pub fn get_field(&self, field: &str) -> Option<String> {
+        match field {
+            "image_path" => Some(self.image_path.clone()),
+            "frame_count" => Some(self.frame_count.to_string()),
+            "is_animated" => Some(self.is_animated.to_string()),
+            "animation_duration_ms" => Some(self.animation_duration_ms.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn get_meta() -> Vec<ImageFieldMeta> {
+        serde_json::from_str::<Vec<ImageFieldMeta>>(IMAGE_FRAMES_COLUMNS_JSON).unwrap()
+    }
+}
+
+impl std::fmt::Display for ImageFrames {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_animated != 0 {
+            write!(f, "{} frames, {:.2}s", self.frame_count, self.animation_duration_ms as f32 / 1000.0)
+        } else {
+            write!(f, "{} pages", self.frame_count)
+        }
+    }
+}
+
+// A thumbnail of one frame or page, kept for an evenly spaced subset of them
+#[derive(Debug, Clone)]
+pub struct ImageFrameThumbnail {
+    pub frame_index: u32,
+    pub frame_delay_ms: u32,
+    pub thumbnail: ImageThumbnail,
+}
+
+impl ImageFrameThumbnail {
+    pub fn new_from_row(row: &sqlx::sqlite::SqliteRow) -> std::io::Result<Self> {
+        let frame_index: u32 = row.try_get("frame_index").map_err(std::io::Error::other)?;
+        let frame_delay_ms: u32 = row.try_get("frame_delay_ms").map_err(std::io::Error::other)?;
+        Ok(Self {
+            frame_index,
+            frame_delay_ms,
+            thumbnail: ImageThumbnail::new_from_row(row)?,
+        })
+    }
+}
+
+// Everything read from a multi-frame file, written together
+#[derive(Debug, Clone)]
+pub struct ImageFramesExtraction {
+    pub frames: ImageFrames,
+    pub thumbnails: Vec<ImageFrameThumbnail>,
+}
+
+impl std::fmt::Display for ImageFramesExtraction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({} frame thumbnails)", self.frames, self.thumbnails.len())
+    }
+}
\ No newline at end of file
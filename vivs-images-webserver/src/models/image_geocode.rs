@@ -0,0 +1,110 @@
+use serde::Deserialize;
+use sqlx::Row;
+
+use crate::models::image::ImageFieldMeta;
+
+// Struct to hold the place an image's GPS coordinates fall in, looked up offline in a GeoNames-style place list.
+// Coordinates far from every known place keep a row with no names, so they are not looked up again
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageGeocode {
+    pub image_path: String,
+    pub place_country_code: Option<String>,
+    pub place_country: Option<String>,
+    pub place_region: Option<String>,
+    pub place_city: Option<String>,
+    pub place_city_distance_km: Option<f32>,
+    pub place_dataset: String,
+}
+
+pub const IMAGE_GEOCODE_COLUMNS_JSON: &str = r#"
+[
+    {"name": "image_path", "label": "Image Path", "description": "The file path of the image", "field_type": "string", "example": "/images/photo.jpg", "category": "gps", "table_name": "image_geocode"},
+    {"name": "place_country_code", "label": "Place Country Code", "description": "ISO 3166 code of the country the GPS coordinates fall in", "field_type": "string", "example": "FR", "category": "gps", "table_name": "image_geocode"},
+    {"name": "place_country", "label": "Place Country", "description": "The country the GPS coordinates fall in", "field_type": "string", "example": "France", "category": "gps", "table_name": "image_geocode"},
+    {"name": "place_region", "label": "Place Region", "description": "The state, province or other first-level region of the nearest known place", "field_type": "string", "example": "Île-de-France", "category": "gps", "table_name": "image_geocode"},
+    {"name": "place_city", "label": "Place City", "description": "The known city nearest to the GPS coordinates", "field_type": "string", "example": "Paris", "category": "gps", "table_name": "image_geocode"},
+    {"name": "place_city_distance_km", "label": "Place City Distance (km)", "description": "How far the GPS coordinates are from the nearest known city", "field_type": "f32", "example": "3.5", "category": "gps", "table_name": "image_geocode"},
+    {"name": "place_dataset", "label": "Place Dataset", "description": "The place list the names were looked up in", "field_type": "string", "example": "bundled", "category": "gps", "table_name": "image_geocode"}
+]"#;
+
+impl ImageGeocode {
+    pub fn new(row: &sqlx::sqlite::SqliteRow) -> Self {
+        let image_path: String = row.try_get("image_path").unwrap_or_default();
+        let place_country_code: Option<String> = row.try_get("place_country_code").unwrap_or_default();
+        let place_country: Option<String> = row.try_get("place_country").unwrap_or_default();
+        let place_region: Option<String> = row.try_get("place_region").unwrap_or_default();
+        let place_city: Option<String> = row.try_get("place_city").unwrap_or_default();
+        let place_city_distance_km: Option<f32> = row.try_get("place_city_distance_km").unwrap_or_default();
+        let place_dataset: String = row.try_get("place_dataset").unwrap_or_default();
+        ImageGeocode {
+            image_path,
+            place_country_code,
+            place_country,
+            place_region,
+            place_city,
+            place_city_distance_km,
+            place_dataset,
+        }
+    }
+
+    pub fn get_field(&self, field: &str) -> Option<String> {
+        match field {
+            "image_path" => Some(self.image_path.clone()),
+            "place_country_code" => self.place_country_code.clone(),
+            "place_country" => self.place_country.clone(),
+            "place_region" => self.place_region.clone(),
+            "place_city" => self.place_city.clone(),
+            "place_city_distance_km" => self.place_city_distance_km.map(|d| format!("{:.1}", d)),
+            "place_dataset" => Some(self.place_dataset.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn get_meta() -> Vec<ImageFieldMeta> {
+        serde_json::from_str::<Vec<ImageFieldMeta>>(IMAGE_GEOCODE_COLUMNS_JSON).unwrap()
+    }
+}
+
+impl std::fmt::Display for ImageGeocode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = [&self.place_city, &self.place_region, &self.place_country]
+            .iter()
+            .filter_map(|n| n.as_deref())
+            .collect();
+        if names.is_empty() {
+            return write!(f, "no known place nearby");
+        }
+        write!(f, "{}", names.join(", "))?;
+        if let Some(distance) = self.place_city_distance_km {
+            write!(f, " ({:.1} km)", distance)?;
+        }
+        Ok(())
+    }
+}
+
+// Where an image was taken according to its exif GPS tags, in signed decimal degrees
+#[derive(Debug, Clone)]
+pub struct ImageGpsCoordinates {
+    pub image_path: String,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl std::fmt::Display for ImageGpsCoordinates {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({:.5}, {:.5})", self.image_path, self.latitude, self.longitude)
+    }
+}
+
+// The images with GPS coordinates but no place names yet
+#[derive(Debug, Clone)]
+pub struct ImageGeocodeAnalysis {
+    pub dataset_name: String,
+    pub coordinates: Vec<ImageGpsCoordinates>,
+}
+
+impl std::fmt::Display for ImageGeocodeAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} images with gps coordinates and no place names, using the {} place list", self.coordinates.len(), self.dataset_name)
+    }
+}
\ No newline at end of file
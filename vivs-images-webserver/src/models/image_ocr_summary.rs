@@ -0,0 +1,82 @@
+use serde::Deserialize;
+use sqlx::Row;
+
+use crate::models::image::ImageFieldMeta;
+
+
+// Struct to hold the structured (tsv) tesseract output summary for an image
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageOcrSummary {
+    pub image_path: String,
+    pub ocr_languages: String,
+    pub ocr_word_count: i32,
+    pub ocr_low_confidence_word_count: i32,
+    pub ocr_mean_confidence: f32,
+    pub ocr_confident_text: String,
+    pub ocr_page_width: i32,
+    pub ocr_page_height: i32,
+    pub ocr_preprocessing: String,
+    pub ocr_options: String,
+}
+
+pub const IMAGE_OCR_SUMMARY_COLUMNS_JSON: &str = r#"
+[
+    {"name": "image_path", "label": "Image Path", "description": "The file path of the image", "field_type": "string", "example": "/images/photo.jpg", "category": "general", "table_name": "image_ocr_summary"},
+    {"name": "ocr_languages", "label": "OCR Languages", "description": "The tesseract languages the text was read with", "field_type": "string", "example": "eng+deu", "category": "general", "table_name": "image_ocr_summary"},
+    {"name": "ocr_word_count", "label": "OCR Word Count", "description": "How many words tesseract found", "field_type": "i32", "example": "12", "category": "general", "table_name": "image_ocr_summary"},
+    {"name": "ocr_low_confidence_word_count", "label": "OCR Low Confidence Words", "description": "How many of the words fell below the confidence threshold", "field_type": "i32", "example": "3", "category": "general", "table_name": "image_ocr_summary"},
+    {"name": "ocr_mean_confidence", "label": "OCR Mean Confidence", "description": "Average word confidence from 0 to 100; junk OCR from photos without text is usually below 50", "field_type": "f32", "example": "85.5", "category": "general", "table_name": "image_ocr_summary"},
+    {"name": "ocr_confident_text", "label": "OCR Confident Text", "description": "The extracted text with low confidence words left out", "field_type": "string", "example": "meow", "category": "general", "table_name": "image_ocr_summary"},
+    {"name": "ocr_page_width", "label": "OCR Page Width", "description": "Width in pixels of the upright image the word boxes are relative to", "field_type": "i32", "example": "1920", "category": "general", "table_name": "image_ocr_summary"},
+    {"name": "ocr_page_height", "label": "OCR Page Height", "description": "Height in pixels of the upright image the word boxes are relative to", "field_type": "i32", "example": "1080", "category": "general", "table_name": "image_ocr_summary"},
+    {"name": "ocr_preprocessing", "label": "OCR Preprocessing", "description": "The preprocessing steps applied before tesseract, in order; empty for the original file", "field_type": "string", "example": "greyscale,upscale:2.00,adaptive_threshold", "category": "general", "table_name": "image_ocr_summary"},
+    {"name": "ocr_options", "label": "OCR Options", "description": "The preprocessing options the text was read with, whether or not each step changed the image", "field_type": "string", "example": "greyscale,upscale,deskew", "category": "general", "table_name": "image_ocr_summary"}
+]"#;
+
+impl ImageOcrSummary {
+    pub fn new(row: &sqlx::sqlite::SqliteRow) -> Self {
+        ImageOcrSummary {
+            image_path: row.try_get("image_path").unwrap_or_default(),
+            ocr_languages: row.try_get("ocr_languages").unwrap_or_default(),
+            ocr_word_count: row.try_get("ocr_word_count").unwrap_or(0),
+            ocr_low_confidence_word_count: row.try_get("ocr_low_confidence_word_count").unwrap_or(0),
+            ocr_mean_confidence: row.try_get("ocr_mean_confidence").unwrap_or(0.0),
+            ocr_confident_text: row.try_get("ocr_confident_text").unwrap_or_default(),
+            ocr_page_width: row.try_get("ocr_page_width").unwrap_or(0),
+            ocr_page_height: row.try_get("ocr_page_height").unwrap_or(0),
+            ocr_preprocessing: row.try_get("ocr_preprocessing").unwrap_or_default(),
+            ocr_options: row.try_get("ocr_options").unwrap_or_default(),
+        }
+    }
+
+    pub fn get_field(&self, field: &str) -> Option<String> {
+        match field {
+            "image_path" => Some(self.image_path.clone()),
+            "ocr_languages" => Some(self.ocr_languages.clone()),
+            "ocr_word_count" => Some(self.ocr_word_count.to_string()),
+            "ocr_low_confidence_word_count" => Some(self.ocr_low_confidence_word_count.to_string()),
+            "ocr_mean_confidence" => Some(format!("{:.2}", self.ocr_mean_confidence)),
+            "ocr_confident_text" => Some(self.ocr_confident_text.clone()),
+            "ocr_page_width" => Some(self.ocr_page_width.to_string()),
+            "ocr_page_height" => Some(self.ocr_page_height.to_string()),
+            "ocr_preprocessing" => Some(self.ocr_preprocessing.clone()),
+            "ocr_options" => Some(self.ocr_options.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn get_meta() -> Vec<ImageFieldMeta> {
+        serde_json::from_str::<Vec<ImageFieldMeta>>(IMAGE_OCR_SUMMARY_COLUMNS_JSON).unwrap()
+    }
+}
+
+impl std::fmt::Display for ImageOcrSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} words ({} low confidence), mean confidence {:.1}, languages {}",
+            self.ocr_word_count, self.ocr_low_confidence_word_count, self.ocr_mean_confidence, self.ocr_languages)?;
+        if !self.ocr_preprocessing.is_empty() {
+            write!(f, ", preprocessing {}", self.ocr_preprocessing)?;
+        }
+        Ok(())
+    }
+}
\ No newline at end of file
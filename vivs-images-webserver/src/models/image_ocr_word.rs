@@ -0,0 +1,43 @@
+use serde::Deserialize;
+use sqlx::Row;
+
+
+// One word tesseract found, with its bounding box in the pixel space of the page of the image it read
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageOcrWord {
+    pub image_path: String,
+    pub word_index: i32,
+    pub page_num: i32,
+    pub block_num: i32,
+    pub line_num: i32,
+    pub text: String,
+    pub confidence: f32,
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl ImageOcrWord {
+    pub fn new(row: &sqlx::sqlite::SqliteRow) -> Self {
+        ImageOcrWord {
+            image_path: row.try_get("image_path").unwrap_or_default(),
+            word_index: row.try_get("word_index").unwrap_or(0),
+            page_num: row.try_get("page_num").unwrap_or(1),
+            block_num: row.try_get("block_num").unwrap_or(0),
+            line_num: row.try_get("line_num").unwrap_or(0),
+            text: row.try_get("text").unwrap_or_default(),
+            confidence: row.try_get("confidence").unwrap_or(0.0),
+            left: row.try_get("left").unwrap_or(0),
+            top: row.try_get("top").unwrap_or(0),
+            width: row.try_get("width").unwrap_or(0),
+            height: row.try_get("height").unwrap_or(0),
+        }
+    }
+}
+
+impl std::fmt::Display for ImageOcrWord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} ({:.0}%) at {},{} {}x{}", self.text, self.confidence, self.left, self.top, self.width, self.height)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,45 @@
+use crate::models::image_aspect_ratio::ImageAspectRatio;
+use crate::models::image_sharpness::ImageSharpness;
+use crate::models::image_thumbnail::ImageThumbnail;
+
+// The rows of an image that depend on which way up it was decoded, recomputed with its EXIF orientation
+// applied; an image that was already upright only needs its pending marker cleared
+#[derive(Debug, Clone)]
+pub struct ImageOrientationRecompute {
+    pub image_path: String,
+    pub orientation: u8,
+    pub aspect_ratio: Option<ImageAspectRatio>,
+    pub thumbnails: Vec<ImageThumbnail>,
+    pub sharpness: Option<ImageSharpness>,
+}
+
+impl ImageOrientationRecompute {
+    pub fn is_upright(&self) -> bool {
+        self.orientation <= 1
+    }
+}
+
+impl std::fmt::Display for ImageOrientationRecompute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_upright() {
+            return write!(f, "already upright");
+        }
+        write!(f, "orientation {}", self.orientation)?;
+        if let Some(aspect_ratio) = &self.aspect_ratio {
+            write!(f, ", {}x{}", aspect_ratio.width_pixels, aspect_ratio.height_pixels)?;
+        }
+        write!(f, ", {} thumbnails", self.thumbnails.len())
+    }
+}
+
+// The images still waiting to be checked
+#[derive(Debug, Clone)]
+pub struct ImageOrientationPendingAnalysis {
+    pub image_paths: Vec<String>,
+}
+
+impl std::fmt::Display for ImageOrientationPendingAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} images decoded before exif orientation was applied", self.image_paths.len())
+    }
+}
\ No newline at end of file
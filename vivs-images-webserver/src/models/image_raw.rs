@@ -0,0 +1,66 @@
+use serde::Deserialize;
+use sqlx::Row;
+
+use crate::models::image::ImageFieldMeta;
+
+// Struct to hold what was read from a camera RAW file: its format, the size of the embedded preview used in its
+// place, and the JPEG written alongside it when the camera shot RAW+JPEG
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageRaw {
+    pub image_path: String,
+    pub raw_format: String,
+    pub preview_width: u32,
+    pub preview_height: u32,
+    pub jpeg_sibling_path: Option<String>,
+}
+
+pub const IMAGE_RAW_COLUMNS_JSON: &str = r#"
+[
+    {"name": "image_path", "label": "Image Path", "description": "The file path of the image", "field_type": "string", "example": "/images/photo.cr2", "category": "general", "table_name": "image_raw"},
+    {"name": "raw_format", "label": "RAW Format", "description": "The camera RAW format, taken from the file extension", "field_type": "string", "example": "cr2", "category": "general", "table_name": "image_raw"},
+    {"name": "preview_width", "label": "Preview Width", "description": "Width of the embedded JPEG preview used to display and analyse the RAW file", "field_type": "u32", "example": "1620", "category": "general", "table_name": "image_raw"},
+    {"name": "preview_height", "label": "Preview Height", "description": "Height of the embedded JPEG preview used to display and analyse the RAW file", "field_type": "u32", "example": "1080", "category": "general", "table_name": "image_raw"},
+    {"name": "jpeg_sibling_path", "label": "JPEG Sibling", "description": "The JPEG with the same name in the same folder, when the camera shot RAW+JPEG", "field_type": "string", "example": "/images/photo.jpg", "category": "general", "table_name": "image_raw"}
+]"#;
+
+impl ImageRaw {
+    pub fn new(row: &sqlx::sqlite::SqliteRow) -> Self {
+        let image_path: String = row.try_get("image_path").unwrap_or_default();
+        let raw_format: String = row.try_get("raw_format").unwrap_or_default();
+        let preview_width: u32 = row.try_get("preview_width").unwrap_or_default();
+        let preview_height: u32 = row.try_get("preview_height").unwrap_or_default();
+        let jpeg_sibling_path: Option<String> = row.try_get("jpeg_sibling_path").unwrap_or_default();
+        ImageRaw {
+            image_path,
+            raw_format,
+            preview_width,
+            preview_height,
+            jpeg_sibling_path,
+        }
+    }
+
+    pub fn get_field(&self, field: &str) -> Option<String> {
+        match field {
+            "image_path" => Some(self.image_path.clone()),
+            "raw_format" => Some(self.raw_format.clone()),
+            "preview_width" => Some(self.preview_width.to_string()),
+            "preview_height" => Some(self.preview_height.to_string()),
+            "jpeg_sibling_path" => self.jpeg_sibling_path.clone(),
+            _ => None,
+        }
+    }
+
+    pub fn get_meta() -> Vec<ImageFieldMeta> {
+        serde_json::from_str::<Vec<ImageFieldMeta>>(IMAGE_RAW_COLUMNS_JSON).unwrap()
+    }
+}
+
+impl std::fmt::Display for ImageRaw {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} preview {}x{}", self.raw_format, self.preview_width, self.preview_height)?;
+        if let Some(sibling) = &self.jpeg_sibling_path {
+            write!(f, ", jpeg sibling {}", sibling)?;
+        }
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,56 @@
+use serde::Deserialize;
+use sqlx::Row;
+
+use crate::models::image::ImageFieldMeta;
+
+// Struct to hold mapping of an image path to calculated sharpness and motion blur
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageSharpness {
+    pub image_path: String,
+    pub sharpness: f32,
+    pub motion_blur: f32,
+    pub motion_blur_angle: f32,
+}
+
+pub const IMAGE_SHARPNESS_COLUMNS_JSON: &str = r#"
+[
+    {"name": "image_path", "label": "Image Path", "description": "The file path of the image", "field_type": "string", "example": "/images/photo.jpg", "category": "general", "table_name": "image_sharpness"},
+    {"name": "sharpness", "label": "Sharpness", "description": "Variance of the Laplacian of a greyscale copy scaled to 1024 pixels on its longer side; higher is sharper, below ~100 is usually blurry", "field_type": "f32", "example": "250.0", "category": "general", "table_name": "image_sharpness"},
+    {"name": "motion_blur", "label": "Motion Blur", "description": "How strongly edges line up in one direction in an image without sharp edges, from 0 (none, or sharp) to 1 (heavy motion blur)", "field_type": "f32", "example": "0.2", "category": "general", "table_name": "image_sharpness"},
+    {"name": "motion_blur_angle", "label": "Motion Blur Angle", "description": "The direction of the motion blur in degrees, 0 is horizontal", "field_type": "f32", "example": "90.0", "category": "general", "table_name": "image_sharpness"}
+]"#;
+
+impl ImageSharpness {
+    pub fn new(row: &sqlx::sqlite::SqliteRow) -> Self {
+        let image_path: String = row.try_get("image_path").unwrap_or_default();
+        let sharpness: f32 = row.try_get("sharpness").unwrap_or(0.0);
+        let motion_blur: f32 = row.try_get("motion_blur").unwrap_or(0.0);
+        let motion_blur_angle: f32 = row.try_get("motion_blur_angle").unwrap_or(0.0);
+        ImageSharpness {
+            image_path,
+            sharpness,
+            motion_blur,
+            motion_blur_angle,
+        }
+    }
+
+    pub fn get_field(&self, field: &str) -> Option<String> {
+        match field {
+            "image_path" => Some(self.image_path.clone()),
+            "sharpness" => Some(format!("{:.2}", self.sharpness)),
+            "motion_blur" => Some(format!("{:.4}", self.motion_blur)),
+            "motion_blur_angle" => Some(format!("{:.1}", self.motion_blur_angle)),
+            _ => None,
+        }
+    }
+
+    pub fn get_meta() -> Vec<ImageFieldMeta> {
+        serde_json::from_str::<Vec<ImageFieldMeta>>(IMAGE_SHARPNESS_COLUMNS_JSON).unwrap()
+    }
+}
+
+impl std::fmt::Display for ImageSharpness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "sharpness: {}", self.sharpness)
+    }
+}
\ No newline at end of file
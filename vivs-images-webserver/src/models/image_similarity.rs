@@ -5,11 +5,39 @@ use sqlx::Row;
 use crate::models::image::ImageFieldMeta;
 
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ImageComparisonAlgorithm {
     Magick,
     CustomV1,
-    CustomV2Thumbnails
+    CustomV2Thumbnails,
+    WindowedSsim,
+    MultiScaleSsim,
+}
+
+impl ImageComparisonAlgorithm {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Magick,
+            Self::CustomV1,
+            Self::CustomV2Thumbnails,
+            Self::WindowedSsim,
+            Self::MultiScaleSsim,
+        ]
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Magick => "magick",
+            Self::CustomV1 => "custom_v1",
+            Self::CustomV2Thumbnails => "custom_v2_thumbnails",
+            Self::WindowedSsim => "windowed_ssim",
+            Self::MultiScaleSsim => "multi_scale_ssim",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all().into_iter().find(|a| a.get_name() == name)
+    }
 }
 
 impl TryFrom<u8> for ImageComparisonAlgorithm {
@@ -20,6 +48,8 @@ impl TryFrom<u8> for ImageComparisonAlgorithm {
             0 => Ok(Self::Magick),
             1 => Ok(Self::CustomV1),
             2 => Ok(Self::CustomV2Thumbnails),
+            3 => Ok(Self::WindowedSsim),
+            4 => Ok(Self::MultiScaleSsim),
             _ => Err(())
         }
     }
@@ -33,6 +63,8 @@ impl TryInto<u8> for &ImageComparisonAlgorithm {
             ImageComparisonAlgorithm::Magick => Ok(0),
             ImageComparisonAlgorithm::CustomV1 => Ok(1),
             ImageComparisonAlgorithm::CustomV2Thumbnails => Ok(2),
+            ImageComparisonAlgorithm::WindowedSsim => Ok(3),
+            ImageComparisonAlgorithm::MultiScaleSsim => Ok(4),
         }
     }
 }
@@ -46,7 +78,6 @@ impl Display for ImageComparisonAlgorithm {
 
 #[derive(Clone, Debug)]
 pub struct ImageSimilarity {
-    pub image_comparison_key: i32,
     pub image_comparison_algorithm: ImageComparisonAlgorithm,
     pub image_path_a: String,
     pub image_path_b: String,
@@ -62,7 +93,6 @@ impl std::fmt::Display for ImageSimilarity {
 
 pub const IMAGE_SIMILARITY_COLUMNS_JSON: &str = r#"
 [
-    {"name": "image_comparison_key", "label": "Image Comparison Key", "description": "The comparison key for image A to image B", "field_type": "i32", "example": "123456", "category": "general", "table_name": "image_similarity"},
     {"name": "image_comparison_algorithm", "label": "Image Comparison Algorithm", "description": "The comparison algorithm for image A to image B", "field_type": "u8", "example": "0", "category": "general", "table_name": "image_similarity"},
     {"name": "image_path_a", "label": "Image Path A", "description": "The file path of the left image", "field_type": "string", "example": "/images/photo.jpg", "category": "general", "table_name": "image_similarity"},
     {"name": "image_path_b", "label": "Image Path B", "description": "The file path of the right image", "field_type": "string", "example": "/images/photo.jpg", "category": "general", "table_name": "image_similarity"},
@@ -73,7 +103,6 @@ pub const IMAGE_SIMILARITY_COLUMNS_JSON: &str = r#"
 
 impl ImageSimilarity {
     pub fn new(row: &sqlx::sqlite::SqliteRow) -> Self {
-        let image_comparison_key: i32 = row.try_get("image_comparison_key").unwrap_or_default();
         let image_comparison_algorithm: u8 = {
             let a = row.try_get("image_comparison_algorithm");
             let b = row.try_get("[image_comparison_algorithm]");
@@ -93,7 +122,6 @@ impl ImageSimilarity {
         let similarity_confidence: f32 = row.try_get("similarity_confidence").unwrap_or(-1.0);
 
         ImageSimilarity {
-            image_comparison_key,
             image_comparison_algorithm,
             image_path_a,
             image_path_b,
@@ -104,7 +132,6 @@ impl ImageSimilarity {
 
     pub fn get_field(&self, field: &str) -> Option<String> {
         match field {
-            "image_comparison_key" => Some(self.image_comparison_key.to_string()),
             "image_comparison_algorithm" => Some(self.image_comparison_algorithm.to_string()),
             "image_path_a" => Some(self.image_path_a.clone()),
             "image_path_b" => Some(self.image_path_b.clone()),
@@ -0,0 +1,77 @@
+use sqlx::Row;
+
+use crate::models::image_similarity::ImageComparisonAlgorithm;
+
+// One of an image's K most similar images, as kept in image_similarity_neighbors
+#[derive(Clone, Debug)]
+pub struct ImageSimilarityNeighbor {
+    pub image_path: String,
+    pub neighbor_path: String,
+    pub image_comparison_algorithm: ImageComparisonAlgorithm,
+    pub similarity_value: f32,
+    pub similarity_confidence: f32,
+}
+
+impl ImageSimilarityNeighbor {
+    pub fn new(row: &sqlx::sqlite::SqliteRow) -> Self {
+        let image_comparison_algorithm: u8 = row.try_get("image_comparison_algorithm").unwrap_or_default();
+        ImageSimilarityNeighbor {
+            image_path: row.try_get("image_path").unwrap_or_default(),
+            neighbor_path: row.try_get("neighbor_path").unwrap_or_default(),
+            image_comparison_algorithm: image_comparison_algorithm.try_into().unwrap_or(ImageComparisonAlgorithm::Magick),
+            similarity_value: row.try_get("similarity_value").unwrap_or(0.0),
+            similarity_confidence: row.try_get("similarity_confidence").unwrap_or(-1.0),
+        }
+    }
+}
+
+impl std::fmt::Display for ImageSimilarityNeighbor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({:.4})", self.neighbor_path, self.similarity_value)
+    }
+}
+
+// Result of comparing one image against every other image: all neighbours above the minimum value, most
+// similar first. The image keeps the first K, the rest are offered to the other images' lists.
+#[derive(Clone, Debug)]
+pub struct ImageSimilarityNeighborScan {
+    pub image_path: String,
+    pub image_comparison_algorithm: ImageComparisonAlgorithm,
+    pub compared_count: usize,
+    pub neighbors: Vec<ImageSimilarityNeighbor>,
+}
+
+impl std::fmt::Display for ImageSimilarityNeighborScan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} neighbours above the minimum out of {} compared", self.neighbors.len(), self.compared_count)
+    }
+}
+
+// What pruning image_similarity down to top-K neighbours will do for one algorithm
+#[derive(Clone, Debug)]
+pub struct ImageSimilarityPrunePlan {
+    pub image_comparison_algorithm: ImageComparisonAlgorithm,
+    pub row_count: usize,
+    pub image_count: usize,
+    pub top_k: usize,
+    pub min_value: f32,
+}
+
+impl std::fmt::Display for ImageSimilarityPrunePlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} pairs over {} images, keeping at most {} neighbours per image with similarity >= {}",
+            self.image_comparison_algorithm.get_name(), self.row_count, self.image_count, self.top_k, self.min_value)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ImageSimilarityPruneAnalysis {
+    pub plans: Vec<ImageSimilarityPrunePlan>,
+}
+
+impl std::fmt::Display for ImageSimilarityPruneAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rows: usize = self.plans.iter().map(|p| p.row_count).sum();
+        write!(f, "{} similarity rows across {} algorithms", rows, self.plans.len())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,53 @@
+use chrono::{FixedOffset, NaiveDateTime};
+
+use crate::converters::extract_image_taken_at::format_taken_at;
+
+// Where the capture time was found, in the order extract_image_taken_at tries them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TakenAtSource {
+    Exif,
+    Xmp,
+    Gps,
+    FileName,
+    FileModified,
+}
+
+impl TakenAtSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TakenAtSource::Exif => "exif",
+            TakenAtSource::Xmp => "xmp",
+            TakenAtSource::Gps => "gps",
+            TakenAtSource::FileName => "filename",
+            TakenAtSource::FileModified => "mtime",
+        }
+    }
+}
+
+impl std::fmt::Display for TakenAtSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+// When an image was taken, as the local wall-clock time; the offset from UTC is only known for some sources
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageTakenAt {
+    pub local_time: NaiveDateTime,
+    pub offset: Option<FixedOffset>,
+    pub source: TakenAtSource,
+}
+
+impl ImageTakenAt {
+    pub fn get_local_time_string(&self) -> String {
+        format_taken_at(&self.local_time)
+    }
+
+    pub fn get_offset_string(&self) -> Option<String> {
+        self.offset.map(|o| o.to_string())
+    }
+
+    pub fn get_utc_time(&self) -> Option<NaiveDateTime> {
+        self.offset.map(|o| self.local_time - o)
+    }
+}
\ No newline at end of file
@@ -7,14 +7,16 @@ use crate::converters::extract_image_thumbnail::convert_vec_u8_to_image;
 use crate::models::image::ImageFieldMeta;
 use crate::converters::extract_image_thumbnail::ExtractImageThumbnailOptions;
 use crate::converters::extract_image_thumbnail::convert_image_to_vec_u8;
+use crate::models::config::thumbnails::get_thumbnail_quality;
 
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ThumbnailFormat { 
     PNG,
     JPG,
     BMP,
     RAW_rgb8,
+    WEBP,
 }
 
 impl TryFrom<u8> for ThumbnailFormat {
@@ -24,6 +26,7 @@ impl TryFrom<u8> for ThumbnailFormat {
             1 => Ok(Self::JPG),
             2 => Ok(Self::BMP),
             3 => Ok(Self::RAW_rgb8),
+            4 => Ok(Self::WEBP),
             _ => Err(std::io::Error::from(std::io::ErrorKind::InvalidData))
         }
     }
@@ -40,11 +43,43 @@ impl TryInto<u8> for &ThumbnailFormat {
             ThumbnailFormat::JPG => Ok(1),
             ThumbnailFormat::BMP => Ok(2),
             ThumbnailFormat::RAW_rgb8 => Ok(3),
+            ThumbnailFormat::WEBP => Ok(4),
             _ => Err(std::io::Error::from(std::io::ErrorKind::InvalidData))
         }
     }
 }
 
+impl ThumbnailFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "png" => Some(Self::PNG),
+            "jpg" | "jpeg" => Some(Self::JPG),
+            // only lossless webp can be written, which ignores the quality setting
+            _ => None,
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::PNG => "image/png",
+            Self::JPG => "image/jpeg",
+            Self::BMP => "image/bmp",
+            Self::RAW_rgb8 => "application/octet-stream",
+            Self::WEBP => "image/webp",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::PNG => "png",
+            Self::JPG => "jpg",
+            Self::BMP => "bmp",
+            Self::RAW_rgb8 => "rgb",
+            Self::WEBP => "webp",
+        }
+    }
+}
+
 impl std::fmt::Display for ThumbnailFormat {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let v: u8 = self.try_into().unwrap();
@@ -105,6 +140,7 @@ impl ImageThumbnail {
             filter: image::imageops::FilterType::Lanczos3,
             output_format: thumbnail_format,
             width_and_length,
+            quality: get_thumbnail_quality(),
         };
         let thumbnail_data = convert_image_to_vec_u8(thumbnail_image, &options).unwrap();
         Self {
@@ -160,4 +196,43 @@ impl std::fmt::Display for ImageThumbnailVec {
         let self_as_str = self_as_strs.join(", ");
         write!(f, "{}", self_as_str)
     }
+}
+
+// A stored thumbnail with its data read back, to be written to the configured thumbnail storage
+#[derive(Clone, Debug)]
+pub struct ImageThumbnailRelocation {
+    pub image_thumbnail_key: i64,
+    pub thumbnail: ImageThumbnail,
+}
+
+impl std::fmt::Display for ImageThumbnailRelocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({}, {} bytes)", self.thumbnail.image_path, self.thumbnail.width_and_length, self.thumbnail.thumbnail_data.len())
+    }
+}
+
+// The thumbnails whose data is not yet in the configured thumbnail storage
+#[derive(Clone, Debug)]
+pub struct ImageThumbnailRelocationAnalysis {
+    pub storage_name: String,
+    pub image_thumbnail_keys: Vec<i64>,
+}
+
+impl std::fmt::Display for ImageThumbnailRelocationAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} thumbnails to move to {} storage", self.image_thumbnail_keys.len(), self.storage_name)
+    }
+}
+
+// The images whose similarity thumbnail is not yet in the in-process thumbnail cache
+#[derive(Clone, Debug)]
+pub struct ThumbnailCacheWarmAnalysis {
+    pub size: u32,
+    pub image_paths: Vec<String>,
+}
+
+impl std::fmt::Display for ThumbnailCacheWarmAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} images without a cached {} px thumbnail", self.image_paths.len(), self.size)
+    }
 }
\ No newline at end of file
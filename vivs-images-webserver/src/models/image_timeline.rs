@@ -0,0 +1,19 @@
+use chrono::NaiveDate;
+
+// Images taken within one year, month or day; the key is the matching prefix of the ISO-8601 capture time
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineBucket {
+    pub key: String,
+    pub count: usize,
+}
+
+impl TimelineBucket {
+    pub fn get_date(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(&self.key, "%Y-%m-%d").ok()
+    }
+
+    // the last part of the key, the year, month or day number
+    pub fn get_number(&self) -> Option<u32> {
+        self.key.rsplit('-').next()?.parse().ok()
+    }
+}
\ No newline at end of file
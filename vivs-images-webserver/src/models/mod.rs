@@ -11,4 +11,19 @@ pub mod image_tag;
 pub mod image_ocr_text;
 pub mod image_xmp;
 pub mod query_params;
-pub mod top_level_metrics;
\ No newline at end of file
+pub mod top_level_metrics;
+pub mod image_color;
+pub mod image_sharpness;
+pub mod image_exposure;
+pub mod image_ocr_summary;
+pub mod image_ocr_word;
+pub mod image_content_hash;
+pub mod image_similarity_neighbor;
+pub mod image_raw;
+pub mod image_frames;
+pub mod image_orientation;
+pub mod image_geocode;
+pub mod image_timeline;
+pub mod image_taken_at;
+pub mod image_event;
+pub mod image_burst;
\ No newline at end of file
@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BurstReviewParams {
+    // bursts already submitted on the review page are left out unless this is set
+    pub show_reviewed: Option<bool>,
+}
+
+impl BurstReviewParams {
+    pub fn get_show_reviewed(&self) -> bool {
+        self.show_reviewed.unwrap_or_default()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,95 @@
+// geo_search_params.rs
+
+use crate::calc::reverse_geocode::haversine_distance_km;
+
+// Mean length of one degree of latitude; SQL filters use a flat-earth approximation scaled by it
+pub const KM_PER_DEGREE_LATITUDE: f64 = 111.195;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeoPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl GeoPoint {
+    // None unless latitude is within -90..90 and longitude within -180..180
+    pub fn new(latitude: f64, longitude: f64) -> Option<Self> {
+        if is_valid_latitude(latitude) && is_valid_longitude(longitude) {
+            Some(Self { latitude, longitude })
+        } else {
+            None
+        }
+    }
+
+    pub fn distance_km(&self, other: &GeoPoint) -> f64 {
+        haversine_distance_km(self.latitude, self.longitude, other.latitude, other.longitude)
+    }
+}
+
+// A min longitude greater than the max longitude means the box crosses the antimeridian
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeoBoundingBox {
+    pub min_latitude: f64,
+    pub max_latitude: f64,
+    pub min_longitude: f64,
+    pub max_longitude: f64,
+}
+
+impl GeoBoundingBox {
+    pub fn new(min_latitude: f64, max_latitude: f64, min_longitude: f64, max_longitude: f64) -> Option<Self> {
+        let valid = is_valid_latitude(min_latitude) && is_valid_latitude(max_latitude)
+            && is_valid_longitude(min_longitude) && is_valid_longitude(max_longitude)
+            && min_latitude <= max_latitude;
+        if valid {
+            Some(Self { min_latitude, max_latitude, min_longitude, max_longitude })
+        } else {
+            None
+        }
+    }
+
+    pub fn crosses_antimeridian(&self) -> bool {
+        self.min_longitude > self.max_longitude
+    }
+
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        let in_latitude = (self.min_latitude..=self.max_latitude).contains(&point.latitude);
+        let in_longitude = if self.crosses_antimeridian() {
+            point.longitude >= self.min_longitude || point.longitude <= self.max_longitude
+        } else {
+            (self.min_longitude..=self.max_longitude).contains(&point.longitude)
+        };
+        in_latitude && in_longitude
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GeoSearchFilter {
+    // Results can be ordered by distance from this point; with radius_km they are also limited to it
+    pub near: Option<GeoPoint>,
+    pub radius_km: Option<f64>,
+    pub bounding_box: Option<GeoBoundingBox>,
+}
+
+impl GeoSearchFilter {
+    pub fn is_empty(&self) -> bool {
+        self.near.is_none() && self.bounding_box.is_none()
+    }
+
+    pub fn get_radius(&self) -> Option<(GeoPoint, f64)> {
+        self.near.zip(self.radius_km)
+    }
+
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        let in_radius = self.get_radius().is_none_or(|(center, radius_km)| center.distance_km(point) <= radius_km);
+        let in_bounding_box = self.bounding_box.as_ref().is_none_or(|b| b.contains(point));
+        in_radius && in_bounding_box
+    }
+}
+
+fn is_valid_latitude(latitude: f64) -> bool {
+    latitude.is_finite() && (-90.0..=90.0).contains(&latitude)
+}
+
+fn is_valid_longitude(longitude: f64) -> bool {
+    longitude.is_finite() && (-180.0..=180.0).contains(&longitude)
+}
\ No newline at end of file
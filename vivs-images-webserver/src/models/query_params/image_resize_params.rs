@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use crate::models::config::derivatives::DERIVATIVE_MAX_DIMENSION;
+use crate::models::image_thumbnail::ThumbnailFormat;
+
+// How a resized image fills the requested box: contain keeps the whole image inside it, cover fills it and crops
+// the overflow, fill stretches to exactly the box. Cover and fill need both a width and a height.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFit {
+    Contain,
+    Cover,
+    Fill,
+}
+
+impl ImageFit {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "contain" => Some(Self::Contain),
+            "cover" => Some(Self::Cover),
+            "fill" => Some(Self::Fill),
+            _ => None,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Contain => "contain",
+            Self::Cover => "cover",
+            Self::Fill => "fill",
+        }
+    }
+}
+
+// The w, h, fit and format parameters of /img
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImageResizeParams {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub fit: ImageFit,
+    pub format: ThumbnailFormat,
+}
+
+impl ImageResizeParams {
+    // None when none of the parameters are given and the original file should be served
+    pub fn from_query(query: &HashMap<String, String>) -> Result<Option<Self>, String> {
+        let dimension = |name: &str| -> Result<Option<u32>, String> {
+            match query.get(name).map(|v| v.trim()).filter(|v| !v.is_empty()) {
+                None => Ok(None),
+                Some(v) => match v.parse::<u32>() {
+                    Ok(d) if d > 0 => Ok(Some(d.min(DERIVATIVE_MAX_DIMENSION))),
+                    _ => Err(format!("Invalid {} parameter", name)),
+                },
+            }
+        };
+        let width = dimension("w")?;
+        let height = dimension("h")?;
+        let fit = match query.get("fit") {
+            None => None,
+            Some(v) => Some(ImageFit::from_name(v).ok_or("Invalid fit parameter, expected contain, cover or fill")?),
+        };
+        let format = match query.get("format") {
+            None => None,
+            Some(v) => Some(ThumbnailFormat::from_name(v).ok_or("Invalid format parameter, expected jpg or png")?),
+        };
+        if width.is_none() && height.is_none() && format.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            width,
+            height,
+            fit: fit.unwrap_or(ImageFit::Contain),
+            // JPEG is much smaller for photos; ask for png to keep transparency
+            format: format.unwrap_or(ThumbnailFormat::JPG),
+        }))
+    }
+}
+
+impl std::fmt::Display for ImageResizeParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let dimension = |d: Option<u32>| d.map(|d| d.to_string()).unwrap_or_else(|| "auto".to_string());
+        write!(f, "{}x{} {} {}", dimension(self.width), dimension(self.height), self.fit.get_name(), self.format.extension())
+    }
+}
\ No newline at end of file
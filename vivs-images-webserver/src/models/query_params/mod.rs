@@ -1,3 +1,10 @@
 pub mod search_params;
 pub mod similar_images_params;
-pub mod default_search_params;
\ No newline at end of file
+pub mod default_search_params;
+pub mod search_by_color_params;
+pub mod wallpaper_params;
+pub mod similarity_algorithms_params;
+pub mod image_resize_params;
+pub mod geo_search_params;
+pub mod timeline_params;
+pub mod burst_review_params;
\ No newline at end of file
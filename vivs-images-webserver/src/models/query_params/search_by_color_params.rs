@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct SearchByColorParams {
+    // hex colour from the picker, e.g. "#3a6ea5"
+    pub color: Option<String>,
+    pub limit: Option<usize>,
+}
\ No newline at end of file
@@ -4,6 +4,10 @@ use serde::Deserialize;
 use std::{collections::HashMap, io::ErrorKind};
 
 use crate::{api::web::get_file_from_exe_dir, models::image::{Image, ImageFieldMeta}};
+use crate::models::query_params::geo_search_params::{GeoBoundingBox, GeoPoint, GeoSearchFilter};
+
+// Select alias SearchBuilder::with_geo_filter gives the squared distance from the near point
+pub const GEO_DISTANCE_ORDER_COLUMN: &str = "geo_distance_sq";
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct SearchParamFieldInput {
@@ -131,7 +135,7 @@ impl SearchParamFieldOutput {
     pub fn get_sql_comparison_operator(&self) -> Option<&'static str> {
         self.field_meta.sql_field.as_ref().and_then(|_| {
             let comparison_operator = match self.field_meta.input_type.as_str() {
-                "number" | "date" => {
+                "number" | "date" | "datetime-local" => {
                     if self.field_meta.name.ends_with("_min") {
                         ">="
                     } else if self.field_meta.name.ends_with("_max") {
@@ -210,6 +214,57 @@ impl SearchParams {
         self.get_field_value("offset").and_then(|v| v.parse::<i32>().ok())
     }
 
+    // Expects "column [ASC|DESC]" where column is a known image field, so the result is safe to put into SQL.
+    // "distance" is also accepted when a near point is given
+    pub fn get_order_by(&self) -> Option<String> {
+        let value = self.get_field_value("order_by")?;
+        let mut parts = value.split_whitespace();
+        let column_name = parts.next()?;
+        let direction = match parts.next().map(|d| d.to_uppercase()).as_deref() {
+            None | Some("ASC") => "ASC",
+            Some("DESC") => "DESC",
+            _ => return None,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        if column_name == "distance" {
+            return self.get_geo_near().map(|_| format!("{} {}", GEO_DISTANCE_ORDER_COLUMN, direction));
+        }
+        let column = Image::get_meta_field(column_name)?;
+        Some(format!("[{}].[{}] {}", column.table_name, column.name, direction))
+    }
+
+    fn get_field_value_f64(&self, name: &str) -> Option<f64> {
+        self.get_field_value(name).and_then(|v| v.trim().parse::<f64>().ok())
+    }
+
+    pub fn get_geo_near(&self) -> Option<GeoPoint> {
+        GeoPoint::new(self.get_field_value_f64("near_lat")?, self.get_field_value_f64("near_lon")?)
+    }
+
+    pub fn get_geo_radius_km(&self) -> Option<f64> {
+        self.get_field_value_f64("radius_km").filter(|r| r.is_finite() && *r > 0.0)
+    }
+
+    pub fn get_geo_bounding_box(&self) -> Option<GeoBoundingBox> {
+        GeoBoundingBox::new(
+            self.get_field_value_f64("bbox_min_lat")?,
+            self.get_field_value_f64("bbox_max_lat")?,
+            self.get_field_value_f64("bbox_min_lon")?,
+            self.get_field_value_f64("bbox_max_lon")?,
+        )
+    }
+
+    // Incomplete or out of range geographic parameters are left out rather than failing the search
+    pub fn get_geo_filter(&self) -> GeoSearchFilter {
+        GeoSearchFilter {
+            near: self.get_geo_near(),
+            radius_km: self.get_geo_radius_km(),
+            bounding_box: self.get_geo_bounding_box(),
+        }
+    }
+
     pub fn into_sql_query_params(&self) -> Vec<(String, HashMap<String, String>)> {
         let mut param_groups = vec![];
         
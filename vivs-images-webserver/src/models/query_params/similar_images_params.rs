@@ -4,4 +4,8 @@ use serde::Deserialize;
 pub struct SimilarImagesParams {
     pub image_path: String,
     pub threshold: Option<f64>,
+    // hide OCR word boxes below this confidence (0-100)
+    pub ocr_min_confidence: Option<f32>,
+    // which stored frame of an animation or multi-page file to show
+    pub frame: Option<u32>,
 }
\ No newline at end of file
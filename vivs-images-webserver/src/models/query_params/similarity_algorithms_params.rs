@@ -0,0 +1,33 @@
+use serde::Deserialize;
+
+use crate::models::image_similarity::ImageComparisonAlgorithm;
+
+#[derive(Debug, Deserialize)]
+pub struct SimilarityAlgorithmsParams {
+    // number of pairs to sample
+    pub sample: Option<usize>,
+    // comma separated algorithm names (e.g. magick,custom_v1) or their stored numbers
+    pub algorithms: Option<String>,
+}
+
+impl SimilarityAlgorithmsParams {
+    pub fn get_algorithms(&self) -> Vec<ImageComparisonAlgorithm> {
+        let mut algos: Vec<ImageComparisonAlgorithm> = self.algorithms.as_deref().unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter_map(|a| ImageComparisonAlgorithm::from_name(a)
+                .or_else(|| a.parse::<u8>().ok().and_then(|v| v.try_into().ok())))
+            .collect();
+        algos.sort();
+        algos.dedup();
+        if algos.is_empty() {
+            vec![
+                ImageComparisonAlgorithm::Magick,
+                ImageComparisonAlgorithm::CustomV1,
+                ImageComparisonAlgorithm::CustomV2Thumbnails,
+            ]
+        } else {
+            algos
+        }
+    }
+}
\ No newline at end of file
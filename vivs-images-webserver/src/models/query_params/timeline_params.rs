@@ -0,0 +1,35 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TimelineParams {
+    pub year: Option<i32>,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+}
+
+impl TimelineParams {
+    // Only the valid leading parts are used, so a month without a year shows every year
+    pub fn get_year(&self) -> Option<i32> {
+        self.year.filter(|y| (1..=9999).contains(y))
+    }
+
+    pub fn get_month(&self) -> Option<u32> {
+        self.get_year()?;
+        self.month.filter(|m| (1..=12).contains(m))
+    }
+
+    pub fn get_date(&self) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(self.get_year()?, self.get_month()?, self.day?)
+    }
+
+    // Prefix of the ISO-8601 capture time shared by every image in the selected period
+    pub fn get_prefix(&self) -> String {
+        match (self.get_year(), self.get_month(), self.get_date()) {
+            (_, _, Some(date)) => date.format("%Y-%m-%d").to_string(),
+            (Some(year), Some(month), None) => format!("{:04}-{:02}", year, month),
+            (Some(year), None, None) => format!("{:04}", year),
+            _ => String::new(),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,25 @@
+use serde::Deserialize;
+
+use crate::models::image_brightness::ImageToBrightnessAlgo;
+
+#[derive(Debug, Deserialize)]
+pub struct WallpaperParams {
+    // algorithm name (e.g. perceptual_lightness) or its stored number; any algorithm when missing
+    pub brightness_algo: Option<String>,
+}
+
+impl WallpaperParams {
+    // Err with the valid names when the parameter is given but matches no algorithm
+    pub fn get_brightness_algo(&self) -> Result<Option<ImageToBrightnessAlgo>, String> {
+        let Some(algo) = self.brightness_algo.as_deref().map(|a| a.trim()).filter(|a| !a.is_empty()) else {
+            return Ok(None);
+        };
+        ImageToBrightnessAlgo::from_name(algo)
+            .or_else(|| algo.parse::<u8>().ok().and_then(|v| v.try_into().ok()))
+            .map(Some)
+            .ok_or_else(|| {
+                let names: Vec<&str> = ImageToBrightnessAlgo::all().iter().map(|a| a.get_name()).collect();
+                format!("Invalid brightness_algo parameter, expected one of {}", names.join(", "))
+            })
+    }
+}
\ No newline at end of file
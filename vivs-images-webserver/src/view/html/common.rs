@@ -1,23 +1,191 @@
 use base64::{prelude::BASE64_STANDARD, Engine};
 
+use chrono::{Datelike, NaiveDate};
+use htmlentity::entity::ICodedDataTrait;
+
+use crate::calc::color_palette::PaletteColor;
+use crate::models::config::ocr::OCR_LOW_CONFIDENCE_THRESHOLD;
+use crate::models::image_ocr_word::ImageOcrWord;
 use crate::models::image_thumbnail::ImageThumbnail;
+use crate::models::image_timeline::TimelineBucket;
+use crate::models::query_params::geo_search_params::{GeoPoint, GeoSearchFilter, KM_PER_DEGREE_LATITUDE};
 
 
 
-// Helper function to generate image HTML with common styling
+// Helper function to generate image HTML with common styling; with a max width the server sends a copy resized to it
 pub fn image_html(image_path: &str, max_width: Option<u32>) -> String {
     let width_style = max_width.map(|w| format!("max-width: {}px;", w)).unwrap_or_default();
-    format!(r#"<img src="/img?path={}" alt="{}" onerror="this.style.display='none'" style="{}">"#,
-        image_path, image_path, width_style)
+    let width_param = max_width.map(|w| format!("&w={}", w)).unwrap_or_default();
+    format!(r#"<img src="/img?path={}{}" alt="{}" onerror="this.style.display='none'" style="{}">"#,
+        encode_string(image_path), width_param, image_path, width_style)
+}
+
+// Links to the stored thumbnail nearest to `size` so the browser can cache it
+pub fn image_thumbnail_url_html(image_path: &str, size: u32, max_width: Option<u32>) -> String {
+    let width_style = max_width.map(|w| format!("max-width: {}px;", w)).unwrap_or_default();
+    format!(r#"<img src="/thumb?path={}&size={}" alt="{}" loading="lazy" onerror="this.style.display='none'" style="{}">"#,
+        encode_string(image_path), size, image_path, width_style)
 }
 
 pub fn image_thumbnail_html(thumb: &ImageThumbnail, max_width: Option<u32>) -> String {
     let width_style = max_width.map(|w| format!("max-width: {}px;", w)).unwrap_or_default();
-    format!(r#"<img alt="{}" onerror="this.style.display='none'" style="{}" src="data:image/png;base64,{}"/>"#,
-        thumb.image_path, width_style,
+    format!(r#"<img alt="{}" onerror="this.style.display='none'" style="{}" src="data:{};base64,{}"/>"#,
+        thumb.image_path, width_style, thumb.thumbnail_format.mime_type(),
         BASE64_STANDARD.encode(&thumb.thumbnail_data))
 }
 
+// One swatch per palette colour, sized by how much of the image it covers
+pub fn color_palette_html(palette: &[PaletteColor]) -> String {
+    let swatches = palette.iter()
+        .map(|c| format!(r#"<span class="color-swatch" title="{} ({:.0}%)" style="background-color: {}; flex-grow: {:.4};"></span>"#,
+            c.to_hex(), c.weight * 100.0, c.to_hex(), c.weight))
+        .collect::<Vec<String>>()
+        .join("");
+    format!(r#"<div class="color-palette">{}</div>"#, swatches)
+}
+
+// Inline SVG bar chart of a luma histogram, darkest bin on the left
+pub fn luma_histogram_html(bins: &[f32]) -> String {
+    let (width, height) = (160.0, 48.0);
+    let peak = bins.iter().cloned().fold(0.0f32, f32::max);
+    if bins.is_empty() || peak <= 0.0 {
+        return String::new();
+    }
+    let bar_width = width / bins.len() as f32;
+    let bars = bins.iter()
+        .enumerate()
+        .map(|(i, b)| {
+            let bar_height = b / peak * height;
+            format!(r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}"/>"#,
+                i as f32 * bar_width, height - bar_height, bar_width, bar_height)
+        })
+        .collect::<Vec<String>>()
+        .join("");
+    format!(r#"<svg class="luma-histogram" width="{}" height="{}" viewBox="0 0 {} {}">{}</svg>"#, width, height, width, height, bars)
+}
+
+// Inline SVG scatter plot of image locations, longitude across and latitude up, with the search area drawn behind.
+// Longitude is scaled by the cosine of the middle latitude so shapes are not stretched away from the equator
+pub fn geo_scatter_plot_html(points: &[(String, GeoPoint)], filter: &GeoSearchFilter) -> String {
+    let (width, max_height, padding) = (800.0, 600.0, 12.0);
+    let mut extent: Vec<GeoPoint> = points.iter().map(|(_, p)| *p).collect();
+    if let Some((center, radius_km)) = filter.get_radius() {
+        let radius_degrees = radius_km / KM_PER_DEGREE_LATITUDE;
+        extent.extend(GeoPoint::new((center.latitude - radius_degrees).max(-90.0), center.longitude));
+        extent.extend(GeoPoint::new((center.latitude + radius_degrees).min(90.0), center.longitude));
+    }
+    if let Some(b) = filter.bounding_box.filter(|b| !b.crosses_antimeridian()) {
+        extent.extend(GeoPoint::new(b.min_latitude, b.min_longitude));
+        extent.extend(GeoPoint::new(b.max_latitude, b.max_longitude));
+    }
+    extent.extend(filter.near);
+    if extent.is_empty() {
+        return String::new();
+    }
+
+    let min_lat = extent.iter().map(|p| p.latitude).fold(f64::INFINITY, f64::min);
+    let max_lat = extent.iter().map(|p| p.latitude).fold(f64::NEG_INFINITY, f64::max);
+    let min_lon = extent.iter().map(|p| p.longitude).fold(f64::INFINITY, f64::min);
+    let max_lon = extent.iter().map(|p| p.longitude).fold(f64::NEG_INFINITY, f64::max);
+    let x_scale = ((min_lat + max_lat) / 2.0).to_radians().cos().max(0.05);
+    // a single location still gets a small area around it
+    let lat_span = (max_lat - min_lat).max(0.01);
+    let lon_span = ((max_lon - min_lon) * x_scale).max(0.01);
+    let pixels_per_degree = ((width - 2.0 * padding) / lon_span).min((max_height - 2.0 * padding) / lat_span);
+    let height = (lat_span * pixels_per_degree + 2.0 * padding).max(120.0);
+    let mid_lon = (min_lon + max_lon) / 2.0;
+    let mid_lat = (min_lat + max_lat) / 2.0;
+    let to_x = |lon: f64| width / 2.0 + (lon - mid_lon) * x_scale * pixels_per_degree;
+    let to_y = |lat: f64| height / 2.0 - (lat - mid_lat) * pixels_per_degree;
+
+    let mut shapes = String::new();
+    if let Some(b) = filter.bounding_box.filter(|b| !b.crosses_antimeridian()) {
+        shapes.push_str(&format!(r#"<rect class="geo-area" x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}"/>"#,
+            to_x(b.min_longitude), to_y(b.max_latitude),
+            (b.max_longitude - b.min_longitude) * x_scale * pixels_per_degree, (b.max_latitude - b.min_latitude) * pixels_per_degree));
+    }
+    if let Some((center, radius_km)) = filter.get_radius() {
+        let radius_pixels = radius_km / KM_PER_DEGREE_LATITUDE * pixels_per_degree;
+        shapes.push_str(&format!(r#"<ellipse class="geo-area" cx="{:.2}" cy="{:.2}" rx="{:.2}" ry="{:.2}"/>"#,
+            to_x(center.longitude), to_y(center.latitude),
+            radius_pixels * x_scale / center.latitude.to_radians().cos().max(0.05), radius_pixels));
+    }
+    for (image_path, point) in points {
+        let distance = filter.near
+            .map(|near| format!(" ({:.1} km away)", near.distance_km(point)))
+            .unwrap_or_default();
+        shapes.push_str(&format!(r#"<a href="/image?image_path={}"><circle class="geo-point" cx="{:.2}" cy="{:.2}" r="4"><title>{} {:.5}, {:.5}{}</title></circle></a>"#,
+            encode_string(image_path), to_x(point.longitude), to_y(point.latitude),
+            encode_html_string(image_path), point.latitude, point.longitude, distance));
+    }
+    if let Some(near) = filter.near {
+        shapes.push_str(&format!(r#"<circle class="geo-near" cx="{:.2}" cy="{:.2}" r="6"><title>{:.5}, {:.5}</title></circle>"#,
+            to_x(near.longitude), to_y(near.latitude), near.latitude, near.longitude));
+    }
+    format!(r#"<svg class="geo-scatter-plot" width="{}" height="{:.0}" viewBox="0 0 {} {:.0}">{}</svg>"#, width, height, width, height, shapes)
+}
+
+// Inline SVG calendar of one year, a column per week starting Monday, each day shaded by how many images were
+// taken on it relative to the busiest day and linked to that day on the timeline
+pub fn calendar_heat_map_html(year: i32, days: &[TimelineBucket]) -> String {
+    let (cell, step, top) = (11.0, 13.0, 14.0);
+    let Some(first_day) = NaiveDate::from_ymd_opt(year, 1, 1) else {
+        return String::new();
+    };
+    let offset = first_day.weekday().num_days_from_monday() as usize;
+    let peak = days.iter().map(|d| d.count).max().unwrap_or_default().max(1);
+    let counts: std::collections::HashMap<NaiveDate, usize> = days.iter()
+        .filter_map(|d| Some((d.get_date()?, d.count)))
+        .collect();
+
+    let mut shapes = String::new();
+    for (i, date) in first_day.iter_days().take_while(|d| d.year() == year).enumerate() {
+        let (x, y) = (((i + offset) / 7) as f32 * step, top + ((i + offset) % 7) as f32 * step);
+        if date.day() == 1 {
+            shapes.push_str(&format!(r#"<text x="{:.0}" y="10">{}</text>"#, x, date.format("%b")));
+        }
+        let count = counts.get(&date).copied().unwrap_or_default();
+        let level = if count == 0 { 0 } else { 1 + (3 * count / peak).min(3) };
+        let rect = format!(r#"<rect class="heat-{}" x="{:.0}" y="{:.0}" width="{}" height="{}"><title>{}: {} images</title></rect>"#,
+            level, x, y, cell, cell, date.format("%Y-%m-%d"), count);
+        if count == 0 {
+            shapes.push_str(&rect);
+        } else {
+            shapes.push_str(&format!(r#"<a href="/browse/timeline?year={}&month={}&day={}">{}</a>"#, date.year(), date.month(), date.day(), rect));
+        }
+    }
+    let width = 54.0 * step;
+    let height = top + 7.0 * step;
+    format!(r#"<svg class="calendar-heat-map" width="{}" height="{}" viewBox="0 0 {} {}">{}</svg>"#, width, height, width, height, shapes)
+}
+
+// Draws each OCR word box over the image, positioned as a percentage of the page tesseract read
+pub fn ocr_overlay_html(image_path: &str, words: &[ImageOcrWord], page_width: i32, page_height: i32, max_width: u32) -> String {
+    if page_width <= 0 || page_height <= 0 {
+        return String::new();
+    }
+    let boxes = words.iter()
+        .map(|w| {
+            let class = if w.confidence < OCR_LOW_CONFIDENCE_THRESHOLD { "ocr-box low-confidence" } else { "ocr-box" };
+            format!(r#"<span class="{}" title="{} ({:.0}%)" style="left: {:.3}%; top: {:.3}%; width: {:.3}%; height: {:.3}%;"></span>"#,
+                class, encode_html_string(&w.text), w.confidence,
+                w.left as f32 * 100.0 / page_width as f32, w.top as f32 * 100.0 / page_height as f32,
+                w.width as f32 * 100.0 / page_width as f32, w.height as f32 * 100.0 / page_height as f32)
+        })
+        .collect::<Vec<String>>()
+        .join("");
+    format!(r#"<div class="ocr-overlay" style="max-width: {}px;"><img src="/img?path={}" alt="{}">{}</div>"#,
+        max_width, encode_string(image_path), encode_html_string(image_path), boxes)
+}
+
+pub fn encode_html_string(input: &str) -> String {
+    htmlentity::entity::encode(
+        input.as_bytes(),
+        &htmlentity::entity::EncodeType::NamedOrHex,
+        &htmlentity::entity::CharacterSet::HtmlAndNonASCII,
+    ).to_string().unwrap_or_default()
+}
+
 pub fn link_html(href: String, inner_content: &str) -> String {
     format!(r#"<a href="{}">{}</a>"#, href, inner_content)
 }
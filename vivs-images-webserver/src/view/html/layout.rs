@@ -20,9 +20,14 @@ pub fn layout_view(title: Option<&str>, inner_content: &str) -> String {
             <a href="/">Home</a> |
             <a href="/actions">Actions</a> |
             <a href="/search">Search</a> |
+            <a href="/search/color">Search By Color</a> |
             <a href="/browse/filesystem">Browse Filesystem</a> |
             <a href="/browse/tags">Browse Tags</a> |
-            <a href="/browse/by-property">Browse By Property</a>
+            <a href="/browse/timeline">Timeline</a> |
+            <a href="/browse/events">Events</a> |
+            <a href="/browse/bursts">Bursts</a> |
+            <a href="/browse/by-property">Browse By Property</a> |
+            <a href="/analysis/similarity-algorithms">Compare Similarity</a>
         </div>
         {}
     </body>
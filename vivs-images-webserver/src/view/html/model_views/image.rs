@@ -1,5 +1,4 @@
 use std::error::Error;
-use std::path::Path;
 
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
@@ -9,8 +8,9 @@ use sqlx::SqlitePool;
 use crate::converters::extract_image_thumbnail::extract_multiple_image_thumbnails_standard_sizes_to_png_vec_u8;
 use crate::database::query::query_image_thumbnail::query_thumbnail_table;
 use crate::models::image_thumbnail::ImageThumbnail;
-use crate::view::html::common::{encode_string, image_html, image_thumbnail_html, link_html};
+use crate::view::html::common::{color_palette_html, encode_string, image_html, image_thumbnail_html, image_thumbnail_url_html, link_html};
 use crate::models::image::Image;
+use crate::converters::open_image::open_image;
 
 
 pub fn generate_image_table_rows(rows: &[Result<Image, Box<dyn Error + Send>>], columns: &[String]) -> String {
@@ -24,8 +24,8 @@ pub fn generate_image_table_rows(rows: &[Result<Image, Box<dyn Error + Send>>],
                     let v = image.get_field(c);
                     match c.as_ref() {
                         "thumbnail" => {
-                            if let Some(thumb) = &image.thumbnail {
-                                format!(r#"<td>{}</td>"#, link_html(view_image_href.clone(), &image_thumbnail_html(&thumb, Some(200))))
+                            if image.has_thumbnail {
+                                format!(r#"<td>{}</td>"#, link_html(view_image_href.clone(), &image_thumbnail_url_html(&image.path, 200, Some(200))))
                             } else {
                                 format!(r#"<td>{}</td>"#, link_html(view_image_href.clone(), &image_html(&image.path, Some(200))))                        
                             }
@@ -36,6 +36,10 @@ pub fn generate_image_table_rows(rows: &[Result<Image, Box<dyn Error + Send>>],
                             format!(r#"<td>{}</td>"#, link_html(view_image_href.clone(), &name))
                         },
                         "path" => format!(r#"<td>{}</td>"#, link_html(view_image_href.clone(), &image.path)),
+                        "color_palette" => {
+                            let palette = image.color.as_ref().map(|c| c.get_palette()).unwrap_or_default();
+                            format!(r#"<td>{}</td>"#, color_palette_html(&palette))
+                        },
                         _ => format!(r#"<td>{}</td>"#, v.unwrap_or_default()),
                     }
                 }).collect::<Vec<String>>().join("");
@@ -86,7 +90,7 @@ pub fn generate_image_thumbnail_table_thumbs(thumbs: Vec<ImageThumbnail>) -> Str
 }
 
 pub fn generate_image_thumbnail_table_open_img(path: &str) -> String {
-    match image::open(Path::new(path)) {
+    match open_image(path) {
         Ok(img) => generate_image_thumbnail_table(&img),
         Err(e) => format!("<div>{}</div>", e),
     }
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use actix_web::http::header::LOCATION;
@@ -8,12 +9,14 @@ use actix_web::HttpResponse;
 use serde::Deserialize;
 
 use crate::actions::action_registry::find_action;
+use crate::actions::action_registry::ActionOption;
 use crate::actions::analysis_task_item_processor::TaskOrchestrationOptions;
 use crate::actions::worker_thread::WorkerThread;
+use crate::view::html::common::encode_html_string;
 use crate::view::html::layout::layout_view;
 
 
-pub fn submit_action_form(name:& String, label: &String, dry_run: bool) -> String {
+pub fn submit_action_form(name:& String, label: &String, dry_run: bool, action_options: &[ActionOption]) -> String {
     format!(
         r#"
 <form method="POST" action="/actions/start/{}">
@@ -25,8 +28,21 @@ pub fn submit_action_form(name:& String, label: &String, dry_run: bool) -> Strin
         <option value="faster">faster</option>
         <option value="extreme">extreme</option>
     </select>
+    {}
 </form>
-        "#, name, label, dry_run)
+        "#, name, label, dry_run, action_options_html(action_options))
+}
+
+pub fn action_options_html(action_options: &[ActionOption]) -> String {
+    action_options.iter()
+        .map(|o| match o.field_type.as_str() {
+            // unchecked boxes are not submitted, so a hidden "false" goes first and a checked box overrides it
+            "bool" => format!(r#"<label><input type="hidden" name="{}" value="false" /><input type="checkbox" name="{}" value="true" {} /> {}</label>"#,
+                o.name, o.name, if o.default == "true" { "checked" } else { "" }, o.label),
+            _ => format!(r#"<label>{} <input type="text" name="{}" value="{}" /></label>"#, o.label, o.name, encode_html_string(&o.default)),
+        })
+        .collect::<Vec<String>>()
+        .join("\n    ")
 }
 
 pub async fn view_page_action_detail_get(
@@ -34,12 +50,13 @@ pub async fn view_page_action_detail_get(
 ) -> Result<HttpResponse> {
     if let Some(action) = find_action(action_name.to_string()) {
         let action_title = action.get_label();
+        let action_options = action.get_action_options();
         let mut submit_actions = vec![
-            submit_action_form(&action_name, &action_title, false)
+            submit_action_form(&action_name, &action_title, false, &action_options)
         ];
         if action.get_can_dry_run() {
             let action_title_dry_run = format!("{} (dry run)", action_title);
-            submit_actions.push(submit_action_form(&action_name, &action_title_dry_run, true));
+            submit_actions.push(submit_action_form(&action_name, &action_title_dry_run, true, &action_options));
         }
         let submit_actions_html = submit_actions.join("");
         let content = format!("{} {}", action.get_description(), submit_actions_html);
@@ -60,15 +77,21 @@ pub async fn view_page_action_detail_post(
     req: HttpRequest, 
     worker_thread_pool: web::Data<Arc<WorkerThread>>,
     action_name: web::Path<String>,
-    web::Form(form): web::Form<ActionTaskPostOptions>,
+    web::Form(form_fields): web::Form<Vec<(String, String)>>,
 ) -> Result<HttpResponse> {
+    // later values win, so a checked checkbox overrides its hidden "false"
+    let mut fields: HashMap<String, String> = form_fields.into_iter().collect();
+    let form = ActionTaskPostOptions {
+        orch_style: fields.remove("orch_style"),
+        dry_run: fields.remove("dry_run"),
+    };
     let orch_options = match form.orch_style.unwrap_or_default().as_str() {
         "linear" => TaskOrchestrationOptions::new_linear(),
         "normal" => TaskOrchestrationOptions::new_defaults(),
         "faster" => TaskOrchestrationOptions::new_faster(),
         "extreme" => TaskOrchestrationOptions::new_extreme(),
         _ => TaskOrchestrationOptions::new_defaults(),
-    };
+    }.with_action_options(fields);
     let dry_run = form.dry_run.unwrap_or_default() == "true";
     match worker_thread_pool.get_ref().run_action(action_name.to_string(), dry_run, orch_options) {
         Ok(task_id) => {
@@ -0,0 +1,95 @@
+use actix_web::http::header::LOCATION;
+use actix_web::{web, HttpResponse, Result};
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::database::query::query_image_burst::{query_image_burst_count, query_image_burst_reviews};
+use crate::database::update::update_image_burst::execute_tag_images_for_deletion_sql;
+use crate::models::config::bursts::BURST_DELETE_TAG_NAME;
+use crate::models::image_burst::{ImageBurstReview, ImageBurstReviewFrame};
+use crate::models::query_params::burst_review_params::BurstReviewParams;
+use crate::view::html::common::{create_html_table, encode_html_string, encode_string, image_thumbnail_url_html, link_html};
+use crate::view::html::layout::layout_view;
+
+
+// Most bursts listed at once; tagging them moves the page on to the next ones
+const BURST_REVIEW_MAX_BURSTS: usize = 50;
+
+// Every frame but the keeper starts out checked, so reviewing a burst is usually a single click
+fn frame_html(frame: &ImageBurstReviewFrame) -> String {
+    let image_path = &frame.frame.image_path;
+    let view_image_href = format!("/image?image_path={}", encode_string(image_path));
+    let choice_html = match (frame.tagged_for_deletion, frame.frame.is_keeper()) {
+        (true, _) => format!("tagged {}", BURST_DELETE_TAG_NAME),
+        (false, keeper) => format!(r#"<label><input type="checkbox" name="image_path" value="{}" {} /> delete</label>"#,
+            encode_html_string(image_path), if keeper { "" } else { "checked" }),
+    };
+    let similarity = frame.frame.get_field("burst_frame_similarity")
+        .map(|s| format!(", similarity {}", s))
+        .unwrap_or_default();
+    format!(r#"<div style="display: inline-block; vertical-align: top; margin: 4px;">{}<br/>{}<br/>score {}{}{}<br/>{}</div>"#,
+        link_html(view_image_href, &image_thumbnail_url_html(image_path, 200, Some(200))),
+        frame.taken_at.replace('T', " "),
+        frame.frame.get_field("burst_frame_score").unwrap_or_default(),
+        similarity,
+        if frame.frame.is_keeper() { "<br/><b>suggested keeper</b>" } else { "" },
+        choice_html)
+}
+
+// Submitting the form marks every burst listed on it as reviewed, even when all of its frames are kept
+fn burst_rows_html(reviews: &[ImageBurstReview]) -> String {
+    reviews.iter()
+        .map(|review| format!(r#"<tr><td>{}{}<input type="hidden" name="burst_id" value="{}" /></td><td>{}</td></tr>"#,
+            link_html(review.burst.get_search_href(), &review.burst.to_string()),
+            if review.burst.is_reviewed() { "<br/>reviewed" } else { "" },
+            review.burst.burst_id,
+            review.frames.iter().map(frame_html).collect::<String>()))
+        .collect()
+}
+
+pub async fn view_page_bursts(
+    pool: web::Data<WebServerActionDataContext>,
+    query: web::Query<BurstReviewParams>,
+) -> Result<HttpResponse> {
+    let params = query.into_inner();
+    let show_reviewed = params.get_show_reviewed();
+    let burst_count = query_image_burst_count(&pool.pool).await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let mut content = String::new();
+    if burst_count == 0 {
+        content.push_str("<p>No bursts yet. Run the detect_bursts action to find bursts among images with a capture time and a thumbnail.</p>");
+    } else {
+        let reviews = query_image_burst_reviews(show_reviewed, BURST_REVIEW_MAX_BURSTS, &pool.pool).await
+            .map_err(actix_web::error::ErrorInternalServerError)?;
+        content.push_str(&match show_reviewed {
+            true => format!("<p>Showing every burst. {}</p>", link_html("/browse/bursts".to_string(), "Only show bursts not reviewed yet")),
+            false => format!("<p>Showing bursts not reviewed yet. {}</p>",
+                link_html("/browse/bursts?show_reviewed=true".to_string(), "Show every burst")),
+        });
+        content.push_str(&format!("<p>{}</p>", link_html(format!("/browse/tags/{}", BURST_DELETE_TAG_NAME), "Images tagged for deletion")));
+        let headers = ["Burst", "Frames"].map(String::from).to_vec();
+        let title = format!("{} of {} bursts", reviews.len(), burst_count);
+        content.push_str(&format!(r#"<form method="post" action="/browse/bursts/tag">{}<button type="submit">Tag checked frames {} and mark these bursts reviewed</button></form>"#,
+            create_html_table(&title, &headers, &burst_rows_html(&reviews)), BURST_DELETE_TAG_NAME));
+    }
+
+    let html = layout_view(Some("Bursts"), &content);
+    Ok(HttpResponse::Ok().content_type("text/html").body(html))
+}
+
+pub async fn view_page_bursts_tag_post(
+    pool: web::Data<WebServerActionDataContext>,
+    web::Form(form_fields): web::Form<Vec<(String, String)>>,
+) -> Result<HttpResponse> {
+    let image_paths: Vec<String> = form_fields.iter()
+        .filter(|(name, _)| name == "image_path")
+        .map(|(_, value)| value.clone())
+        .collect();
+    let burst_ids: Vec<i64> = form_fields.iter()
+        .filter(|(name, _)| name == "burst_id")
+        .filter_map(|(_, value)| value.parse().ok())
+        .collect();
+    execute_tag_images_for_deletion_sql(&image_paths, &burst_ids, &pool.pool).await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    Ok(HttpResponse::SeeOther().insert_header((LOCATION, "/browse/bursts")).finish())
+}
\ No newline at end of file
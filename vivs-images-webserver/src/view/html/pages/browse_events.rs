@@ -0,0 +1,71 @@
+use actix_web::{web, HttpResponse, Result};
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::database::query::query_image_event::{query_image_event_year_counts, query_image_events};
+use crate::models::image_event::ImageEvent;
+use crate::models::image_timeline::TimelineBucket;
+use crate::models::query_params::timeline_params::TimelineParams;
+use crate::view::html::common::{create_html_table, image_thumbnail_url_html, link_html};
+use crate::view::html::layout::layout_view;
+
+
+fn years_html(years: &[TimelineBucket]) -> String {
+    let mut html = String::from("<div class='value-list'>");
+    for year in years {
+        html.push_str(&format!(r#"<a href="/browse/events?year={}" class="value-item">{} ({})</a>"#, year.key, year.key, year.count));
+    }
+    html.push_str("</div>");
+    html
+}
+
+// The end date is left out when the event starts and ends on the same day
+fn event_when(event: &ImageEvent) -> String {
+    let start = event.event_start.replace('T', " ");
+    match (event.event_start.get(..10), event.event_end.get(..10)) {
+        (Some(a), Some(b)) if a == b => format!("{} to {}", start, event.event_end.get(11..).unwrap_or_default()),
+        _ => format!("{} to {}", start, event.event_end.replace('T', " ")),
+    }
+}
+
+// Each event links to the search for its images through the cover thumbnail and the date
+fn event_rows_html(events: &[ImageEvent]) -> String {
+    events.iter()
+        .map(|event| {
+            let href = event.get_search_href();
+            format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                link_html(href.clone(), &image_thumbnail_url_html(&event.event_cover_image_path, 200, Some(200))),
+                link_html(href, &event_when(event)),
+                event.event_image_count,
+                event.event_place.as_deref().unwrap_or_default())
+        })
+        .collect()
+}
+
+pub async fn view_page_events(
+    pool: web::Data<WebServerActionDataContext>,
+    query: web::Query<TimelineParams>,
+) -> Result<HttpResponse> {
+    let params = query.into_inner();
+    let years = query_image_event_year_counts(&pool.pool).await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let mut content = String::new();
+    if years.is_empty() {
+        content.push_str("<p>No events yet. Run the cluster_events action to group images with a capture time into events.</p>");
+    } else {
+        content.push_str(&years_html(&years));
+        // the newest year unless a period is picked
+        let prefix = match params.get_prefix() {
+            p if p.is_empty() => years[0].key.clone(),
+            p => p,
+        };
+        let events = query_image_events(&prefix, &pool.pool).await
+            .map_err(actix_web::error::ErrorInternalServerError)?;
+        let headers = ["Cover", "When", "Images", "Place"].map(String::from).to_vec();
+        let title = format!("{} events starting in {}", events.len(), prefix);
+        content.push_str(&create_html_table(&title, &headers, &event_rows_html(&events)));
+    }
+
+    let html = layout_view(Some("Events"), &content);
+    Ok(HttpResponse::Ok().content_type("text/html").body(html))
+}
\ No newline at end of file
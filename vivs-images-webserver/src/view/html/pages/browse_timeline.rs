@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use actix_web::{web, HttpResponse, Result};
+use chrono::{Datelike, NaiveDate};
+
+use crate::core::data_context::WebServerActionDataContext;
+use crate::database::query::query_image_timeline::{query_taken_at_bucket_counts, query_undated_image_count};
+use crate::database::query::search::search_images_by_criteria;
+use crate::models::image_timeline::TimelineBucket;
+use crate::models::query_params::search_params::SearchParams;
+use crate::models::query_params::timeline_params::TimelineParams;
+use crate::view::html::common::{calendar_heat_map_html, create_html_table, link_html};
+use crate::view::html::layout::layout_view;
+use crate::view::html::model_views::image::generate_image_table_rows;
+
+
+// Most images listed for one day; the search page linked below has the rest
+const TIMELINE_DAY_MAX_IMAGES: usize = 200;
+
+fn month_name(month: u32) -> String {
+    NaiveDate::from_ymd_opt(2000, month, 1).map(|d| d.format("%B").to_string()).unwrap_or_default()
+}
+
+fn bucket_label(bucket: &TimelineBucket) -> String {
+    match (bucket.key.len(), bucket.get_number()) {
+        (7, Some(month)) => month_name(month),
+        (_, Some(number)) => number.to_string(),
+        _ => bucket.key.clone(),
+    }
+}
+
+fn bucket_href(bucket: &TimelineBucket) -> String {
+    let parts: Vec<&str> = bucket.key.split('-').collect();
+    let names = ["year", "month", "day"];
+    let query = parts.iter().zip(names)
+        .map(|(value, name)| format!("{}={}", name, value.trim_start_matches('0')))
+        .collect::<Vec<String>>()
+        .join("&");
+    format!("/browse/timeline?{}", query)
+}
+
+fn breadcrumbs_html(params: &TimelineParams) -> String {
+    let mut crumbs = vec![link_html("/browse/timeline".to_string(), "Timeline")];
+    if let Some(year) = params.get_year() {
+        crumbs.push(link_html(format!("/browse/timeline?year={}", year), &year.to_string()));
+        if let Some(month) = params.get_month() {
+            crumbs.push(link_html(format!("/browse/timeline?year={}&month={}", year, month), &month_name(month)));
+            if let Some(date) = params.get_date() {
+                crumbs.push(date.day().to_string());
+            }
+        }
+    }
+    format!(r#"<p class="breadcrumbs">{}</p>"#, crumbs.join(" / "))
+}
+
+fn buckets_html(buckets: &[TimelineBucket]) -> String {
+    let mut html = String::from("<div class='value-list'>");
+    for bucket in buckets {
+        html.push_str(&format!(r#"<a href="{}" class="value-item">{} ({})</a>"#, bucket_href(bucket), bucket_label(bucket), bucket.count));
+    }
+    html.push_str("</div>");
+    html
+}
+
+// One calendar per year, newest first
+fn heat_maps_html(days: &[TimelineBucket]) -> String {
+    let mut days_by_year: HashMap<i32, Vec<TimelineBucket>> = HashMap::new();
+    for day in days {
+        if let Some(date) = day.get_date() {
+            days_by_year.entry(date.year()).or_default().push(day.clone());
+        }
+    }
+    let mut years: Vec<i32> = days_by_year.keys().copied().collect();
+    years.sort_unstable_by(|a, b| b.cmp(a));
+    years.iter()
+        .map(|year| format!("<h3>{}</h3>{}", year, calendar_heat_map_html(*year, &days_by_year[year])))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+async fn day_images_html(pool: &WebServerActionDataContext, date: NaiveDate) -> Result<String> {
+    let day = date.format("%Y-%m-%d");
+    let query = HashMap::from([
+        ("image_taken_at_min".to_string(), format!("{}T00:00:00", day)),
+        ("image_taken_at_max".to_string(), format!("{}T23:59:59.999", day)),
+        ("order_by".to_string(), "image_taken_at ASC".to_string()),
+        ("limit".to_string(), TIMELINE_DAY_MAX_IMAGES.to_string()),
+    ]);
+    let params = SearchParams::new_from_hashmap(&query);
+    let image_search = search_images_by_criteria(pool.clone(), &params, params.get_order_by().as_deref())
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let columns = ["thumbnail", "name", "camera_model", "image_taken_at"].map(String::from).to_vec();
+    let title = format!("{} images taken on {}", image_search.total_count, day);
+    let table_html = create_html_table(&title, &SearchParams::get_column_titles(&columns), &generate_image_table_rows(&image_search.items, &columns));
+    let search_href = format!("/search?{}", serde_urlencoded::to_string(params.into_html_params()).unwrap_or_default());
+    Ok(format!("<p>{}</p>{}", link_html(search_href, "Search within this day"), table_html))
+}
+
+pub async fn view_page_timeline(
+    pool: web::Data<WebServerActionDataContext>,
+    query: web::Query<TimelineParams>,
+) -> Result<HttpResponse> {
+    let params = query.into_inner();
+    let prefix = params.get_prefix();
+
+    let mut content = breadcrumbs_html(&params);
+    if let Some(date) = params.get_date() {
+        content.push_str(&day_images_html(pool.get_ref(), date).await?);
+    } else {
+        // years at the top, then the months of a year, then the days of a month
+        let bucket_len = match prefix.len() {
+            0 => 4,
+            4 => 7,
+            _ => 10,
+        };
+        let buckets = query_taken_at_bucket_counts(&pool.pool, &prefix, bucket_len).await
+            .map_err(actix_web::error::ErrorInternalServerError)?;
+        if buckets.is_empty() {
+            content.push_str("<p>No images with a capture time found.</p>");
+        } else {
+            content.push_str(&buckets_html(&buckets));
+        }
+
+        let year_prefix = params.get_year().map(|y| format!("{:04}", y)).unwrap_or_default();
+        let days = query_taken_at_bucket_counts(&pool.pool, &year_prefix, 10).await
+            .map_err(actix_web::error::ErrorInternalServerError)?;
+        content.push_str(&heat_maps_html(&days));
+
+        if prefix.is_empty() {
+            let undated = query_undated_image_count(&pool.pool).await
+                .map_err(actix_web::error::ErrorInternalServerError)?;
+            content.push_str(&format!("<p>{} images have no known capture time.</p>", undated));
+        }
+    }
+
+    let title = match prefix.is_empty() {
+        true => "Timeline".to_string(),
+        false => format!("Timeline: {}", prefix),
+    };
+    let html = layout_view(Some(&title), &content);
+    Ok(HttpResponse::Ok().content_type("text/html").body(html))
+}
\ No newline at end of file
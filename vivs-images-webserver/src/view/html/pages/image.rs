@@ -5,14 +5,52 @@ use htmlentity::entity::ICodedDataTrait;
 
 use crate::core::data_context::WebServerActionDataContext;
 use crate::database::query::search::{find_image_by_path, SearchBuilder};
+use crate::models::config::similarity::{get_similarity_storage_mode, SimilarityStorageMode};
+use crate::models::image_frames::{ImageFrameThumbnail, ImageFrames};
 use crate::models::image_similarity::ImageSimilarity;
 use crate::models::query_params::search_params::SearchParams;
 use crate::models::query_params::similar_images_params::SimilarImagesParams;
-use crate::view::html::common::{create_html_table, image_html};
+use crate::database::query::query_image_ocr_word::query_ocr_words_from_db;
+use crate::database::query::query_image_frames::query_frame_thumbnails;
+use crate::database::query::query_image_raw::query_raw_paths_for_jpeg_sibling;
+use crate::database::query::query_image_event::query_image_event;
+use crate::view::html::common::{color_palette_html, create_html_table, encode_html_string, encode_string, image_html, image_thumbnail_html, link_html, luma_histogram_html, ocr_overlay_html};
 use crate::view::html::layout::layout_view;
 use crate::view::html::model_views::image::{generate_image_table_rows, generate_image_thumbnail_table_query_thumbnails_db};
 
 
+fn frame_link_html(image_path: &str, frame: &ImageFrameThumbnail, inner_content: &str) -> String {
+    link_html(format!("/image?image_path={}&frame={}", encode_string(image_path), frame.frame_index), inner_content)
+}
+
+// Steps through the stored frame thumbnails of an animation or multi-page file
+fn frames_html(image_path: &str, frames: &ImageFrames, thumbnails: &[ImageFrameThumbnail], selected: Option<u32>) -> String {
+    let mut html = format!("<p>frames: {}</p>", frames);
+    let Some(current) = selected
+        .and_then(|s| thumbnails.iter().position(|t| t.frame_index == s))
+        .or(if thumbnails.is_empty() { None } else { Some(0) }) else {
+        return html;
+    };
+
+    let frame = &thumbnails[current];
+    let prev_html = current.checked_sub(1)
+        .map(|i| frame_link_html(image_path, &thumbnails[i], "&lt; previous"))
+        .unwrap_or_default();
+    let next_html = thumbnails.get(current + 1)
+        .map(|t| frame_link_html(image_path, t, "next &gt;"))
+        .unwrap_or_default();
+    let delay_html = if frames.is_animated != 0 { format!(", shown for {}ms", frame.frame_delay_ms) } else { String::new() };
+    html.push_str(&format!("<div>{}<p>{} frame {} of {}{} {}</p></div>",
+        image_thumbnail_html(&frame.thumbnail, None),
+        prev_html, frame.frame_index + 1, frames.frame_count, delay_html, next_html));
+
+    let strip_html: String = thumbnails.iter()
+        .map(|t| frame_link_html(image_path, t, &image_thumbnail_html(&t.thumbnail, Some(48))))
+        .collect();
+    html.push_str(&format!("<div>{}</div>", strip_html));
+    html
+}
+
 pub async fn view_image(
     pool: web::Data<WebServerActionDataContext>,
     params: web::Query<SimilarImagesParams>,
@@ -23,29 +61,53 @@ pub async fn view_image(
             
             let threshold = params.threshold.unwrap_or(1.0);
 
-            let select_from_source_query = SearchBuilder::new()
-                .with_base_table("image_similarity")
-                .with_select_columns(vec![
-                    "CASE WHEN image_similarity.image_path_a = ? THEN image_similarity.image_path_b ELSE image_similarity.image_path_a END as image_path".to_string(),
-                ])
-                .with_field_meta_columns(ImageSimilarity::get_meta_for_single())
-                .with_select_clause_param(params.image_path.clone())
-                .with_criteria(vec![
-                    ("AND".to_string(), {
-                        let mut map = HashMap::new();
-                        map.insert("image_similarity.similarity_value >= ?".to_string(), "0.5".to_string());
-                        map.insert("image_similarity.similarity_value <= ?".to_string(), threshold.to_string());
-                        map
-                    }),
-                    ("OR".to_string(), {
-                        let mut map = HashMap::new();
-                        map.insert("image_similarity.image_path_a = ?".to_string(), params.image_path.clone());
-                        map.insert("image_similarity.image_path_b = ?".to_string(), params.image_path.clone());
-                        map
-                    })
-                ])
-                .with_order_by("image_similarity.similarity_value DESC")
-                .with_pagination(20, 0);
+            let select_from_source_query = match get_similarity_storage_mode() {
+                SimilarityStorageMode::Full => SearchBuilder::new()
+                    .with_base_table("image_similarity")
+                    .with_select_columns(vec![
+                        "CASE WHEN image_similarity.image_path_a = ? THEN image_similarity.image_path_b ELSE image_similarity.image_path_a END as image_path".to_string(),
+                        "CASE WHEN image_similarity.image_path_a = ? THEN image_similarity.image_id_b ELSE image_similarity.image_id_a END as image_id".to_string(),
+                    ])
+                    .with_field_meta_columns(ImageSimilarity::get_meta_for_single())
+                    .with_select_clause_param(params.image_path.clone())
+                    .with_select_clause_param(params.image_path.clone())
+                    .with_criteria(vec![
+                        ("AND".to_string(), {
+                            let mut map = HashMap::new();
+                            map.insert("image_similarity.similarity_value >= ?".to_string(), "0.5".to_string());
+                            map.insert("image_similarity.similarity_value <= ?".to_string(), threshold.to_string());
+                            map
+                        }),
+                        ("OR".to_string(), {
+                            let mut map = HashMap::new();
+                            map.insert("image_similarity.image_path_a = ?".to_string(), params.image_path.clone());
+                            map.insert("image_similarity.image_path_b = ?".to_string(), params.image_path.clone());
+                            map
+                        })
+                    ])
+                    .with_order_by("image_similarity.similarity_value DESC")
+                    .with_pagination(20, 0),
+                // each image's nearest neighbours are kept under its own path, so no need to check both sides
+                SimilarityStorageMode::TopK => SearchBuilder::new()
+                    .with_base_table("image_similarity_neighbors")
+                    .with_select_columns(vec![
+                        "image_similarity_neighbors.neighbor_path as image_path".to_string(),
+                        "image_similarity_neighbors.neighbor_image_id as image_id".to_string(),
+                        "image_similarity_neighbors.image_comparison_algorithm".to_string(),
+                        "image_similarity_neighbors.similarity_value".to_string(),
+                        "image_similarity_neighbors.similarity_confidence".to_string(),
+                    ])
+                    .with_criteria(vec![
+                        ("AND".to_string(), {
+                            let mut map = HashMap::new();
+                            map.insert("image_similarity_neighbors.image_path = ?".to_string(), params.image_path.clone());
+                            map.insert("image_similarity_neighbors.similarity_value <= ?".to_string(), threshold.to_string());
+                            map
+                        }),
+                    ])
+                    .with_order_by("image_similarity_neighbors.similarity_value DESC")
+                    .with_pagination(20, 0),
+            };
             
             let similar_images = SearchBuilder::new()
                 .with_base_table("image_similarity")
@@ -81,12 +143,81 @@ pub async fn view_image(
             ).to_string().unwrap_or_default();
             let ocr_text = format!("<h4>ocr text:</h4><p><textarea>{}</textarea></p><p>{}</p>", ocr_text, ocr_text);
             let aspect_ratio_html = format!("<p>aspect ratio: {}</p>", image.aspect_ratio.map(|x| x.to_string()).unwrap_or_default());
+            let ocr_min_confidence = params.ocr_min_confidence.unwrap_or(0.0);
+            let ocr_overlay_html = match (image.ocr_summary.as_ref(), query_ocr_words_from_db(&params.image_path, ocr_min_confidence, &pool.get_ref().pool).await) {
+                (Some(summary), Ok(words)) if !words.is_empty() => format!(
+                    "<h4>ocr text regions:</h4><p>{}</p>{}",
+                    summary,
+                    ocr_overlay_html(&params.image_path, &words, summary.ocr_page_width, summary.ocr_page_height, 600)
+                ),
+                (_, Err(e)) => format!("<p>could not get ocr words: {}</p>", e),
+                _ => String::new(),
+            };
+            let color_html = format!("<p>colors: {}</p>", image.color.map(|x| color_palette_html(&x.get_palette())).unwrap_or_default());
+            let exposure_html = image.exposure
+                .filter(|x| !x.luma_histogram.is_empty())
+                .map(|x| format!("<p>exposure: {}</p>{}", x, luma_histogram_html(&x.get_histogram())))
+                .unwrap_or_default();
+            let frames_html = match &image.frames {
+                Some(frames) if frames.frame_count > 1 => match query_frame_thumbnails(&params.image_path, &pool.get_ref().pool).await {
+                    Ok(thumbnails) => frames_html(&params.image_path, frames, &thumbnails, params.frame),
+                    Err(e) => format!("<p>could not get frame thumbnails: {}</p>", e),
+                },
+                _ => String::new(),
+            };
+            let raw_html = match &image.raw {
+                Some(raw) => format!("<p>raw: {}</p>{}", encode_html_string(&raw.to_string()), raw.jpeg_sibling_path.as_ref()
+                    .map(|sibling| format!("<p>jpeg sibling: {}</p>", link_html(format!("/image?image_path={}", encode_string(sibling)), &encode_html_string(sibling))))
+                    .unwrap_or_default()),
+                None => query_raw_paths_for_jpeg_sibling(&params.image_path, &pool.get_ref().pool).await
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|raw_path| format!("<p>raw sibling: {}</p>", link_html(format!("/image?image_path={}", encode_string(raw_path)), &encode_html_string(raw_path))))
+                    .collect(),
+            };
+            // each place name links to the search for other images taken there
+            let place_html = image.geocode
+                .map(|geocode| {
+                    let names: Vec<String> = [("place_city", &geocode.place_city), ("place_region", &geocode.place_region), ("place_country", &geocode.place_country)]
+                        .iter()
+                        .filter_map(|(field, name)| name.as_ref().map(|name| link_html(format!("/search?{}={}", field, encode_string(name)), &encode_html_string(name))))
+                        .collect();
+                    if names.is_empty() {
+                        format!("<p>place: {}</p>", encode_html_string(&geocode.to_string()))
+                    } else {
+                        format!("<p>place: {} ({:.1} km from the city)</p>", names.join(", "), geocode.place_city_distance_km.unwrap_or_default())
+                    }
+                })
+                .unwrap_or_default();
+            let event_html = match image.event.and_then(|e| e.event_id) {
+                Some(event_id) => match query_image_event(event_id, &pool.get_ref().pool).await {
+                    Ok(Some(event)) => format!("<p>event: {}</p>", link_html(event.get_search_href(), &event.to_string())),
+                    Ok(None) => String::new(),
+                    Err(e) => format!("<p>could not get event: {}</p>", e),
+                },
+                None => String::new(),
+            };
+            let burst_html = match image.burst.as_ref().and_then(|b| b.get_search_href().map(|href| (b, href))) {
+                Some((frame, href)) => format!("<p>burst: {}, frame score {}{}</p>",
+                    link_html(href, "frames taken with this one"),
+                    frame.get_field("burst_frame_score").unwrap_or_default(),
+                    if frame.is_keeper() { ", the suggested keeper" } else { "" }),
+                None => String::new(),
+            };
 
-            let body_html = format!("{}{}{}<h4>other properties:</h4>{}{}", 
+            let body_html = format!("{}{}{}{}{}<h4>other properties:</h4>{}{}{}{}{}{}{}{}", 
                 image_html(&params.image_path, Some(200)),
+                frames_html,
                 ocr_text,
+                ocr_overlay_html,
                 thumbnails_html,
                 aspect_ratio_html,
+                place_html,
+                event_html,
+                burst_html,
+                raw_html,
+                color_html,
+                exposure_html,
                 similarity_table_html
             );
 
@@ -8,7 +8,8 @@ use chrono::Utc;
 use sqlx::SqlitePool;
 
 use crate::actions::common::get_all_action_indicators;
-use crate::converters::extract_image_thumbnail::DEFAULT_THUMBNAIL_SIZE_LIST;
+use crate::core::data_context::WebServerActionDataContext;
+use crate::models::config::thumbnails::get_thumbnail_sizes;
 use crate::database::query::query_top_level_metrics::get_top_level_metrics;
 use crate::filesystem::query::images::get_images_in_photo_sync_path;
 use crate::models::query_params::search_params::SearchParams;
@@ -42,6 +43,7 @@ pub async fn get_indicators_html(pool: &SqlitePool) -> Result<String> {
 
 pub async fn index(
     pool: web::Data<SqlitePool>,
+    data: web::Data<WebServerActionDataContext>,
 ) -> Result<HttpResponse> {
     let mut content = search_images_simple_form(pool.get_ref(), &SearchParams::default()).await?;
     content.push_str(r#"
@@ -64,7 +66,7 @@ pub async fn index(
     let xmp_percent = metrics.total_xmp as f32 / total_images_on_disk as f32 * 100.0;
     let brightness_percent = metrics.total_brightness as f32 / total_images_on_disk as f32 * 100.0;
     let similarity_percent = metrics.total_similarity as f32 / total_images_on_disk_factorial as f32 * 100.0;
-    let thumbnail_expected_count = metrics.total_images * (DEFAULT_THUMBNAIL_SIZE_LIST.len() as u32);
+    let thumbnail_expected_count = metrics.total_images * (get_thumbnail_sizes().len() as u32);
     let thumbnail_percent = metrics.total_thumbnails as f32 / thumbnail_expected_count as f32 * 100.0;
     let ocr_text_percent = metrics.total_ocr_text as f32 / total_images_on_disk as f32 * 100.0;
     
@@ -83,6 +85,7 @@ pub async fn index(
                 <li>Total Image Thumbnails: {} ({:.2}% of expected {})</li>
                 <li>Total Image OCR Text: {} ({:.2}% of expected {})</li>
                 <li>Total Tags: {}</li>
+                <li>Thumbnail cache: {}</li>
                 <li>Last Updated: {}</li>
             </ul>
         </div>
@@ -95,7 +98,7 @@ pub async fn index(
     metrics.total_similarity, similarity_percent, total_images_on_disk_factorial,
     metrics.total_thumbnails, thumbnail_percent, thumbnail_expected_count,
     metrics.total_ocr_text, ocr_text_percent, total_images_on_disk,
-    metrics.total_tags, local_time.format("%B %d, %Y, at %T")); // show pretty date
+    metrics.total_tags, data.thumbnail_cache.stats().await, local_time.format("%B %d, %Y, at %T")); // show pretty date
     content.push_str(&dataset_info);
 
     let indicators_to_list_html = get_indicators_html(&pool).await?;
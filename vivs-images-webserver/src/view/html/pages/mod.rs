@@ -7,4 +7,9 @@ pub mod browse_by_property_detail;
 pub mod index;
 pub mod image;
 pub mod search;
-pub mod task_detail;
\ No newline at end of file
+pub mod search_by_color;
+pub mod task_detail;
+pub mod similarity_algorithms;
+pub mod browse_timeline;
+pub mod browse_events;
+pub mod browse_bursts;
\ No newline at end of file
@@ -6,8 +6,10 @@ use crate::core::data_context::WebServerActionDataContext;
 use crate::models::query_params::default_search_params::get_image_wallpaper_based_on_brightness_search_params;
 use crate::view::html::model_views::image::generate_image_table_rows;
 use crate::view::html::layout::layout_view;
-use crate::view::html::common::create_html_table;
+use crate::view::html::common::{create_html_table, geo_scatter_plot_html, link_html};
+use crate::models::query_params::geo_search_params::GeoPoint;
 use crate::models::query_params::search_params::SearchParams;
+use crate::models::query_params::wallpaper_params::WallpaperParams;
 use crate::database::query::search::search_images_by_criteria;
 use crate::view::html::model_views::search_params_advanced::search_images_advanced_form;
 use crate::view::html::model_views::search_params_simple::search_images_simple_form;
@@ -24,7 +26,8 @@ pub async fn search_images(
         search_images_advanced_form(&pool.get_ref().pool, &params).await
     }?;
 
-    let image_search = search_images_by_criteria(pool.get_ref().clone(), &params, Some("image_taken_at DESC"))
+    let order_by = params.get_order_by().unwrap_or("image_taken_at DESC".to_string());
+    let image_search = search_images_by_criteria(pool.get_ref().clone(), &params, Some(&order_by))
         .await
         .map_err(|e| actix_web::error::ErrorInternalServerError(e))?;
 
@@ -42,18 +45,73 @@ pub async fn search_images(
         &rows_html
     );
 
+    let map_link_html = link_html(format!("/search/map?{}", req.query_string()), "View result locations");
+
     let mut content_html = String::new();
     content_html.push_str(&search_form);
+    content_html.push_str(&format!("<p>{}</p>", map_link_html));
     content_html.push_str(&table_html);
 
     let html = layout_view(Some(&title), &content_html);
     Ok(HttpResponse::Ok().content_type("text/html").body(html))
 }
 
+// Most results plotted on one map; the search's own limit and offset are ignored here
+const SEARCH_MAP_MAX_POINTS: i32 = 500;
+
+pub async fn search_images_map(
+    pool: web::Data<WebServerActionDataContext>,
+    req: HttpRequest
+) -> Result<HttpResponse> {
+    let mut params = SearchParams::new_from_querystring(req.query_string());
+    params.set_field_value("limit", Some(SEARCH_MAP_MAX_POINTS.to_string()))
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    params.set_field_value("offset", None)
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let has_latitude_min = params.fields.fields.iter()
+        .any(|f| f.field_meta.name == "gps_latitude_min" && f.field_input.value.as_ref().is_some_and(|v| !v.is_empty()));
+    if !has_latitude_min {
+        // only images with coordinates can be plotted
+        params.set_field_value("gps_latitude_min", Some("-90".to_string()))
+            .map_err(actix_web::error::ErrorInternalServerError)?;
+    }
+
+    let order_by = params.get_order_by().unwrap_or("image_taken_at DESC".to_string());
+    let image_search = search_images_by_criteria(pool.get_ref().clone(), &params, Some(&order_by))
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let points: Vec<(String, GeoPoint)> = image_search.items.iter()
+        .filter_map(|image| image.as_ref().ok())
+        .filter_map(|image| {
+            let exif = image.exif.as_ref()?;
+            Some((image.path.clone(), GeoPoint::new(exif.gps_latitude?, exif.gps_longitude?)?))
+        })
+        .collect();
+
+    let title = format!("Locations of {} of {} results for '{}'", points.len(), image_search.total_count, params.to_string());
+    let plot_html = geo_scatter_plot_html(&points, &params.get_geo_filter());
+
+    let mut content_html = String::new();
+    content_html.push_str(&format!("<p>{}</p>", link_html(format!("/search?{}", req.query_string()), "Back to results")));
+    if plot_html.is_empty() {
+        content_html.push_str("<p>No results have GPS coordinates.</p>");
+    } else {
+        content_html.push_str(&plot_html);
+    }
+
+    let html = layout_view(Some(&title), &content_html);
+    Ok(HttpResponse::Ok().content_type("text/html").body(html))
+}
+
 
 
-pub async fn search_wallpapers() -> Result<HttpResponse> {
-    let params = get_image_wallpaper_based_on_brightness_search_params();
+pub async fn search_wallpapers(query: web::Query<WallpaperParams>) -> Result<HttpResponse> {
+    let brightness_algo = match query.get_brightness_algo() {
+        Ok(algo) => algo,
+        Err(e) => return Ok(HttpResponse::BadRequest().body(e)),
+    };
+    let params = get_image_wallpaper_based_on_brightness_search_params(brightness_algo);
     let params = params.into_html_params();
     let query_string = serde_urlencoded::to_string(&params).unwrap();
     let href = format!("/search?{}", query_string);
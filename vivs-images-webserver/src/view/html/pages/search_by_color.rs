@@ -0,0 +1,78 @@
+use actix_web::{web, HttpResponse, Result};
+
+use crate::calc::color_palette::{hex_to_rgb, hsv_histogram_bin, neighbouring_histogram_bins, palette_distance, rgb_to_hex};
+use crate::core::data_context::WebServerActionDataContext;
+use crate::database::query::query_image_color::query_image_colors_in_histogram_bins;
+use crate::database::query::search::get_images_by_paths;
+use crate::models::query_params::search_by_color_params::SearchByColorParams;
+use crate::models::query_params::search_params::SearchParams;
+use crate::view::html::common::create_html_table;
+use crate::view::html::layout::layout_view;
+use crate::view::html::model_views::image::generate_image_table_rows;
+
+
+const DEFAULT_COLOR_SEARCH_LIMIT: usize = 20;
+// Images with less of the target hue than this are not ranked at all
+const COLOR_SEARCH_MIN_BIN_WEIGHT: f32 = 0.02;
+
+fn search_by_color_form(color: &str, limit: usize) -> String {
+    format!(r#"
+    <div class="search-form">
+        <h3>Search By Color</h3>
+        <form method="get" action="/search/color">
+            <div class="form-group">
+                <label for="color">Color</label>
+                <input type="color" id="color" name="color" value="{}"/>
+                <label for="limit">Results Limit</label>
+                <input type="number" id="limit" name="limit" value="{}"/>
+                <button type="submit">Search</button>
+            </div>
+        </form>
+    </div>
+    "#, color, limit)
+}
+
+pub async fn search_images_by_color(
+    pool: web::Data<WebServerActionDataContext>,
+    params: web::Query<SearchByColorParams>,
+) -> Result<HttpResponse> {
+    let limit = params.limit.unwrap_or(DEFAULT_COLOR_SEARCH_LIMIT);
+    let target = params.color.as_deref().and_then(hex_to_rgb);
+
+    let mut content_html = search_by_color_form(&rgb_to_hex(target.unwrap_or([128, 128, 128])), limit);
+
+    if let Some(target) = target {
+        let bins = neighbouring_histogram_bins(hsv_histogram_bin(target));
+        let colors = query_image_colors_in_histogram_bins(&bins, COLOR_SEARCH_MIN_BIN_WEIGHT, &pool.pool).await
+            .map_err(actix_web::error::ErrorInternalServerError)?;
+
+        let mut ranked: Vec<(f32, String)> = colors.iter()
+            .filter_map(|c| palette_distance(&c.get_palette(), target).map(|d| (d, c.image_path.clone())))
+            .collect();
+        ranked.sort_by(|a, b| a.0.total_cmp(&b.0));
+        ranked.truncate(limit);
+        let paths: Vec<String> = ranked.into_iter().map(|(_, path)| path).collect();
+
+        let mut images = if paths.is_empty() {
+            vec![]
+        } else {
+            get_images_by_paths(pool.get_ref().clone(), paths.clone()).await
+                .map_err(actix_web::error::ErrorInternalServerError)?
+        };
+        // the lookup by paths does not keep the ranking order
+        images.sort_by_key(|img| paths.iter().position(|p| *p == img.path).unwrap_or(usize::MAX));
+        let images: Vec<_> = images.into_iter().map(Ok).collect();
+
+        let columns = ["thumbnail", "name", "color_palette", "dominant_color", "average_saturation", "average_warmth"];
+        let columns = columns.map(String::from).to_vec();
+        let rows_html = generate_image_table_rows(&images, &columns);
+        content_html.push_str(&create_html_table(
+            &format!("{} images closest to {}", images.len(), rgb_to_hex(target)),
+            &SearchParams::get_column_titles(&columns),
+            &rows_html
+        ));
+    }
+
+    let html = layout_view(Some("Search By Color"), &content_html);
+    Ok(HttpResponse::Ok().content_type("text/html").body(html))
+}
\ No newline at end of file
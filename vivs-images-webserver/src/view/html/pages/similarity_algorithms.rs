@@ -0,0 +1,163 @@
+use std::collections::{BTreeMap, HashMap};
+
+use actix_web::{web, HttpResponse, Result};
+
+use crate::calc::similarity_agreement::{pearson_correlation, percentile_ranks, quantile, spearman_correlation, top_quantile_agreement};
+use crate::core::data_context::WebServerActionDataContext;
+use crate::database::query::query_image_similarity::query_similarity_sample_for_algorithms;
+use crate::models::image_similarity::ImageComparisonAlgorithm;
+use crate::models::query_params::similarity_algorithms_params::SimilarityAlgorithmsParams;
+use crate::view::html::common::{create_html_table, encode_string, image_html, link_html};
+use crate::view::html::layout::layout_view;
+
+
+const DEFAULT_SIMILARITY_SAMPLE_SIZE: usize = 500;
+const DISAGREEMENT_ROW_COUNT: usize = 20;
+const DISTRIBUTION_QUANTILES: [f64; 7] = [0.0, 0.1, 0.25, 0.5, 0.75, 0.9, 1.0];
+const AGREEMENT_QUANTILES: [f64; 3] = [0.5, 0.9, 0.99];
+
+type SampledPair = ((String, String), HashMap<ImageComparisonAlgorithm, f64>);
+
+fn similarity_algorithms_form(sample_size: usize, algos: &[ImageComparisonAlgorithm]) -> String {
+    let algo_names: Vec<&str> = algos.iter().map(|a| a.get_name()).collect();
+    let all_names: Vec<&str> = ImageComparisonAlgorithm::all().iter().map(|a| a.get_name()).collect();
+    format!(r#"
+    <div class="search-form">
+        <h3>Compare Similarity Algorithms</h3>
+        <form method="get" action="/analysis/similarity-algorithms">
+            <div class="form-group">
+                <label for="algorithms">Algorithms</label>
+                <input type="text" id="algorithms" name="algorithms" value="{}" title="{}"/>
+                <label for="sample">Sample Size</label>
+                <input type="number" id="sample" name="sample" value="{}"/>
+                <button type="submit">Compare</button>
+            </div>
+        </form>
+        <p>Pairs are sampled from those compared by at least two of the algorithms. Magick stores PSNR in dB while the
+        others store SSIM, so agreement is measured on ranks and each algorithm's threshold is its own quantile.</p>
+    </div>
+    "#, algo_names.join(","), all_names.join(", "), sample_size)
+}
+
+fn format_statistic(v: Option<f64>) -> String {
+    v.map(|v| format!("{:.3}", v)).unwrap_or_else(|| "-".to_string())
+}
+
+fn values_for_algo(pairs: &[SampledPair], algo: &ImageComparisonAlgorithm) -> Vec<f64> {
+    pairs.iter().filter_map(|(_, values)| values.get(algo).copied()).collect()
+}
+
+fn value_distribution_table(algos: &[ImageComparisonAlgorithm], pairs: &[SampledPair]) -> String {
+    let mut rows_html = String::new();
+    for algo in algos {
+        let values = values_for_algo(pairs, algo);
+        let cells: String = DISTRIBUTION_QUANTILES.iter()
+            .map(|q| format!("<td>{}</td>", format_statistic(quantile(&values, *q))))
+            .collect();
+        rows_html.push_str(&format!("<tr><td>{}</td><td>{}</td>{}</tr>", algo.get_name(), values.len(), cells));
+    }
+
+    let mut headers = vec!["Algorithm".to_string(), "Pairs".to_string()];
+    headers.extend(DISTRIBUTION_QUANTILES.iter().map(|q| format!("P{:.0}", q * 100.0)));
+    create_html_table("Similarity value distribution", &headers, &rows_html)
+}
+
+fn agreement_table(algos: &[ImageComparisonAlgorithm], pairs: &[SampledPair]) -> String {
+    let mut rows_html = String::new();
+    for (i, algo_a) in algos.iter().enumerate() {
+        for algo_b in algos.iter().skip(i + 1) {
+            let (values_a, values_b): (Vec<f64>, Vec<f64>) = pairs.iter()
+                .filter_map(|(_, values)| Some((*values.get(algo_a)?, *values.get(algo_b)?)))
+                .unzip();
+            let agreement_cells: String = AGREEMENT_QUANTILES.iter()
+                .map(|q| format!("<td>{}</td>", format_statistic(top_quantile_agreement(&values_a, &values_b, *q))))
+                .collect();
+            rows_html.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>{}</tr>",
+                algo_a.get_name(), algo_b.get_name(), values_a.len(),
+                format_statistic(pearson_correlation(&values_a, &values_b)),
+                format_statistic(spearman_correlation(&values_a, &values_b)),
+                agreement_cells));
+        }
+    }
+
+    let mut headers = ["Algorithm A", "Algorithm B", "Shared Pairs", "Pearson", "Spearman"].map(String::from).to_vec();
+    headers.extend(AGREEMENT_QUANTILES.iter().map(|q| format!("Agree Top {:.0}%", (1.0 - q) * 100.0)));
+    create_html_table("Agreement between algorithms", &headers, &rows_html)
+}
+
+// Pairs whose percentile rank differs the most between algorithms
+fn disagreement_table(algos: &[ImageComparisonAlgorithm], pairs: &[SampledPair]) -> String {
+    let mut ranks: HashMap<&ImageComparisonAlgorithm, HashMap<usize, f64>> = HashMap::new();
+    for algo in algos {
+        let indices: Vec<usize> = pairs.iter().enumerate()
+            .filter(|(_, (_, values))| values.contains_key(algo))
+            .map(|(i, _)| i)
+            .collect();
+        let algo_ranks = percentile_ranks(&values_for_algo(pairs, algo));
+        ranks.insert(algo, indices.into_iter().zip(algo_ranks).collect());
+    }
+
+    let mut spreads: Vec<(f64, usize)> = (0..pairs.len())
+        .filter_map(|i| {
+            let pair_ranks: Vec<f64> = algos.iter().filter_map(|a| ranks[a].get(&i).copied()).collect();
+            if pair_ranks.len() < 2 {
+                return None;
+            }
+            let max = pair_ranks.iter().copied().fold(f64::MIN, f64::max);
+            let min = pair_ranks.iter().copied().fold(f64::MAX, f64::min);
+            Some((max - min, i))
+        })
+        .collect();
+    spreads.sort_by(|a, b| b.0.total_cmp(&a.0));
+    spreads.truncate(DISAGREEMENT_ROW_COUNT);
+
+    let mut rows_html = String::new();
+    for (spread, i) in &spreads {
+        let ((path_a, path_b), values) = &pairs[*i];
+        let algo_cells: String = algos.iter()
+            .map(|a| match (values.get(a), ranks[a].get(i)) {
+                (Some(v), Some(r)) => format!("<td>{:.4} (P{:.0})</td>", v, r * 100.0),
+                _ => "<td>-</td>".to_string(),
+            })
+            .collect();
+        rows_html.push_str(&format!("<tr><td>{}</td><td>{}</td>{}<td>{:.2}</td></tr>",
+            link_html(format!("/image?image_path={}", encode_string(path_a)), &image_html(path_a, Some(120))),
+            link_html(format!("/image?image_path={}", encode_string(path_b)), &image_html(path_b, Some(120))),
+            algo_cells, spread));
+    }
+
+    let mut headers = vec!["Image A".to_string(), "Image B".to_string()];
+    headers.extend(algos.iter().map(|a| a.get_name().to_string()));
+    headers.push("Rank Spread".to_string());
+    create_html_table("Most disagreed upon pairs", &headers, &rows_html)
+}
+
+pub async fn view_page_similarity_algorithms(
+    pool: web::Data<WebServerActionDataContext>,
+    params: web::Query<SimilarityAlgorithmsParams>,
+) -> Result<HttpResponse> {
+    let sample_size = params.sample.unwrap_or(DEFAULT_SIMILARITY_SAMPLE_SIZE);
+    let algos = params.get_algorithms();
+    let mut content_html = similarity_algorithms_form(sample_size, &algos);
+
+    let rows = query_similarity_sample_for_algorithms(&algos, sample_size, &pool.pool).await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut grouped: BTreeMap<(String, String), HashMap<ImageComparisonAlgorithm, f64>> = BTreeMap::new();
+    for row in rows {
+        grouped.entry((row.image_path_a, row.image_path_b))
+            .or_default()
+            .insert(row.image_comparison_algorithm, row.similarity_value as f64);
+    }
+    let pairs: Vec<SampledPair> = grouped.into_iter().collect();
+
+    if pairs.is_empty() {
+        content_html.push_str("<p>No pairs have been compared by more than one of these algorithms yet.</p>");
+    } else {
+        content_html.push_str(&value_distribution_table(&algos, &pairs));
+        content_html.push_str(&agreement_table(&algos, &pairs));
+        content_html.push_str(&disagreement_table(&algos, &pairs));
+    }
+
+    let html = layout_view(Some("Compare Similarity Algorithms"), &content_html);
+    Ok(HttpResponse::Ok().content_type("text/html").body(html))
+}
\ No newline at end of file
@@ -0,0 +1,60 @@
+extern crate image_exif_explorer;
+
+mod tests {
+    use image::{DynamicImage, RgbImage};
+    use image_exif_explorer::calc::brightness::{center_weighted_lightness, mean_rec709_luminance, perceptual_lightness};
+    use image_exif_explorer::converters::extract_image_brightness::{compute_image_brightness, ImageToBrightnessOptions};
+    use image_exif_explorer::models::image_brightness::ImageToBrightnessAlgo;
+    use image_exif_explorer::models::query_params::wallpaper_params::WallpaperParams;
+
+    #[test]
+    fn test_reference_values() {
+        assert!(perceptual_lightness([0, 0, 0]).abs() < 1e-9);
+        assert!((perceptual_lightness([255, 255, 255]) - 1.0).abs() < 1e-9);
+        // sRGB 119 grey is the usual "middle grey" at L* = 50
+        assert!((perceptual_lightness([119, 119, 119]) - 0.50).abs() < 0.005);
+        // linear light makes the same grey far darker
+        assert!((mean_rec709_luminance(&[[119, 119, 119]]) - 0.184).abs() < 0.002);
+    }
+
+    #[test]
+    fn test_center_weighted_prefers_the_middle() {
+        // 9x9 black frame with a white centre pixel
+        let mut pixels = vec![[0u8; 3]; 81];
+        pixels[40] = [255, 255, 255];
+        let uniform = 1.0 / 81.0;
+        assert!(center_weighted_lightness(&pixels, 9, 9) > uniform);
+        assert!(center_weighted_lightness(&pixels, 9, 3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_algorithms_agree_on_extremes() {
+        let white = DynamicImage::ImageRgb8(RgbImage::from_pixel(600, 400, image::Rgb([255, 255, 255])));
+        for algo in ImageToBrightnessAlgo::all() {
+            let v = compute_image_brightness(&white, &ImageToBrightnessOptions { algo });
+            assert!((v - 1.0).abs() < 1e-5, "{:?} gave {}", algo, v);
+        }
+    }
+
+    #[test]
+    fn test_algo_round_trips_through_storage_value() {
+        for algo in ImageToBrightnessAlgo::all() {
+            let stored: u8 = (&algo).into();
+            assert_eq!(ImageToBrightnessAlgo::try_from(stored), Ok(algo));
+            assert_eq!(ImageToBrightnessAlgo::from_name(algo.get_name()), Some(algo));
+        }
+    }
+
+    #[test]
+    fn test_wallpaper_brightness_algo_param() {
+        let algo = |v: Option<&str>| WallpaperParams { brightness_algo: v.map(String::from) }.get_brightness_algo();
+        assert_eq!(algo(None), Ok(None));
+        assert_eq!(algo(Some(" ")), Ok(None));
+        assert_eq!(algo(Some("perceptual_lightness")), Ok(Some(ImageToBrightnessAlgo::PerceptualLightness)));
+        assert_eq!(algo(Some("4")), Ok(Some(ImageToBrightnessAlgo::CenterWeighted)));
+        // a typo is reported with the names that would have worked
+        let e = algo(Some("perceptual")).unwrap_err();
+        assert!(e.contains("simple_image_rs") && e.contains("center_weighted"), "{}", e);
+        assert!(algo(Some("9")).is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,222 @@
+extern crate image_exif_explorer;
+
+mod tests {
+    use std::collections::HashMap;
+
+    use actix_web::web;
+    use chrono::{Duration, NaiveDateTime};
+    use image::{DynamicImage, RgbImage};
+    use image_exif_explorer::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+    use image_exif_explorer::actions::import::new_burst_action::InsertNewBurstsProcessor;
+    use image_exif_explorer::cache::thumbnail_cache::ThumbnailCache;
+    use image_exif_explorer::calc::burst_detection::{score_burst_frames, split_burst_runs, split_run_into_bursts};
+    use image_exif_explorer::core::data_context::WebServerActionDataContext;
+    use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+    use image_exif_explorer::database::query::query_image_burst::query_image_burst_reviews;
+    use image_exif_explorer::database::query::search::search_images_by_criteria;
+    use image_exif_explorer::database::update::update_image_exif::execute_insert_image_exif_sql;
+    use image_exif_explorer::database::update::update_image_image_paths::execute_insert_image_path_sql;
+    use image_exif_explorer::database::update::update_image_sharpness::execute_insert_image_sharpness_sql;
+    use image_exif_explorer::database::update::update_image_thumbnail::execute_insert_image_thumbnail_sql;
+    use image_exif_explorer::models::image_burst::{ImageBurstCandidate, ImageBurstRun};
+    use image_exif_explorer::models::image_exif::ImageExif;
+    use image_exif_explorer::models::image_sharpness::ImageSharpness;
+    use image_exif_explorer::models::image_thumbnail::{ImageThumbnail, ThumbnailFormat};
+    use image_exif_explorer::models::query_params::burst_review_params::BurstReviewParams;
+    use image_exif_explorer::models::query_params::search_params::SearchParams;
+    use image_exif_explorer::view::html::pages::browse_bursts::{view_page_bursts, view_page_bursts_tag_post};
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::{Row, SqlitePool};
+
+    fn candidate(path: &str, taken_at: &str) -> ImageBurstCandidate {
+        ImageBurstCandidate {
+            image_path: path.to_string(),
+            local_time: NaiveDateTime::parse_from_str(taken_at, "%Y-%m-%dT%H:%M:%S%.f").unwrap(),
+            sharpness: None,
+            brightness: None,
+            clipped_highlights: None,
+            clipped_shadows: None,
+        }
+    }
+
+    fn paths(run: &ImageBurstRun) -> Vec<String> {
+        run.get_image_paths()
+    }
+
+    #[test]
+    fn test_runs_split_on_time_gaps() {
+        let candidates = vec![
+            candidate("/e.jpg", "2024-03-02T10:05:00"),
+            candidate("/a.jpg", "2024-03-02T10:00:00"),
+            candidate("/b.jpg", "2024-03-02T10:00:01.500"),
+            candidate("/c.jpg", "2024-03-02T10:00:03"),
+            // alone, so never part of a burst
+            candidate("/d.jpg", "2024-03-02T10:00:10"),
+            candidate("/f.jpg", "2024-03-02T10:05:01"),
+        ];
+        let runs = split_burst_runs(candidates, Duration::seconds(2));
+        assert_eq!(runs.iter().map(paths).collect::<Vec<_>>(), vec![
+            vec!["/a.jpg", "/b.jpg", "/c.jpg"],
+            vec!["/e.jpg", "/f.jpg"],
+        ]);
+        assert!(split_burst_runs(vec![], Duration::seconds(2)).is_empty());
+    }
+
+    #[test]
+    fn test_dissimilar_or_uncompared_frames_end_a_burst() {
+        let mut frames = vec![
+            candidate("/a.jpg", "2024-03-02T10:00:00"),
+            candidate("/b.jpg", "2024-03-02T10:00:01"),
+            candidate("/c.jpg", "2024-03-02T10:00:02"),
+            candidate("/d.jpg", "2024-03-02T10:00:03"),
+            candidate("/e.jpg", "2024-03-02T10:00:04"),
+            candidate("/f.jpg", "2024-03-02T10:00:05"),
+        ];
+        frames[1].sharpness = Some(0.8);
+        frames[0].sharpness = Some(0.4);
+        let run = ImageBurstRun { frames };
+        // the camera turned to something else after b, and e has no thumbnail to compare
+        let bursts = split_run_into_bursts(&run, &[Some(0.95), Some(0.3), Some(0.9), None, Some(0.99)], 0.8);
+        let frame_paths: Vec<Vec<&str>> = bursts.iter().map(|b| b.frames.iter().map(|f| f.image_path.as_str()).collect()).collect();
+        assert_eq!(frame_paths, vec![vec!["/a.jpg", "/b.jpg"], vec!["/c.jpg", "/d.jpg"], vec!["/e.jpg", "/f.jpg"]]);
+        assert_eq!(bursts[0].keeper_image_path, "/b.jpg");
+        assert_eq!(bursts[0].frames[0].similarity, None);
+        assert_eq!(bursts[0].frames[1].similarity, Some(0.95));
+        assert_eq!(bursts[2].frames[0].similarity, None);
+        // nothing known about either frame, so the first one is kept
+        assert_eq!(bursts[1].keeper_image_path, "/c.jpg");
+    }
+
+    #[test]
+    fn test_scores_sharpness_then_brightness_then_exposure() {
+        let mut frames = vec![
+            candidate("/a.jpg", "2024-03-02T10:00:00"),
+            candidate("/b.jpg", "2024-03-02T10:00:01"),
+            candidate("/c.jpg", "2024-03-02T10:00:02"),
+        ];
+        frames[0].brightness = Some(0.9);
+        frames[1].brightness = Some(0.5);
+        frames[2].brightness = Some(0.5);
+        frames[1].clipped_highlights = Some(20.0);
+        frames[1].clipped_shadows = Some(5.0);
+        frames[2].clipped_highlights = Some(1.0);
+        frames[2].clipped_shadows = Some(0.0);
+        let scores = score_burst_frames(&frames);
+        assert!(scores[2] > scores[1] && scores[1] > scores[0]);
+        assert!((scores[2] - 10.99).abs() < 1e-9);
+
+        // a sharper frame wins however it is exposed
+        frames[0].sharpness = Some(120.0);
+        frames[2].sharpness = Some(60.0);
+        let scores = score_burst_frames(&frames);
+        assert!(scores[0] > scores[2] && scores[2] > scores[1]);
+        assert!((scores[0] - 102.0).abs() < 1e-9);
+    }
+
+    fn gradient(horizontal: bool) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(32, 32, |x, y| {
+            let v = (if horizontal { x } else { y } * 8) as u8;
+            image::Rgb([v, v, v])
+        }))
+    }
+
+    async fn detect(pool: &SqlitePool) -> usize {
+        let data = WebServerActionDataContext::new(pool.clone(), ThumbnailCache::new());
+        let processor = InsertNewBurstsProcessor::with_limits(2.0, 0.8);
+        let analysis = processor.get_analysis(data.clone(), None).await.unwrap();
+        let mut processed = 0;
+        for item in processor.get_task_items_from_analysis(data.clone(), analysis, None).await.unwrap() {
+            if processor.task_already_completed(&item, data.clone()).await.unwrap() {
+                continue;
+            }
+            let output = processor.process_task_item(item, false, data.clone()).await.unwrap().unwrap();
+            processor.process_task_output(output, data.clone()).await.unwrap();
+            processed += 1;
+        }
+        processed
+    }
+
+    async fn page_body(data: &WebServerActionDataContext, query: &str) -> String {
+        let params = web::Query::<BurstReviewParams>::from_query(query).unwrap();
+        let resp = view_page_bursts(web::Data::new(data.clone()), params).await.unwrap();
+        String::from_utf8(actix_web::body::to_bytes(resp.into_body()).await.unwrap().to_vec()).unwrap()
+    }
+
+    #[actix_web::test]
+    async fn test_detect_action_stores_bursts_for_review_and_tagging() {
+        // search_params.json is looked up relative to the working directory
+        std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+        let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        apply_migrations(&pool).await.unwrap();
+        let images = [
+            ("/burst/a.jpg", "2024-03-02T10:00:00", true, 0.3),
+            ("/burst/b.jpg", "2024-03-02T10:00:00.400", true, 0.9),
+            ("/burst/c.jpg", "2024-03-02T10:00:00.800", true, 0.5),
+            // a second later, but of something else
+            ("/burst/d.jpg", "2024-03-02T10:00:01.500", false, 1.0),
+            ("/later.jpg", "2024-03-02T12:00:00", true, 1.0),
+        ];
+        for (path, taken_at, horizontal, sharpness) in images {
+            execute_insert_image_path_sql(&path.to_string(), &pool).await.unwrap();
+            let mut exif = ImageExif::default(path);
+            exif.image_taken_at = Some(taken_at.to_string());
+            execute_insert_image_exif_sql(exif, pool.clone()).await.unwrap();
+            let thumbnail = ImageThumbnail::from_image(path.to_string(), ThumbnailFormat::PNG, &gradient(horizontal));
+            execute_insert_image_thumbnail_sql(&thumbnail, &pool).await.unwrap();
+            let sharpness = ImageSharpness { image_path: path.to_string(), sharpness, motion_blur: 0.0, motion_blur_angle: 0.0 };
+            execute_insert_image_sharpness_sql(&sharpness, &pool).await.unwrap();
+        }
+
+        assert_eq!(detect(&pool).await, 1);
+        let reviews = query_image_burst_reviews(false, 10, &pool).await.unwrap();
+        assert_eq!(reviews.len(), 1);
+        let burst = &reviews[0].burst;
+        assert_eq!((burst.burst_start.as_str(), burst.burst_end.as_str()), ("2024-03-02T10:00:00", "2024-03-02T10:00:00.800"));
+        assert_eq!(burst.burst_keeper_image_path, "/burst/b.jpg");
+        let frames: Vec<(&str, bool)> = reviews[0].frames.iter().map(|f| (f.frame.image_path.as_str(), f.frame.is_keeper())).collect();
+        assert_eq!(frames, vec![("/burst/a.jpg", false), ("/burst/b.jpg", true), ("/burst/c.jpg", false)]);
+
+        // every image of the run was checked, including the one left out of the burst
+        assert_eq!(detect(&pool).await, 0);
+
+        let data = WebServerActionDataContext::new(pool.clone(), ThumbnailCache::new());
+        let query = HashMap::from([("burst_id".to_string(), burst.burst_id.to_string())]);
+        let params = SearchParams::new_from_hashmap(&query);
+        let search = search_images_by_criteria(data.clone(), &params, None).await.unwrap();
+        assert_eq!(search.total_count, 3);
+
+        let body = page_body(&data, "").await;
+        assert!(body.contains("1 of 1 bursts"));
+        assert!(body.contains("suggested keeper"));
+        assert_eq!(body.matches(r#"name="image_path""#).count(), 3);
+        assert_eq!(body.matches(r#" checked />"#).count(), 2);
+
+        // keeping every frame still marks the burst reviewed
+        let burst_field = ("burst_id".to_string(), burst.burst_id.to_string());
+        let resp = view_page_bursts_tag_post(web::Data::new(data.clone()), web::Form(vec![burst_field.clone()])).await.unwrap();
+        assert_eq!(resp.status(), actix_web::http::StatusCode::SEE_OTHER);
+        assert!(page_body(&data, "").await.contains("0 of 1 bursts"));
+        let tag_count: i64 = sqlx::query("SELECT COUNT(*) FROM image_tags").fetch_one(&pool).await.unwrap().get(0);
+        assert_eq!(tag_count, 0);
+
+        let form = vec![
+            ("image_path".to_string(), "/burst/a.jpg".to_string()),
+            ("image_path".to_string(), "/burst/c.jpg".to_string()),
+            burst_field,
+        ];
+        for _ in 0..2 {
+            let resp = view_page_bursts_tag_post(web::Data::new(data.clone()), web::Form(form.clone())).await.unwrap();
+            assert_eq!(resp.status(), actix_web::http::StatusCode::SEE_OTHER);
+        }
+        let rows = sqlx::query("SELECT image_path FROM image_tags WHERE tag_name = 'to-delete' ORDER BY image_path").fetch_all(&pool).await.unwrap();
+        let tagged: Vec<String> = rows.iter().map(|r| r.get("image_path")).collect();
+        assert_eq!(tagged, vec!["/burst/a.jpg", "/burst/c.jpg"]);
+
+        // a reviewed burst drops off the page unless asked for
+        assert!(page_body(&data, "").await.contains("0 of 1 bursts"));
+        let body = page_body(&data, "show_reviewed=true").await;
+        assert!(body.contains("1 of 1 bursts"));
+        assert!(body.contains("<br/>reviewed"));
+        assert_eq!(body.matches("tagged to-delete").count(), 2);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,117 @@
+extern crate image_exif_explorer;
+
+mod common;
+
+mod tests {
+    use image_exif_explorer::calc::color_palette::{format_histogram, hex_to_rgb, hsv_histogram, hsv_histogram_bin, median_cut_palette, neighbouring_histogram_bins, palette_distance, parse_palette, HSV_HISTOGRAM_BINS};
+    use image_exif_explorer::converters::extract_image_color::extract_image_color_model;
+    use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+    use image_exif_explorer::database::query::query_image_color::query_image_colors_in_histogram_bins;
+    use image_exif_explorer::database::update::update_image_color::execute_insert_image_color_sql;
+    use image_exif_explorer::database::update::update_image_image_paths::execute_insert_image_path_sql;
+    use image::{DynamicImage, RgbImage};
+
+    use crate::common::open_memory_pool;
+
+    const RED: [u8; 3] = [220, 30, 30];
+    const BLUE: [u8; 3] = [30, 60, 220];
+
+    // The left `red_columns` of every row are red, the rest blue
+    fn red_and_blue(width: u32, height: u32, red_columns: u32) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |x, _| image::Rgb(if x < red_columns { RED } else { BLUE })))
+    }
+
+    fn solid(rgb: [u8; 3]) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_pixel(32, 32, image::Rgb(rgb)))
+    }
+
+    #[test]
+    fn test_median_cut_palette_splits_two_colours_by_weight() {
+        let pixels = |img: DynamicImage| img.to_rgb8().pixels().map(|p| p.0).collect::<Vec<[u8; 3]>>();
+
+        // split at the median, an even image can not be split further than its two colours
+        let palette = median_cut_palette(&pixels(red_and_blue(40, 10, 20)), 5);
+        assert_eq!(palette.iter().map(|c| (c.rgb, c.weight)).collect::<Vec<_>>(), vec![(RED, 0.5), (BLUE, 0.5)]);
+
+        // an uneven one may list a colour twice, but the weights per colour still add up
+        let palette = median_cut_palette(&pixels(red_and_blue(40, 10, 30)), 5);
+        let weight_of = |rgb| palette.iter().filter(|c| c.rgb == rgb).map(|c| c.weight).sum::<f32>();
+        assert_eq!(palette[0].rgb, RED);
+        assert!((weight_of(RED) - 0.75).abs() < 1e-6);
+        assert!((weight_of(BLUE) - 0.25).abs() < 1e-6);
+
+        assert_eq!(median_cut_palette(&pixels(solid(BLUE)), 5).len(), 1);
+        assert!(median_cut_palette(&[], 5).is_empty());
+    }
+
+    #[test]
+    fn test_extract_image_color_model_of_a_synthetic_image() {
+        let color = extract_image_color_model("/images/flag.png", &red_and_blue(64, 64, 48));
+        assert_eq!(color.dominant_color, "#dc1e1e");
+        assert_eq!(hex_to_rgb(&color.dominant_color), Some(RED));
+
+        let palette = parse_palette(&color.color_palette);
+        assert!(palette.iter().all(|c| c.rgb == RED || c.rgb == BLUE));
+        assert!((palette.iter().map(|c| c.weight).sum::<f32>() - 1.0).abs() < 1e-3);
+
+        let histogram = color.get_histogram();
+        assert_eq!(histogram.len(), HSV_HISTOGRAM_BINS);
+        assert!((histogram[hsv_histogram_bin(RED)] - 0.75).abs() < 1e-4);
+        assert!((histogram[hsv_histogram_bin(BLUE)] - 0.25).abs() < 1e-4);
+        assert!(color.average_warmth > 0.0);
+
+        let grey = extract_image_color_model("/images/grey.png", &solid([128, 128, 128]));
+        assert_eq!(grey.average_saturation, 0.0);
+        assert_eq!(grey.get_histogram()[hsv_histogram_bin([128, 128, 128])], 1.0);
+    }
+
+    #[test]
+    fn test_palette_distance_prefers_dominant_match() {
+        let mostly_red = median_cut_palette(&[RED, RED, RED, BLUE], 5);
+        let mostly_blue = median_cut_palette(&[RED, BLUE, BLUE, BLUE], 5);
+        let to_red = |p| palette_distance(p, [230, 20, 20]).unwrap();
+        assert!(to_red(&mostly_red) < to_red(&mostly_blue));
+        assert_eq!(palette_distance(&[], RED), None);
+    }
+
+    #[test]
+    fn test_neighbouring_histogram_bins() {
+        assert_eq!(neighbouring_histogram_bins(0), vec![11, 0, 1]);
+        assert_eq!(neighbouring_histogram_bins(5), vec![4, 5, 6]);
+        assert_eq!(neighbouring_histogram_bins(12), vec![12, 13]);
+        assert_eq!(neighbouring_histogram_bins(14), vec![13, 14]);
+        // every stored bin has the same width, whatever its value
+        let formatted = format_histogram(&hsv_histogram(&[RED, RED, BLUE]));
+        assert!(formatted.split(',').all(|b| b.len() == 6));
+    }
+
+    #[tokio::test]
+    async fn test_query_image_colors_in_histogram_bins_filters_in_sql() {
+        let pool = open_memory_pool().await;
+        apply_migrations(&pool).await.unwrap();
+        let images = [
+            ("/images/red.png", solid(RED)),
+            ("/images/blue.png", solid(BLUE)),
+            ("/images/red_accent.png", red_and_blue(64, 64, 1)),
+            ("/images/grey.png", solid([128, 128, 128])),
+        ];
+        for (path, img) in images {
+            execute_insert_image_path_sql(&path.to_string(), &pool).await.unwrap();
+            execute_insert_image_color_sql(&extract_image_color_model(path, &img), &pool).await.unwrap();
+        }
+
+        let red_bins = neighbouring_histogram_bins(hsv_histogram_bin(RED));
+        let mut paths: Vec<String> = query_image_colors_in_histogram_bins(&red_bins, 0.02, &pool).await.unwrap()
+            .into_iter().map(|c| c.image_path).collect();
+        paths.sort();
+        // the accent covers less than the minimum weight of the image, so it is left out
+        assert_eq!(paths, vec!["/images/red.png"]);
+
+        let grey_bins = neighbouring_histogram_bins(hsv_histogram_bin([128, 128, 128]));
+        let paths: Vec<String> = query_image_colors_in_histogram_bins(&grey_bins, 0.02, &pool).await.unwrap()
+            .into_iter().map(|c| c.image_path).collect();
+        assert_eq!(paths, vec!["/images/grey.png"]);
+
+        assert!(query_image_colors_in_histogram_bins(&[], 0.0, &pool).await.unwrap().is_empty());
+    }
+}
\ No newline at end of file
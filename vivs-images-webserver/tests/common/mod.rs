@@ -0,0 +1,78 @@
+// Fixtures shared by the integration tests; each test crate uses only some of them
+#![allow(dead_code)]
+
+use std::str::FromStr;
+
+use image::{DynamicImage, RgbImage};
+use image_exif_explorer::cache::thumbnail_cache::ThumbnailCache;
+use image_exif_explorer::core::data_context::WebServerActionDataContext;
+use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+use image_exif_explorer::database::update::update_image_image_paths::execute_insert_image_path_sql;
+use image_exif_explorer::database::update::update_image_thumbnail::execute_insert_image_thumbnail_sql;
+use image_exif_explorer::models::image_thumbnail::{ImageThumbnail, ThumbnailFormat};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+
+// One connection, so every query sees the same in-memory database
+pub async fn open_memory_pool() -> SqlitePool {
+    let options = SqliteConnectOptions::from_str("sqlite::memory:").unwrap().foreign_keys(true);
+    SqlitePoolOptions::new().max_connections(1).connect_with(options).await.unwrap()
+}
+
+// A black square JPEG thumbnail of the given size
+pub fn thumbnail(path: &str, size: u32) -> ImageThumbnail {
+    let img = DynamicImage::ImageRgb8(RgbImage::new(size, size));
+    ImageThumbnail::from_image(path.to_string(), ThumbnailFormat::JPG, &img)
+}
+
+// Registers every path and stores a thumbnail of each size for it
+pub async fn data_context_with_thumbnails(paths: &[&str], sizes: &[u32]) -> WebServerActionDataContext {
+    let pool = open_memory_pool().await;
+    apply_migrations(&pool).await.unwrap();
+    for path in paths {
+        execute_insert_image_path_sql(&path.to_string(), &pool).await.unwrap();
+        for size in sizes {
+            execute_insert_image_thumbnail_sql(&thumbnail(path, *size), &pool).await.unwrap();
+        }
+    }
+    WebServerActionDataContext::new(pool, ThumbnailCache::new())
+}
+
+pub const TIFF_SHORT: u16 = 3;
+pub const TIFF_LONG: u16 = 4;
+
+// Appends one 12 byte little-endian IFD entry holding a single value
+pub fn ifd_entry(out: &mut Vec<u8>, tag: u16, field_type: u16, value: u32) {
+    out.extend_from_slice(&tag.to_le_bytes());
+    out.extend_from_slice(&field_type.to_le_bytes());
+    out.extend_from_slice(&1u32.to_le_bytes());
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+// An uncompressed 8-bit greyscale TIFF with one width x height page per pixel buffer, each page
+// tagged with the orientation when one is given
+pub fn greyscale_tiff_bytes(width: u32, height: u32, pages: &[Vec<u8>], orientation: Option<u32>) -> Vec<u8> {
+    let entries: u16 = if orientation.is_some() { 9 } else { 8 };
+    let ifd_len = 2 + entries as u32 * 12 + 4;
+    let mut out = b"II\x2A\x00".to_vec();
+    out.extend_from_slice(&8u32.to_le_bytes());
+    for (i, pixels) in pages.iter().enumerate() {
+        let pixels_offset = out.len() as u32 + ifd_len;
+        out.extend_from_slice(&entries.to_le_bytes());
+        ifd_entry(&mut out, 256, TIFF_LONG, width);
+        ifd_entry(&mut out, 257, TIFF_LONG, height);
+        ifd_entry(&mut out, 258, TIFF_LONG, 8);
+        ifd_entry(&mut out, 259, TIFF_LONG, 1);
+        ifd_entry(&mut out, 262, TIFF_LONG, 1);
+        ifd_entry(&mut out, 273, TIFF_LONG, pixels_offset);
+        if let Some(orientation) = orientation {
+            ifd_entry(&mut out, 274, TIFF_LONG, orientation);
+        }
+        ifd_entry(&mut out, 278, TIFF_LONG, height);
+        ifd_entry(&mut out, 279, TIFF_LONG, pixels.len() as u32);
+        let next = if i + 1 < pages.len() { pixels_offset + pixels.len() as u32 } else { 0 };
+        out.extend_from_slice(&next.to_le_bytes());
+        out.extend_from_slice(pixels);
+    }
+    out
+}
\ No newline at end of file
@@ -0,0 +1,98 @@
+extern crate image_exif_explorer;
+
+mod common;
+
+mod tests {
+    use image_exif_explorer::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+    use image_exif_explorer::actions::import::new_image_paths_action::ImagePathsProcessor;
+    use image_exif_explorer::cache::thumbnail_cache::ThumbnailCache;
+    use image_exif_explorer::converters::extract_image_content_hash::hash_reader_sha256;
+    use image_exif_explorer::core::data_context::WebServerActionDataContext;
+    use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+    use image_exif_explorer::database::update::update_image_image_paths::execute_insert_image_path_sql;
+    use image_exif_explorer::database::update::update_image_path_rekey::execute_rekey_image_path_sql;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::Row;
+
+    use crate::common::open_memory_pool;
+
+    #[test]
+    fn test_hash_reader_sha256_known_vector() {
+        let (hash, size) = hash_reader_sha256("abc".as_bytes()).unwrap();
+        assert_eq!(hash, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(size, 3);
+    }
+
+    #[test]
+    fn test_hash_reader_sha256_spans_buffer_reads() {
+        let data = vec![7u8; 200 * 1024 + 13];
+        let (hash_a, size) = hash_reader_sha256(data.as_slice()).unwrap();
+        let (hash_b, _) = hash_reader_sha256(std::io::Cursor::new(data.clone())).unwrap();
+        assert_eq!(size, data.len() as i64);
+        assert_eq!(hash_a, hash_b);
+        assert_ne!(hash_a, hash_reader_sha256(&data[1..]).unwrap().0);
+    }
+
+    #[tokio::test]
+    async fn test_rekey_image_path_moves_rows() {
+        let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        for sql in [
+            "CREATE TABLE image_paths (image_id INTEGER PRIMARY KEY, image_path TEXT NOT NULL UNIQUE);",
+            "INSERT INTO image_paths (image_path) VALUES ('/old/a.jpg');",
+            "CREATE TABLE image_content_hash (image_path TEXT PRIMARY KEY, content_hash TEXT NOT NULL, file_size INTEGER NOT NULL);",
+            "CREATE TABLE image_brightness (image_path TEXT PRIMARY KEY, brightness REAL NOT NULL);",
+            "CREATE TABLE image_similarity (image_comparison_algorithm INTEGER NOT NULL, image_path_a TEXT NOT NULL, image_path_b TEXT NOT NULL, similarity_value REAL NOT NULL, similarity_confidence REAL NOT NULL, PRIMARY KEY (image_path_a, image_path_b, image_comparison_algorithm));",
+            "INSERT INTO image_content_hash VALUES ('/old/a.jpg', 'abc', 3);",
+            "INSERT INTO image_brightness VALUES ('/old/a.jpg', 0.5);",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+        sqlx::query("INSERT INTO image_similarity VALUES (0, '/m.jpg', '/old/a.jpg', 0.9, 1.0);")
+            .execute(&pool).await.unwrap();
+
+        let moved = execute_rekey_image_path_sql("/old/a.jpg", "/archive/a.jpg", &pool).await.unwrap();
+        assert_eq!(moved, 4);
+
+        let row = sqlx::query("SELECT image_path FROM image_brightness").fetch_one(&pool).await.unwrap();
+        assert_eq!(row.get::<String, _>("image_path"), "/archive/a.jpg");
+        // the moved path now sorts first, so the pair is stored the other way around
+        let row = sqlx::query("SELECT image_path_a, image_path_b FROM image_similarity").fetch_one(&pool).await.unwrap();
+        assert_eq!(row.get::<String, _>("image_path_a"), "/archive/a.jpg");
+        assert_eq!(row.get::<String, _>("image_path_b"), "/m.jpg");
+        let count: i64 = sqlx::query("SELECT COUNT(*) ct FROM image_content_hash WHERE image_path = '/old/a.jpg'").fetch_one(&pool).await.unwrap().get("ct");
+        assert_eq!(count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_adding_a_moved_path_takes_over_the_old_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let moved_path = dir.path().join("moved.jpg").to_str().unwrap().to_string();
+        let new_path = dir.path().join("new.jpg").to_str().unwrap().to_string();
+        std::fs::write(&moved_path, b"abc").unwrap();
+        std::fs::write(&new_path, b"abcd").unwrap();
+        let pool = open_memory_pool().await;
+        apply_migrations(&pool).await.unwrap();
+        execute_insert_image_path_sql(&"/gone/a.jpg".to_string(), &pool).await.unwrap();
+        sqlx::query("INSERT INTO image_content_hash (image_path, content_hash, file_size) VALUES ('/gone/a.jpg', ?, 3)")
+            .bind(hash_reader_sha256("abc".as_bytes()).unwrap().0)
+            .execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO image_brightness (image_path, brightness) VALUES ('/gone/a.jpg', 0.5)").execute(&pool).await.unwrap();
+
+        // the hash action is never run; adding the paths finds the move by itself
+        let data = WebServerActionDataContext::new(pool.clone(), ThumbnailCache::new());
+        let processor = ImagePathsProcessor::new();
+        for image_path in [&moved_path, &new_path] {
+            let output = processor.process_task_item(image_path.clone(), false, data.clone()).await.unwrap().unwrap();
+            processor.process_task_output(output, data.clone()).await.unwrap();
+        }
+
+        let paths: Vec<String> = sqlx::query_scalar("SELECT image_path FROM image_paths ORDER BY image_path").fetch_all(&pool).await.unwrap();
+        let mut expected = vec![moved_path.clone(), new_path.clone()];
+        expected.sort();
+        assert_eq!(paths, expected);
+        let brightness_path: String = sqlx::query_scalar("SELECT image_path FROM image_brightness").fetch_one(&pool).await.unwrap();
+        assert_eq!(brightness_path, moved_path);
+        let hashed: Vec<String> = sqlx::query_scalar("SELECT image_path FROM image_content_hash ORDER BY image_path").fetch_all(&pool).await.unwrap();
+        assert_eq!(hashed, expected);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,96 @@
+extern crate image_exif_explorer;
+
+mod tests {
+    use std::collections::HashMap;
+
+    use actix_web::http::{header, StatusCode};
+    use actix_web::test::TestRequest;
+    use actix_web::web;
+    use image::{DynamicImage, GenericImageView, RgbImage};
+    use image_exif_explorer::api::web::get_image;
+    use image_exif_explorer::cache::derivative_cache::DerivativeCache;
+    use image_exif_explorer::converters::resize_image::resize_image;
+    use image_exif_explorer::models::config::derivatives::DERIVATIVE_CACHE_DIR_ENV_VAR;
+    use image_exif_explorer::models::image_thumbnail::ThumbnailFormat;
+    use image_exif_explorer::models::query_params::image_resize_params::{ImageFit, ImageResizeParams};
+
+    fn params(query: &str) -> Result<Option<ImageResizeParams>, String> {
+        ImageResizeParams::from_query(&serde_urlencoded::from_str::<HashMap<String, String>>(query).unwrap())
+    }
+
+    #[test]
+    fn test_resize_params_and_fits() {
+        assert_eq!(params("path=a.jpg").unwrap(), None);
+        assert!(params("w=0").is_err());
+        assert!(params("w=10&fit=squash").is_err());
+        let p = params("w=100000&format=png").unwrap().unwrap();
+        assert_eq!((p.width, p.fit, p.format), (Some(4096), ImageFit::Contain, ThumbnailFormat::PNG));
+        // webp could only be written lossless, ignoring the quality
+        assert!(params("w=10&format=webp").is_err());
+
+        let img = DynamicImage::ImageRgb8(RgbImage::new(400, 200));
+        let resize = |q: &str| resize_image(&img, &params(q).unwrap().unwrap()).dimensions();
+        assert_eq!(resize("w=100"), (100, 50));
+        assert_eq!(resize("w=100&h=100&fit=cover"), (100, 100));
+        assert_eq!(resize("w=100&h=100&fit=fill"), (100, 100));
+        assert_eq!(resize("w=100&h=100"), (100, 50));
+        // never enlarged
+        assert_eq!(resize("w=1000"), (400, 200));
+        // a box bigger than the image shrinks as a whole, keeping its shape
+        assert_eq!(resize("w=800&h=800&fit=cover"), (200, 200));
+        assert_eq!(resize("w=1000&h=250&fit=fill"), (400, 100));
+    }
+
+    #[test]
+    fn test_cache_key_changes_with_quality() {
+        let dir = tempfile::tempdir().unwrap();
+        let image_path = dir.path().join("a.jpg");
+        std::fs::write(&image_path, [0; 4]).unwrap();
+        let image_path = image_path.to_str().unwrap();
+        let p = params("w=100&format=jpg").unwrap().unwrap();
+        assert_eq!(DerivativeCache::cache_key(image_path, &p, 80).unwrap(), DerivativeCache::cache_key(image_path, &p, 80).unwrap());
+        assert_ne!(DerivativeCache::cache_key(image_path, &p, 80).unwrap(), DerivativeCache::cache_key(image_path, &p, 90).unwrap());
+    }
+
+    #[test]
+    fn test_least_recently_used_copies_are_evicted() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DerivativeCache::new(dir.path().to_path_buf(), 25);
+        cache.insert("a.jpg", &[0; 10]).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        cache.insert("b.jpg", &[1; 10]).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        // reading a makes b the least recently used
+        assert_eq!(cache.get("a.jpg").unwrap(), vec![0; 10]);
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        cache.insert("c.jpg", &[2; 10]).unwrap();
+
+        assert!(cache.get("b.jpg").is_none());
+        assert!(cache.get("a.jpg").is_some());
+        assert!(cache.get("c.jpg").is_some());
+        assert_eq!(cache.total_bytes().unwrap(), 20);
+    }
+
+    #[actix_web::test]
+    async fn test_img_serves_cached_resized_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        std::env::set_var(DERIVATIVE_CACHE_DIR_ENV_VAR, dir.path().join("derivatives"));
+        let image_path = dir.path().join("photo.png");
+        DynamicImage::ImageRgb8(RgbImage::new(300, 150)).save(&image_path).unwrap();
+        let query = format!("path={}&w=60", urlencoding::encode(image_path.to_str().unwrap()));
+
+        let req = TestRequest::default().to_http_request();
+        let resp = get_image(req, web::Query::from_query(&query).unwrap()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get(header::CONTENT_TYPE).unwrap(), "image/jpeg");
+        let etag = resp.headers().get(header::ETAG).unwrap().clone();
+        let body = actix_web::body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(image::load_from_memory(&body).unwrap().dimensions(), (60, 30));
+        assert_eq!(std::fs::read_dir(dir.path().join("derivatives")).unwrap().count(), 1);
+
+        let req = TestRequest::default().insert_header((header::IF_NONE_MATCH, etag)).to_http_request();
+        let resp = get_image(req, web::Query::from_query(&query).unwrap()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        std::env::remove_var(DERIVATIVE_CACHE_DIR_ENV_VAR);
+    }
+}
\ No newline at end of file
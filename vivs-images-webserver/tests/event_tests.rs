@@ -0,0 +1,171 @@
+extern crate image_exif_explorer;
+
+mod tests {
+    use std::collections::HashMap;
+
+    use actix_web::web;
+    use chrono::{Duration, NaiveDateTime};
+    use image_exif_explorer::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+    use image_exif_explorer::actions::refresh::cluster_image_events_action::ClusterImageEventsProcessor;
+    use image_exif_explorer::cache::thumbnail_cache::ThumbnailCache;
+    use image_exif_explorer::calc::event_clustering::{cluster_image_events, match_existing_events};
+    use image_exif_explorer::core::data_context::WebServerActionDataContext;
+    use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+    use image_exif_explorer::database::query::query_image_event::{query_image_events, query_images_missing_event_count};
+    use image_exif_explorer::database::query::search::search_images_by_criteria;
+    use image_exif_explorer::database::update::update_image_exif::execute_insert_image_exif_sql;
+    use image_exif_explorer::database::update::update_image_image_paths::execute_insert_image_path_sql;
+    use image_exif_explorer::models::image_event::ImageEventPhoto;
+    use image_exif_explorer::models::image_exif::ImageExif;
+    use image_exif_explorer::models::query_params::geo_search_params::GeoPoint;
+    use image_exif_explorer::models::query_params::search_params::SearchParams;
+    use image_exif_explorer::models::query_params::timeline_params::TimelineParams;
+    use image_exif_explorer::view::html::pages::browse_events::view_page_events;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::SqlitePool;
+
+    const PARIS: (f64, f64) = (48.8566, 2.3522);
+    const VERSAILLES: (f64, f64) = (48.8049, 2.1204);
+    const LYON: (f64, f64) = (45.7640, 4.8357);
+
+    fn photo(path: &str, taken_at: &str, location: Option<(f64, f64)>, sharpness: Option<f64>) -> ImageEventPhoto {
+        ImageEventPhoto {
+            image_path: path.to_string(),
+            local_time: NaiveDateTime::parse_from_str(taken_at, "%Y-%m-%dT%H:%M:%S").unwrap(),
+            location: location.and_then(|(lat, lon)| GeoPoint::new(lat, lon)),
+            sharpness,
+        }
+    }
+
+    #[test]
+    fn test_splits_on_time_gaps_and_gps_jumps() {
+        let photos = vec![
+            photo("/d.jpg", "2023-05-07T18:00:00", Some(LYON), None),
+            photo("/a.jpg", "2023-05-07T09:00:00", Some(PARIS), None),
+            // a short drive, and a photo with no fix in between, stay in the same event
+            photo("/b.jpg", "2023-05-07T10:00:00", None, None),
+            photo("/c.jpg", "2023-05-07T11:30:00", Some(VERSAILLES), None),
+            // the next day, without a location
+            photo("/e.jpg", "2023-05-08T09:00:00", None, None),
+        ];
+        let events = cluster_image_events(photos, Duration::hours(3), 50.0);
+        let paths: Vec<Vec<&str>> = events.iter().map(|e| e.image_paths.iter().map(String::as_str).collect()).collect();
+        // Lyon is within three hours of Versailles by the clock but 400 km away
+        assert_eq!(paths, vec![vec!["/a.jpg", "/b.jpg", "/c.jpg"], vec!["/d.jpg"], vec!["/e.jpg"]]);
+        assert_eq!(events[0].start.to_string(), "2023-05-07 09:00:00");
+        assert_eq!(events[0].end.to_string(), "2023-05-07 11:30:00");
+        assert_eq!(events[0].location, GeoPoint::new(VERSAILLES.0, VERSAILLES.1));
+        assert!(events[2].location.is_none());
+
+        assert!(cluster_image_events(vec![], Duration::hours(3), 50.0).is_empty());
+    }
+
+    #[test]
+    fn test_cover_is_sharpest_else_middle() {
+        let photos = vec![
+            photo("/a.jpg", "2023-05-07T09:00:00", None, None),
+            photo("/b.jpg", "2023-05-07T09:01:00", None, None),
+            photo("/c.jpg", "2023-05-07T09:02:00", None, None),
+        ];
+        assert_eq!(cluster_image_events(photos.clone(), Duration::hours(3), 50.0)[0].cover_image_path, "/b.jpg");
+
+        let mut photos = photos;
+        photos[0].sharpness = Some(0.9);
+        photos[2].sharpness = Some(0.4);
+        assert_eq!(cluster_image_events(photos, Duration::hours(3), 50.0)[0].cover_image_path, "/a.jpg");
+    }
+
+    async fn cluster(pool: &SqlitePool) {
+        let data = WebServerActionDataContext::new(pool.clone(), ThumbnailCache::new());
+        let processor = ClusterImageEventsProcessor::with_gaps(3.0, 50.0);
+        let analysis = processor.get_analysis(data.clone(), None).await.unwrap();
+        for item in processor.get_task_items_from_analysis(data.clone(), analysis, None).await.unwrap() {
+            let output = processor.process_task_item(item, false, data.clone()).await.unwrap().unwrap();
+            processor.process_task_output(output, data.clone()).await.unwrap();
+        }
+    }
+
+    #[test]
+    fn test_new_events_take_the_id_of_the_stored_event_they_overlap_most() {
+        let time = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap();
+        let existing = [
+            (1, time("2022-08-01T10:00:00"), time("2022-08-01T11:00:00")),
+            (2, time("2022-08-01T11:30:00"), time("2022-08-01T12:00:00")),
+            (3, time("2023-01-01T00:10:00"), time("2023-01-01T00:10:00")),
+        ];
+        let events = cluster_image_events(vec![
+            photo("/a.jpg", "2022-08-01T10:00:00", None, None),
+            photo("/b.jpg", "2022-08-01T10:50:00", None, None),
+            // the two stored events merged into one, which keeps the id it shares the most time with
+            photo("/c.jpg", "2022-08-01T11:40:00", None, None),
+            photo("/d.jpg", "2023-01-01T00:10:00", None, None),
+            photo("/e.jpg", "2024-01-01T00:00:00", None, None),
+        ], Duration::hours(1), 50.0);
+        assert_eq!(events.len(), 3);
+        assert_eq!(match_existing_events(&existing, &events), vec![Some(1), Some(3), None]);
+    }
+
+    #[actix_web::test]
+    async fn test_cluster_action_stores_events_for_browsing_and_search() {
+        // search_params.json is looked up relative to the working directory
+        std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+        let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        apply_migrations(&pool).await.unwrap();
+        let images = [
+            ("/trip/a.jpg", Some("2022-08-01T10:00:00")),
+            ("/trip/b.jpg", Some("2022-08-01T11:00:00.500")),
+            ("/party/c.jpg", Some("2023-01-01T00:10:00")),
+            ("/scan.jpg", None),
+        ];
+        for (path, taken_at) in images {
+            execute_insert_image_path_sql(&path.to_string(), &pool).await.unwrap();
+            let mut exif = ImageExif::default(path);
+            exif.image_taken_at = taken_at.map(String::from);
+            execute_insert_image_exif_sql(exif, pool.clone()).await.unwrap();
+        }
+        assert_eq!(query_images_missing_event_count(&pool).await.unwrap(), 3);
+
+        cluster(&pool).await;
+        assert_eq!(query_images_missing_event_count(&pool).await.unwrap(), 0);
+        let events = query_image_events("", &pool).await.unwrap();
+        assert_eq!(events.len(), 2);
+        let trip = &events[1];
+        assert_eq!((trip.event_start.as_str(), trip.event_end.as_str()), ("2022-08-01T10:00:00", "2022-08-01T11:00:00.500"));
+        assert_eq!(trip.event_image_count, 2);
+
+        let data = WebServerActionDataContext::new(pool.clone(), ThumbnailCache::new());
+        let query = HashMap::from([("event_id".to_string(), trip.event_id.to_string())]);
+        let params = SearchParams::new_from_hashmap(&query);
+        let search = search_images_by_criteria(data.clone(), &params, None).await.unwrap();
+        let mut paths: Vec<String> = search.items.iter().map(|i| i.as_ref().unwrap().path.clone()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["/trip/a.jpg", "/trip/b.jpg"]);
+        assert_eq!(search.items[0].as_ref().unwrap().get_field("event_id"), Some(trip.event_id.to_string()));
+
+        // the newest year is shown unless another one is picked
+        let resp = view_page_events(web::Data::new(data.clone()), web::Query(TimelineParams::default())).await.unwrap();
+        let body = String::from_utf8(actix_web::body::to_bytes(resp.into_body()).await.unwrap().to_vec()).unwrap();
+        assert!(body.contains("1 events starting in 2023"));
+        assert!(body.contains("/browse/events?year=2022"));
+        let params = web::Query::<TimelineParams>::from_query("year=2022").unwrap();
+        let resp = view_page_events(web::Data::new(data), params).await.unwrap();
+        let body = String::from_utf8(actix_web::body::to_bytes(resp.into_body()).await.unwrap().to_vec()).unwrap();
+        assert!(body.contains(&trip.get_search_href()));
+        assert!(body.contains("2022-08-01 10:00:00 to 11:00:00.500"));
+
+        // a new run keeps the ids of the events it finds again, and never reuses a removed one
+        cluster(&pool).await;
+        let rerun = query_image_events("", &pool).await.unwrap();
+        assert_eq!(rerun.iter().map(|e| e.event_id).collect::<Vec<_>>(), events.iter().map(|e| e.event_id).collect::<Vec<_>>());
+        sqlx::query("UPDATE image_exif SET image_taken_at = '2024-05-01T09:00:00' WHERE image_path = '/party/c.jpg'").execute(&pool).await.unwrap();
+        execute_insert_image_path_sql(&"/trip/d.jpg".to_string(), &pool).await.unwrap();
+        let mut exif = ImageExif::default("/trip/d.jpg");
+        exif.image_taken_at = Some("2022-08-01T12:00:00".to_string());
+        execute_insert_image_exif_sql(exif, pool.clone()).await.unwrap();
+        cluster(&pool).await;
+        let moved = query_image_events("", &pool).await.unwrap();
+        assert_eq!(moved.len(), 2);
+        assert_eq!((moved[1].event_id, moved[1].event_image_count), (trip.event_id, 3));
+        assert!(moved[0].event_id > events[0].event_id);
+    }
+}
\ No newline at end of file
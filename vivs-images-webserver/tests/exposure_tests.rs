@@ -0,0 +1,40 @@
+extern crate image_exif_explorer;
+
+mod tests {
+    use image_exif_explorer::calc::exposure::{downsample_histogram, exposure_stats, histogram_percentile, luma_histogram};
+
+    #[test]
+    fn test_percentiles_of_a_ramp() {
+        let luma: Vec<u8> = (0..=255).collect();
+        let histogram = luma_histogram(&luma);
+        assert_eq!(histogram_percentile(&histogram, 50.0), 127);
+        assert_eq!(histogram_percentile(&histogram, 100.0), 255);
+        assert_eq!(histogram_percentile(&histogram, 0.0), 0);
+    }
+
+    #[test]
+    fn test_clipping_and_contrast() {
+        // half pure black, half pure white: everything is clipped and contrast is at its maximum
+        let luma: Vec<u8> = (0..100).map(|i| if i % 2 == 0 { 0 } else { 255 }).collect();
+        let stats = exposure_stats(&luma_histogram(&luma));
+        assert!((stats.clipped_highlights - 50.0).abs() < 1e-9);
+        assert!((stats.clipped_shadows - 50.0).abs() < 1e-9);
+        assert_eq!(stats.luma_p5, 0);
+        assert_eq!(stats.luma_p95, 255);
+        assert!((stats.contrast - 0.5).abs() < 1e-9);
+
+        let flat = exposure_stats(&luma_histogram(&[128; 64]));
+        assert_eq!(flat.clipped_highlights, 0.0);
+        assert_eq!(flat.clipped_shadows, 0.0);
+        assert_eq!(flat.luma_p50, 128);
+        assert_eq!(flat.contrast, 0.0);
+    }
+
+    #[test]
+    fn test_downsampled_histogram_sums_to_one() {
+        let luma: Vec<u8> = (0..1000).map(|i| (i * 7 % 256) as u8).collect();
+        let bins = downsample_histogram(&luma_histogram(&luma), 32);
+        assert_eq!(bins.len(), 32);
+        assert!((bins.iter().sum::<f32>() - 1.0).abs() < 1e-4);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,89 @@
+extern crate image_exif_explorer;
+
+mod common;
+
+mod tests {
+    use image::codecs::gif::GifEncoder;
+    use image::{Delay, Frame, RgbaImage};
+    use image_exif_explorer::converters::extract_image_frames::{open_and_extract_image_frames, FrameSampler};
+    use image_exif_explorer::converters::tiff_structure::{count_tiff_pages, tiff_with_page_first};
+    use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+    use image_exif_explorer::database::query::query_image_frames::query_frame_thumbnails;
+    use image_exif_explorer::database::update::update_image_frames::{execute_delete_image_frames_sql, execute_insert_image_frames_sql};
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use crate::common::greyscale_tiff_bytes;
+
+    fn write_gif(path: &std::path::Path, frame_count: u8) {
+        let mut encoder = GifEncoder::new(std::fs::File::create(path).unwrap());
+        let frames = (0..frame_count).map(|i| Frame::from_parts(
+            RgbaImage::from_pixel(4, 4, image::Rgba([i * 40, 0, 0, 255])), 0, 0, Delay::from_numer_denom_ms(100, 1)));
+        encoder.encode_frames(frames).unwrap();
+    }
+
+    // An uncompressed greyscale TIFF with one 2x2 page per given grey level
+    fn tiff_bytes(pages: &[u8]) -> Vec<u8> {
+        let pages: Vec<Vec<u8>> = pages.iter().map(|grey| vec![*grey; 4]).collect();
+        greyscale_tiff_bytes(2, 2, &pages, None)
+    }
+
+    #[test]
+    fn test_frame_sampler_keeps_evenly_spaced_frames() {
+        let mut sampler = FrameSampler::new(8);
+        for i in 0..100 {
+            if sampler.wants(i) {
+                sampler.push(i, i);
+            }
+        }
+        let kept: Vec<u32> = sampler.into_kept().into_iter().map(|(i, _)| i).collect();
+        assert_eq!(kept, vec![0, 16, 32, 48, 64, 80, 96]);
+    }
+
+    #[test]
+    fn test_tiff_pages_are_counted_and_decoded() {
+        let data = tiff_bytes(&[10, 200, 90]);
+        assert_eq!(count_tiff_pages(&data), 3);
+        let second = image::load_from_memory_with_format(&tiff_with_page_first(&data, 1).unwrap(), image::ImageFormat::Tiff).unwrap();
+        assert_eq!(second.to_luma8().get_pixel(1, 1).0, [200]);
+        assert!(tiff_with_page_first(&data, 3).is_none());
+        assert_eq!(count_tiff_pages(b"GIF89a"), 0);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("scan.tiff");
+        std::fs::write(&path, &data).unwrap();
+        let extraction = open_and_extract_image_frames(path.to_str().unwrap()).unwrap();
+        assert_eq!(extraction.frames.frame_count, 3);
+        assert_eq!(extraction.frames.is_animated, 0);
+        assert_eq!(extraction.thumbnails.iter().map(|t| t.frame_index).collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_animated_gif_frames_are_stored_and_deleted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("anim.gif");
+        write_gif(&path, 3);
+        let still_path = dir.path().join("still.gif");
+        write_gif(&still_path, 1);
+
+        let extraction = open_and_extract_image_frames(path.to_str().unwrap()).unwrap();
+        assert_eq!(extraction.frames.frame_count, 3);
+        assert_eq!(extraction.frames.is_animated, 1);
+        assert_eq!(extraction.frames.animation_duration_ms, 300);
+        assert_eq!(extraction.thumbnails.len(), 3);
+        assert!(extraction.thumbnails.iter().all(|t| t.frame_delay_ms == 100));
+
+        let still = open_and_extract_image_frames(still_path.to_str().unwrap()).unwrap();
+        assert_eq!((still.frames.frame_count, still.frames.is_animated), (1, 0));
+        assert!(still.thumbnails.is_empty());
+
+        let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        apply_migrations(&pool).await.unwrap();
+        execute_insert_image_frames_sql(&extraction, &pool).await.unwrap();
+        let stored = query_frame_thumbnails(&extraction.frames.image_path, &pool).await.unwrap();
+        assert_eq!(stored.iter().map(|t| t.frame_index).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert!(stored[1].thumbnail.to_image().is_ok());
+
+        execute_delete_image_frames_sql(&extraction.frames.image_path, &pool).await.unwrap();
+        assert!(query_frame_thumbnails(&extraction.frames.image_path, &pool).await.unwrap().is_empty());
+    }
+}
\ No newline at end of file
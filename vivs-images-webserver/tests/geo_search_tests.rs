@@ -0,0 +1,127 @@
+extern crate image_exif_explorer;
+
+mod tests {
+    use actix_web::web;
+    use image_exif_explorer::cache::thumbnail_cache::ThumbnailCache;
+    use image_exif_explorer::core::data_context::WebServerActionDataContext;
+    use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+    use image_exif_explorer::database::query::search::search_images_by_criteria;
+    use image_exif_explorer::database::update::update_image_exif::execute_insert_image_exif_sql;
+    use image_exif_explorer::database::update::update_image_image_paths::execute_insert_image_path_sql;
+    use image_exif_explorer::models::image_exif::ImageExif;
+    use image_exif_explorer::models::query_params::geo_search_params::{GeoBoundingBox, GeoPoint};
+    use image_exif_explorer::models::query_params::search_params::SearchParams;
+    use image_exif_explorer::view::html::pages::search::search_images_map;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    // search_params.json is looked up relative to the workspace root
+    fn use_workspace_dir() {
+        std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    }
+
+    async fn data_with_located_images() -> WebServerActionDataContext {
+        let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        apply_migrations(&pool).await.unwrap();
+        let located = [
+            ("/images/paris.jpg", 48.8584, 2.2945),
+            ("/images/versailles.jpg", 48.8049, 2.1204),
+            ("/images/london.jpg", 51.5074, -0.1278),
+            ("/images/fiji_east.jpg", -16.5, 179.8),
+            ("/images/fiji_west.jpg", -16.7, -179.9),
+        ];
+        for (path, latitude, longitude) in located {
+            execute_insert_image_path_sql(&path.to_string(), &pool).await.unwrap();
+            let mut exif = ImageExif::default(path);
+            exif.gps_latitude = Some(latitude);
+            exif.gps_longitude = Some(longitude);
+            execute_insert_image_exif_sql(exif, pool.clone()).await.unwrap();
+        }
+        execute_insert_image_path_sql(&"/images/no_gps.jpg".to_string(), &pool).await.unwrap();
+        execute_insert_image_exif_sql(ImageExif::default("/images/no_gps.jpg"), pool.clone()).await.unwrap();
+        WebServerActionDataContext::new(pool, ThumbnailCache::new())
+    }
+
+    async fn search_paths(data: &WebServerActionDataContext, query_string: &str) -> (usize, Vec<String>) {
+        let params = SearchParams::new_from_querystring(query_string);
+        let order_by = params.get_order_by().unwrap_or("image_taken_at DESC".to_string());
+        let search = search_images_by_criteria(data.clone(), &params, Some(&order_by)).await.unwrap();
+        let paths = search.items.into_iter().map(|image| image.unwrap().path).collect();
+        (search.total_count, paths)
+    }
+
+    #[test]
+    fn test_geo_params_are_validated() {
+        use_workspace_dir();
+        let params = SearchParams::new_from_querystring("near_lat=48.85&near_lon=2.35&radius_km=10&order_by=distance%20DESC");
+        let filter = params.get_geo_filter();
+        assert_eq!(filter.get_radius(), Some((GeoPoint::new(48.85, 2.35).unwrap(), 10.0)));
+        assert_eq!(params.get_order_by().as_deref(), Some("geo_distance_sq DESC"));
+
+        // distance needs a near point, and out of range or partial values are dropped
+        let params = SearchParams::new_from_querystring("near_lat=95&near_lon=2&radius_km=-1&bbox_min_lat=1&order_by=distance");
+        assert!(params.get_geo_filter().is_empty());
+        assert!(params.get_geo_radius_km().is_none());
+        assert!(params.get_order_by().is_none());
+
+        let bounding_box = GeoBoundingBox::new(-20.0, -10.0, 170.0, -170.0).unwrap();
+        assert!(bounding_box.crosses_antimeridian());
+        assert!(bounding_box.contains(&GeoPoint::new(-15.0, 179.0).unwrap()));
+        assert!(bounding_box.contains(&GeoPoint::new(-15.0, -179.0).unwrap()));
+        assert!(!bounding_box.contains(&GeoPoint::new(-15.0, 0.0).unwrap()));
+        assert!(GeoBoundingBox::new(10.0, -10.0, 0.0, 1.0).is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_radius_search_sorted_by_distance() {
+        use_workspace_dir();
+        let data = data_with_located_images().await;
+
+        let (count, paths) = search_paths(&data, "near_lat=48.8566&near_lon=2.3522&radius_km=30&order_by=distance").await;
+        assert_eq!(count, 2);
+        assert_eq!(paths, vec!["/images/paris.jpg", "/images/versailles.jpg"]);
+
+        let (_, paths) = search_paths(&data, "near_lat=48.8566&near_lon=2.3522&radius_km=30&order_by=distance%20DESC").await;
+        assert_eq!(paths, vec!["/images/versailles.jpg", "/images/paris.jpg"]);
+
+        // without a radius the near point only orders the results; images without coordinates sort first
+        let (count, paths) = search_paths(&data, "near_lat=48.8566&near_lon=2.3522&order_by=distance&limit=100").await;
+        assert_eq!(count, 6);
+        assert_eq!(&paths[1..4], ["/images/paris.jpg", "/images/versailles.jpg", "/images/london.jpg"]);
+
+        // the radius wraps around the antimeridian
+        let (_, paths) = search_paths(&data, "near_lat=-16.6&near_lon=180&radius_km=50&order_by=distance").await;
+        assert_eq!(paths.len(), 2);
+    }
+
+    #[actix_web::test]
+    async fn test_bounding_box_search_and_map() {
+        use_workspace_dir();
+        let data = data_with_located_images().await;
+
+        let (count, paths) = search_paths(&data, "bbox_min_lat=45&bbox_max_lat=55&bbox_min_lon=-5&bbox_max_lon=2.2").await;
+        assert_eq!(count, 2);
+        assert!(paths.contains(&"/images/london.jpg".to_string()));
+        assert!(paths.contains(&"/images/versailles.jpg".to_string()));
+
+        let (count, _) = search_paths(&data, "bbox_min_lat=-20&bbox_max_lat=-10&bbox_min_lon=179&bbox_max_lon=-179").await;
+        assert_eq!(count, 2);
+
+        let req = actix_web::test::TestRequest::get()
+            .uri("/search/map?near_lat=48.8566&near_lon=2.3522&radius_km=30")
+            .to_http_request();
+        let resp = search_images_map(web::Data::new(data.clone()), req).await.unwrap();
+        let body = actix_web::body::to_bytes(resp.into_body()).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("<svg class=\"geo-scatter-plot\""));
+        assert_eq!(body.matches("class=\"geo-point\"").count(), 2);
+        assert!(body.contains("<ellipse class=\"geo-area\""));
+
+        // with no area given every located image is plotted
+        let req = actix_web::test::TestRequest::get().uri("/search/map").to_http_request();
+        let resp = search_images_map(web::Data::new(data), req).await.unwrap();
+        let body = actix_web::body::to_bytes(resp.into_body()).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert_eq!(body.matches("class=\"geo-point\"").count(), 5);
+        assert!(body.contains("Locations of 5 of 5 results"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,168 @@
+extern crate image_exif_explorer;
+
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use actix_web::web;
+    use image_exif_explorer::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+    use image_exif_explorer::actions::import::new_exif_action::ExifProcessor;
+    use image_exif_explorer::actions::import::new_geocode_action::GeocodeProcessor;
+    use image_exif_explorer::cache::thumbnail_cache::ThumbnailCache;
+    use image_exif_explorer::calc::reverse_geocode::{haversine_distance_km, parse_geo_places, ReverseGeocoder};
+    use image_exif_explorer::converters::extract_image_exif::gps_dms_to_decimal_degrees;
+    use image_exif_explorer::converters::extract_image_geocode::extract_image_geocode;
+    use image_exif_explorer::core::data_context::WebServerActionDataContext;
+    use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+    use image_exif_explorer::database::migration::migrations::SQL_MIGRATE_IMAGE_EXIF_GPS_COORDINATES;
+    use image_exif_explorer::database::query::query_image_geocode::query_gps_coordinates_missing_geocode_count;
+    use image_exif_explorer::database::update::update_image_exif::execute_insert_image_exif_sql;
+    use image_exif_explorer::database::update::update_image_image_paths::execute_insert_image_path_sql;
+    use image_exif_explorer::models::image::Image;
+    use image_exif_explorer::models::image_exif::ImageExif;
+    use image_exif_explorer::models::image_geocode::ImageGpsCoordinates;
+    use image_exif_explorer::view::html::pages::browse_by_property_detail::view_page_property_details;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::Row;
+
+    fn coordinates(latitude: f64, longitude: f64) -> ImageGpsCoordinates {
+        ImageGpsCoordinates { image_path: "/images/a.jpg".to_string(), latitude, longitude }
+    }
+
+    #[test]
+    fn test_bundled_places_name_country_region_and_city() {
+        let geocoder = ReverseGeocoder::bundled();
+        assert!(geocoder.len() > 500);
+
+        // the Eiffel Tower
+        let geocode = extract_image_geocode(&coordinates(48.8584, 2.2945), &geocoder, 300.0);
+        assert_eq!(geocode.place_country_code.as_deref(), Some("FR"));
+        assert_eq!(geocode.place_country.as_deref(), Some("France"));
+        assert_eq!(geocode.place_region.as_deref(), Some("Île-de-France"));
+        assert_eq!(geocode.place_city.as_deref(), Some("Paris"));
+        assert!(geocode.place_city_distance_km.unwrap() < 10.0);
+        assert_eq!(geocode.place_dataset, "bundled");
+
+        // southern and western hemispheres
+        let geocode = extract_image_geocode(&coordinates(-33.8568, 151.2153), &geocoder, 300.0);
+        assert_eq!(geocode.place_city.as_deref(), Some("Sydney"));
+        let geocode = extract_image_geocode(&coordinates(-22.9519, -43.2105), &geocoder, 300.0);
+        assert_eq!(geocode.place_country.as_deref(), Some("Brazil"));
+    }
+
+    #[test]
+    fn test_open_sea_and_no_fix_get_no_place_names() {
+        let geocoder = ReverseGeocoder::bundled();
+        let geocode = extract_image_geocode(&coordinates(-40.0, -130.0), &geocoder, 300.0);
+        assert!(geocode.place_city.is_none());
+        assert!(geocode.place_country.is_none());
+        assert!(geocode.place_city_distance_km.is_none());
+        let geocode = extract_image_geocode(&coordinates(0.0, 0.0), &geocoder, 20000.0);
+        assert!(geocode.place_city.is_none());
+    }
+
+    #[test]
+    fn test_grid_lookup_matches_a_full_scan() {
+        let geocoder = ReverseGeocoder::bundled();
+        let places = parse_geo_places(include_str!("../data/geonames_cities.tsv"), &HashMap::new()).unwrap();
+        // including the poles, the antimeridian and open ocean far from any place
+        for latitude in (-90..=90).step_by(7) {
+            for longitude in (-180..=180).step_by(11) {
+                let (latitude, longitude) = (latitude as f64 + 0.25, longitude as f64 - 0.5);
+                let expected = places.iter()
+                    .map(|p| haversine_distance_km(latitude, longitude, p.latitude, p.longitude))
+                    .fold(f64::INFINITY, f64::min);
+                let (_, distance) = geocoder.nearest(latitude, longitude).unwrap();
+                assert!((distance - expected).abs() < 1e-6, "{} {}: {} != {}", latitude, longitude, distance, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_geonames_dump_lines_resolve_admin1_names() {
+        let dump = "5391959\tSan Francisco\tSan Francisco\t\t37.77493\t-122.41942\tP\tPPLA2\tUS\t\tCA\t075\t\t\t864816\t16\t28\tAmerica/Los_Angeles\t2022-01-01\n";
+        let admin1_names = HashMap::from([("US.CA".to_string(), "California".to_string())]);
+        let places = parse_geo_places(dump, &admin1_names).unwrap();
+        assert_eq!(places.len(), 1);
+        assert_eq!(places[0].name, "San Francisco");
+        assert_eq!(places[0].country_code, "US");
+        assert_eq!(places[0].region.as_deref(), Some("California"));
+        assert_eq!(places[0].population, 864816);
+
+        assert!(parse_geo_places("Nowhere\t1.0\n", &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_gps_degrees_minutes_seconds_and_distance() {
+        let latitude = gps_dms_to_decimal_degrees(&[37.0, 46.0, 29.64], Some("N")).unwrap();
+        assert!((latitude - 37.7749).abs() < 1e-4);
+        let longitude = gps_dms_to_decimal_degrees(&[122.0, 25.0, 9.84], Some("W")).unwrap();
+        assert!((longitude + 122.4194).abs() < 1e-4);
+        assert!(gps_dms_to_decimal_degrees(&[], Some("N")).is_none());
+
+        // London to Paris is about 344 km
+        let distance = haversine_distance_km(51.5074, -0.1278, 48.8566, 2.3522);
+        assert!((distance - 344.0).abs() < 3.0);
+    }
+
+    #[actix_web::test]
+    async fn test_geocode_action_fills_place_names_for_browsing() {
+        let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        apply_migrations(&pool).await.unwrap();
+        for path in ["/images/paris.jpg", "/images/no_gps.jpg"] {
+            execute_insert_image_path_sql(&path.to_string(), &pool).await.unwrap();
+        }
+        let mut exif = ImageExif::default("/images/paris.jpg");
+        exif.gps_latitude = Some(48.8584);
+        exif.gps_longitude = Some(2.2945);
+        execute_insert_image_exif_sql(exif, pool.clone()).await.unwrap();
+        execute_insert_image_exif_sql(ImageExif::default("/images/no_gps.jpg"), pool.clone()).await.unwrap();
+        assert_eq!(query_gps_coordinates_missing_geocode_count(&pool).await.unwrap(), 1);
+
+        let data = WebServerActionDataContext::new(pool.clone(), ThumbnailCache::new());
+        let processor = GeocodeProcessor::with_geocoder(ReverseGeocoder::bundled(), 300.0);
+        let analysis = processor.get_analysis(data.clone(), None).await.unwrap();
+        let items = processor.get_task_items_from_analysis(data.clone(), analysis, None).await.unwrap();
+        assert_eq!(items.len(), 1);
+        for item in items {
+            let output = processor.process_task_item(item, false, data.clone()).await.unwrap().unwrap();
+            processor.process_task_output(output, data.clone()).await.unwrap();
+        }
+        assert_eq!(query_gps_coordinates_missing_geocode_count(&pool).await.unwrap(), 0);
+
+        assert_eq!(Image::get_meta_field("place_country").unwrap().table_name, "image_geocode");
+        let resp = view_page_property_details(web::Data::new(data), web::Path::from("place_country".to_string())).await.unwrap();
+        let body = actix_web::body::to_bytes(resp.into_body()).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("/search?place_country=France"));
+    }
+
+    #[actix_web::test]
+    async fn test_rows_without_gps_coordinates_are_queued_to_be_read_again() {
+        let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        apply_migrations(&pool).await.unwrap();
+        execute_insert_image_path_sql(&"/images/old.jpg".to_string(), &pool).await.unwrap();
+        let mut exif = ImageExif::default("/images/old.jpg");
+        exif.camera_model = Some("EOS".to_string());
+        exif.gps_latitude_ref = Some("N".to_string());
+        execute_insert_image_exif_sql(exif, pool.clone()).await.unwrap();
+
+        let data = WebServerActionDataContext::new(pool.clone(), ThumbnailCache::new());
+        let processor = ExifProcessor::new();
+        let image_path = "/images/old.jpg".to_string();
+        assert!(processor.task_already_completed(&image_path, data.clone()).await.unwrap());
+
+        sqlx::query(SQL_MIGRATE_IMAGE_EXIF_GPS_COORDINATES).execute(&pool).await.unwrap();
+        // the row is kept until the file is read again
+        let camera_model: Option<String> = sqlx::query("SELECT camera_model FROM image_exif").fetch_one(&pool).await.unwrap().get(0);
+        assert_eq!(camera_model.as_deref(), Some("EOS"));
+        assert!(!processor.task_already_completed(&image_path, data.clone()).await.unwrap());
+
+        let mut reread = ImageExif::default("/images/old.jpg");
+        reread.gps_latitude = Some(48.8584);
+        reread.gps_longitude = Some(2.2945);
+        processor.process_task_output(Arc::new(reread), data.clone()).await.unwrap();
+        assert!(processor.task_already_completed(&image_path, data.clone()).await.unwrap());
+        assert_eq!(query_gps_coordinates_missing_geocode_count(&pool).await.unwrap(), 1);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,94 @@
+extern crate image_exif_explorer;
+
+mod common;
+
+mod tests {
+    use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+    use image_exif_explorer::database::update::update_image_image_paths::execute_delete_image_path_sql;
+    use image_exif_explorer::database::update::update_image_image_paths::execute_insert_image_path_sql;
+    use image_exif_explorer::database::update::update_image_path_rekey::execute_rekey_image_path_sql;
+    use sqlx::{Row, SqlitePool};
+
+    use crate::common::open_memory_pool;
+
+    async fn image_id_of(pool: &SqlitePool, table: &str, image_path: &str) -> Option<i64> {
+        sqlx::query(&format!("SELECT image_id FROM {} WHERE image_path = ?", table))
+            .bind(image_path)
+            .fetch_one(pool).await.unwrap()
+            .get("image_id")
+    }
+
+    async fn count_rows(pool: &SqlitePool, table: &str) -> i64 {
+        sqlx::query(&format!("SELECT COUNT(*) ct FROM {}", table)).fetch_one(pool).await.unwrap().get("ct")
+    }
+
+    #[tokio::test]
+    async fn test_migration_assigns_image_ids_to_existing_rows() {
+        let pool = open_memory_pool().await;
+        // the schema from before image_id existed
+        for sql in [
+            "CREATE TABLE image_paths (image_path TEXT PRIMARY KEY);",
+            "CREATE TABLE image_brightness (image_path TEXT PRIMARY KEY, brightness REAL NOT NULL);",
+            "INSERT INTO image_paths VALUES ('/a.jpg'), ('/b.jpg');",
+            "INSERT INTO image_brightness VALUES ('/a.jpg', 0.25), ('/b.jpg', 0.75);",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+
+        apply_migrations(&pool).await.unwrap();
+
+        let id_a: i64 = sqlx::query("SELECT image_id FROM image_paths WHERE image_path = '/a.jpg'").fetch_one(&pool).await.unwrap().get("image_id");
+        assert_eq!(image_id_of(&pool, "image_brightness", "/a.jpg").await, Some(id_a));
+
+        execute_delete_image_path_sql("/a.jpg", &pool).await.unwrap();
+        assert_eq!(count_rows(&pool, "image_brightness").await, 1);
+
+        // running again on an up to date database changes nothing
+        assert!(apply_migrations(&pool).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_feature_rows_pick_up_image_id_from_image_paths() {
+        let pool = open_memory_pool().await;
+        apply_migrations(&pool).await.unwrap();
+
+        // feature rows added before the path get their id once the path is inserted
+        sqlx::query("INSERT INTO image_sharpness (image_path, sharpness, motion_blur, motion_blur_angle) VALUES ('/c.jpg', 100.0, 0.1, 0.0);")
+            .execute(&pool).await.unwrap();
+        assert_eq!(image_id_of(&pool, "image_sharpness", "/c.jpg").await, None);
+        execute_insert_image_path_sql(&"/c.jpg".to_string(), &pool).await.unwrap();
+        let id_c = image_id_of(&pool, "image_sharpness", "/c.jpg").await;
+        assert!(id_c.is_some());
+
+        sqlx::query("INSERT INTO image_xmp (image_path, xmp) VALUES ('/c.jpg', '<x/>');").execute(&pool).await.unwrap();
+        assert_eq!(image_id_of(&pool, "image_xmp", "/c.jpg").await, id_c);
+
+        // a moved file keeps its image_id
+        execute_rekey_image_path_sql("/c.jpg", "/moved/c.jpg", &pool).await.unwrap();
+        assert_eq!(image_id_of(&pool, "image_paths", "/moved/c.jpg").await, id_c);
+        assert_eq!(image_id_of(&pool, "image_sharpness", "/moved/c.jpg").await, id_c);
+
+        execute_delete_image_path_sql("/moved/c.jpg", &pool).await.unwrap();
+        assert_eq!(count_rows(&pool, "image_sharpness").await, 0);
+        assert_eq!(count_rows(&pool, "image_xmp").await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_image_paths_triggers_are_recreated_without_a_migration() {
+        let pool = open_memory_pool().await;
+        apply_migrations(&pool).await.unwrap();
+
+        // a database whose image_paths trigger predates the newer tables
+        for sql in [
+            "DROP TRIGGER trg_image_paths_image_id_insert;",
+            "CREATE TRIGGER trg_image_paths_image_id_insert AFTER INSERT ON image_paths BEGIN SELECT 1; END;",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+        assert!(apply_migrations(&pool).await.unwrap().is_empty());
+
+        sqlx::query("INSERT INTO image_burst_frame (image_path) VALUES ('/d.jpg');").execute(&pool).await.unwrap();
+        execute_insert_image_path_sql(&"/d.jpg".to_string(), &pool).await.unwrap();
+        assert!(image_id_of(&pool, "image_burst_frame", "/d.jpg").await.is_some());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,225 @@
+extern crate image_exif_explorer;
+
+mod common;
+
+mod tests {
+    use image_exif_explorer::converters::extract_image_ocr_text::{map_ocr_result_to_original, parse_tesseract_tsv, OcrOptions};
+    use image_exif_explorer::calc::ocr_preprocess::OcrPageTransform;
+    use image_exif_explorer::models::config::ocr::parse_ocr_languages;
+    use image_exif_explorer::calc::ocr_preprocess::adaptive_threshold;
+    use image_exif_explorer::calc::ocr_preprocess::estimate_skew_degrees;
+    use image_exif_explorer::calc::ocr_preprocess::rotate_gray;
+    use image_exif_explorer::converters::ocr_preprocess_image::{preprocess_image_for_ocr, OcrPreprocessOptions};
+    use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+    use image_exif_explorer::database::query::query_image_ocr_word::query_ocr_words_from_db;
+    use image_exif_explorer::database::update::update_image_image_paths::execute_insert_image_path_sql;
+    use image_exif_explorer::database::update::update_image_ocr_result::execute_replace_image_ocr_result_sql;
+    use std::collections::HashMap;
+    use image::metadata::Orientation;
+    use image::{GrayImage, Luma};
+
+    use crate::common::{greyscale_tiff_bytes, open_memory_pool};
+
+    const SAMPLE_TSV: &str = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n1\t1\t0\t0\t0\t0\t0\t0\t640\t480\t-1\t\n2\t1\t1\t0\t0\t0\t10\t10\t200\t60\t-1\t\n4\t1\t1\t1\t1\t0\t10\t10\t200\t20\t-1\t\n5\t1\t1\t1\t1\t1\t10\t10\t80\t20\t96.5\tHello\n5\t1\t1\t1\t1\t2\t100\t10\t90\t20\t91\tworld\n5\t1\t1\t1\t2\t1\t10\t40\t50\t20\t30.25\t~%j\n5\t1\t2\t1\t1\t1\t10\t100\t60\t20\t88\tBye\n5\t1\t2\t1\t1\t2\t80\t100\t10\t20\t95\t ";
+
+    #[test]
+    fn test_parse_tesseract_tsv_words_and_boxes() {
+        let result = parse_tesseract_tsv("/images/a.jpg", "eng", SAMPLE_TSV);
+        assert_eq!(result.words.len(), 4);
+        assert_eq!(result.words[1].text, "world");
+        assert_eq!((result.words[1].left, result.words[1].top, result.words[1].width, result.words[1].height), (100, 10, 90, 20));
+        assert_eq!(result.words[3].word_index, 3);
+        assert_eq!((result.summary.ocr_page_width, result.summary.ocr_page_height), (640, 480));
+        assert_eq!(result.text.ocr_text, "Hello world\n~%j\n\nBye");
+    }
+
+    #[test]
+    fn test_parse_tesseract_tsv_confidence_summary() {
+        let result = parse_tesseract_tsv("/images/a.jpg", "eng+deu", SAMPLE_TSV);
+        assert_eq!(result.summary.ocr_word_count, 4);
+        assert_eq!(result.summary.ocr_low_confidence_word_count, 1);
+        assert!((result.summary.ocr_mean_confidence - (96.5 + 91.0 + 30.25 + 88.0) / 4.0).abs() < 1e-4);
+        assert_eq!(result.summary.ocr_confident_text, "Hello world\n\nBye");
+        assert_eq!(result.summary.ocr_languages, "eng+deu");
+    }
+
+    #[test]
+    fn test_parse_tesseract_tsv_keeps_pages_apart() {
+        let tsv = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n\
+            1\t1\t0\t0\t0\t0\t0\t0\t640\t480\t-1\t\n\
+            5\t1\t1\t1\t1\t1\t10\t10\t80\t20\t96\tfirst\n\
+            1\t2\t0\t0\t0\t0\t0\t0\t300\t200\t-1\t\n\
+            5\t2\t1\t1\t1\t1\t10\t10\t80\t20\t96\tsecond\n";
+        let result = parse_tesseract_tsv("/images/a.tif", "eng", tsv);
+        // block, paragraph and line all restart at 1 on the second page, which still starts a new paragraph
+        assert_eq!(result.text.ocr_text, "first\n\nsecond");
+        assert_eq!(result.words.iter().map(|w| w.page_num).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!((result.summary.ocr_page_width, result.summary.ocr_page_height), (640, 480));
+    }
+
+    #[tokio::test]
+    async fn test_ocr_result_is_stored_all_or_nothing() {
+        let pool = open_memory_pool().await;
+        apply_migrations(&pool).await.unwrap();
+        execute_insert_image_path_sql(&"/images/a.jpg".to_string(), &pool).await.unwrap();
+        let count = |table: &'static str| {
+            let pool = pool.clone();
+            async move { sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM {}", table)).fetch_one(&pool).await.unwrap() }
+        };
+
+        // two words with the same index break the key part way through, so nothing of the run is kept
+        let mut broken = parse_tesseract_tsv("/images/a.jpg", "eng", SAMPLE_TSV);
+        broken.words[2].word_index = 0;
+        assert!(execute_replace_image_ocr_result_sql(&broken, &pool).await.is_err());
+        assert_eq!((count("image_ocr_text").await, count("image_ocr_word").await, count("image_ocr_summary").await), (0, 0, 0));
+
+        let result = parse_tesseract_tsv("/images/a.jpg", "eng", SAMPLE_TSV);
+        execute_replace_image_ocr_result_sql(&result, &pool).await.unwrap();
+        execute_replace_image_ocr_result_sql(&result, &pool).await.unwrap();
+        assert_eq!((count("image_ocr_text").await, count("image_ocr_word").await, count("image_ocr_summary").await), (1, 4, 1));
+        let words = query_ocr_words_from_db("/images/a.jpg", 0.0, &pool).await.unwrap();
+        assert_eq!(words.iter().map(|w| w.text.as_str()).collect::<Vec<_>>(), vec!["Hello", "world", "~%j", "Bye"]);
+    }
+
+    #[test]
+    fn test_parse_tesseract_tsv_empty_output() {
+        let result = parse_tesseract_tsv("/images/a.jpg", "eng", "");
+        assert!(result.words.is_empty());
+        assert_eq!(result.summary.ocr_mean_confidence, 0.0);
+        assert_eq!(result.text.ocr_text, "");
+    }
+
+    #[test]
+    fn test_parse_ocr_languages() {
+        assert_eq!(parse_ocr_languages("eng+deu"), vec!["eng", "deu"]);
+        assert_eq!(parse_ocr_languages(" eng, chi_sim "), vec!["eng", "chi_sim"]);
+        assert!(parse_ocr_languages("eng;rm -rf").is_empty());
+    }
+
+    fn text_lines_image() -> GrayImage {
+        GrayImage::from_fn(400, 300, |_, y| if y % 40 < 6 { Luma([0u8]) } else { Luma([255u8]) })
+    }
+
+    #[test]
+    fn test_adaptive_threshold_binarises_uneven_lighting() {
+        // dark strokes on a background that fades from light to mid grey
+        let img = GrayImage::from_fn(200, 100, |x, y| {
+            let background = 250 - (x / 2) as u8;
+            if y % 20 < 3 { Luma([background / 3]) } else { Luma([background]) }
+        });
+        let out = adaptive_threshold(&img, 25, 0.15);
+        assert!(out.pixels().all(|p| p[0] == 0 || p[0] == 255));
+        assert_eq!(out.get_pixel(190, 1)[0], 0);
+        assert_eq!(out.get_pixel(190, 10)[0], 255);
+        assert_eq!(out.get_pixel(10, 10)[0], 255);
+    }
+
+    #[test]
+    fn test_deskew_levels_rotated_lines() {
+        let level = text_lines_image();
+        assert_eq!(estimate_skew_degrees(&level, 10.0, 0.25), 0.0);
+
+        let skewed = rotate_gray(&level, 3.0, 255);
+        let skew = estimate_skew_degrees(&skewed, 10.0, 0.25);
+        assert!((skew.abs() - 3.0).abs() <= 0.5, "estimated skew {}", skew);
+
+        let straightened = rotate_gray(&skewed, -skew, 255);
+        assert!(estimate_skew_degrees(&straightened, 10.0, 0.25).abs() <= 0.5);
+    }
+
+    #[test]
+    fn test_preprocess_options_from_action_options() {
+        assert!(!OcrPreprocessOptions::default().is_enabled());
+
+        let mut action_options = HashMap::new();
+        action_options.insert("ocr_deskew".to_string(), "true".to_string());
+        action_options.insert("ocr_upscale".to_string(), "false".to_string());
+        let options = OcrPreprocessOptions::from_action_options(&action_options);
+        assert!(options.deskew);
+        assert!(!options.upscale);
+        assert!(options.is_enabled());
+        assert_eq!(OcrPreprocessOptions::get_action_options().len(), 5);
+        assert_eq!(options.get_enabled_names(), "deskew");
+        assert_eq!(OcrPreprocessOptions::default().get_enabled_names(), "");
+
+        assert!(!OcrOptions::from_action_options(&action_options).reprocess);
+        action_options.insert("ocr_reprocess".to_string(), "true".to_string());
+        assert!(OcrOptions::from_action_options(&action_options).reprocess);
+    }
+
+    #[test]
+    fn test_word_boxes_map_back_from_upscaled_page() {
+        let transform = OcrPageTransform { original_width: 320, original_height: 240, page_width: 640, page_height: 480, deskew_degrees: 0.0, orientation: Orientation::NoTransforms };
+        let mut result = parse_tesseract_tsv("/images/a.jpg", "eng", SAMPLE_TSV);
+        map_ocr_result_to_original(&mut result, &transform);
+        let hello = &result.words[0];
+        assert_eq!((hello.left, hello.top, hello.width, hello.height), (5, 5, 40, 10));
+        assert_eq!((result.summary.ocr_page_width, result.summary.ocr_page_height), (320, 240));
+
+        let identity = OcrPageTransform::identity(640, 480);
+        assert!(identity.is_identity());
+        assert_eq!(identity.box_to_original(10, 10, 80, 20), (10, 10, 80, 20));
+    }
+
+    #[test]
+    fn test_word_boxes_map_back_from_deskewed_page() {
+        // a dark dot, and where deskewing moves it to
+        let mut original = GrayImage::from_pixel(400, 300, Luma([255u8]));
+        for y in 60..66 {
+            for x in 300..306 {
+                original.put_pixel(x, y, Luma([0u8]));
+            }
+        }
+        let skew = 4.0;
+        let page = rotate_gray(&original, -skew, 255);
+        let dark: Vec<(u32, u32)> = page.enumerate_pixels().filter(|(_, _, p)| p[0] < 128).map(|(x, y, _)| (x, y)).collect();
+        let (x0, y0) = (dark.iter().map(|p| p.0).min().unwrap(), dark.iter().map(|p| p.1).min().unwrap());
+        let (x1, y1) = (dark.iter().map(|p| p.0).max().unwrap(), dark.iter().map(|p| p.1).max().unwrap());
+        assert!((x0, y0) != (300, 60));
+
+        let transform = OcrPageTransform { original_width: 400, original_height: 300, page_width: 400, page_height: 300, deskew_degrees: skew, orientation: Orientation::NoTransforms };
+        let (left, top, width, height) = transform.box_to_original(x0 as i32, y0 as i32, (x1 - x0 + 1) as i32, (y1 - y0 + 1) as i32);
+        assert!((left - 300).abs() <= 2 && (top - 60).abs() <= 2, "mapped to {}, {}", left, top);
+        assert!(left + width >= 305 && top + height >= 65);
+    }
+
+    #[test]
+    fn test_word_boxes_are_turned_upright_with_the_image() {
+        // a 400x300 image shown rotated clockwise, so 300x400
+        let transform = OcrPageTransform { orientation: Orientation::Rotate90, ..OcrPageTransform::identity(400, 300) };
+        assert!(!transform.is_identity());
+        assert_eq!(transform.upright_size(), (300, 400));
+        assert_eq!(transform.box_to_original(10, 20, 50, 30), (250, 10, 30, 50));
+
+        let mut result = parse_tesseract_tsv("/images/a.jpg", "eng", SAMPLE_TSV);
+        map_ocr_result_to_original(&mut result, &OcrPageTransform { orientation: Orientation::Rotate270, ..OcrPageTransform::identity(640, 480) });
+        let hello = &result.words[0];
+        // Hello at (10, 10) 80x20 lands at the bottom left
+        assert_eq!((hello.left, hello.top, hello.width, hello.height), (10, 550, 20, 80));
+        assert_eq!((result.summary.ocr_page_width, result.summary.ocr_page_height), (480, 640));
+
+        let flipped = OcrPageTransform { orientation: Orientation::FlipHorizontal, ..OcrPageTransform::identity(400, 300) };
+        assert_eq!(flipped.box_to_original(10, 20, 50, 30), (340, 20, 50, 30));
+    }
+
+    #[test]
+    fn test_preprocessing_without_exif_orientation_keeps_it_in_the_transform() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sideways.tiff");
+        // stored 4x2, shown rotated clockwise
+        std::fs::write(&path, greyscale_tiff_bytes(4, 2, &[vec![0, 50, 100, 150, 200, 210, 220, 230]], Some(6))).unwrap();
+        let path = path.to_string_lossy().to_string();
+
+        let greyscale = OcrPreprocessOptions { greyscale: true, ..Default::default() };
+        let (img, _, transform) = preprocess_image_for_ocr(&path, &greyscale).unwrap();
+        assert_eq!((img.width(), img.height()), (4, 2));
+        assert_eq!(transform.orientation, Orientation::Rotate90);
+        assert_eq!(transform.upright_size(), (2, 4));
+
+        let upright = OcrPreprocessOptions { exif_orientation: true, greyscale: true, ..Default::default() };
+        let (img, steps, transform) = preprocess_image_for_ocr(&path, &upright).unwrap();
+        assert_eq!((img.width(), img.height()), (2, 4));
+        assert_eq!(steps[0], "exif_orientation");
+        assert_eq!(transform.orientation, Orientation::NoTransforms);
+    }
+}
\ No newline at end of file
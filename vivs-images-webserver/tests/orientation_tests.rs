@@ -0,0 +1,71 @@
+extern crate image_exif_explorer;
+
+mod common;
+
+mod tests {
+    use image::GenericImageView;
+    use image_exif_explorer::converters::extract_image_aspect_ratio::extract_image_aspect_ratio_model;
+    use image_exif_explorer::converters::open_image::{open_image, read_image_orientation};
+    use image_exif_explorer::converters::tiff_structure::tiff_orientation;
+    use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+    use image_exif_explorer::database::query::query_image_aspect_ratio::query_aspect_ratio_table_count;
+    use image_exif_explorer::database::query::query_image_orientation_pending::query_orientation_pending_total;
+    use image_exif_explorer::database::update::update_image_aspect_ratio::execute_insert_image_aspect_ratio_sql;
+    use image_exif_explorer::database::update::update_image_orientation::execute_apply_image_orientation_recompute_sql;
+    use image_exif_explorer::models::image_orientation::ImageOrientationRecompute;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use crate::common::greyscale_tiff_bytes;
+
+    // An uncompressed 4x2 greyscale TIFF stored sideways, with the given orientation tag
+    fn tiff_bytes(orientation: u32) -> Vec<u8> {
+        greyscale_tiff_bytes(4, 2, &[vec![0, 50, 100, 150, 200, 210, 220, 230]], Some(orientation))
+    }
+
+    #[test]
+    fn test_exif_orientation_is_applied_when_opening() {
+        let dir = tempfile::tempdir().unwrap();
+        let rotated = dir.path().join("rotated.tiff");
+        std::fs::write(&rotated, tiff_bytes(6)).unwrap();
+        let upright = dir.path().join("upright.tiff");
+        std::fs::write(&upright, tiff_bytes(1)).unwrap();
+
+        assert_eq!(tiff_orientation(&tiff_bytes(6)), Some(6));
+        assert_eq!(read_image_orientation(rotated.to_str().unwrap()).unwrap().to_exif(), 6);
+        assert_eq!(open_image(rotated.to_str().unwrap()).unwrap().dimensions(), (2, 4));
+        assert_eq!(open_image(upright.to_str().unwrap()).unwrap().dimensions(), (4, 2));
+
+        let aspect_ratio = extract_image_aspect_ratio_model(rotated.to_str().unwrap()).unwrap();
+        assert_eq!((aspect_ratio.width_pixels, aspect_ratio.height_pixels), (2, 4));
+    }
+
+    #[tokio::test]
+    async fn test_recompute_replaces_rows_and_clears_pending() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rotated.tiff");
+        std::fs::write(&path, tiff_bytes(6)).unwrap();
+        let path = path.to_str().unwrap().to_string();
+
+        let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        apply_migrations(&pool).await.unwrap();
+        sqlx::query("INSERT INTO image_orientation_pending (image_path) VALUES (?)").bind(&path).execute(&pool).await.unwrap();
+        let mut sideways = extract_image_aspect_ratio_model(&path).unwrap();
+        (sideways.width_pixels, sideways.height_pixels) = (4, 2);
+        execute_insert_image_aspect_ratio_sql(&sideways, &pool).await.unwrap();
+
+        let item = ImageOrientationRecompute {
+            image_path: path.clone(),
+            orientation: 6,
+            aspect_ratio: Some(extract_image_aspect_ratio_model(&path).unwrap()),
+            thumbnails: vec![],
+            sharpness: None,
+        };
+        execute_apply_image_orientation_recompute_sql(&item, &pool).await.unwrap();
+
+        assert_eq!(query_orientation_pending_total(&pool).await.unwrap(), 0);
+        assert_eq!(query_aspect_ratio_table_count(&path, &pool).await.unwrap(), 1);
+        let (width, height): (u32, u32) = sqlx::query_as("SELECT width_pixels, height_pixels FROM image_aspect_ratio WHERE image_path = ?")
+            .bind(&path).fetch_one(&pool).await.unwrap();
+        assert_eq!((width, height), (2, 4));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+extern crate image_exif_explorer;
+
+mod common;
+
+mod tests {
+    use std::io::Cursor;
+
+    use image::{DynamicImage, GenericImageView, ImageFormat, RgbImage};
+    use image_exif_explorer::converters::extract_image_raw::open_and_extract_image_raw_model;
+    use image_exif_explorer::converters::extract_raw_preview::{extract_largest_embedded_jpeg, find_embedded_jpeg_ranges, is_decodable_jpeg};
+    use image_exif_explorer::converters::open_image::open_image;
+    use image_exif_explorer::filesystem::query::images::{find_jpeg_sibling, get_images_in_folder, is_raw_image_path};
+
+    use crate::common::{ifd_entry, TIFF_LONG, TIFF_SHORT};
+
+    fn jpeg_bytes(width: u32, height: u32) -> Vec<u8> {
+        let img = DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |x, y| image::Rgb([(x * 8) as u8, (y * 8) as u8, 128])));
+        let mut buf = Cursor::new(vec![]);
+        img.write_to(&mut buf, ImageFormat::Jpeg).unwrap();
+        buf.into_inner()
+    }
+
+    // A little-endian TIFF laid out like a CR2: IFD0 points at a small JPEG thumbnail and, through SubIFDs, at
+    // an IFD holding a larger preview as a single JPEG-compressed strip
+    fn raw_bytes(thumbnail: &[u8], preview: &[u8]) -> Vec<u8> {
+        let ifd0 = 8u32;
+        let ifd0_len = 2 + 3 * 12 + 4;
+        let sub_ifd = ifd0 + ifd0_len;
+        let sub_ifd_len = 2 + 3 * 12 + 4;
+        let thumbnail_offset = sub_ifd + sub_ifd_len;
+        let preview_offset = thumbnail_offset + thumbnail.len() as u32;
+
+        let mut out = b"II\x2A\x00".to_vec();
+        out.extend_from_slice(&ifd0.to_le_bytes());
+        out.extend_from_slice(&3u16.to_le_bytes());
+        ifd_entry(&mut out, 0x014A, TIFF_LONG, sub_ifd);
+        ifd_entry(&mut out, 0x0201, TIFF_LONG, thumbnail_offset);
+        ifd_entry(&mut out, 0x0202, TIFF_LONG, thumbnail.len() as u32);
+        out.extend_from_slice(&0u32.to_le_bytes());
+        out.extend_from_slice(&3u16.to_le_bytes());
+        ifd_entry(&mut out, 0x0103, TIFF_SHORT, 6);
+        ifd_entry(&mut out, 0x0111, TIFF_LONG, preview_offset);
+        ifd_entry(&mut out, 0x0117, TIFF_LONG, preview.len() as u32);
+        out.extend_from_slice(&0u32.to_le_bytes());
+        out.extend_from_slice(thumbnail);
+        out.extend_from_slice(preview);
+        out
+    }
+
+    #[test]
+    fn test_extract_largest_embedded_jpeg() {
+        let thumbnail = jpeg_bytes(8, 6);
+        let preview = jpeg_bytes(64, 48);
+        let raw = raw_bytes(&thumbnail, &preview);
+
+        assert_eq!(find_embedded_jpeg_ranges(&raw).len(), 2);
+        assert_eq!(extract_largest_embedded_jpeg(&raw), Some(preview.as_slice()));
+        assert!(is_decodable_jpeg(&preview));
+
+        // lossless JPEG, as used for the sensor data itself, is skipped
+        assert!(!is_decodable_jpeg(&[0xFF, 0xD8, 0xFF, 0xC3, 0x00, 0x0B, 0x08, 0x00, 0x01, 0x00, 0x01, 0x01, 0x01, 0x11, 0x00]));
+        assert!(extract_largest_embedded_jpeg(b"not a tiff").is_none());
+        // a truncated file points past its end
+        assert!(extract_largest_embedded_jpeg(&raw[..100]).is_none());
+
+        // offsets and lengths that overflow or point past the end are dropped instead of panicking
+        let mut malformed = b"II\x2A\x00".to_vec();
+        malformed.extend_from_slice(&8u32.to_le_bytes());
+        malformed.extend_from_slice(&2u16.to_le_bytes());
+        ifd_entry(&mut malformed, 0x0201, TIFF_LONG, u32::MAX - 4);
+        ifd_entry(&mut malformed, 0x0202, TIFF_LONG, u32::MAX);
+        malformed.extend_from_slice(&u32::MAX.to_le_bytes());
+        assert!(find_embedded_jpeg_ranges(&malformed).is_empty());
+    }
+
+    #[test]
+    fn test_raw_files_are_indexed_and_linked_to_jpeg_siblings() {
+        let dir = tempfile::tempdir().unwrap();
+        let raw_path = dir.path().join("IMG_0001.CR2");
+        std::fs::write(&raw_path, raw_bytes(&jpeg_bytes(8, 6), &jpeg_bytes(64, 48))).unwrap();
+        std::fs::write(dir.path().join("IMG_0002.NEF"), raw_bytes(&jpeg_bytes(8, 6), &jpeg_bytes(32, 24))).unwrap();
+        let sibling_path = dir.path().join("IMG_0001.JPG");
+        std::fs::write(&sibling_path, jpeg_bytes(64, 48)).unwrap();
+        let raw_path = raw_path.canonicalize().unwrap().to_string_lossy().into_owned();
+        let sibling_path = sibling_path.canonicalize().unwrap().to_string_lossy().into_owned();
+
+        let images = get_images_in_folder(dir.path().to_string_lossy().into_owned());
+        assert_eq!(images.len(), 3);
+        assert_eq!(images.iter().filter(|p| is_raw_image_path(p)).count(), 2);
+
+        assert_eq!(open_image(&raw_path).unwrap().dimensions(), (64, 48));
+        assert_eq!(find_jpeg_sibling(&raw_path), Some(sibling_path.clone()));
+
+        let raw = open_and_extract_image_raw_model(&raw_path).unwrap();
+        assert_eq!(raw.raw_format, "cr2");
+        assert_eq!((raw.preview_width, raw.preview_height), (64, 48));
+        assert_eq!(raw.jpeg_sibling_path, Some(sibling_path));
+
+        let nef_path = dir.path().join("IMG_0002.NEF").to_string_lossy().into_owned();
+        assert_eq!(open_and_extract_image_raw_model(&nef_path).unwrap().jpeg_sibling_path, None);
+    }
+}
\ No newline at end of file
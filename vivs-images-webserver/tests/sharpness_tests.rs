@@ -0,0 +1,109 @@
+extern crate image_exif_explorer;
+
+mod tests {
+    use image_exif_explorer::calc::luma_plane::LumaPlane;
+    use image_exif_explorer::calc::sharpness::{estimate_motion_blur, laplacian_variance, motion_blur_score, MOTION_BLUR_MAX_LAPLACIAN_VARIANCE};
+    use image_exif_explorer::converters::extract_image_sharpness::{extract_image_sharpness_model, SHARPNESS_ANALYSIS_DIMENSION};
+    use image::{DynamicImage, GrayImage, Luma};
+
+    const SIZE: usize = 128;
+    const CELL: usize = 16;
+
+    // Hard edged vertical stripes, CELL pixels wide
+    fn stripes_sized(width: usize, height: usize) -> LumaPlane {
+        LumaPlane::new(width, height, (0..width * height).map(|i| if (i % width) / CELL % 2 == 0 { 0.0 } else { 255.0 }).collect())
+    }
+
+    fn stripes() -> LumaPlane {
+        stripes_sized(SIZE, SIZE)
+    }
+
+    fn checkerboard() -> LumaPlane {
+        LumaPlane::new(SIZE, SIZE, (0..SIZE * SIZE).map(|i| if ((i % SIZE) / CELL + (i / SIZE) / CELL) % 2 == 0 { 0.0 } else { 255.0 }).collect())
+    }
+
+    // Box blur over CELL pixels, wrapping at the edges so the pattern stays periodic
+    fn blur(plane: &LumaPlane, horizontal: bool, vertical: bool) -> LumaPlane {
+        let mut pixels = plane.pixels.clone();
+        let passes = [(horizontal, 1, 0), (vertical, 0, 1)];
+        for (enabled, dx, dy) in passes {
+            if !enabled {
+                continue;
+            }
+            let source = LumaPlane::new(plane.width, plane.height, pixels.clone());
+            for y in 0..plane.height {
+                for x in 0..plane.width {
+                    let sum: f64 = (0..CELL).map(|k| source.get((x + k * dx) % plane.width, (y + k * dy) % plane.height)).sum();
+                    pixels[y * plane.width + x] = sum / CELL as f64;
+                }
+            }
+        }
+        LumaPlane::new(plane.width, plane.height, pixels)
+    }
+
+    #[test]
+    fn test_laplacian_variance_drops_when_blurred() {
+        let sharp = laplacian_variance(&stripes());
+        let blurred = laplacian_variance(&blur(&stripes(), true, false));
+        assert!(sharp > MOTION_BLUR_MAX_LAPLACIAN_VARIANCE, "sharp {}", sharp);
+        assert!(blurred < MOTION_BLUR_MAX_LAPLACIAN_VARIANCE, "blurred {}", blurred);
+
+        assert_eq!(laplacian_variance(&LumaPlane::new(SIZE, SIZE, vec![90.0; SIZE * SIZE])), 0.0);
+        assert_eq!(laplacian_variance(&LumaPlane::new(2, 2, vec![0.0, 255.0, 255.0, 0.0])), 0.0);
+    }
+
+    #[test]
+    fn test_estimate_motion_blur_coherence_and_angle() {
+        // stripes only have horizontal gradients, so the smear runs vertically
+        let sharp = estimate_motion_blur(&stripes());
+        assert!(sharp.coherence > 0.99, "coherence {}", sharp.coherence);
+        assert!((sharp.angle_degrees - 90.0).abs() < 1e-6);
+
+        let blurred = estimate_motion_blur(&blur(&stripes(), true, false));
+        assert!(blurred.coherence > 0.99, "coherence {}", blurred.coherence);
+
+        // the checkerboard has as many vertical as horizontal edges
+        let even = estimate_motion_blur(&blur(&checkerboard(), true, true));
+        assert!(even.coherence < 0.1, "coherence {}", even.coherence);
+
+        let flat = estimate_motion_blur(&LumaPlane::new(SIZE, SIZE, vec![90.0; SIZE * SIZE]));
+        assert_eq!(flat.coherence, 0.0);
+    }
+
+    #[test]
+    fn test_motion_blur_needs_soft_edges_and_one_direction() {
+        let score = |plane: &LumaPlane| motion_blur_score(&estimate_motion_blur(plane), laplacian_variance(plane));
+
+        // a sharp oriented pattern is the subject, not motion blur
+        assert_eq!(score(&stripes()), 0.0);
+        assert!(score(&blur(&stripes(), true, false)) > 0.99);
+        // soft but with no dominant direction
+        assert!(score(&blur(&checkerboard(), true, true)) < 0.1);
+    }
+
+    fn to_image(plane: &LumaPlane) -> DynamicImage {
+        DynamicImage::ImageLuma8(GrayImage::from_fn(plane.width as u32, plane.height as u32, |x, y| {
+            Luma([plane.get(x as usize, y as usize).round() as u8])
+        }))
+    }
+
+    #[test]
+    fn test_extract_image_sharpness_model_flags_only_the_blurred_image() {
+        let width = SHARPNESS_ANALYSIS_DIMENSION as usize;
+        let sharp = extract_image_sharpness_model("/images/sharp.png", &to_image(&stripes_sized(width, 64)));
+        assert!(sharp.sharpness as f64 > MOTION_BLUR_MAX_LAPLACIAN_VARIANCE);
+        assert_eq!(sharp.motion_blur, 0.0);
+
+        let blurred = extract_image_sharpness_model("/images/blurred.png", &to_image(&blur(&stripes_sized(width, 64), true, false)));
+        assert!((blurred.sharpness as f64) < MOTION_BLUR_MAX_LAPLACIAN_VARIANCE);
+        assert!(blurred.motion_blur > 0.99);
+    }
+
+    #[test]
+    fn test_small_images_are_scored_scaled_up() {
+        // the same stripes drawn at an eighth of the size are scaled up to the analysis size, which softens them
+        let small = extract_image_sharpness_model("/images/small.png", &to_image(&stripes_sized(128, 8)));
+        let full = extract_image_sharpness_model("/images/full.png", &to_image(&stripes_sized(SHARPNESS_ANALYSIS_DIMENSION as usize, 64)));
+        assert!(small.sharpness < full.sharpness);
+    }
+}
\ No newline at end of file
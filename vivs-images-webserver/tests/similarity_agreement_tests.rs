@@ -0,0 +1,67 @@
+extern crate image_exif_explorer;
+
+mod tests {
+    use image_exif_explorer::calc::similarity_agreement::{average_ranks, pearson_correlation, quantile, spearman_correlation, top_quantile_agreement};
+    use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+    use image_exif_explorer::database::query::query_image_similarity::query_similarity_sample_for_algorithms;
+    use image_exif_explorer::models::image_similarity::ImageComparisonAlgorithm;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[test]
+    fn test_ranks_and_correlation_ignore_scale() {
+        assert_eq!(average_ranks(&[30.0, 10.0, 20.0, 10.0]), vec![4.0, 1.5, 3.0, 1.5]);
+
+        // psnr in dB against ssim: same ordering, very different scales
+        let psnr = [12.0, 18.0, 25.0, 31.0, 48.0];
+        let ssim = [0.10, 0.35, 0.40, 0.80, 0.99];
+        assert!((spearman_correlation(&psnr, &ssim).unwrap() - 1.0).abs() < 1e-9);
+        assert!(pearson_correlation(&psnr, &ssim).unwrap() < 1.0);
+
+        let reversed: Vec<f64> = ssim.iter().rev().copied().collect();
+        assert!((spearman_correlation(&psnr, &reversed).unwrap() + 1.0).abs() < 1e-9);
+        assert_eq!(pearson_correlation(&psnr, &[0.5; 5]), None);
+    }
+
+    #[test]
+    fn test_quantiles_and_top_agreement() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(quantile(&values, 0.5), Some(3.0));
+        assert_eq!(quantile(&values, 0.25), Some(2.0));
+        assert_eq!(quantile(&values, 0.9), Some(4.6));
+        assert_eq!(quantile(&[], 0.5), None);
+
+        // both put pairs 3 and 4 in their top half, only one of them also flags pair 2
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let b = [0.2, 0.1, 0.5, 0.7, 0.9];
+        assert!((top_quantile_agreement(&a, &b, 0.5).unwrap() - 1.0).abs() < 1e-9);
+        let c = [0.2, 0.9, 0.1, 0.7, 0.8];
+        assert!((top_quantile_agreement(&a, &c, 0.5).unwrap() - 0.5).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_sample_only_includes_pairs_with_several_algorithms() {
+        let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        apply_migrations(&pool).await.unwrap();
+        for sql in [
+            "INSERT INTO image_similarity (image_comparison_algorithm, image_path_a, image_path_b, similarity_value, similarity_confidence) VALUES (0, '/a.jpg', '/b.jpg', 32.0, 0.6);",
+            "INSERT INTO image_similarity (image_comparison_algorithm, image_path_a, image_path_b, similarity_value, similarity_confidence) VALUES (1, '/a.jpg', '/b.jpg', 0.8, 0.9);",
+            "INSERT INTO image_similarity (image_comparison_algorithm, image_path_a, image_path_b, similarity_value, similarity_confidence) VALUES (2, '/a.jpg', '/b.jpg', 0.7, 0.9);",
+            "INSERT INTO image_similarity (image_comparison_algorithm, image_path_a, image_path_b, similarity_value, similarity_confidence) VALUES (0, '/a.jpg', '/c.jpg', 14.0, 0.2);",
+            "INSERT INTO image_similarity (image_comparison_algorithm, image_path_a, image_path_b, similarity_value, similarity_confidence) VALUES (3, '/a.jpg', '/c.jpg', 0.1, 1.0);",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+
+        let algos = [ImageComparisonAlgorithm::Magick, ImageComparisonAlgorithm::CustomV1];
+        let rows = query_similarity_sample_for_algorithms(&algos, 10, &pool).await.unwrap();
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|r| r.image_path_b == "/b.jpg" && algos.contains(&r.image_comparison_algorithm)));
+
+        let algos = [ImageComparisonAlgorithm::Magick, ImageComparisonAlgorithm::WindowedSsim];
+        let rows = query_similarity_sample_for_algorithms(&algos, 10, &pool).await.unwrap();
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|r| r.image_path_b == "/c.jpg"));
+
+        assert_eq!(ImageComparisonAlgorithm::from_name("custom_v2_thumbnails"), Some(ImageComparisonAlgorithm::CustomV2Thumbnails));
+    }
+}
\ No newline at end of file
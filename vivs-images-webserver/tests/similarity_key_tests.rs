@@ -0,0 +1,84 @@
+extern crate image_exif_explorer;
+
+mod common;
+
+mod tests {
+    use image_exif_explorer::converters::extract_image_similarity::order_comparison_pair;
+    use image_exif_explorer::converters::string_to_hashcode::string_hashcode_java_style;
+    use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+    use image_exif_explorer::database::query::query_image_similarity::get_image_similarity_value_exists_in_db;
+    use image_exif_explorer::models::image_similarity::ImageComparisonAlgorithm;
+    use sqlx::Row;
+
+    use crate::common::open_memory_pool;
+
+    // "Aa" and "BB" hash the same, so these two give pairs with the same old key
+    const STORED_PATH: &str = "/\u{e9}t\u{e9}/Aa.jpg";
+    const COLLIDED_PATH: &str = "/\u{e9}t\u{e9}/BB.jpg";
+
+    // the key the old schema stored, which is the same whichever way round the pair is
+    fn old_comparison_key(image_path_a: &str, image_path_b: &str) -> i32 {
+        string_hashcode_java_style(image_path_a).wrapping_add(string_hashcode_java_style(image_path_b))
+    }
+
+    #[test]
+    fn test_order_comparison_pair_is_symmetric() {
+        assert_eq!(order_comparison_pair("/b.jpg", "/a.jpg"), ("/a.jpg".to_string(), "/b.jpg".to_string()));
+        assert_eq!(order_comparison_pair("/a.jpg", "/b.jpg"), order_comparison_pair("/b.jpg", "/a.jpg"));
+    }
+
+    #[tokio::test]
+    async fn test_migration_rekeys_pairs_and_records_collided_keys() {
+        let pool = open_memory_pool().await;
+        assert_eq!(old_comparison_key(STORED_PATH, "/x.jpg"), old_comparison_key("/x.jpg", COLLIDED_PATH));
+        // the schema from before pairs were keyed by path; the pair with COLLIDED_PATH was never stored
+        for sql in [
+            "CREATE TABLE image_paths (image_path TEXT PRIMARY KEY);",
+            "CREATE TABLE image_similarity (image_comparison_key INTEGER NOT NULL PRIMARY KEY, image_comparison_algorithm INTEGER NOT NULL, image_path_a TEXT, image_path_b TEXT, similarity_value REAL NOT NULL, similarity_confidence REAL NOT NULL);",
+            "CREATE INDEX idx_image_similarity_comparison_key ON image_similarity(image_comparison_key);",
+            "CREATE UNIQUE INDEX idx_image_similarity_image_path ON image_similarity(image_path_a, image_path_b);",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+        for image_path in [STORED_PATH, COLLIDED_PATH, "/c.jpg", "/d.jpg", "/x.jpg"] {
+            sqlx::query("INSERT INTO image_paths VALUES (?)").bind(image_path).execute(&pool).await.unwrap();
+        }
+        for (image_path_a, image_path_b, similarity_value) in [(STORED_PATH, "/x.jpg", 0.8), ("/d.jpg", "/c.jpg", 0.4)] {
+            sqlx::query("INSERT INTO image_similarity VALUES (?, 0, ?, ?, ?, 1.0)")
+                .bind(old_comparison_key(image_path_a, image_path_b))
+                .bind(image_path_a).bind(image_path_b).bind(similarity_value)
+                .execute(&pool).await.unwrap();
+        }
+
+        apply_migrations(&pool).await.unwrap();
+
+        let rows = sqlx::query("SELECT image_path_a, image_path_b, image_comparison_algorithm, similarity_value FROM image_similarity ORDER BY image_path_a, image_comparison_algorithm")
+            .fetch_all(&pool).await.unwrap();
+        let rows: Vec<(String, String, i64, f64)> = rows.iter()
+            .map(|r| (r.get("image_path_a"), r.get("image_path_b"), r.get("image_comparison_algorithm"), r.get("similarity_value")))
+            .collect();
+        assert_eq!(rows, vec![
+            ("/c.jpg".to_string(), "/d.jpg".to_string(), 0, 0.4),
+            ("/x.jpg".to_string(), STORED_PATH.to_string(), 0, 0.8),
+        ]);
+
+        let collisions = sqlx::query("SELECT image_path_a, image_path_b, collided_path_a, collided_path_b, image_comparison_key FROM image_similarity_key_collisions")
+            .fetch_all(&pool).await.unwrap();
+        assert_eq!(collisions.len(), 1);
+        assert_eq!(collisions[0].get::<String, _>("image_path_b"), STORED_PATH);
+        assert_eq!(collisions[0].get::<String, _>("collided_path_a"), "/x.jpg");
+        assert_eq!(collisions[0].get::<String, _>("collided_path_b"), COLLIDED_PATH);
+        assert_eq!(collisions[0].get::<i64, _>("image_comparison_key"), old_comparison_key(STORED_PATH, "/x.jpg") as i64);
+        let duplicates: i64 = sqlx::query("SELECT COUNT(*) AS n FROM image_similarity_duplicates").fetch_one(&pool).await.unwrap().get("n");
+        assert_eq!(duplicates, 0);
+
+        assert!(get_image_similarity_value_exists_in_db("/d.jpg", "/c.jpg", &ImageComparisonAlgorithm::Magick, &pool).await.unwrap());
+        assert!(!get_image_similarity_value_exists_in_db("/d.jpg", "/c.jpg", &ImageComparisonAlgorithm::WindowedSsim, &pool).await.unwrap());
+        // the collided pair is missing, so the similarity action compares it
+        assert!(!get_image_similarity_value_exists_in_db(COLLIDED_PATH, "/x.jpg", &ImageComparisonAlgorithm::Magick, &pool).await.unwrap());
+
+        // the same pair in the other order is now rejected by the key
+        assert!(sqlx::query("INSERT INTO image_similarity (image_comparison_algorithm, image_path_a, image_path_b, similarity_value, similarity_confidence) VALUES (0, '/d.jpg', '/c.jpg', 0.1, 1.0)")
+            .execute(&pool).await.is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+extern crate image_exif_explorer;
+
+mod tests {
+    use image_exif_explorer::actions::action_registry::find_action;
+    use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+    use image_exif_explorer::database::query::query_image_similarity_neighbors::{query_similarity_neighbor_scanned_paths, query_similarity_neighbors_for_image, query_similarity_prune_plans};
+    use image_exif_explorer::database::update::update_image_similarity_neighbors::{execute_prune_image_similarity_to_top_k_sql, execute_replace_image_similarity_neighbors_sql};
+    use image_exif_explorer::models::image_similarity::ImageComparisonAlgorithm;
+    use image_exif_explorer::models::image_similarity_neighbor::{ImageSimilarityNeighbor, ImageSimilarityNeighborScan};
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::SqlitePool;
+
+    async fn open_migrated_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        apply_migrations(&pool).await.unwrap();
+        pool
+    }
+
+    fn neighbor_paths(neighbors: &[ImageSimilarityNeighbor]) -> Vec<&str> {
+        neighbors.iter().map(|n| n.neighbor_path.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_prune_keeps_top_k_neighbours_and_drops_the_rest() {
+        let pool = open_migrated_pool().await;
+        for (a, b, v) in [
+            ("/a.jpg", "/b.jpg", 0.9), ("/a.jpg", "/c.jpg", 0.8), ("/a.jpg", "/d.jpg", 0.2),
+            ("/b.jpg", "/c.jpg", 0.6), ("/b.jpg", "/d.jpg", 0.3), ("/c.jpg", "/d.jpg", 0.55),
+        ] {
+            sqlx::query("INSERT INTO image_similarity (image_comparison_algorithm, image_path_a, image_path_b, similarity_value, similarity_confidence) VALUES (2, ?, ?, ?, 1.0);")
+                .bind(a).bind(b).bind(v)
+                .execute(&pool).await.unwrap();
+        }
+        // an incomplete algorithm: /e was only compared with /a
+        sqlx::query("INSERT INTO image_similarity (image_comparison_algorithm, image_path_a, image_path_b, similarity_value, similarity_confidence) VALUES (1, '/a.jpg', '/e.jpg', 0.7, 1.0);")
+            .execute(&pool).await.unwrap();
+
+        let plans = query_similarity_prune_plans(1, 0.5, &pool).await.unwrap();
+        assert_eq!(plans.len(), 2);
+        let plan = plans.iter().find(|p| p.image_comparison_algorithm == ImageComparisonAlgorithm::CustomV2Thumbnails).unwrap();
+        assert_eq!((plan.row_count, plan.image_count), (6, 4));
+
+        let deleted = execute_prune_image_similarity_to_top_k_sql(plan, &pool).await.unwrap();
+        let algo = ImageComparisonAlgorithm::CustomV2Thumbnails;
+        assert_eq!(neighbor_paths(&query_similarity_neighbors_for_image("/a.jpg", &algo, &pool).await.unwrap()), vec!["/b.jpg"]);
+        assert_eq!(neighbor_paths(&query_similarity_neighbors_for_image("/b.jpg", &algo, &pool).await.unwrap()), vec!["/a.jpg"]);
+        assert_eq!(neighbor_paths(&query_similarity_neighbors_for_image("/c.jpg", &algo, &pool).await.unwrap()), vec!["/a.jpg"]);
+        assert_eq!(neighbor_paths(&query_similarity_neighbors_for_image("/d.jpg", &algo, &pool).await.unwrap()), vec!["/c.jpg"]);
+
+        // only a-b, a-c and c-d are still somebody's neighbour
+        assert_eq!(deleted, 3);
+        let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM image_similarity WHERE image_comparison_algorithm = 2;")
+            .fetch_one(&pool).await.unwrap();
+        assert_eq!(remaining, 3);
+        assert_eq!(query_similarity_neighbor_scanned_paths(&algo, &pool).await.unwrap().len(), 4);
+
+        // the other algorithm is untouched until its own plan runs
+        let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM image_similarity WHERE image_comparison_algorithm = 1;")
+            .fetch_one(&pool).await.unwrap();
+        assert_eq!(remaining, 1);
+    }
+
+    #[tokio::test]
+    async fn test_scan_replaces_own_list_and_offers_itself_to_neighbours() {
+        let pool = open_migrated_pool().await;
+        let algo = ImageComparisonAlgorithm::CustomV2Thumbnails;
+        let neighbor = |image_path: &str, neighbor_path: &str, similarity_value: f32| ImageSimilarityNeighbor {
+            image_path: image_path.to_string(),
+            neighbor_path: neighbor_path.to_string(),
+            image_comparison_algorithm: algo.clone(),
+            similarity_value,
+            similarity_confidence: 1.0,
+        };
+
+        let scan = ImageSimilarityNeighborScan {
+            image_path: "/a.jpg".to_string(),
+            image_comparison_algorithm: algo.clone(),
+            compared_count: 3,
+            neighbors: vec![neighbor("/a.jpg", "/b.jpg", 0.9), neighbor("/a.jpg", "/c.jpg", 0.7)],
+        };
+        execute_replace_image_similarity_neighbors_sql(&scan, 1, &pool).await.unwrap();
+        assert_eq!(neighbor_paths(&query_similarity_neighbors_for_image("/a.jpg", &algo, &pool).await.unwrap()), vec!["/b.jpg"]);
+        assert_eq!(neighbor_paths(&query_similarity_neighbors_for_image("/c.jpg", &algo, &pool).await.unwrap()), vec!["/a.jpg"]);
+
+        // a later, closer image pushes /a out of /c's list
+        let scan = ImageSimilarityNeighborScan {
+            image_path: "/d.jpg".to_string(),
+            image_comparison_algorithm: algo.clone(),
+            compared_count: 3,
+            neighbors: vec![neighbor("/d.jpg", "/c.jpg", 0.95)],
+        };
+        execute_replace_image_similarity_neighbors_sql(&scan, 1, &pool).await.unwrap();
+        assert_eq!(neighbor_paths(&query_similarity_neighbors_for_image("/c.jpg", &algo, &pool).await.unwrap()), vec!["/d.jpg"]);
+        assert_eq!(neighbor_paths(&query_similarity_neighbors_for_image("/d.jpg", &algo, &pool).await.unwrap()), vec!["/c.jpg"]);
+
+        let scanned = query_similarity_neighbor_scanned_paths(&algo, &pool).await.unwrap();
+        assert!(scanned.contains("/a.jpg") && scanned.contains("/d.jpg") && !scanned.contains("/c.jpg"));
+
+        assert!(find_action("add_top_k_neighbors_similarity".to_string()).is_some());
+        assert!(find_action("prune_to_top_k_similarity".to_string()).is_some());
+    }
+}
\ No newline at end of file
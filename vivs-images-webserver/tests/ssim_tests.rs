@@ -0,0 +1,125 @@
+extern crate image_exif_explorer;
+
+mod tests {
+    use image_exif_explorer::calc::luma_plane::LumaPlane;
+    use image_exif_explorer::calc::ssim::{compute_ms_ssim, compute_windowed_ssim, gaussian_kernel_1d, MS_SSIM_WEIGHTS};
+
+    const EPSILON: f64 = 1e-9;
+
+    fn constant_plane(width: usize, height: usize, value: f64) -> LumaPlane {
+        LumaPlane::new(width, height, vec![value; width * height])
+    }
+
+    // Deterministic textured plane so the tests do not depend on image files
+    fn textured_plane(width: usize, height: usize) -> LumaPlane {
+        let pixels = (0..width * height)
+            .map(|i| {
+                let (x, y) = ((i % width) as f64, (i / width) as f64);
+                127.5 + 60.0 * (x * 0.7).sin() + 40.0 * (y * 0.3).cos() + 20.0 * ((x + y) * 1.3).sin()
+            })
+            .collect();
+        LumaPlane::new(width, height, pixels)
+    }
+
+    fn add_noise(plane: &LumaPlane, amplitude: f64) -> LumaPlane {
+        let mut seed: u32 = 12345;
+        let pixels = plane.pixels.iter()
+            .map(|p| {
+                seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                let noise = ((seed >> 16) & 0x7fff) as f64 / 32767.0 * 2.0 - 1.0;
+                (p + noise * amplitude).clamp(0.0, 255.0)
+            })
+            .collect();
+        LumaPlane::new(plane.width, plane.height, pixels)
+    }
+
+    #[test]
+    fn test_gaussian_kernel_reference_values() {
+        let kernel = gaussian_kernel_1d(11, 1.5);
+        assert_eq!(kernel.len(), 11);
+        assert!((kernel.iter().sum::<f64>() - 1.0).abs() < EPSILON);
+        assert!((kernel[5] - 0.266011724861794).abs() < 1e-12);
+        assert!((kernel[0] - 0.001028380084479).abs() < 1e-12);
+        assert!((kernel[0] - kernel[10]).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_identical_images_score_one() {
+        let a = textured_plane(64, 48);
+        assert!((compute_windowed_ssim(&a, &a).unwrap() - 1.0).abs() < EPSILON);
+        assert!((compute_ms_ssim(&a, &a).unwrap() - 1.0).abs() < EPSILON);
+
+        let big = textured_plane(192, 192);
+        assert!((compute_ms_ssim(&big, &big).unwrap() - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_constant_images_match_luminance_term() {
+        // With no variance the contrast-structure term is exactly 1, leaving
+        // (2 * 100 * 110 + C1) / (100^2 + 110^2 + C1) with C1 = (0.01 * 255)^2
+        let expected = 0.9954764440915066;
+        let a = constant_plane(32, 32, 100.0);
+        let b = constant_plane(32, 32, 110.0);
+        assert!((compute_windowed_ssim(&a, &b).unwrap() - expected).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_constant_images_ms_ssim_uses_coarsest_luminance_only() {
+        // 192px supports all five scales, so only the last weight applies to luminance
+        let luminance: f64 = 0.9954764440915066;
+        let weight_sum: f64 = MS_SSIM_WEIGHTS.iter().sum();
+        let expected = luminance.powf(MS_SSIM_WEIGHTS[4] / weight_sum);
+        let a = constant_plane(192, 192, 100.0);
+        let b = constant_plane(192, 192, 110.0);
+        assert!((compute_ms_ssim(&a, &b).unwrap() - expected).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_ssim_is_symmetric() {
+        let a = textured_plane(80, 60);
+        let b = add_noise(&a, 40.0);
+        let ab = compute_windowed_ssim(&a, &b).unwrap();
+        let ba = compute_windowed_ssim(&b, &a).unwrap();
+        assert!((ab - ba).abs() < EPSILON);
+
+        let ab = compute_ms_ssim(&a, &b).unwrap();
+        let ba = compute_ms_ssim(&b, &a).unwrap();
+        assert!((ab - ba).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_more_noise_scores_lower() {
+        let a = textured_plane(128, 128);
+        let light = add_noise(&a, 10.0);
+        let heavy = add_noise(&a, 60.0);
+
+        let light_ssim = compute_windowed_ssim(&a, &light).unwrap();
+        let heavy_ssim = compute_windowed_ssim(&a, &heavy).unwrap();
+        assert!(light_ssim < 1.0);
+        assert!(heavy_ssim < light_ssim);
+
+        let light_ms_ssim = compute_ms_ssim(&a, &light).unwrap();
+        let heavy_ms_ssim = compute_ms_ssim(&a, &heavy).unwrap();
+        assert!(light_ms_ssim < 1.0);
+        assert!(heavy_ms_ssim < light_ms_ssim);
+    }
+
+    #[test]
+    fn test_mismatched_or_empty_planes_are_rejected() {
+        let a = constant_plane(16, 16, 0.0);
+        let b = constant_plane(16, 8, 0.0);
+        let empty = LumaPlane::new(0, 0, vec![]);
+        assert!(compute_windowed_ssim(&a, &b).is_none());
+        assert!(compute_ms_ssim(&a, &b).is_none());
+        assert!(compute_windowed_ssim(&empty, &empty).is_none());
+    }
+
+    #[test]
+    fn test_planes_smaller_than_window() {
+        let a = textured_plane(6, 4);
+        let b = add_noise(&a, 30.0);
+        let value = compute_windowed_ssim(&a, &b).unwrap();
+        assert!(value.is_finite() && value < 1.0);
+        assert!(compute_ms_ssim(&a, &b).unwrap().is_finite());
+    }
+}
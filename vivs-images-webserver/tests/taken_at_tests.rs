@@ -0,0 +1,204 @@
+extern crate image_exif_explorer;
+
+mod tests {
+    use std::collections::HashMap;
+    use std::io::Cursor;
+
+    use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime};
+    use exif::{Field, In, Rational, Tag, Value};
+    use image_exif_explorer::converters::extract_image_taken_at::{
+        extract_image_taken_at, extract_taken_at_from_exif, extract_taken_at_from_file_name, extract_taken_at_from_gps,
+        extract_taken_at_from_xmp, format_taken_at,
+    };
+    use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+    use image_exif_explorer::models::image_taken_at::TakenAtSource;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::Row;
+
+    fn ascii(tag: Tag, value: &str) -> Field {
+        Field { tag, ifd_num: In::PRIMARY, value: Value::Ascii(vec![value.as_bytes().to_vec()]) }
+    }
+
+    fn gps_time(hours: u32, minutes: u32, seconds: u32) -> Field {
+        let value = Value::Rational(vec![Rational::from((hours, 1)), Rational::from((minutes, 1)), Rational::from((seconds, 1))]);
+        Field { tag: Tag::GPSTimeStamp, ifd_num: In::PRIMARY, value }
+    }
+
+    // writes the fields into a tiff blob and reads them back the way a camera file would be read
+    fn exif_blob(fields: &[Field]) -> exif::Exif {
+        let mut writer = exif::experimental::Writer::new();
+        for field in fields {
+            writer.push_field(field);
+        }
+        let mut buf = Cursor::new(Vec::new());
+        writer.write(&mut buf, false).unwrap();
+        exif::Reader::new().read_raw(buf.into_inner()).unwrap()
+    }
+
+    fn at(date: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%S%.f").unwrap()
+    }
+
+    #[test]
+    fn test_exif_sub_seconds_and_offset() {
+        let exif = exif_blob(&[
+            ascii(Tag::DateTimeOriginal, "2023:05:07 14:03:09"),
+            ascii(Tag::SubSecTimeOriginal, "25"),
+            ascii(Tag::OffsetTimeOriginal, "+02:00"),
+        ]);
+        let taken_at = extract_taken_at_from_exif(&exif).unwrap();
+        assert_eq!(taken_at.source, TakenAtSource::Exif);
+        assert_eq!(taken_at.get_local_time_string(), "2023-05-07T14:03:09.250");
+        assert_eq!(taken_at.get_offset_string(), Some("+02:00".to_string()));
+        assert_eq!(taken_at.get_utc_time(), Some(at("2023-05-07T12:03:09.250")));
+    }
+
+    #[test]
+    fn test_exif_placeholder_falls_through_to_next_tag() {
+        let exif = exif_blob(&[
+            ascii(Tag::DateTimeOriginal, "0000:00:00 00:00:00"),
+            ascii(Tag::DateTime, "2022:01:02 03:04:05"),
+        ]);
+        let taken_at = extract_taken_at_from_exif(&exif).unwrap();
+        assert_eq!(taken_at.get_local_time_string(), "2022-01-02T03:04:05");
+        assert_eq!(taken_at.offset, None);
+    }
+
+    #[test]
+    fn test_exif_offset_from_gps_clock() {
+        // the camera clock is three seconds behind the satellites, which still reads as +09:00
+        let exif = exif_blob(&[
+            ascii(Tag::DateTimeOriginal, "2023:05:07 14:03:09"),
+            ascii(Tag::GPSDateStamp, "2023:05:07"),
+            gps_time(5, 3, 12),
+        ]);
+        let taken_at = extract_taken_at_from_exif(&exif).unwrap();
+        assert_eq!(taken_at.get_offset_string(), Some("+09:00".to_string()));
+
+        // a camera clock that was never set gives no zone rather than a wrong one
+        let exif = exif_blob(&[
+            ascii(Tag::DateTimeOriginal, "2023:05:07 14:11:09"),
+            ascii(Tag::GPSDateStamp, "2023:05:07"),
+            gps_time(5, 3, 12),
+        ]);
+        assert_eq!(extract_taken_at_from_exif(&exif).unwrap().offset, None);
+    }
+
+    #[test]
+    fn test_gps_only_is_utc() {
+        let exif = exif_blob(&[ascii(Tag::GPSDateStamp, "2023:05:07"), gps_time(5, 3, 12)]);
+        assert!(extract_taken_at_from_exif(&exif).is_none());
+        let taken_at = extract_taken_at_from_gps(&exif).unwrap();
+        assert_eq!(taken_at.source, TakenAtSource::Gps);
+        assert_eq!(taken_at.get_local_time_string(), "2023-05-07T05:03:12");
+        assert_eq!(taken_at.get_offset_string(), Some("+00:00".to_string()));
+    }
+
+    #[test]
+    fn test_file_name_fixtures() {
+        let fixtures = [
+            ("/photos/IMG_20230507_140309.jpg", Some(("2023-05-07T14:03:09", None))),
+            ("/photos/DSC_20230507_140309.JPG", Some(("2023-05-07T14:03:09", None))),
+            ("/photos/20230507_140309.jpg", Some(("2023-05-07T14:03:09", None))),
+            ("/photos/VID_20230507_140309.mp4", Some(("2023-05-07T14:03:09", None))),
+            ("/photos/Screenshot_20230507-140309.png", Some(("2023-05-07T14:03:09", None))),
+            ("/photos/PXL_20230507_120309250.jpg", Some(("2023-05-07T12:03:09.250", Some("+00:00")))),
+            ("/photos/2023-05-07 14.03.09.jpg", Some(("2023-05-07T14:03:09", None))),
+            ("/photos/signal-2023-05-07-140309.jpg", Some(("2023-05-07T14:03:09", None))),
+            ("/photos/IMG-20230507-WA0003.jpg", Some(("2023-05-07T00:00:00", None))),
+            ("/photos/holiday.jpg", None),
+            ("/photos/IMG_20231307_140309.jpg", None),
+            ("/photos/a1b2_99990507_140309.jpg", None),
+        ];
+        for (path, expected) in fixtures {
+            let taken_at = extract_taken_at_from_file_name(path);
+            let actual = taken_at.as_ref().map(|t| (t.get_local_time_string(), t.get_offset_string()));
+            let expected = expected.map(|(time, offset)| (time.to_string(), offset.map(String::from)));
+            assert_eq!(actual, expected, "{}", path);
+            assert!(taken_at.iter().all(|t| t.source == TakenAtSource::FileName));
+        }
+    }
+
+    #[test]
+    fn test_xmp_tags() {
+        let mut tags = HashMap::new();
+        tags.insert("Xmp.xmp.CreateDate".to_string(), "2021-06-01T08:30:00".to_string());
+        tags.insert("Xmp.photoshop.DateCreated".to_string(), "2021-06-01T09:30:00-04:00".to_string());
+        let taken_at = extract_taken_at_from_xmp(&tags).unwrap();
+        assert_eq!(taken_at.source, TakenAtSource::Xmp);
+        assert_eq!(taken_at.get_local_time_string(), "2021-06-01T09:30:00");
+        assert_eq!(taken_at.get_offset_string(), Some("-04:00".to_string()));
+
+        tags.insert("Xmp.exif.DateTimeOriginal".to_string(), "not a date".to_string());
+        assert_eq!(extract_taken_at_from_xmp(&tags).unwrap().get_local_time_string(), "2021-06-01T09:30:00");
+        assert!(extract_taken_at_from_xmp(&HashMap::new()).is_none());
+    }
+
+    #[test]
+    fn test_resolution_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let modified: DateTime<FixedOffset> = DateTime::parse_from_rfc3339("2020-01-02T03:04:05+00:00").unwrap();
+
+        let undated = dir.path().join("holiday.jpg");
+        let named = dir.path().join("IMG_20230507_140309.jpg");
+        for path in [&undated, &named] {
+            let file = std::fs::File::create(path).unwrap();
+            file.set_modified(modified.into()).unwrap();
+        }
+
+        // nothing in the file or its name, so the modified time is used in the server's zone
+        let taken_at = extract_image_taken_at(undated.to_str().unwrap(), None).unwrap();
+        assert_eq!(taken_at.source, TakenAtSource::FileModified);
+        assert_eq!(taken_at.get_utc_time(), Some(modified.naive_utc()));
+        assert!(taken_at.offset.is_some());
+
+        let taken_at = extract_image_taken_at(named.to_str().unwrap(), None).unwrap();
+        assert_eq!(taken_at.source, TakenAtSource::FileName);
+
+        // exif wins over the file name, and gps is only used when no exif date is set
+        let exif = exif_blob(&[
+            ascii(Tag::DateTimeOriginal, "2019:03:04 05:06:07"),
+            ascii(Tag::GPSDateStamp, "2019:03:04"),
+            gps_time(5, 6, 7),
+        ]);
+        let taken_at = extract_image_taken_at(named.to_str().unwrap(), Some(&exif)).unwrap();
+        assert_eq!((taken_at.source, taken_at.get_offset_string()), (TakenAtSource::Exif, Some("+00:00".to_string())));
+        let exif = exif_blob(&[ascii(Tag::GPSDateStamp, "2019:03:04"), gps_time(5, 6, 7)]);
+        assert_eq!(extract_image_taken_at(named.to_str().unwrap(), Some(&exif)).unwrap().source, TakenAtSource::Gps);
+    }
+
+    #[test]
+    fn test_format_taken_at_millis_only_when_set() {
+        let date = NaiveDate::from_ymd_opt(2023, 5, 7).unwrap();
+        assert_eq!(format_taken_at(&date.and_hms_opt(14, 3, 9).unwrap()), "2023-05-07T14:03:09");
+        assert_eq!(format_taken_at(&date.and_hms_milli_opt(14, 3, 9, 5).unwrap()), "2023-05-07T14:03:09.005");
+    }
+
+    #[tokio::test]
+    async fn test_upgrade_keeps_exif_rows_and_queues_those_without_a_capture_time() {
+        let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        // image_exif from before the capture time source was recorded
+        for sql in [
+            "CREATE TABLE image_paths (image_path TEXT PRIMARY KEY);",
+            "CREATE TABLE image_exif (image_path TEXT PRIMARY KEY, image_taken_at TIMESTAMP NULL, camera_model TEXT NULL,
+                gps_latitude REAL NULL, gps_longitude REAL NULL, gps_latitude_ref TEXT NULL, gps_longitude_ref TEXT NULL);",
+            "INSERT INTO image_paths VALUES ('/dated.jpg'), ('/undated.jpg');",
+            "INSERT INTO image_exif (image_path, image_taken_at, camera_model) VALUES ('/dated.jpg', '2023:05:07 14:03:09', 'EOS'), ('/undated.jpg', NULL, 'EOS');",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+        apply_migrations(&pool).await.unwrap();
+
+        let rows = sqlx::query("SELECT image_path, image_taken_at, image_taken_at_source, camera_model FROM image_exif ORDER BY image_path").fetch_all(&pool).await.unwrap();
+        let rows: Vec<(String, Option<String>, Option<String>, String)> = rows.iter()
+            .map(|r| (r.get(0), r.get(1), r.get(2), r.get(3)))
+            .collect();
+        assert_eq!(rows, vec![
+            ("/dated.jpg".to_string(), Some("2023-05-07T14:03:09".to_string()), Some("exif".to_string()), "EOS".to_string()),
+            ("/undated.jpg".to_string(), None, None, "EOS".to_string()),
+        ]);
+        let pending: Vec<String> = sqlx::query("SELECT image_path FROM image_exif_pending").fetch_all(&pool).await.unwrap()
+            .iter().map(|r| r.get(0)).collect();
+        assert_eq!(pending, vec!["/undated.jpg"]);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,80 @@
+extern crate image_exif_explorer;
+
+mod common;
+
+mod tests {
+    use image_exif_explorer::actions::analysis_task_item_processor::AnalysisTaskItemProcessor;
+    use image_exif_explorer::actions::refresh::warm_thumbnail_cache_action::WarmThumbnailCacheProcessor;
+    use image_exif_explorer::cache::thumbnail_cache::ThumbnailCache;
+
+    use crate::common::{data_context_with_thumbnails, thumbnail};
+
+    #[tokio::test]
+    async fn test_cache_is_keyed_on_path_and_size() {
+        let cache = ThumbnailCache::with_max_bytes(1 << 20);
+        cache.insert("/a.png".to_string(), 32, thumbnail("/a.png", 32)).await;
+        cache.insert("/a.png".to_string(), 128, thumbnail("/a.png", 128)).await;
+
+        assert_eq!(cache.get("/a.png", 32).await.unwrap().width_and_length, 32);
+        assert_eq!(cache.get("/a.png", 128).await.unwrap().width_and_length, 128);
+        assert!(cache.get("/a.png", 64).await.is_none());
+        assert!(cache.get("/b.png", 32).await.is_none());
+
+        let stats = cache.stats().await;
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.misses, 2);
+    }
+
+    #[tokio::test]
+    async fn test_least_recently_used_is_evicted_over_budget() {
+        let one = thumbnail("/a.png", 32);
+        // room for two entries but not three
+        let budget = (one.thumbnail_data.len() + 200) * 2 + 100;
+        let cache = ThumbnailCache::with_max_bytes(budget);
+        cache.insert("/a.png".to_string(), 32, one).await;
+        cache.insert("/b.png".to_string(), 32, thumbnail("/b.png", 32)).await;
+        // using a makes b the oldest
+        assert!(cache.get("/a.png", 32).await.is_some());
+        cache.insert("/c.png".to_string(), 32, thumbnail("/c.png", 32)).await;
+
+        assert!(cache.contains("/a.png", 32).await);
+        assert!(!cache.contains("/b.png", 32).await);
+        assert!(cache.contains("/c.png", 32).await);
+        let stats = cache.stats().await;
+        assert_eq!(stats.entries, 2);
+        assert!(stats.bytes <= stats.max_bytes);
+    }
+
+    #[tokio::test]
+    async fn test_different_sizes_are_not_served_from_one_cache_entry() {
+        let data = data_context_with_thumbnails(&["/a.png"], &[32, 128]).await;
+        let small = data.get_thumbnail_at_most_width_length("/a.png", 32).await.unwrap().unwrap();
+        let large = data.get_thumbnail_at_most_width_length("/a.png", 128).await.unwrap().unwrap();
+        assert_eq!(small.width_and_length, 32);
+        assert_eq!(large.width_and_length, 128);
+        let again = data.get_thumbnail_at_most_width_length("/a.png", 32).await.unwrap().unwrap();
+        assert_eq!(again.width_and_length, 32);
+        assert_eq!(data.thumbnail_cache.stats().await.hits, 1);
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_loads_similarity_thumbnails_without_counting_misses() {
+        let data = data_context_with_thumbnails(&["/a.png", "/b.png"], &[32, 128]).await;
+        let processor = WarmThumbnailCacheProcessor::new();
+        let analysis = processor.get_analysis(data.clone(), None).await.unwrap();
+        let items = processor.get_task_items_from_analysis(data.clone(), analysis, None).await.unwrap();
+        assert_eq!(items, vec!["/a.png".to_string(), "/b.png".to_string()]);
+        for item in items {
+            let output = processor.process_task_item(item, false, data.clone()).await.unwrap().unwrap();
+            processor.process_task_output(output, data.clone()).await.unwrap();
+        }
+
+        assert!(processor.task_already_completed(&"/a.png".to_string(), data.clone()).await.unwrap());
+        let stats = data.thumbnail_cache.stats().await;
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.misses, 0);
+        assert_eq!(data.get_thumbnail_at_most_width_length("/b.png", 32).await.unwrap().unwrap().width_and_length, 32);
+        assert_eq!(data.thumbnail_cache.stats().await.hits, 1);
+    }
+}
\ No newline at end of file
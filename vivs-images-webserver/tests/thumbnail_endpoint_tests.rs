@@ -0,0 +1,69 @@
+extern crate image_exif_explorer;
+
+mod common;
+
+mod tests {
+    use actix_web::http::{header, StatusCode};
+    use actix_web::{test, web};
+    use image_exif_explorer::api::web::get_thumbnail;
+    use image_exif_explorer::core::data_context::WebServerActionDataContext;
+    use image_exif_explorer::database::query::search::get_images_by_paths;
+    use image_exif_explorer::database::update::update_image_image_paths::execute_insert_image_path_sql;
+
+    use crate::common::data_context_with_thumbnails;
+
+    async fn data_with_thumbnails(path: &str, sizes: &[u32]) -> web::Data<WebServerActionDataContext> {
+        web::Data::new(data_context_with_thumbnails(&[path], sizes).await)
+    }
+
+    fn query(s: &str) -> web::Query<std::collections::HashMap<String, String>> {
+        web::Query::from_query(s).unwrap()
+    }
+
+    #[actix_web::test]
+    async fn test_thumbnail_is_served_with_cache_headers() {
+        let data = data_with_thumbnails("/images/a.png", &[16, 64]).await;
+
+        let req = test::TestRequest::default().to_http_request();
+        let resp = get_thumbnail(req, query("path=%2Fimages%2Fa.png&size=40"), data.clone()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get(header::CONTENT_TYPE).unwrap(), "image/jpeg");
+        assert!(resp.headers().get(header::CACHE_CONTROL).unwrap().to_str().unwrap().contains("max-age="));
+        let etag = resp.headers().get(header::ETAG).unwrap().clone();
+        let body = actix_web::body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(image::load_from_memory(&body).unwrap().width(), 64);
+
+        let req = test::TestRequest::default().insert_header((header::IF_NONE_MATCH, etag.clone())).to_http_request();
+        let resp = get_thumbnail(req, query("path=%2Fimages%2Fa.png&size=40"), data.clone()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+
+        // larger than anything stored falls back to the largest thumbnail
+        let req = test::TestRequest::default().to_http_request();
+        let resp = get_thumbnail(req, query("path=%2Fimages%2Fa.png&size=500"), data.clone()).await.unwrap();
+        assert_eq!(resp.headers().get(header::ETAG).unwrap(), etag);
+    }
+
+    #[actix_web::test]
+    async fn test_missing_thumbnail_and_bad_size() {
+        let data = data_with_thumbnails("/images/a.png", &[16]).await;
+        let req = test::TestRequest::default().to_http_request();
+        let resp = get_thumbnail(req, query("path=%2Fimages%2Fb.png"), data.clone()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let req = test::TestRequest::default().to_http_request();
+        let resp = get_thumbnail(req, query("path=%2Fimages%2Fa.png&size=big"), data.clone()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let req = test::TestRequest::default().to_http_request();
+        let resp = get_thumbnail(req, query("path=%2Fimages%2F..%2Fa.png"), data.clone()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_search_results_flag_thumbnails_without_loading_them() {
+        let data = data_with_thumbnails("/images/a.png", &[16]).await;
+        execute_insert_image_path_sql(&"/images/b.png".to_string(), &data.pool).await.unwrap();
+        let mut images = get_images_by_paths(data.get_ref().clone(), vec!["/images/a.png".to_string(), "/images/b.png".to_string()]).await.unwrap();
+        images.sort_by(|a, b| a.path.cmp(&b.path));
+        let flags: Vec<(bool, bool)> = images.iter().map(|i| (i.has_thumbnail, i.thumbnail.is_some())).collect();
+        assert_eq!(flags, vec![(true, false), (false, false)]);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,91 @@
+extern crate image_exif_explorer;
+
+mod tests {
+    use image::{DynamicImage, GenericImageView, RgbaImage};
+    use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+    use image_exif_explorer::database::query::query_image_thumbnail::{query_thumbnail_count_not_in_storage, query_thumbnail_table, query_thumbnail_table_width_length};
+    use image_exif_explorer::database::update::update_image_thumbnail::{execute_delete_image_thumbnail_sql, execute_insert_image_thumbnail_sql};
+    use image_exif_explorer::models::config::thumbnails::{parse_thumbnail_format, parse_thumbnail_sizes, ThumbnailStorage, THUMBNAIL_STORAGE_ENV_VAR, THUMBNAIL_STORE_DB_FILE_ENV_VAR, THUMBNAIL_STORE_DIR_ENV_VAR};
+    use image_exif_explorer::models::image_thumbnail::{ImageThumbnail, ThumbnailFormat};
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    fn test_image(size: u32) -> DynamicImage {
+        DynamicImage::ImageRgba8(RgbaImage::from_fn(size, size, |x, y| image::Rgba([(x * 16) as u8, (y * 16) as u8, 128, 200])))
+    }
+
+    #[test]
+    fn test_thumbnail_settings_are_parsed() {
+        assert_eq!(parse_thumbnail_sizes("256, 64,x,0,64"), vec![64, 256]);
+        assert!(parse_thumbnail_sizes("").is_empty());
+        assert_eq!(ThumbnailFormat::from_name("JPEG").map(|f| f.extension()), Some("jpg"));
+        assert_eq!(parse_thumbnail_format(" jpeg"), Some(ThumbnailFormat::JPG));
+        // only lossless webp can be written, which would ignore the quality setting
+        assert_eq!(parse_thumbnail_format("webp"), None);
+        assert_eq!(ThumbnailStorage::from_name("db"), Some(ThumbnailStorage::Database));
+        assert!(ThumbnailStorage::from_name("s3").is_none());
+    }
+
+    #[test]
+    fn test_jpeg_and_webp_thumbnails_decode() {
+        for format in [ThumbnailFormat::JPG, ThumbnailFormat::WEBP] {
+            let thumbnail = ImageThumbnail::from_image("/images/a.png".to_string(), format, &test_image(16));
+            assert_eq!(thumbnail.to_image().unwrap().dimensions(), (16, 16));
+        }
+    }
+
+    fn walk_files(dir: &std::path::Path) -> usize {
+        std::fs::read_dir(dir).unwrap()
+            .map(|e| e.unwrap().path())
+            .map(|p| if p.is_dir() { walk_files(&p) } else { 1 })
+            .sum()
+    }
+
+    // One test sets the storage variables, so nothing else in this file depends on them
+    #[tokio::test]
+    async fn test_thumbnails_round_trip_through_each_storage() {
+        let dir = tempfile::tempdir().unwrap();
+        // a file database, since sqlite opens databases attached to an in-memory one in memory as well
+        let db_url = format!("sqlite://{}?mode=rwc", dir.path().join("images.db").to_str().unwrap());
+        let pool = SqlitePoolOptions::new().max_connections(2).connect(&db_url).await.unwrap();
+        apply_migrations(&pool).await.unwrap();
+
+        std::env::set_var(THUMBNAIL_STORE_DB_FILE_ENV_VAR, dir.path().join("thumbs.db"));
+        std::env::set_var(THUMBNAIL_STORE_DIR_ENV_VAR, dir.path().join("thumbs"));
+        for (i, name) in ["db", "sqlite", "dir"].iter().enumerate() {
+            std::env::set_var(THUMBNAIL_STORAGE_ENV_VAR, name);
+            let storage = ThumbnailStorage::from_name(name).unwrap();
+            let image_path = format!("/images/{}.png", i);
+            let thumbnail = ImageThumbnail::from_image(image_path.clone(), ThumbnailFormat::PNG, &test_image(8));
+            execute_insert_image_thumbnail_sql(&thumbnail, &pool).await.unwrap();
+
+            let stored = query_thumbnail_table_width_length(&image_path, 8, &pool).await.unwrap().unwrap();
+            assert_eq!(stored.thumbnail_data, thumbnail.thumbnail_data);
+            assert_eq!(query_thumbnail_count_not_in_storage(&storage, None, &pool).await.unwrap(), i);
+            let inline: Vec<u8> = sqlx::query_scalar("SELECT thumbnail_data FROM image_thumbnail WHERE image_path = ?")
+                .bind(&image_path).fetch_one(&pool).await.unwrap();
+            assert_eq!(inline.is_empty(), storage != ThumbnailStorage::Database);
+        }
+        assert!(dir.path().join("thumbs.db").exists());
+        assert_eq!(std::fs::read_dir(dir.path().join("thumbs")).unwrap().count(), 1);
+
+        // thumbnails written before a storage change are still read from where they were put
+        assert_eq!(query_thumbnail_table("/images/1.png", &pool).await.unwrap().len(), 1);
+
+        // stored data goes with the last thumbnail that uses it
+        let same_data = ImageThumbnail::from_image("/images/3.png".to_string(), ThumbnailFormat::PNG, &test_image(8));
+        execute_insert_image_thumbnail_sql(&same_data, &pool).await.unwrap();
+        let store_files = || walk_files(&dir.path().join("thumbs"));
+        execute_delete_image_thumbnail_sql(&"/images/2.png".to_string(), &pool).await.unwrap();
+        assert_eq!(store_files(), 1);
+        execute_delete_image_thumbnail_sql(&"/images/3.png".to_string(), &pool).await.unwrap();
+        assert_eq!(store_files(), 0);
+        execute_delete_image_thumbnail_sql(&"/images/1.png".to_string(), &pool).await.unwrap();
+        let store_db_url = format!("sqlite://{}", dir.path().join("thumbs.db").to_str().unwrap());
+        let store_pool = SqlitePoolOptions::new().max_connections(1).connect(&store_db_url).await.unwrap();
+        let blobs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM thumbnail_blob").fetch_one(&store_pool).await.unwrap();
+        assert_eq!(blobs, 0);
+        for env_var in [THUMBNAIL_STORAGE_ENV_VAR, THUMBNAIL_STORE_DB_FILE_ENV_VAR, THUMBNAIL_STORE_DIR_ENV_VAR] {
+            std::env::remove_var(env_var);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,120 @@
+extern crate image_exif_explorer;
+
+mod tests {
+    use actix_web::web;
+    use image_exif_explorer::cache::thumbnail_cache::ThumbnailCache;
+    use image_exif_explorer::converters::extract_image_taken_at::normalize_taken_at;
+    use image_exif_explorer::core::data_context::WebServerActionDataContext;
+    use image_exif_explorer::database::migration::apply_migrations::apply_migrations;
+    use image_exif_explorer::database::migration::migrations::SQL_MIGRATE_IMAGE_EXIF_TAKEN_AT_ISO;
+    use image_exif_explorer::database::query::query_image_timeline::{query_taken_at_bucket_counts, query_undated_image_count};
+    use image_exif_explorer::database::update::update_image_exif::execute_insert_image_exif_sql;
+    use image_exif_explorer::database::update::update_image_image_paths::execute_insert_image_path_sql;
+    use image_exif_explorer::models::image_exif::ImageExif;
+    use image_exif_explorer::models::image_timeline::TimelineBucket;
+    use image_exif_explorer::models::query_params::timeline_params::TimelineParams;
+    use image_exif_explorer::view::html::pages::browse_timeline::view_page_timeline;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::{Row, SqlitePool};
+
+    async fn pool_with_images(taken_at: &[(&str, Option<&str>)]) -> SqlitePool {
+        let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        apply_migrations(&pool).await.unwrap();
+        for (path, value) in taken_at {
+            execute_insert_image_path_sql(&path.to_string(), &pool).await.unwrap();
+            let mut exif = ImageExif::default(path);
+            exif.image_taken_at = value.map(String::from);
+            execute_insert_image_exif_sql(exif, pool.clone()).await.unwrap();
+        }
+        pool
+    }
+
+    async fn timeline_body(pool: &SqlitePool, query: &str) -> String {
+        let data = WebServerActionDataContext::new(pool.clone(), ThumbnailCache::new());
+        let params = web::Query::<TimelineParams>::from_query(query).unwrap();
+        let resp = view_page_timeline(web::Data::new(data), params).await.unwrap();
+        let body = actix_web::body::to_bytes(resp.into_body()).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_normalize_taken_at_forms() {
+        let expected = Some("2023-05-07T14:03:09".to_string());
+        assert_eq!(normalize_taken_at("2023:05:07 14:03:09"), expected);
+        assert_eq!(normalize_taken_at("2023-05-07 14:03:09"), expected);
+        assert_eq!(normalize_taken_at("2023-05-07 14:03:09 UTC"), expected);
+        assert_eq!(normalize_taken_at("2023-05-07T14:03:09.250"), Some("2023-05-07T14:03:09.250".to_string()));
+        // the offset is dropped, keeping the time the camera showed
+        assert_eq!(normalize_taken_at("2023-05-07T14:03:09+02:00"), expected);
+        assert_eq!(normalize_taken_at("2023:05:07"), Some("2023-05-07T00:00:00".to_string()));
+        assert_eq!(normalize_taken_at("0000:00:00 00:00:00"), None);
+        assert_eq!(normalize_taken_at("sometime in May"), None);
+    }
+
+    #[tokio::test]
+    async fn test_migration_normalizes_stored_capture_times() {
+        let pool = pool_with_images(&[
+            ("/a.jpg", Some("2023-05-07 14:03:09")),
+            ("/b.jpg", Some("2021:12:31 23:59:59")),
+            ("/c.jpg", Some("2020-01-02 03:04:05 UTC")),
+            ("/d.jpg", Some("0000:00:00 00:00:00")),
+            ("/e.jpg", None),
+        ]).await;
+        sqlx::query(SQL_MIGRATE_IMAGE_EXIF_TAKEN_AT_ISO).execute(&pool).await.unwrap();
+
+        let rows = sqlx::query("SELECT image_path, image_taken_at FROM image_exif ORDER BY image_path").fetch_all(&pool).await.unwrap();
+        let rows: Vec<(String, Option<String>)> = rows.iter().map(|r| (r.get("image_path"), r.get("image_taken_at"))).collect();
+        // the placeholder date is cleared and the image queued so the exif import reads the file again
+        assert_eq!(rows, vec![
+            ("/a.jpg".to_string(), Some("2023-05-07T14:03:09".to_string())),
+            ("/b.jpg".to_string(), Some("2021-12-31T23:59:59".to_string())),
+            ("/c.jpg".to_string(), Some("2020-01-02T03:04:05".to_string())),
+            ("/d.jpg".to_string(), None),
+            ("/e.jpg".to_string(), None),
+        ]);
+        let pending = sqlx::query("SELECT image_path, image_id FROM image_exif_pending").fetch_all(&pool).await.unwrap();
+        let pending: Vec<(String, Option<i64>)> = pending.iter().map(|r| (r.get("image_path"), r.get("image_id"))).collect();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].0, "/d.jpg");
+        assert!(pending[0].1.is_some());
+    }
+
+    #[actix_web::test]
+    async fn test_timeline_drills_down_from_years_to_days() {
+        // search_params.json is looked up relative to the workspace root
+        std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+        let pool = pool_with_images(&[
+            ("/2022/new_year.jpg", Some("2022-01-01T00:10:00")),
+            ("/2023/may_a.jpg", Some("2023-05-07T09:00:00")),
+            ("/2023/may_b.jpg", Some("2023-05-07T18:30:00")),
+            ("/2023/june.jpg", Some("2023-06-20T12:00:00")),
+            ("/undated.jpg", None),
+        ]).await;
+
+        let months = query_taken_at_bucket_counts(&pool, "2023", 7).await.unwrap();
+        assert_eq!(months, vec![
+            TimelineBucket { key: "2023-05".to_string(), count: 2 },
+            TimelineBucket { key: "2023-06".to_string(), count: 1 },
+        ]);
+        assert_eq!(query_undated_image_count(&pool).await.unwrap(), 1);
+
+        let body = timeline_body(&pool, "").await;
+        assert!(body.contains(r#"<a href="/browse/timeline?year=2023" class="value-item">2023 (3)</a>"#));
+        assert!(body.contains(r#"<a href="/browse/timeline?year=2022" class="value-item">2022 (1)</a>"#));
+        assert_eq!(body.matches(r#"<svg class="calendar-heat-map""#).count(), 2);
+        assert!(body.contains("1 images have no known capture time"));
+
+        let body = timeline_body(&pool, "year=2023").await;
+        assert!(body.contains(r#"<a href="/browse/timeline?year=2023&month=5" class="value-item">May (2)</a>"#));
+        assert!(body.contains(r#"<title>2023-05-07: 2 images</title>"#));
+        assert_eq!(body.matches(r#"<svg class="calendar-heat-map""#).count(), 1);
+
+        let body = timeline_body(&pool, "year=2023&month=5").await;
+        assert!(body.contains(r#"<a href="/browse/timeline?year=2023&month=5&day=7" class="value-item">7 (2)</a>"#));
+
+        let body = timeline_body(&pool, "year=2023&month=5&day=7").await;
+        assert!(body.contains("2 images taken on 2023-05-07"));
+        assert!(body.contains("may_a.jpg") && body.contains("may_b.jpg"));
+        assert!(!body.contains("june.jpg"));
+    }
+}
\ No newline at end of file
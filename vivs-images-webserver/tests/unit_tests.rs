@@ -8,7 +8,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_wallpaper_api() {
         let pool = WebServerActionDataContext::open().await.expect("data");
-        let result = api_get_wallpaper_image_path_inner(pool).await;
+        let result = api_get_wallpaper_image_path_inner(pool, None).await;
         match result {
             Ok(w) => {
                 if let Some(w) = w {